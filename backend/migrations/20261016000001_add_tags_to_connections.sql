@@ -0,0 +1,2 @@
+-- Add free-form tags to saved_connections for grouping/filtering
+ALTER TABLE saved_connections ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,2 @@
+-- Per-connection statement timeout override (NULL = use server default)
+ALTER TABLE saved_connections ADD COLUMN statement_timeout_ms INTEGER;
@@ -0,0 +1,10 @@
+-- Map verified email domains to organizations for automatic membership on signup
+
+CREATE TABLE IF NOT EXISTS org_email_domains (
+    domain VARCHAR(255) PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    default_role VARCHAR(20) NOT NULL DEFAULT 'member',
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_org_email_domains_org ON org_email_domains(organization_id);
@@ -0,0 +1,13 @@
+-- Record of data changes made through the API, per connection
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    connection_id UUID NOT NULL REFERENCES saved_connections(id) ON DELETE CASCADE,
+    actor_id UUID REFERENCES app_users(id) ON DELETE SET NULL,
+    action VARCHAR(50) NOT NULL,
+    table_name VARCHAR(255),
+    details JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_connection_created
+    ON audit_log(connection_id, created_at DESC);
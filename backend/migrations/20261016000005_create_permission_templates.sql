@@ -0,0 +1,19 @@
+-- Named bundles of table grants, stored per organization and expanded into
+-- concrete table permissions when applied to a user or group
+
+CREATE TABLE IF NOT EXISTS permission_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(organization_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS permission_template_tables (
+    template_id UUID NOT NULL REFERENCES permission_templates(id) ON DELETE CASCADE,
+    table_name VARCHAR(200) NOT NULL,
+    permission VARCHAR(20) NOT NULL DEFAULT 'read',
+    PRIMARY KEY (template_id, table_name)
+);
@@ -0,0 +1,4 @@
+-- Allow groups to nest; permissions granted to a group apply to members of its descendants
+ALTER TABLE groups ADD COLUMN parent_group_id UUID REFERENCES groups(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_groups_parent ON groups(parent_group_id);
@@ -0,0 +1,9 @@
+-- App-level display metadata for tables (friendly names, descriptions); never touches the target DB
+CREATE TABLE IF NOT EXISTS connection_table_metadata (
+    connection_id UUID NOT NULL REFERENCES saved_connections(id) ON DELETE CASCADE,
+    table_name VARCHAR(255) NOT NULL,
+    display_name VARCHAR(255),
+    description TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (connection_id, table_name)
+);
@@ -0,0 +1,10 @@
+-- App-level display metadata for columns (descriptions, display hints)
+CREATE TABLE IF NOT EXISTS connection_column_metadata (
+    connection_id UUID NOT NULL REFERENCES saved_connections(id) ON DELETE CASCADE,
+    table_name VARCHAR(255) NOT NULL,
+    column_name VARCHAR(255) NOT NULL,
+    description TEXT,
+    display_hint VARCHAR(50),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (connection_id, table_name, column_name)
+);
@@ -0,0 +1,3 @@
+-- Platform-wide events (e.g. a super_admin acting as another user) are not
+-- tied to a connection; connection-scoped entries still cascade on delete
+ALTER TABLE audit_log ALTER COLUMN connection_id DROP NOT NULL;
@@ -0,0 +1,10 @@
+-- Org-wide group grants: the default permission a group's members get on
+-- every connection in the organization that has no connection-specific grant
+CREATE TABLE IF NOT EXISTS group_org_permissions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    permission VARCHAR(20) NOT NULL DEFAULT 'read',
+    granted_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(group_id, organization_id)
+);
@@ -0,0 +1,2 @@
+-- Read-only connections refuse every write through the app, regardless of permissions
+ALTER TABLE saved_connections ADD COLUMN read_only BOOLEAN NOT NULL DEFAULT false;
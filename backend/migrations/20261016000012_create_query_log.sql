@@ -0,0 +1,15 @@
+-- Datasource calls made through the API, per connection (no SQL or row data).
+-- Entries outlive their connection so the history stays available for review.
+CREATE TABLE IF NOT EXISTS query_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    connection_id UUID NOT NULL,
+    user_id UUID REFERENCES app_users(id) ON DELETE SET NULL,
+    operation VARCHAR(50) NOT NULL,
+    table_name VARCHAR(255),
+    duration_ms BIGINT NOT NULL,
+    success BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_query_log_connection_created
+    ON query_log(connection_id, created_at DESC);
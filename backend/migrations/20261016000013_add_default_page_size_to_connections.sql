@@ -0,0 +1,2 @@
+-- Rows per page when a listing omits per_page (NULL = server default)
+ALTER TABLE saved_connections ADD COLUMN default_page_size INTEGER;
@@ -0,0 +1,3 @@
+-- Applied to every pooled session: SET search_path / SET ROLE (PostgreSQL only)
+ALTER TABLE saved_connections ADD COLUMN search_path TEXT;
+ALTER TABLE saved_connections ADD COLUMN role TEXT;
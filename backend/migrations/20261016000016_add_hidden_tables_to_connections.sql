@@ -0,0 +1,2 @@
+-- Tables the app treats as nonexistent on this connection
+ALTER TABLE saved_connections ADD COLUMN hidden_tables TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,2 @@
+-- Read replicas ("host" or "host:port") sharing the primary's credentials
+ALTER TABLE saved_connections ADD COLUMN replica_hosts TEXT[] NOT NULL DEFAULT '{}';
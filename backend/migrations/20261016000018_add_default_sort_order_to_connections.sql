@@ -0,0 +1,2 @@
+-- Direction ("asc" or "desc") for sorted listings that omit sort_order
+ALTER TABLE saved_connections ADD COLUMN default_sort_order TEXT;
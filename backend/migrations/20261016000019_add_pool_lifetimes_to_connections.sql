@@ -0,0 +1,3 @@
+-- Per-connection overrides for how long pooled connections may idle or live
+ALTER TABLE saved_connections ADD COLUMN pool_idle_timeout_ms INTEGER;
+ALTER TABLE saved_connections ADD COLUMN pool_max_lifetime_ms INTEGER;
@@ -0,0 +1,2 @@
+-- Schema listed and resolved in ahead of search_path (PostgreSQL only)
+ALTER TABLE saved_connections ADD COLUMN default_schema TEXT;
@@ -0,0 +1,45 @@
+-- Connection names are unique per organization, and per owner for personal
+-- connections. Existing duplicates keep the oldest name; later ones get the
+-- first numeric suffix not already taken in the same scope.
+DO $$
+DECLARE
+    dup RECORD;
+    candidate TEXT;
+    n INT;
+BEGIN
+    FOR dup IN
+        SELECT id, name, organization_id, owner_user_id
+        FROM (
+            SELECT id, name, organization_id, owner_user_id, created_at,
+                   ROW_NUMBER() OVER (
+                       PARTITION BY COALESCE(organization_id, owner_user_id), organization_id IS NULL, name
+                       ORDER BY created_at, id
+                   ) AS rank
+            FROM saved_connections
+        ) ranked
+        WHERE rank > 1
+        ORDER BY created_at, id
+    LOOP
+        n := 2;
+        LOOP
+            candidate := LEFT(dup.name, 190) || ' (' || n || ')';
+            EXIT WHEN NOT EXISTS (
+                SELECT 1 FROM saved_connections
+                WHERE name = candidate
+                  AND organization_id IS NOT DISTINCT FROM dup.organization_id
+                  AND (dup.organization_id IS NOT NULL
+                       OR owner_user_id IS NOT DISTINCT FROM dup.owner_user_id)
+            );
+            n := n + 1;
+        END LOOP;
+        UPDATE saved_connections SET name = candidate WHERE id = dup.id;
+    END LOOP;
+END $$;
+
+CREATE UNIQUE INDEX uq_saved_connections_org_name
+    ON saved_connections (organization_id, name)
+    WHERE organization_id IS NOT NULL;
+
+CREATE UNIQUE INDEX uq_saved_connections_owner_name
+    ON saved_connections (owner_user_id, name)
+    WHERE organization_id IS NULL;
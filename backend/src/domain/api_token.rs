@@ -0,0 +1,99 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::permission::PermissionLevel;
+use crate::domain::user::AppUser;
+
+/// Role given to the principal a request authenticates as with an API token.
+pub const API_TOKEN_ROLE: &str = "api_token";
+
+/// A bearer token scoped to one connection. Only the hash is stored.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    pub name: String,
+    pub permission: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub created_by: Uuid,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Returned once when a token is created; the plaintext is never shown again.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedApiToken {
+    #[serde(flatten)]
+    pub api_token: ApiToken,
+    pub token: String,
+}
+
+/// What a token-authenticated request may touch: one connection, at one level.
+/// `created_by` is the user the token's actions are attributed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiTokenScope {
+    pub connection_id: Uuid,
+    pub permission: PermissionLevel,
+    pub created_by: Uuid,
+}
+
+impl ApiToken {
+    /// The synthetic user a request made with this token acts as. Its access
+    /// is exactly the token's scope; actions are attributed to the creator.
+    pub fn principal(&self) -> AppUser {
+        AppUser {
+            id: self.id,
+            name: self.name.clone(),
+            email: String::new(),
+            role: API_TOKEN_ROLE.to_string(),
+            auth_provider: None,
+            provider_id: None,
+            avatar_url: None,
+            created_at: Some(self.created_at),
+            updated_at: None,
+            acting_admin_id: None,
+            api_token_scope: Some(ApiTokenScope {
+                connection_id: self.connection_id,
+                permission: PermissionLevel::from_str(&self.permission),
+                created_by: self.created_by,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn principal_carries_scope_and_creator() {
+        let token = ApiToken {
+            id: Uuid::new_v4(),
+            connection_id: Uuid::new_v4(),
+            name: "etl".to_string(),
+            permission: "read".to_string(),
+            token_hash: "hash".to_string(),
+            created_by: Uuid::new_v4(),
+            expires_at: None,
+            revoked_at: None,
+            created_at: chrono::Utc::now(),
+        };
+
+        let principal = token.principal();
+        assert_eq!(principal.role, API_TOKEN_ROLE);
+        assert_eq!(principal.acting_admin_id, None);
+        assert_eq!(principal.actor_id(), token.created_by);
+        assert_eq!(
+            principal.api_token_scope,
+            Some(ApiTokenScope {
+                connection_id: token.connection_id,
+                permission: PermissionLevel::Read,
+                created_by: token.created_by,
+            })
+        );
+        let json = serde_json::to_value(&token).unwrap();
+        assert!(json.get("token_hash").is_none());
+    }
+}
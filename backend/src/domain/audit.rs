@@ -0,0 +1,62 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A single recorded action, against a connection unless it is platform-wide
+/// (such as a super_admin acting as another user).
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub connection_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub table_name: Option<String>,
+    pub details: Option<serde_json::Value>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// An audit entry for a write made inside a held transaction. It is only
+/// recorded once the transaction commits, and dropped on rollback.
+#[derive(Debug, Clone)]
+pub struct PendingAuditEntry {
+    pub connection_id: Uuid,
+    pub actor_id: Uuid,
+    pub action: String,
+    pub table_name: String,
+    pub details: Option<serde_json::Value>,
+}
+
+/// A datasource call made through the API, recorded for debugging and
+/// compliance. Only the operation and table are kept, never SQL or row data.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct QueryLogEntry {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub operation: String,
+    pub table_name: Option<String>,
+    pub duration_ms: i64,
+    pub success: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Filters for listing audit entries. Unset fields match everything.
+#[derive(Debug, Clone)]
+pub struct AuditFilter {
+    pub action: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+impl Default for AuditFilter {
+    fn default() -> Self {
+        Self {
+            action: None,
+            actor_id: None,
+            since: None,
+            page: 1,
+            per_page: 20,
+        }
+    }
+}
@@ -19,6 +19,18 @@ pub struct SavedConnectionRow {
     pub owner_user_id: Option<Uuid>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub tags: Vec<String>,
+    pub statement_timeout_ms: Option<i32>,
+    pub pool_idle_timeout_ms: Option<i32>,
+    pub pool_max_lifetime_ms: Option<i32>,
+    pub read_only: bool,
+    pub default_page_size: Option<i32>,
+    pub default_sort_order: Option<String>,
+    pub search_path: Option<String>,
+    pub default_schema: Option<String>,
+    pub role: Option<String>,
+    pub hidden_tables: Vec<String>,
+    pub replica_hosts: Vec<String>,
 }
 
 /// Returned to API (no password)
@@ -35,6 +47,18 @@ pub struct SavedConnectionResponse {
     pub created_by: Option<Uuid>,
     pub owner_user_id: Option<Uuid>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub tags: Vec<String>,
+    pub statement_timeout_ms: Option<i32>,
+    pub pool_idle_timeout_ms: Option<i32>,
+    pub pool_max_lifetime_ms: Option<i32>,
+    pub read_only: bool,
+    pub default_page_size: Option<i32>,
+    pub default_sort_order: Option<String>,
+    pub search_path: Option<String>,
+    pub default_schema: Option<String>,
+    pub role: Option<String>,
+    pub hidden_tables: Vec<String>,
+    pub replica_hosts: Vec<String>,
 }
 
 impl From<&SavedConnectionRow> for SavedConnectionResponse {
@@ -51,6 +75,18 @@ impl From<&SavedConnectionRow> for SavedConnectionResponse {
             created_by: row.created_by,
             owner_user_id: row.owner_user_id,
             created_at: row.created_at,
+            tags: row.tags.clone(),
+            statement_timeout_ms: row.statement_timeout_ms,
+            pool_idle_timeout_ms: row.pool_idle_timeout_ms,
+            pool_max_lifetime_ms: row.pool_max_lifetime_ms,
+            read_only: row.read_only,
+            default_page_size: row.default_page_size,
+            default_sort_order: row.default_sort_order.clone(),
+            search_path: row.search_path.clone(),
+            default_schema: row.default_schema.clone(),
+            role: row.role.clone(),
+            hidden_tables: row.hidden_tables.clone(),
+            replica_hosts: row.replica_hosts.clone(),
         }
     }
 }
@@ -68,6 +104,319 @@ pub struct ConnectionInfo {
     pub password: String,
     pub organization_id: Option<Uuid>,
     pub owner_user_id: Option<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Per-connection statement timeout override in milliseconds.
+    /// `None` falls back to the server-wide default.
+    #[serde(default)]
+    pub statement_timeout_ms: Option<u32>,
+    /// Close pooled connections idle this long. `None` falls back to
+    /// `POOL_IDLE_TIMEOUT_MS`; `0` keeps them open.
+    #[serde(default)]
+    pub pool_idle_timeout_ms: Option<u32>,
+    /// Replace pooled connections after this long. `None` falls back to
+    /// `POOL_MAX_LIFETIME_MS`; `0` lets them live indefinitely.
+    #[serde(default)]
+    pub pool_max_lifetime_ms: Option<u32>,
+    /// Refuse every write through this tool, regardless of permissions.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Rows per page when a listing omits `per_page`. `None` uses 20.
+    #[serde(default)]
+    pub default_page_size: Option<u32>,
+    /// Direction (`asc` or `desc`) for sorted listings that omit
+    /// `sort_order`. `None` sorts ascending.
+    #[serde(default)]
+    pub default_sort_order: Option<String>,
+    /// Comma-separated schemas set as `search_path` on every pooled
+    /// session (PostgreSQL only).
+    #[serde(default)]
+    pub search_path: Option<String>,
+    /// Schema that tables are listed from and resolved in, put ahead of
+    /// `search_path` on every pooled session (PostgreSQL only). `None`
+    /// keeps the server's default, normally `public`.
+    #[serde(default)]
+    pub default_schema: Option<String>,
+    /// Role assumed with `SET ROLE` on every pooled session (PostgreSQL only).
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Tables left out of `list_tables` and treated as nonexistent by every
+    /// table operation on this connection.
+    #[serde(default)]
+    pub hidden_tables: Vec<String>,
+    /// Read replicas as `host` or `host:port` (default: the primary's port),
+    /// reached with the primary's database and credentials. Reads are spread
+    /// across them; writes always go to the primary (PostgreSQL only).
+    #[serde(default)]
+    pub replica_hosts: Vec<String>,
+    /// Whether the connection is saved in the app database. `false` means it
+    /// only lives in memory (no connection repository or `ENCRYPTION_KEY`)
+    /// and is gone after a restart.
+    #[serde(default)]
+    pub persisted: bool,
+}
+
+/// The per-connection settings of a new connection, everything but where it
+/// points and who owns it. Create, import and the startup config all pass
+/// one of these instead of each setting as its own argument.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionSettings {
+    pub tags: Vec<String>,
+    pub statement_timeout_ms: Option<u32>,
+    pub pool_idle_timeout_ms: Option<u32>,
+    pub pool_max_lifetime_ms: Option<u32>,
+    pub read_only: bool,
+    pub default_page_size: Option<u32>,
+    pub default_sort_order: Option<String>,
+    pub search_path: Option<String>,
+    pub default_schema: Option<String>,
+    pub role: Option<String>,
+    pub hidden_tables: Vec<String>,
+    pub replica_hosts: Vec<String>,
+}
+
+/// In-memory usage counters for a live connection. Not persisted, so they
+/// reset when the server restarts.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionStats {
+    pub connection_id: Uuid,
+    pub last_accessed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Number of times the connection's datasource was used (one per data request).
+    pub query_count: u64,
+}
+
+/// One datasource call kept in a connection's in-memory profile
+/// (see `QueryProfile`). Lost on restart, like `ConnectionStats`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlowQuery {
+    pub operation: String,
+    pub table_name: Option<String>,
+    pub duration_ms: f64,
+    pub success: bool,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Outcome of importing a single connection
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionImportResult {
+    pub name: String,
+    pub connection_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+/// Outcome of loading one saved connection into memory
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionLoadResult {
+    pub connection_id: Uuid,
+    pub name: String,
+    pub error: Option<String>,
+}
+
+/// Database backend a connection points at, as stored in `db_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionKind {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl ConnectionKind {
+    pub fn parse(db_type: &str) -> Option<Self> {
+        match db_type {
+            "postgres" => Some(Self::Postgres),
+            "mysql" => Some(Self::MySql),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    /// Port used when the request omits one. File-based backends have none.
+    pub fn default_port(&self) -> Option<u16> {
+        match self {
+            Self::Postgres => Some(5432),
+            Self::MySql => Some(3306),
+            Self::Sqlite => None,
+        }
+    }
+
+    /// Whether the backend is reached over the network (and so takes host/port).
+    pub fn is_networked(&self) -> bool {
+        self.default_port().is_some()
+    }
+}
+
+impl ConnectionInfo {
+    /// Whether the connection carries the given tag (exact match).
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// The `search_path` set on pooled sessions: `default_schema` first, so
+    /// it is what `current_schema()` resolves to, then the configured
+    /// `search_path` without it.
+    pub fn session_search_path(&self) -> Option<String> {
+        let Some(schema) = self.default_schema.as_deref() else {
+            return self.search_path.clone();
+        };
+        let mut schemas = vec![schema];
+        if let Some(search_path) = self.search_path.as_deref() {
+            schemas.extend(
+                search_path_schemas(search_path)
+                    .into_iter()
+                    .filter(|s| *s != schema),
+            );
+        }
+        Some(schemas.join(", "))
+    }
+
+    /// The key under which this connection's pool can be shared: the
+    /// normalized target plus every option that shapes the pool or its
+    /// sessions.
+    pub fn pool_key(&self) -> PoolKey {
+        let mut hidden_tables = self.hidden_tables.clone();
+        hidden_tables.sort();
+        PoolKey {
+            db_type: self.db_type.clone(),
+            host: self.host.trim().to_ascii_lowercase(),
+            port: self.port,
+            database: self.database.clone(),
+            user: self.user.clone(),
+            password: self.password.clone(),
+            statement_timeout_ms: self.statement_timeout_ms,
+            pool_idle_timeout_ms: self.pool_idle_timeout_ms,
+            pool_max_lifetime_ms: self.pool_max_lifetime_ms,
+            read_only: self.read_only,
+            search_path: self.session_search_path(),
+            role: self.role.clone(),
+            hidden_tables,
+            replica_hosts: self
+                .replica_hosts
+                .iter()
+                .map(|h| h.trim().to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Whether `other` would take this connection's name in its scope:
+    /// org connections are unique per organization, personal ones per owner.
+    pub fn name_conflicts_with(&self, other: &ConnectionInfo) -> bool {
+        if self.name != other.name {
+            return false;
+        }
+        match (self.organization_id, other.organization_id) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                self.owner_user_id.is_some() && self.owner_user_id == other.owner_user_id
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the name, host or database contains `search`, ignoring case.
+    pub fn matches_search(&self, search: &str) -> bool {
+        let search = search.to_lowercase();
+        [&self.name, &self.host, &self.database]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&search))
+    }
+}
+
+/// Identifies a live pool that connections may share (see
+/// `ConnectionInfo::pool_key`). Two connections share a pool only when
+/// every field matches.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PoolKey {
+    db_type: String,
+    host: String,
+    port: u16,
+    database: String,
+    user: String,
+    password: String,
+    statement_timeout_ms: Option<u32>,
+    pool_idle_timeout_ms: Option<u32>,
+    pool_max_lifetime_ms: Option<u32>,
+    read_only: bool,
+    search_path: Option<String>,
+    role: Option<String>,
+    hidden_tables: Vec<String>,
+    replica_hosts: Vec<String>,
+}
+
+/// A connection with the same name already exists in the organization (or,
+/// for personal connections, for the same owner).
+#[derive(Debug)]
+pub struct ConnectionNameTaken(pub String);
+
+impl std::fmt::Display for ConnectionNameTaken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "A connection named '{}' already exists", self.0)
+    }
+}
+
+impl std::error::Error for ConnectionNameTaken {}
+
+/// PostgreSQL truncates identifiers beyond this many bytes.
+const MAX_IDENTIFIER_BYTES: usize = 63;
+
+/// Schemas named by a comma-separated `search_path`, trimmed.
+pub fn search_path_schemas(search_path: &str) -> Vec<&str> {
+    search_path.split(',').map(str::trim).collect()
+}
+
+/// Check a connection's `default_sort_order` and lowercase it: only `asc`
+/// and `desc` are accepted.
+pub fn normalize_sort_order(order: Option<String>) -> Result<Option<String>, String> {
+    match order.map(|o| o.trim().to_ascii_lowercase()) {
+        Some(o) if o == "asc" || o == "desc" => Ok(Some(o)),
+        Some(o) => Err(format!(
+            "Invalid default_sort_order '{}': expected asc or desc",
+            o
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Check the per-session settings a connection applies on open. Each
+/// schema and the role must be a non-empty identifier; they are always
+/// quoted when set, so no other SQL can be smuggled through them.
+/// `default_schema` names exactly one schema.
+pub fn validate_session_settings(
+    default_schema: Option<&str>,
+    search_path: Option<&str>,
+    role: Option<&str>,
+) -> Result<(), String> {
+    let check = |what: &str, name: &str| {
+        if name.is_empty() {
+            return Err(format!("{} must not be empty", what));
+        }
+        if name.len() > MAX_IDENTIFIER_BYTES || name.contains('\0') {
+            return Err(format!("Invalid {} '{}'", what, name));
+        }
+        Ok(())
+    };
+    if let Some(schema) = default_schema {
+        if schema.contains(',') {
+            return Err(format!("Invalid default_schema '{}'", schema));
+        }
+        check("default_schema", schema)?;
+    }
+    if let Some(search_path) = search_path {
+        for schema in search_path_schemas(search_path) {
+            check("search_path schema", schema)?;
+        }
+    }
+    if let Some(role) = role {
+        check("role", role)?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -86,9 +435,56 @@ mod tests {
             password: "secret123".to_string(),
             organization_id: None,
             owner_user_id: None,
+            tags: vec![],
+            statement_timeout_ms: None,
+            pool_idle_timeout_ms: None,
+            pool_max_lifetime_ms: None,
+            read_only: false,
+            default_page_size: None,
+            default_sort_order: None,
+            search_path: None,
+            default_schema: None,
+            role: None,
+            hidden_tables: vec![],
+            replica_hosts: vec![],
+            persisted: false,
         }
     }
 
+    #[test]
+    fn session_settings_must_be_identifiers() {
+        assert!(validate_session_settings(None, None, None).is_ok());
+        assert!(
+            validate_session_settings(Some("analytics"), Some("tenant_a, public"), Some("reader"))
+                .is_ok()
+        );
+        assert!(validate_session_settings(None, Some("tenant_a,,public"), None).is_err());
+        assert!(validate_session_settings(None, None, Some("")).is_err());
+        assert!(validate_session_settings(None, None, Some(&"r".repeat(64))).is_err());
+        assert!(validate_session_settings(Some("a, b"), None, None).is_err());
+        assert!(validate_session_settings(Some(""), None, None).is_err());
+    }
+
+    #[test]
+    fn default_schema_leads_session_search_path() {
+        let info = ConnectionInfo {
+            default_schema: Some("analytics".to_string()),
+            search_path: Some("public, analytics, shared".to_string()),
+            ..sample_connection_info()
+        };
+        assert_eq!(
+            info.session_search_path().as_deref(),
+            Some("analytics, public, shared")
+        );
+
+        let info = ConnectionInfo {
+            search_path: None,
+            ..info
+        };
+        assert_eq!(info.session_search_path().as_deref(), Some("analytics"));
+        assert_eq!(sample_connection_info().session_search_path(), None);
+    }
+
     #[test]
     fn password_is_not_serialized() {
         let info = sample_connection_info();
@@ -139,6 +535,18 @@ mod tests {
             owner_user_id: None,
             created_at: None,
             updated_at: None,
+            tags: vec!["prod".to_string()],
+            statement_timeout_ms: Some(5000),
+            pool_idle_timeout_ms: None,
+            pool_max_lifetime_ms: None,
+            read_only: false,
+            default_page_size: None,
+            default_sort_order: None,
+            search_path: None,
+            default_schema: None,
+            role: None,
+            hidden_tables: vec![],
+            replica_hosts: vec![],
         };
 
         let response = SavedConnectionResponse::from(&row);
@@ -150,5 +558,115 @@ mod tests {
         assert_eq!(response.username, "admin");
         assert_eq!(response.organization_id, row.organization_id);
         assert_eq!(response.created_by, row.created_by);
+        assert_eq!(response.tags, vec!["prod".to_string()]);
+        assert_eq!(response.statement_timeout_ms, Some(5000));
+    }
+
+    #[test]
+    fn has_tag_matches_exactly() {
+        let info = ConnectionInfo {
+            tags: vec!["prod".to_string(), "analytics".to_string()],
+            ..sample_connection_info()
+        };
+        assert!(info.has_tag("prod"));
+        assert!(info.has_tag("analytics"));
+        assert!(!info.has_tag("Prod"));
+        assert!(!info.has_tag("staging"));
+    }
+
+    #[test]
+    fn pool_key_ignores_identity_but_not_pool_options() {
+        let base = sample_connection_info();
+        let renamed = ConnectionInfo {
+            id: Uuid::new_v4(),
+            name: "other".to_string(),
+            host: " LocalHost ".to_string(),
+            owner_user_id: Some(Uuid::new_v4()),
+            tags: vec!["prod".to_string()],
+            default_page_size: Some(10),
+            ..sample_connection_info()
+        };
+        assert!(base.pool_key() == renamed.pool_key());
+
+        let read_only = ConnectionInfo {
+            read_only: true,
+            ..sample_connection_info()
+        };
+        let short_lived = ConnectionInfo {
+            pool_max_lifetime_ms: Some(1000),
+            ..sample_connection_info()
+        };
+        assert!(base.pool_key() != read_only.pool_key());
+        assert!(base.pool_key() != short_lived.pool_key());
+    }
+
+    #[test]
+    fn name_conflicts_only_within_the_same_scope() {
+        let org = Uuid::new_v4();
+        let owner = Uuid::new_v4();
+        let in_org = |org_id| ConnectionInfo {
+            organization_id: Some(org_id),
+            ..sample_connection_info()
+        };
+        let personal = |owner_id| ConnectionInfo {
+            owner_user_id: Some(owner_id),
+            ..sample_connection_info()
+        };
+        assert!(in_org(org).name_conflicts_with(&in_org(org)));
+        assert!(!in_org(org).name_conflicts_with(&in_org(Uuid::new_v4())));
+        assert!(personal(owner).name_conflicts_with(&personal(owner)));
+        assert!(!personal(owner).name_conflicts_with(&personal(Uuid::new_v4())));
+        assert!(!in_org(org).name_conflicts_with(&personal(owner)));
+        let renamed = ConnectionInfo {
+            name: "other".to_string(),
+            ..in_org(org)
+        };
+        assert!(!in_org(org).name_conflicts_with(&renamed));
+        // Unscoped connections are never persisted, so never clash
+        assert!(!sample_connection_info().name_conflicts_with(&sample_connection_info()));
+    }
+
+    #[test]
+    fn normalize_sort_order_accepts_only_asc_and_desc() {
+        assert_eq!(normalize_sort_order(None), Ok(None));
+        assert_eq!(
+            normalize_sort_order(Some(" DESC ".to_string())),
+            Ok(Some("desc".to_string()))
+        );
+        assert!(normalize_sort_order(Some("newest".to_string())).is_err());
+    }
+
+    #[test]
+    fn matches_search_ignores_case_across_name_host_and_database() {
+        let info = ConnectionInfo {
+            name: "Billing".to_string(),
+            host: "db-eu-1.internal".to_string(),
+            database: "ledger".to_string(),
+            ..sample_connection_info()
+        };
+        assert!(info.matches_search("bill"));
+        assert!(info.matches_search("EU-1"));
+        assert!(info.matches_search("Ledg"));
+        assert!(!info.matches_search("staging"));
+    }
+
+    #[test]
+    fn default_port_per_kind() {
+        assert_eq!(ConnectionKind::Postgres.default_port(), Some(5432));
+        assert_eq!(ConnectionKind::MySql.default_port(), Some(3306));
+        assert_eq!(ConnectionKind::Sqlite.default_port(), None);
+        assert!(!ConnectionKind::Sqlite.is_networked());
+    }
+
+    #[test]
+    fn connection_kind_round_trips_db_type() {
+        for kind in [
+            ConnectionKind::Postgres,
+            ConnectionKind::MySql,
+            ConnectionKind::Sqlite,
+        ] {
+            assert_eq!(ConnectionKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(ConnectionKind::parse("oracle"), None);
     }
 }
@@ -5,6 +5,19 @@ use serde::Serialize;
 pub struct TableInfo {
     pub table_name: String,
     pub table_schema: String,
+    /// Friendly name from app-level table metadata, if one was set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// App-level display metadata for a table, stored in the app database
+#[derive(Debug, Clone, Default, Serialize, sqlx::FromRow)]
+pub struct TableMetadata {
+    pub table_name: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
 }
 
 /// Metadata about a column within a table
@@ -16,6 +29,35 @@ pub struct ColumnInfo {
     pub column_default: Option<String>,
     pub is_primary_key: bool,
     pub max_length: Option<i32>,
+    /// Identity, auto-increment or generated column filled in by the database
+    pub is_generated: bool,
+    /// Human description from app-level column metadata, if one was set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Display formatting hint for clients (e.g. "currency", "datetime-local")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_hint: Option<String>,
+    /// Allowed values, in declaration order, when the column has an enum type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<String>>,
+    /// Element type of an array column (e.g. `text`, `integer`), PostgreSQL only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element_type: Option<String>,
+}
+
+/// App-level display metadata for a column, stored in the app database
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ColumnMetadata {
+    pub column_name: String,
+    pub description: Option<String>,
+    pub display_hint: Option<String>,
+}
+
+impl ColumnInfo {
+    /// Whether an insert must supply a value for this column.
+    pub fn is_required(&self) -> bool {
+        !self.is_nullable && self.column_default.is_none() && !self.is_generated
+    }
 }
 
 /// Schema for a table (columns + primary key)
@@ -26,6 +68,239 @@ pub struct TableSchema {
     pub primary_key_columns: Vec<String>,
 }
 
+impl TableSchema {
+    /// Check an insert payload against the schema, returning one message per
+    /// problem: required columns that are missing (or null) and keys that do
+    /// not name a column. An empty result means the payload looks insertable.
+    pub fn validate_insert(&self, data: &serde_json::Value) -> Vec<String> {
+        let Some(obj) = data.as_object() else {
+            return vec!["Row data must be a JSON object".to_string()];
+        };
+        let mut problems = Vec::new();
+        for col in self.columns.iter().filter(|c| c.is_required()) {
+            if obj.get(&col.column_name).is_none_or(|v| v.is_null()) {
+                problems.push(format!("Missing required column '{}'", col.column_name));
+            }
+        }
+        for key in obj.keys() {
+            if !self.columns.iter().any(|c| &c.column_name == key) {
+                problems.push(format!("Unknown column '{}'", key));
+            }
+        }
+        problems
+    }
+
+    /// Describe the table as a JSON Schema object for form libraries.
+    /// Nullable columns also accept `null`, required lists the columns an
+    /// insert must supply, and primary key columns are flagged with the
+    /// `x-primary-key` extension.
+    pub fn to_json_schema(&self) -> serde_json::Value {
+        let mut properties = serde_json::Map::new();
+        for col in &self.columns {
+            let mut prop = serde_json::Map::new();
+            if let Some(json_type) = json_type(&col.data_type) {
+                prop.insert(
+                    "type".to_string(),
+                    if col.is_nullable {
+                        serde_json::json!([json_type, "null"])
+                    } else {
+                        serde_json::json!(json_type)
+                    },
+                );
+            }
+            if let Some(format) = json_format(&col.data_type) {
+                prop.insert("format".to_string(), serde_json::json!(format));
+            }
+            if let Some(max_length) = col.max_length {
+                prop.insert("maxLength".to_string(), serde_json::json!(max_length));
+            }
+            if let Some(values) = &col.enum_values {
+                let mut values = serde_json::json!(values);
+                if col.is_nullable {
+                    values.as_array_mut().unwrap().push(serde_json::Value::Null);
+                }
+                prop.insert("enum".to_string(), values);
+            }
+            if let Some(description) = &col.description {
+                prop.insert("description".to_string(), serde_json::json!(description));
+            }
+            if col.is_generated {
+                prop.insert("readOnly".to_string(), serde_json::json!(true));
+            }
+            if col.is_primary_key {
+                prop.insert("x-primary-key".to_string(), serde_json::json!(true));
+            }
+            properties.insert(col.column_name.clone(), serde_json::Value::Object(prop));
+        }
+        let required: Vec<&str> = self
+            .columns
+            .iter()
+            .filter(|c| c.is_required())
+            .map(|c| c.column_name.as_str())
+            .collect();
+
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": self.table_name,
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    /// The database-provided default of every column that has one, for
+    /// insert forms to show before submission.
+    pub fn insert_defaults(&self) -> Vec<InsertDefault> {
+        self.columns
+            .iter()
+            .filter_map(|col| {
+                let expression = col.column_default.as_ref()?;
+                let (kind, value) = evaluate_default(expression, &col.data_type);
+                Some(InsertDefault {
+                    column_name: col.column_name.clone(),
+                    expression: expression.clone(),
+                    kind,
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
+/// How a column default was classified by `TableSchema::insert_defaults`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultKind {
+    /// A constant; `value` holds it.
+    Literal,
+    /// The current time or date; `value` is the app server's clock now.
+    Timestamp,
+    /// The next value of a sequence, unknown until the insert runs.
+    Sequence,
+    /// Any other expression, left unevaluated.
+    Expression,
+}
+
+/// One column's default, as reported by `.../insert-defaults`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InsertDefault {
+    pub column_name: String,
+    /// The default exactly as the database reports it
+    pub expression: String,
+    pub kind: DefaultKind,
+    /// Best-effort evaluation; `None` when it can't be known in advance
+    pub value: Option<serde_json::Value>,
+}
+
+/// Classify a `column_default` expression and evaluate it when it is a
+/// simple literal or a current-time function. PostgreSQL reports literals
+/// with a cast (`'active'::text`), which is stripped first.
+fn evaluate_default(expression: &str, data_type: &str) -> (DefaultKind, Option<serde_json::Value>) {
+    let expr = expression.trim();
+    let lower = expr.to_ascii_lowercase();
+    if lower.starts_with("nextval(") {
+        return (DefaultKind::Sequence, None);
+    }
+    let function = lower.split('(').next().unwrap_or_default().trim();
+    match function {
+        "now"
+        | "current_timestamp"
+        | "localtimestamp"
+        | "transaction_timestamp"
+        | "statement_timestamp"
+        | "clock_timestamp" => {
+            let now = chrono::Utc::now();
+            let text = match data_type.to_ascii_lowercase().as_str() {
+                "date" => now.format("%Y-%m-%d").to_string(),
+                "timestamp without time zone" | "datetime" => {
+                    now.format("%Y-%m-%dT%H:%M:%S%.6f").to_string()
+                }
+                _ => now.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string(),
+            };
+            return (DefaultKind::Timestamp, Some(serde_json::json!(text)));
+        }
+        "current_date" => {
+            let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+            return (DefaultKind::Timestamp, Some(serde_json::json!(today)));
+        }
+        _ => {}
+    }
+
+    // Drop a trailing `::type` cast, but not one inside a quoted string
+    let literal = match expr.rfind("::") {
+        Some(i) if !expr[i..].contains('\'') => expr[..i].trim(),
+        _ => expr,
+    };
+    let (text, quoted) = match literal
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+    {
+        Some(inner) => (inner.replace("''", "'"), true),
+        None => (literal.trim_matches(['(', ')']).to_string(), false),
+    };
+    let value = match json_type(data_type) {
+        _ if !quoted && text.eq_ignore_ascii_case("null") => Some(serde_json::Value::Null),
+        Some("integer") => text.parse::<i64>().ok().map(|n| serde_json::json!(n)),
+        Some("number") => text.parse::<f64>().ok().map(|n| serde_json::json!(n)),
+        Some("boolean") => text
+            .to_ascii_lowercase()
+            .parse::<bool>()
+            .ok()
+            .map(|b| serde_json::json!(b)),
+        _ if quoted => Some(serde_json::json!(text)),
+        _ => None,
+    };
+    match value {
+        Some(value) => (DefaultKind::Literal, Some(value)),
+        None => (DefaultKind::Expression, None),
+    }
+}
+
+/// What the caller can do with a table, so clients know whether to offer
+/// edit controls. Writes need a primary key, a base table, write permission
+/// and a connection that isn't read-only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableCapabilities {
+    pub can_read: bool,
+    pub can_write: bool,
+    pub has_primary_key: bool,
+    pub is_view: bool,
+}
+
+/// `$type` of the object that stands in for binary (bytea) column values.
+/// The bytes travel base64-encoded next to it:
+/// `{"$type": "bytea", "base64": "3q2+7w=="}`. Writes accept the same form.
+pub const BYTEA_TYPE_MARKER: &str = "bytea";
+
+/// JSON type a column's values serialize as, or `None` when any JSON value
+/// is possible (json/jsonb columns).
+fn json_type(data_type: &str) -> Option<&'static str> {
+    match data_type.to_lowercase().as_str() {
+        "smallint" | "integer" | "bigint" | "int" | "tinyint" | "mediumint" | "year" => {
+            Some("integer")
+        }
+        "numeric" | "decimal" | "real" | "double precision" | "double" | "float" => Some("number"),
+        "boolean" | "bool" => Some("boolean"),
+        "array" => Some("array"),
+        "bytea" => Some("object"),
+        "json" | "jsonb" => None,
+        _ => Some("string"),
+    }
+}
+
+/// JSON Schema `format` for string columns with a well-known representation.
+fn json_format(data_type: &str) -> Option<&'static str> {
+    match data_type.to_lowercase().as_str() {
+        "timestamp with time zone" | "timestamp without time zone" | "timestamp" | "datetime" => {
+            Some("date-time")
+        }
+        "date" => Some("date"),
+        "time with time zone" | "time without time zone" | "time" => Some("time"),
+        "uuid" => Some("uuid"),
+        _ => None,
+    }
+}
+
 /// Paginated response for rows
 #[derive(Debug, Serialize)]
 pub struct RowsResponse {
@@ -34,3 +309,268 @@ pub struct RowsResponse {
     pub page: u32,
     pub per_page: u32,
 }
+
+/// One `ORDER BY` term parsed from a `sort_by` list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: String,
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Parse `col[:asc|desc][,col[:asc|desc]...]`. Terms without a direction
+    /// use `default_order`, so the single-column `sort_by` + `sort_order`
+    /// form keeps working.
+    pub fn parse_list(sort_by: &str, default_order: Option<&str>) -> Vec<SortKey> {
+        let default_desc = default_order.is_some_and(|o| o.eq_ignore_ascii_case("desc"));
+        sort_by
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(|term| match term.rsplit_once(':') {
+                Some((column, dir)) if dir.eq_ignore_ascii_case("asc") => SortKey {
+                    column: column.to_string(),
+                    descending: false,
+                },
+                Some((column, dir)) if dir.eq_ignore_ascii_case("desc") => SortKey {
+                    column: column.to_string(),
+                    descending: true,
+                },
+                _ => SortKey {
+                    column: term.to_string(),
+                    descending: default_desc,
+                },
+            })
+            .collect()
+    }
+
+    pub fn direction(&self) -> &'static str {
+        if self.descending { "DESC" } else { "ASC" }
+    }
+}
+
+/// Aggregate functions allowed in group-by queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateFn {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Count,
+}
+
+impl AggregateFn {
+    /// Parse a function name (case-insensitive). Returns `None` for anything
+    /// outside the allowlist.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sum" => Some(Self::Sum),
+            "avg" => Some(Self::Avg),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "count" => Some(Self::Count),
+            _ => None,
+        }
+    }
+
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Self::Sum => "SUM",
+            Self::Avg => "AVG",
+            Self::Min => "MIN",
+            Self::Max => "MAX",
+            Self::Count => "COUNT",
+        }
+    }
+}
+
+/// A validated `SELECT group, agg(metric) ... GROUP BY group` request.
+/// `metric_col` may only be omitted for `count`, which then counts rows.
+#[derive(Debug, Clone)]
+pub struct AggregateSpec {
+    pub group_by: String,
+    pub metric_col: Option<String>,
+    pub agg_fn: AggregateFn,
+}
+
+/// One group in an aggregate result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AggregateRow {
+    pub group: serde_json::Value,
+    pub value: serde_json::Value,
+}
+
+/// A row change pushed by the database through `LISTEN/NOTIFY`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowChange {
+    pub table: String,
+    /// Lowercase operation name, used as the SSE event type (e.g. "insert")
+    pub operation: String,
+    pub row: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_key_single_column_uses_default_order() {
+        assert_eq!(
+            SortKey::parse_list("name", Some("DESC")),
+            vec![SortKey {
+                column: "name".to_string(),
+                descending: true,
+            }]
+        );
+        assert!(!SortKey::parse_list("name", None)[0].descending);
+    }
+
+    #[test]
+    fn sort_key_parses_list_with_directions() {
+        let keys = SortKey::parse_list("last_name:asc, created_at:desc,id", Some("desc"));
+        let parsed: Vec<(&str, bool)> = keys
+            .iter()
+            .map(|k| (k.column.as_str(), k.descending))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![("last_name", false), ("created_at", true), ("id", true)]
+        );
+    }
+
+    #[test]
+    fn aggregate_fn_parses_allowlist_case_insensitively() {
+        assert_eq!(AggregateFn::parse("sum"), Some(AggregateFn::Sum));
+        assert_eq!(AggregateFn::parse("AVG"), Some(AggregateFn::Avg));
+        assert_eq!(AggregateFn::parse("Count"), Some(AggregateFn::Count));
+        assert_eq!(AggregateFn::parse("stddev"), None);
+        assert_eq!(AggregateFn::parse("sum(1); --"), None);
+    }
+
+    fn column(name: &str, is_nullable: bool, default: Option<&str>, generated: bool) -> ColumnInfo {
+        ColumnInfo {
+            column_name: name.to_string(),
+            data_type: "text".to_string(),
+            is_nullable,
+            column_default: default.map(str::to_string),
+            is_primary_key: false,
+            max_length: None,
+            is_generated: generated,
+            description: None,
+            display_hint: None,
+            enum_values: None,
+            element_type: None,
+        }
+    }
+
+    #[test]
+    fn validate_insert_reports_missing_required_and_unknown_columns() {
+        let schema = TableSchema {
+            table_name: "users".to_string(),
+            columns: vec![
+                column("id", false, None, true),
+                column("email", false, None, false),
+                column("status", false, Some("'active'"), false),
+                column("nickname", true, None, false),
+            ],
+            primary_key_columns: vec!["id".to_string()],
+        };
+
+        assert!(
+            schema
+                .validate_insert(&serde_json::json!({ "email": "a@example.com" }))
+                .is_empty()
+        );
+        assert_eq!(
+            schema.validate_insert(&serde_json::json!({ "email": null, "nick": "x" })),
+            vec![
+                "Missing required column 'email'".to_string(),
+                "Unknown column 'nick'".to_string(),
+            ]
+        );
+        assert_eq!(schema.validate_insert(&serde_json::json!([1])).len(), 1);
+    }
+
+    #[test]
+    fn json_schema_maps_varchar_constraints() {
+        let mut name = column("name", false, None, false);
+        name.data_type = "character varying".to_string();
+        name.max_length = Some(50);
+        let mut id = column("id", false, None, true);
+        id.data_type = "integer".to_string();
+        id.is_primary_key = true;
+        let schema = TableSchema {
+            table_name: "users".to_string(),
+            columns: vec![id, name, column("bio", true, None, false)],
+            primary_key_columns: vec!["id".to_string()],
+        };
+
+        let json_schema = schema.to_json_schema();
+        assert_eq!(
+            json_schema["properties"]["name"],
+            serde_json::json!({ "type": "string", "maxLength": 50 })
+        );
+        assert_eq!(
+            json_schema["properties"]["id"],
+            serde_json::json!({ "type": "integer", "readOnly": true, "x-primary-key": true })
+        );
+        assert_eq!(
+            json_schema["properties"]["bio"]["type"],
+            serde_json::json!(["string", "null"])
+        );
+        assert_eq!(json_schema["required"], serde_json::json!(["name"]));
+    }
+
+    #[test]
+    fn insert_defaults_evaluate_literals_and_current_time() {
+        let mut created_at = column("created_at", false, Some("now()"), false);
+        created_at.data_type = "timestamp with time zone".to_string();
+        let mut id = column(
+            "id",
+            false,
+            Some("nextval('users_id_seq'::regclass)"),
+            false,
+        );
+        id.data_type = "integer".to_string();
+        let mut score = column("score", false, Some("'-1'::integer"), false);
+        score.data_type = "integer".to_string();
+        let schema = TableSchema {
+            table_name: "users".to_string(),
+            columns: vec![
+                id,
+                column("status", false, Some("'it''s new'::text"), false),
+                score,
+                created_at,
+                column("token", false, Some("gen_random_uuid()"), false),
+                column("bio", true, None, false),
+            ],
+            primary_key_columns: vec!["id".to_string()],
+        };
+
+        let defaults = schema.insert_defaults();
+        let by_name = |name: &str| defaults.iter().find(|d| d.column_name == name).unwrap();
+        assert_eq!(defaults.len(), 5);
+        assert_eq!(by_name("id").kind, DefaultKind::Sequence);
+        assert_eq!(by_name("id").value, None);
+
+        let status = by_name("status");
+        assert_eq!(status.kind, DefaultKind::Literal);
+        assert_eq!(status.expression, "'it''s new'::text");
+        assert_eq!(status.value, Some(serde_json::json!("it's new")));
+        assert_eq!(by_name("score").value, Some(serde_json::json!(-1)));
+
+        let created_at = by_name("created_at");
+        assert_eq!(created_at.kind, DefaultKind::Timestamp);
+        assert!(
+            created_at
+                .value
+                .as_ref()
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .ends_with('Z')
+        );
+        assert_eq!(by_name("token").kind, DefaultKind::Expression);
+    }
+}
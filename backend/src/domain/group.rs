@@ -7,6 +7,8 @@ pub struct Group {
     pub organization_id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    /// Permissions granted to the parent (and its ancestors) apply to this group's members.
+    pub parent_group_id: Option<Uuid>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -1,8 +1,12 @@
+pub mod api_token;
+pub mod audit;
 pub mod connection;
 pub mod data;
 pub mod group;
+pub mod notification;
 pub mod organization;
 pub mod organization_member;
+pub mod pagination;
 pub mod permission;
 pub mod repository;
 pub mod user;
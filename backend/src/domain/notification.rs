@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+/// Delivers a message to a user outside the app, e.g. by email.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> anyhow::Result<()>;
+}
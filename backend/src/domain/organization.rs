@@ -8,3 +8,61 @@ pub struct Organization {
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+/// Maps an email domain to an organization. New users signing up with a
+/// verified email on this domain join the organization automatically.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OrgEmailDomain {
+    pub domain: String,
+    pub organization_id: Uuid,
+    pub default_role: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl OrgEmailDomain {
+    /// Normalize a user-supplied domain (`@Example.COM ` → `example.com`).
+    /// Returns `None` if the result does not look like a domain.
+    pub fn normalize(domain: &str) -> Option<String> {
+        let domain = domain.trim().trim_start_matches('@').to_ascii_lowercase();
+        let valid = domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+            && !domain.contains('@')
+            && !domain.chars().any(char::is_whitespace);
+        valid.then_some(domain)
+    }
+
+    /// Extract the normalized domain part of an email address.
+    pub fn of_email(email: &str) -> Option<String> {
+        email
+            .rsplit_once('@')
+            .and_then(|(local, domain)| (!local.is_empty()).then_some(domain))
+            .and_then(Self::normalize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_domain() {
+        assert_eq!(
+            OrgEmailDomain::normalize(" @Example.COM "),
+            Some("example.com".to_string())
+        );
+        assert_eq!(OrgEmailDomain::normalize("localhost"), None);
+        assert_eq!(OrgEmailDomain::normalize("a@b.com"), None);
+        assert_eq!(OrgEmailDomain::normalize(".example.com"), None);
+    }
+
+    #[test]
+    fn domain_of_email() {
+        assert_eq!(
+            OrgEmailDomain::of_email("Alice@Example.com"),
+            Some("example.com".to_string())
+        );
+        assert_eq!(OrgEmailDomain::of_email("@example.com"), None);
+        assert_eq!(OrgEmailDomain::of_email("not-an-email"), None);
+    }
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// One page of results plus the total number of matching items.
+#[derive(Debug, Clone, Serialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total_count: i64,
+    pub page: u32,
+    pub per_page: u32,
+}
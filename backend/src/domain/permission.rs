@@ -43,6 +43,21 @@ pub struct GroupConnectionPermission {
     pub granted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// ============================================================
+// Group Org Permission
+// ============================================================
+
+/// Org-wide default for a group: applies to every connection in the
+/// organization that has no connection-specific grant for the user.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GroupOrgPermission {
+    pub id: Uuid,
+    pub group_id: Uuid,
+    pub organization_id: Uuid,
+    pub permission: String,
+    pub granted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 // ============================================================
 // Group Table Permission
 // ============================================================
@@ -57,11 +72,74 @@ pub struct GroupTablePermission {
     pub granted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+// ============================================================
+// Permission Template
+// ============================================================
+
+/// A named bundle of table grants stored per organization. Applying it to a
+/// user or group on a connection expands it into table permission rows.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PermissionTemplate {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    #[sqlx(skip)]
+    pub tables: Vec<TemplateTableGrant>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema, sqlx::FromRow,
+)]
+pub struct TemplateTableGrant {
+    pub table_name: String,
+    pub permission: String,
+}
+
+// ============================================================
+// Connection Access (read model)
+// ============================================================
+
+/// How a user came to have access to a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessSource {
+    Owner,
+    Direct,
+    Group,
+}
+
+/// One row of the "who has access" view for a connection. A user reachable
+/// through several sources appears once per source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionAccessEntry {
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub source: AccessSource,
+    /// Group that granted the access, for `source == group`
+    pub group_id: Option<Uuid>,
+    pub permission: String,
+    pub all_tables: bool,
+}
+
+/// Permission rows that deleting a connection would cascade away.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, sqlx::FromRow)]
+pub struct ConnectionDeletionImpact {
+    pub user_connection_permissions: i64,
+    pub user_table_permissions: i64,
+    pub group_connection_permissions: i64,
+    pub group_table_permissions: i64,
+}
+
 // ============================================================
 // Resolved Permission Level (value object)
 // ============================================================
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PermissionLevel {
     None,
     Read,
@@ -89,6 +167,35 @@ impl PermissionLevel {
     }
 }
 
+/// Which rule decided a resolved permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionSource {
+    SuperAdmin,
+    Owner,
+    OrgMember,
+    User,
+    Group,
+    /// An org-wide group grant, used when no connection-specific grant exists
+    OrgDefault,
+    /// The scope of the API token the request authenticated with
+    ApiToken,
+    /// No rule granted anything
+    None,
+}
+
+/// A resolved permission together with the rule that produced it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedPermission {
+    pub level: PermissionLevel,
+    pub source: PermissionSource,
+    /// Whether a table-level grant decided the result rather than the connection-level one
+    pub table_level: bool,
+    /// Why access was denied, for showing to the affected user; `None` when
+    /// anything was granted
+    pub reason: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
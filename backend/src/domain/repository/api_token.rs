@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::api_token::ApiToken;
+
+#[async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    async fn create(
+        &self,
+        conn_id: &Uuid,
+        name: &str,
+        permission: &str,
+        token_hash: &str,
+        created_by: &Uuid,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<ApiToken>;
+
+    /// The token with this hash, if it is neither revoked nor expired.
+    async fn find_active_by_hash(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>>;
+
+    /// Every token for a connection, revoked and expired ones included, newest first.
+    async fn list_by_connection(&self, conn_id: &Uuid) -> anyhow::Result<Vec<ApiToken>>;
+
+    /// Mark a token revoked. Returns false if it does not exist on the
+    /// connection or was already revoked.
+    async fn revoke(&self, conn_id: &Uuid, token_id: &Uuid) -> anyhow::Result<bool>;
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::audit::{AuditEntry, AuditFilter};
+use crate::domain::pagination::Paginated;
+
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    async fn record(
+        &self,
+        conn_id: &Uuid,
+        actor_id: &Uuid,
+        action: &str,
+        table_name: Option<&str>,
+        details: Option<&serde_json::Value>,
+    ) -> anyhow::Result<AuditEntry>;
+
+    /// Record that `admin_id` made a request as `target_id` through
+    /// `X-Act-As`. The entry belongs to no connection.
+    async fn record_act_as(
+        &self,
+        admin_id: &Uuid,
+        target_id: &Uuid,
+        method: &str,
+        path: &str,
+    ) -> anyhow::Result<AuditEntry>;
+
+    /// Entries for a connection, newest first.
+    async fn list(
+        &self,
+        conn_id: &Uuid,
+        filter: &AuditFilter,
+    ) -> anyhow::Result<Paginated<AuditEntry>>;
+}
@@ -5,6 +5,8 @@ use crate::domain::connection::{ConnectionInfo, SavedConnectionRow};
 
 #[async_trait]
 pub trait ConnectionRepository: Send + Sync {
+    /// Insert a connection. The database generates the row's id; `info.id`
+    /// is ignored, so callers must use the id of the returned row.
     async fn save(
         &self,
         org_id: Option<&Uuid>,
@@ -12,9 +14,14 @@ pub trait ConnectionRepository: Send + Sync {
         info: &ConnectionInfo,
     ) -> anyhow::Result<SavedConnectionRow>;
     async fn list(&self) -> anyhow::Result<Vec<SavedConnectionRow>>;
+    async fn get(&self, conn_id: &Uuid) -> anyhow::Result<Option<SavedConnectionRow>>;
     async fn delete(&self, conn_id: &Uuid) -> anyhow::Result<bool>;
     async fn get_ownership(
         &self,
         conn_id: &Uuid,
     ) -> anyhow::Result<Option<(Option<Uuid>, Option<Uuid>)>>;
+    async fn set_tags(&self, conn_id: &Uuid, tags: &[String]) -> anyhow::Result<bool>;
+    async fn set_read_only(&self, conn_id: &Uuid, read_only: bool) -> anyhow::Result<bool>;
+    async fn set_password(&self, conn_id: &Uuid, password: &str) -> anyhow::Result<bool>;
+    async fn set_owner(&self, conn_id: &Uuid, owner_user_id: &Uuid) -> anyhow::Result<bool>;
 }
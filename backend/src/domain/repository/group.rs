@@ -13,8 +13,31 @@ pub trait GroupRepository: Send + Sync {
         description: Option<&str>,
     ) -> anyhow::Result<Group>;
     async fn list_by_org(&self, org_id: &Uuid) -> anyhow::Result<Vec<Group>>;
-    async fn add_member(&self, group_id: &Uuid, user_id: &Uuid) -> anyhow::Result<()>;
+    /// Update the fields that are `Some`, leaving the others unchanged.
+    async fn update(
+        &self,
+        group_id: &Uuid,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> anyhow::Result<Option<Group>>;
+    /// Delete a group together with its memberships and permission rows,
+    /// in one transaction. Returns `false` if the group did not exist.
+    async fn delete(&self, group_id: &Uuid) -> anyhow::Result<bool>;
+    /// Add a user to a group. Returns `false` if they were already a member.
+    async fn add_member(&self, group_id: &Uuid, user_id: &Uuid) -> anyhow::Result<bool>;
     async fn remove_member(&self, group_id: &Uuid, user_id: &Uuid) -> anyhow::Result<bool>;
+    /// Add several users in one statement, skipping existing members.
+    /// Returns how many were newly added.
+    async fn add_members_bulk(&self, group_id: &Uuid, user_ids: &[Uuid]) -> anyhow::Result<u64>;
+    /// Remove several users in one statement. Returns how many were members.
+    async fn remove_members_bulk(&self, group_id: &Uuid, user_ids: &[Uuid]) -> anyhow::Result<u64>;
     async fn list_members(&self, group_id: &Uuid) -> anyhow::Result<Vec<AppUser>>;
+    /// Groups the user is a direct member of, across all organizations.
+    async fn list_for_user(&self, user_id: &Uuid) -> anyhow::Result<Vec<Group>>;
     async fn get_org_id(&self, group_id: &Uuid) -> anyhow::Result<Option<Uuid>>;
+    async fn set_parent(&self, group_id: &Uuid, parent_id: Option<&Uuid>) -> anyhow::Result<bool>;
+    /// IDs of `group_id` and every group above it, nearest first.
+    async fn list_ancestor_ids(&self, group_id: &Uuid) -> anyhow::Result<Vec<Uuid>>;
+    /// IDs of `group_id` and every group nested below it.
+    async fn list_descendant_ids(&self, group_id: &Uuid) -> anyhow::Result<Vec<Uuid>>;
 }
@@ -1,13 +1,25 @@
+mod api_token;
+mod audit_log;
 mod connection;
 mod group;
+mod org_email_domain;
 mod organization;
 mod organization_member;
 mod permission;
+mod permission_template;
+mod query_log;
+mod table_metadata;
 mod user;
 
+pub use api_token::ApiTokenRepository;
+pub use audit_log::AuditLogRepository;
 pub use connection::ConnectionRepository;
 pub use group::GroupRepository;
+pub use org_email_domain::OrgEmailDomainRepository;
 pub use organization::OrganizationRepository;
 pub use organization_member::OrganizationMemberRepository;
 pub use permission::PermissionRepository;
+pub use permission_template::PermissionTemplateRepository;
+pub use query_log::QueryLogRepository;
+pub use table_metadata::TableMetadataRepository;
 pub use user::UserRepository;
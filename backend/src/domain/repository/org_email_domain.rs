@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::organization::OrgEmailDomain;
+
+#[async_trait]
+pub trait OrgEmailDomainRepository: Send + Sync {
+    async fn create(
+        &self,
+        domain: &str,
+        org_id: &Uuid,
+        default_role: &str,
+    ) -> anyhow::Result<OrgEmailDomain>;
+
+    async fn find_by_domain(&self, domain: &str) -> anyhow::Result<Option<OrgEmailDomain>>;
+}
@@ -12,6 +12,8 @@ pub trait OrganizationMemberRepository: Send + Sync {
         role: &str,
     ) -> anyhow::Result<OrganizationMember>;
 
+    /// Remove the user from the organization, together with their grants on
+    /// its connections and their membership of its groups.
     async fn remove_member(&self, org_id: &Uuid, user_id: &Uuid) -> anyhow::Result<bool>;
 
     async fn list_members(&self, org_id: &Uuid) -> anyhow::Result<Vec<OrganizationMember>>;
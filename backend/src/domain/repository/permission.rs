@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
+use crate::domain::pagination::Paginated;
 use crate::domain::permission::*;
 use crate::domain::user::AppUser;
 
@@ -22,7 +23,9 @@ pub trait PermissionRepository: Send + Sync {
     async fn list_user_connection_permissions(
         &self,
         conn_id: &Uuid,
-    ) -> anyhow::Result<Vec<UserConnectionPermission>>;
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<UserConnectionPermission>>;
 
     // User Table Permissions
     async fn grant_user_table_permission(
@@ -42,7 +45,23 @@ pub trait PermissionRepository: Send + Sync {
         &self,
         conn_id: &Uuid,
         user_id: &Uuid,
-    ) -> anyhow::Result<Vec<UserTablePermission>>;
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<UserTablePermission>>;
+
+    /// Copy `from_user`'s connection grant and table overrides on `conn_id`
+    /// to `to_user` in one transaction, overwriting matching grants.
+    /// Returns the number of rows written.
+    async fn copy_user_permissions(
+        &self,
+        conn_id: &Uuid,
+        from_user: &Uuid,
+        to_user: &Uuid,
+    ) -> anyhow::Result<u64>;
+    /// Delete every user-level connection and table grant `user_id` holds,
+    /// across all connections, in one transaction. Group memberships are
+    /// untouched. Returns the number of rows deleted.
+    async fn revoke_all_for_user(&self, user_id: &Uuid) -> anyhow::Result<u64>;
 
     // Group Connection Permissions
     async fn grant_group_connection_permission(
@@ -60,7 +79,9 @@ pub trait PermissionRepository: Send + Sync {
     async fn list_group_connection_permissions(
         &self,
         conn_id: &Uuid,
-    ) -> anyhow::Result<Vec<GroupConnectionPermission>>;
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<GroupConnectionPermission>>;
 
     // Group Table Permissions
     async fn grant_group_table_permission(
@@ -80,7 +101,35 @@ pub trait PermissionRepository: Send + Sync {
         &self,
         conn_id: &Uuid,
         group_id: &Uuid,
-    ) -> anyhow::Result<Vec<GroupTablePermission>>;
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<GroupTablePermission>>;
+
+    // Group Org Permissions
+    async fn grant_group_org_permission(
+        &self,
+        org_id: &Uuid,
+        group_id: &Uuid,
+        permission: &str,
+    ) -> anyhow::Result<GroupOrgPermission>;
+    async fn revoke_group_org_permission(
+        &self,
+        org_id: &Uuid,
+        group_id: &Uuid,
+    ) -> anyhow::Result<bool>;
+    async fn list_group_org_permissions(
+        &self,
+        org_id: &Uuid,
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<GroupOrgPermission>>;
+
+    /// Count the user and group grants on `conn_id`, i.e. what deleting the
+    /// connection would remove with it.
+    async fn count_connection_grants(
+        &self,
+        conn_id: &Uuid,
+    ) -> anyhow::Result<ConnectionDeletionImpact>;
 
     // Permission Resolution
     async fn resolve_connection_permission(
@@ -93,5 +142,17 @@ pub trait PermissionRepository: Send + Sync {
         user: &AppUser,
         conn_id: &Uuid,
         table_name: &str,
-    ) -> anyhow::Result<PermissionLevel>;
+    ) -> anyhow::Result<PermissionLevel> {
+        Ok(self
+            .explain_table_permission(user, conn_id, table_name)
+            .await?
+            .level)
+    }
+    /// Same resolution as `resolve_table_permission`, also reporting which rule decided it.
+    async fn explain_table_permission(
+        &self,
+        user: &AppUser,
+        conn_id: &Uuid,
+        table_name: &str,
+    ) -> anyhow::Result<ResolvedPermission>;
 }
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::permission::{PermissionTemplate, TemplateTableGrant};
+
+#[async_trait]
+pub trait PermissionTemplateRepository: Send + Sync {
+    async fn create(
+        &self,
+        org_id: &Uuid,
+        name: &str,
+        description: Option<&str>,
+        tables: &[TemplateTableGrant],
+    ) -> anyhow::Result<PermissionTemplate>;
+    async fn find_by_id(&self, id: &Uuid) -> anyhow::Result<Option<PermissionTemplate>>;
+    async fn list_by_org(&self, org_id: &Uuid) -> anyhow::Result<Vec<PermissionTemplate>>;
+    /// Replace the template's name, description and table grants.
+    async fn update(
+        &self,
+        id: &Uuid,
+        name: &str,
+        description: Option<&str>,
+        tables: &[TemplateTableGrant],
+    ) -> anyhow::Result<Option<PermissionTemplate>>;
+    async fn delete(&self, id: &Uuid) -> anyhow::Result<bool>;
+
+    /// Expand the template into `user_table_permissions` rows on `conn_id`,
+    /// overwriting matching grants so re-applying is a no-op.
+    /// Returns the number of rows written.
+    async fn apply_to_user(
+        &self,
+        template_id: &Uuid,
+        conn_id: &Uuid,
+        user_id: &Uuid,
+    ) -> anyhow::Result<u64>;
+    /// Same as `apply_to_user`, targeting `group_table_permissions`.
+    async fn apply_to_group(
+        &self,
+        template_id: &Uuid,
+        conn_id: &Uuid,
+        group_id: &Uuid,
+    ) -> anyhow::Result<u64>;
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::audit::QueryLogEntry;
+use crate::domain::pagination::Paginated;
+
+#[async_trait]
+pub trait QueryLogRepository: Send + Sync {
+    async fn record(
+        &self,
+        conn_id: &Uuid,
+        user_id: &Uuid,
+        operation: &str,
+        table_name: Option<&str>,
+        duration_ms: i64,
+        success: bool,
+    ) -> anyhow::Result<()>;
+
+    /// Delete entries created before `cutoff`, returning how many were removed.
+    async fn prune(&self, cutoff: chrono::DateTime<chrono::Utc>) -> anyhow::Result<u64>;
+
+    /// Entries for a connection, newest first.
+    async fn list(
+        &self,
+        conn_id: &Uuid,
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<QueryLogEntry>>;
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::data::{ColumnMetadata, TableMetadata};
+
+#[async_trait]
+pub trait TableMetadataRepository: Send + Sync {
+    /// All metadata rows for a connection, ordered by table name.
+    async fn list(&self, conn_id: &Uuid) -> anyhow::Result<Vec<TableMetadata>>;
+
+    async fn get(&self, conn_id: &Uuid, table_name: &str) -> anyhow::Result<Option<TableMetadata>>;
+
+    /// Insert or replace the metadata for a table.
+    async fn upsert(
+        &self,
+        conn_id: &Uuid,
+        table_name: &str,
+        display_name: Option<&str>,
+        description: Option<&str>,
+    ) -> anyhow::Result<TableMetadata>;
+
+    /// Column metadata rows for a table, ordered by column name.
+    async fn list_columns(
+        &self,
+        conn_id: &Uuid,
+        table_name: &str,
+    ) -> anyhow::Result<Vec<ColumnMetadata>>;
+
+    /// Insert or replace the metadata for a column.
+    async fn upsert_column(
+        &self,
+        conn_id: &Uuid,
+        table_name: &str,
+        column_name: &str,
+        description: Option<&str>,
+        display_hint: Option<&str>,
+    ) -> anyhow::Result<ColumnMetadata>;
+}
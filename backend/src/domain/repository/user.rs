@@ -8,4 +8,7 @@ pub trait UserRepository: Send + Sync {
     async fn create(&self, name: &str, email: &str, role: &str) -> anyhow::Result<AppUser>;
     async fn list_by_org(&self, org_id: &Uuid) -> anyhow::Result<Vec<AppUser>>;
     async fn get(&self, user_id: &Uuid) -> anyhow::Result<Option<AppUser>>;
+    /// Delete a user together with their permission and membership rows,
+    /// in one transaction. Returns `false` if the user did not exist.
+    async fn delete(&self, user_id: &Uuid) -> anyhow::Result<bool>;
 }
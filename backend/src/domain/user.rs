@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::api_token::ApiTokenScope;
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct AppUser {
     pub id: Uuid,
@@ -12,4 +14,106 @@ pub struct AppUser {
     pub avatar_url: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set when a super_admin is acting as this user via `X-Act-As`; never persisted.
+    #[serde(skip)]
+    #[sqlx(skip)]
+    pub acting_admin_id: Option<Uuid>,
+    /// Set only for API token principals: the one connection and level they may use.
+    #[serde(skip)]
+    #[sqlx(skip)]
+    pub api_token_scope: Option<ApiTokenScope>,
+}
+
+// ============================================================
+// Global Role (value object)
+// ============================================================
+
+/// Global role stored in `AppUser::role`, ordered by privilege.
+/// `org_admin` manages users and groups of the organizations it belongs to;
+/// only `super_admin` can do platform-wide actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Member,
+    OrgAdmin,
+    SuperAdmin,
+}
+
+impl Role {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "member" => Some(Self::Member),
+            "org_admin" => Some(Self::OrgAdmin),
+            "super_admin" => Some(Self::SuperAdmin),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Member => "member",
+            Self::OrgAdmin => "org_admin",
+            Self::SuperAdmin => "super_admin",
+        }
+    }
+}
+
+impl AppUser {
+    /// The user's place in the role hierarchy; unrecognized roles rank as `member`.
+    pub fn role_level(&self) -> Role {
+        Role::parse(&self.role).unwrap_or(Role::Member)
+    }
+
+    pub fn is_super_admin(&self) -> bool {
+        self.role_level() == Role::SuperAdmin
+    }
+
+    /// `org_admin` or above.
+    pub fn is_org_admin(&self) -> bool {
+        self.role_level() >= Role::OrgAdmin
+    }
+
+    /// The real user behind a request: the admin under `X-Act-As`, the
+    /// issuer for an API token, otherwise the user itself.
+    pub fn actor_id(&self) -> Uuid {
+        self.acting_admin_id
+            .or(self.api_token_scope.as_ref().map(|s| s.created_by))
+            .unwrap_or(self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(role: &str) -> AppUser {
+        AppUser {
+            id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            email: "test@example.com".to_string(),
+            role: role.to_string(),
+            auth_provider: None,
+            provider_id: None,
+            avatar_url: None,
+            created_at: None,
+            updated_at: None,
+            acting_admin_id: None,
+            api_token_scope: None,
+        }
+    }
+
+    #[test]
+    fn roles_are_ordered_by_privilege() {
+        assert!(Role::Member < Role::OrgAdmin);
+        assert!(Role::OrgAdmin < Role::SuperAdmin);
+        assert_eq!(Role::parse("org_admin"), Some(Role::OrgAdmin));
+        assert_eq!(Role::parse("owner"), None);
+    }
+
+    #[test]
+    fn predicates_follow_hierarchy() {
+        assert!(user("super_admin").is_super_admin() && user("super_admin").is_org_admin());
+        assert!(user("org_admin").is_org_admin() && !user("org_admin").is_super_admin());
+        assert!(!user("member").is_org_admin());
+        assert_eq!(user("custom").role_level(), Role::Member);
+    }
 }
@@ -0,0 +1,41 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+
+/// Prefix on every issued API token, so it can be told apart from a JWT.
+pub const API_TOKEN_PREFIX: &str = "dbw_";
+
+/// A new random token (32 bytes of entropy) in its plaintext form.
+pub fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    rand::fill(&mut bytes);
+    format!("{}{}", API_TOKEN_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Hex SHA-256 of a plaintext token, as stored in `api_tokens.token_hash`.
+pub fn hash(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Whether a bearer credential is an API token rather than a JWT.
+pub fn is_api_token(credential: &str) -> bool {
+    credential.starts_with(API_TOKEN_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_are_prefixed_and_unique() {
+        let a = generate();
+        let b = generate();
+        assert!(is_api_token(&a));
+        assert_ne!(a, b);
+        assert_ne!(hash(&a), hash(&b));
+        assert_eq!(hash(&a).len(), 64);
+        assert_eq!(hash(&a), hash(&a));
+    }
+}
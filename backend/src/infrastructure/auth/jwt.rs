@@ -1,8 +1,89 @@
-use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use serde::{Deserialize, Serialize};
 
 use crate::domain::user::AppUser;
 
+/// Default token lifetime (7 days).
+pub const DEFAULT_JWT_EXPIRY_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Signing/verification settings for issued JWTs.
+///
+/// HS256 with a shared `JWT_SECRET` is the default. RS256 signs with a
+/// private key so other services can verify tokens with only the public key.
+#[derive(Clone)]
+pub struct JwtConfig {
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    expiry_secs: i64,
+}
+
+impl JwtConfig {
+    /// HS256 with a shared HMAC secret.
+    pub fn hs256(secret: &str) -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            expiry_secs: DEFAULT_JWT_EXPIRY_SECS,
+        }
+    }
+
+    /// RS256 with a PEM-encoded RSA key pair.
+    pub fn rs256(private_key_pem: &[u8], public_key_pem: &[u8]) -> anyhow::Result<Self> {
+        Ok(Self {
+            algorithm: Algorithm::RS256,
+            encoding_key: EncodingKey::from_rsa_pem(private_key_pem)?,
+            decoding_key: DecodingKey::from_rsa_pem(public_key_pem)?,
+            expiry_secs: DEFAULT_JWT_EXPIRY_SECS,
+        })
+    }
+
+    /// Override the token lifetime.
+    pub fn with_expiry_secs(mut self, expiry_secs: i64) -> Self {
+        self.expiry_secs = expiry_secs;
+        self
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Build from environment variables:
+    /// - `JWT_ALGORITHM`: `HS256` (default) or `RS256`
+    /// - `JWT_SECRET`: HMAC secret for HS256
+    /// - `JWT_PRIVATE_KEY` / `JWT_PUBLIC_KEY`: PEM key pair for RS256
+    /// - `JWT_EXPIRY_SECS`: token lifetime in seconds (default 7 days)
+    pub fn from_env() -> anyhow::Result<Self> {
+        let algorithm = std::env::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".to_string());
+        let config = match algorithm.to_ascii_uppercase().as_str() {
+            "HS256" => {
+                let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| {
+                    tracing::warn!("JWT_SECRET not set, using default (insecure for production!)");
+                    "dbworks-dev-secret-change-me".to_string()
+                });
+                Self::hs256(&secret)
+            }
+            "RS256" => {
+                let private_key = std::env::var("JWT_PRIVATE_KEY")
+                    .map_err(|_| anyhow::anyhow!("JWT_PRIVATE_KEY is required for RS256"))?;
+                let public_key = std::env::var("JWT_PUBLIC_KEY")
+                    .map_err(|_| anyhow::anyhow!("JWT_PUBLIC_KEY is required for RS256"))?;
+                Self::rs256(private_key.as_bytes(), public_key.as_bytes())?
+            }
+            other => anyhow::bail!("Unsupported JWT_ALGORITHM '{}': use HS256 or RS256", other),
+        };
+
+        let expiry_secs = match std::env::var("JWT_EXPIRY_SECS") {
+            Ok(v) => v
+                .parse()
+                .map_err(|_| anyhow::anyhow!("JWT_EXPIRY_SECS must be an integer"))?,
+            Err(_) => DEFAULT_JWT_EXPIRY_SECS,
+        };
+        Ok(config.with_expiry_secs(expiry_secs))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String, // user_id
@@ -14,10 +95,10 @@ pub struct Claims {
 impl Claims {
     pub fn generate_token(
         user: &AppUser,
-        jwt_secret: &str,
+        config: &JwtConfig,
     ) -> Result<String, jsonwebtoken::errors::Error> {
         let expiration = chrono::Utc::now()
-            .checked_add_signed(chrono::Duration::days(7))
+            .checked_add_signed(chrono::Duration::seconds(config.expiry_secs))
             .expect("valid timestamp")
             .timestamp() as usize;
 
@@ -29,17 +110,17 @@ impl Claims {
         };
 
         encode(
-            &Header::default(),
+            &Header::new(config.algorithm),
             &claims,
-            &EncodingKey::from_secret(jwt_secret.as_bytes()),
+            &config.encoding_key,
         )
     }
 
-    pub fn decode(token: &str, jwt_secret: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    pub fn decode(token: &str, config: &JwtConfig) -> Result<Claims, jsonwebtoken::errors::Error> {
         let token_data = decode::<Claims>(
             token,
-            &DecodingKey::from_secret(jwt_secret.as_bytes()),
-            &Validation::default(),
+            &config.decoding_key,
+            &Validation::new(config.algorithm),
         )?;
         Ok(token_data.claims)
     }
@@ -1,2 +1,3 @@
+pub mod api_token;
 pub mod jwt;
 pub mod oauth;
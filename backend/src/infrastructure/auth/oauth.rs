@@ -12,6 +12,8 @@ use serde::Deserialize;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::domain::organization::OrgEmailDomain;
+use crate::domain::repository::{OrgEmailDomainRepository, OrganizationMemberRepository};
 use crate::domain::user::AppUser;
 use crate::presentation::state::AppState;
 
@@ -90,6 +92,8 @@ struct GoogleUserInfo {
     sub: String,
     name: Option<String>,
     email: String,
+    #[serde(default)]
+    email_verified: bool,
     picture: Option<String>,
 }
 
@@ -212,13 +216,19 @@ pub async fn google_callback(
     let name = user_info.name.unwrap_or_else(|| user_info.email.clone());
 
     // Find or create user
+    let profile = OAuthProfile {
+        provider: "google",
+        provider_id: &user_info.sub,
+        name: &name,
+        email: &user_info.email,
+        email_verified: user_info.email_verified,
+        avatar_url: user_info.picture.as_deref(),
+    };
     let user = match find_or_create_user(
         &state.pool,
-        "google",
-        &user_info.sub,
-        &name,
-        &user_info.email,
-        user_info.picture.as_deref(),
+        &*state.org_email_domain_repo,
+        &*state.org_member_repo,
+        &profile,
     )
     .await
     {
@@ -234,7 +244,7 @@ pub async fn google_callback(
     };
 
     // Generate JWT
-    let token = match Claims::generate_token(&user, &state.jwt_secret) {
+    let token = match Claims::generate_token(&user, &state.jwt) {
         Ok(t) => t,
         Err(e) => {
             tracing::error!(error = %e, "Failed to generate JWT");
@@ -338,42 +348,49 @@ pub async fn github_callback(
         }
     };
 
-    // Get primary email if not in profile
-    let email = if let Some(email) = &user_info.email {
-        email.clone()
-    } else {
-        // Fetch emails from GitHub API
-        match http_client
-            .get("https://api.github.com/user/emails")
-            .bearer_auth(token.access_token().secret())
-            .header("User-Agent", "dbworks")
-            .send()
-            .await
-        {
-            Ok(res) => {
-                let emails: Vec<GitHubEmail> = res.json().await.unwrap_or_default();
-                emails
-                    .into_iter()
-                    .find(|e| e.primary && e.verified)
-                    .map(|e| e.email)
-                    .unwrap_or_else(|| format!("{}@github.local", user_info.login))
-            }
-            Err(_) => format!("{}@github.local", user_info.login),
-        }
+    // Fetch the account's emails to find the primary address and verification status
+    let emails: Vec<GitHubEmail> = match http_client
+        .get("https://api.github.com/user/emails")
+        .bearer_auth(token.access_token().secret())
+        .header("User-Agent", "dbworks")
+        .send()
+        .await
+    {
+        Ok(res) => res.json().await.unwrap_or_default(),
+        Err(_) => vec![],
     };
 
+    // Use the profile email, falling back to the primary verified one
+    let email = user_info
+        .email
+        .clone()
+        .or_else(|| {
+            emails
+                .iter()
+                .find(|e| e.primary && e.verified)
+                .map(|e| e.email.clone())
+        })
+        .unwrap_or_else(|| format!("{}@github.local", user_info.login));
+    let email_verified = emails.iter().any(|e| e.verified && e.email == email);
+
     let name = user_info.name.unwrap_or_else(|| user_info.login.clone());
 
     let provider_id = user_info.id.to_string();
 
     // Find or create user
+    let profile = OAuthProfile {
+        provider: "github",
+        provider_id: &provider_id,
+        name: &name,
+        email: &email,
+        email_verified,
+        avatar_url: user_info.avatar_url.as_deref(),
+    };
     let user = match find_or_create_user(
         &state.pool,
-        "github",
-        &provider_id,
-        &name,
-        &email,
-        user_info.avatar_url.as_deref(),
+        &*state.org_email_domain_repo,
+        &*state.org_member_repo,
+        &profile,
     )
     .await
     {
@@ -389,7 +406,7 @@ pub async fn github_callback(
     };
 
     // Generate JWT
-    let jwt = match Claims::generate_token(&user, &state.jwt_secret) {
+    let jwt = match Claims::generate_token(&user, &state.jwt) {
         Ok(t) => t,
         Err(e) => {
             tracing::error!(error = %e, "Failed to generate JWT");
@@ -422,7 +439,7 @@ pub async fn me(
         }
     };
 
-    let claims = match Claims::decode(&token, &state.jwt_secret) {
+    let claims = match Claims::decode(&token, &state.jwt) {
         Ok(c) => c,
         Err(e) => {
             tracing::warn!(error = %e, "Invalid JWT");
@@ -467,14 +484,32 @@ pub async fn me(
 // Helpers
 // ============================================================
 
-async fn find_or_create_user(
+/// Identity returned by an OAuth provider after a successful login.
+pub struct OAuthProfile<'a> {
+    pub provider: &'a str,
+    pub provider_id: &'a str,
+    pub name: &'a str,
+    pub email: &'a str,
+    /// Whether the provider vouches for ownership of `email`
+    pub email_verified: bool,
+    pub avatar_url: Option<&'a str>,
+}
+
+pub async fn find_or_create_user(
     pool: &PgPool,
-    provider: &str,
-    provider_id: &str,
-    name: &str,
-    email: &str,
-    avatar_url: Option<&str>,
+    domain_repo: &dyn OrgEmailDomainRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    profile: &OAuthProfile<'_>,
 ) -> anyhow::Result<AppUser> {
+    let OAuthProfile {
+        provider,
+        provider_id,
+        name,
+        email,
+        email_verified,
+        avatar_url,
+    } = *profile;
+
     // 1. Try to find by provider
     let existing = sqlx::query_as::<_, AppUser>(
         "SELECT * FROM app_users WHERE auth_provider = $1 AND provider_id = $2",
@@ -539,5 +574,23 @@ async fn find_or_create_user(
     .await?;
 
     tracing::info!(user_id = %user.id, provider = provider, email = email, "Created new user via OAuth");
+
+    // 4. Auto-join the organization mapped to the verified email's domain
+    if email_verified
+        && let Some(domain) = OrgEmailDomain::of_email(email)
+        && let Some(mapping) = domain_repo.find_by_domain(&domain).await?
+    {
+        org_member_repo
+            .add_member(&mapping.organization_id, &user.id, &mapping.default_role)
+            .await?;
+        tracing::info!(
+            user_id = %user.id,
+            org_id = %mapping.organization_id,
+            domain = %domain,
+            role = %mapping.default_role,
+            "Auto-joined organization by email domain"
+        );
+    }
+
     Ok(user)
 }
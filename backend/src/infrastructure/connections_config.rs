@@ -0,0 +1,118 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Connections to register at startup, read from the JSON file named by
+/// `CONNECTIONS_CONFIG`:
+///
+/// ```json
+/// { "connections": [
+///     { "name": "analytics", "host": "analytics.internal", "database": "warehouse",
+///       "user": "reader", "password": "${ANALYTICS_DB_PASSWORD}", "read_only": true }
+/// ] }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConnectionsConfig {
+    pub connections: Vec<ConnectionTemplate>,
+}
+
+/// One PostgreSQL connection in a `ConnectionsConfig`. Fields mirror
+/// `ConnectionRequest`; omitted ones take the same defaults.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConnectionTemplate {
+    pub name: String,
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    pub database: String,
+    pub user: String,
+    /// Literal password, or `${VAR}` to read it from the environment
+    pub password: String,
+    /// Org the connection belongs to; personal connections need `owner_user_id`
+    pub organization_id: Option<Uuid>,
+    pub owner_user_id: Option<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub statement_timeout_ms: Option<u32>,
+    pub pool_idle_timeout_ms: Option<u32>,
+    pub pool_max_lifetime_ms: Option<u32>,
+    #[serde(default)]
+    pub read_only: bool,
+    pub default_page_size: Option<u32>,
+    pub default_sort_order: Option<String>,
+    pub search_path: Option<String>,
+    pub default_schema: Option<String>,
+    pub role: Option<String>,
+    #[serde(default)]
+    pub hidden_tables: Vec<String>,
+    #[serde(default)]
+    pub replica_hosts: Vec<String>,
+}
+
+fn default_port() -> u16 {
+    5432
+}
+
+impl ConnectionsConfig {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).map_err(|e| anyhow::anyhow!("Invalid connections config: {}", e))
+    }
+
+    /// Read the file named by `CONNECTIONS_CONFIG`, if the variable is set.
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let Ok(path) = std::env::var("CONNECTIONS_CONFIG") else {
+            return Ok(None);
+        };
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+        Self::parse(&json).map(Some)
+    }
+}
+
+impl ConnectionTemplate {
+    /// The password, with a `${VAR}` reference replaced by that variable's value.
+    pub fn resolve_password(&self) -> anyhow::Result<String> {
+        match self
+            .password
+            .strip_prefix("${")
+            .and_then(|p| p.strip_suffix('}'))
+        {
+            Some(var) => std::env::var(var)
+                .map_err(|_| anyhow::anyhow!("Password references {}, which is not set", var)),
+            None => Ok(self.password.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(password: &str) -> ConnectionTemplate {
+        let json = format!(
+            r#"{{"name": "a", "host": "h", "database": "d", "user": "u", "password": "{}"}}"#,
+            password
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn omitted_fields_take_defaults() {
+        let t = template("secret");
+        assert_eq!(t.port, 5432);
+        assert!(!t.read_only);
+        assert!(t.tags.is_empty());
+    }
+
+    #[test]
+    fn literal_password_is_kept() {
+        assert_eq!(template("secret").resolve_password().unwrap(), "secret");
+    }
+
+    #[test]
+    fn missing_password_variable_is_an_error() {
+        let err = template("${DBWORKS_UNSET_TEST_PASSWORD}")
+            .resolve_password()
+            .unwrap_err();
+        assert!(err.to_string().contains("DBWORKS_UNSET_TEST_PASSWORD"));
+    }
+}
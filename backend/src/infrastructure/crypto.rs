@@ -139,7 +139,7 @@ mod tests {
     fn decrypt_too_short_ciphertext_fails() {
         let enc = test_encryptor();
         // base64 of less than 12 bytes
-        let short = BASE64.encode(&[1u8; 5]);
+        let short = BASE64.encode([1u8; 5]);
         assert!(enc.decrypt(&short).is_err());
     }
 }
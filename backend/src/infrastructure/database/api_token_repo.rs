@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::api_token::ApiToken;
+use crate::domain::repository::ApiTokenRepository;
+
+pub struct PgApiTokenRepository {
+    pool: PgPool,
+}
+
+impl PgApiTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for PgApiTokenRepository {
+    async fn create(
+        &self,
+        conn_id: &Uuid,
+        name: &str,
+        permission: &str,
+        token_hash: &str,
+        created_by: &Uuid,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> anyhow::Result<ApiToken> {
+        let token = sqlx::query_as::<_, ApiToken>(
+            r#"INSERT INTO api_tokens (connection_id, name, permission, token_hash, created_by, expires_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING *"#,
+        )
+        .bind(conn_id)
+        .bind(name)
+        .bind(permission)
+        .bind(token_hash)
+        .bind(created_by)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(token)
+    }
+
+    async fn find_active_by_hash(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>> {
+        let token = sqlx::query_as::<_, ApiToken>(
+            r#"SELECT * FROM api_tokens
+               WHERE token_hash = $1 AND revoked_at IS NULL
+                 AND (expires_at IS NULL OR expires_at > NOW())"#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(token)
+    }
+
+    async fn list_by_connection(&self, conn_id: &Uuid) -> anyhow::Result<Vec<ApiToken>> {
+        let tokens = sqlx::query_as::<_, ApiToken>(
+            "SELECT * FROM api_tokens WHERE connection_id = $1 ORDER BY created_at DESC, id DESC",
+        )
+        .bind(conn_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(tokens)
+    }
+
+    async fn revoke(&self, conn_id: &Uuid, token_id: &Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"UPDATE api_tokens SET revoked_at = NOW()
+               WHERE id = $1 AND connection_id = $2 AND revoked_at IS NULL"#,
+        )
+        .bind(token_id)
+        .bind(conn_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::domain::audit::{AuditEntry, AuditFilter};
+use crate::domain::pagination::Paginated;
+use crate::domain::repository::AuditLogRepository;
+
+pub struct PgAuditLogRepository {
+    pool: PgPool,
+}
+
+impl PgAuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Append the WHERE clause shared by the count and page queries.
+fn push_filters(qb: &mut QueryBuilder<'_, Postgres>, conn_id: &Uuid, filter: &AuditFilter) {
+    qb.push(" WHERE connection_id = ").push_bind(*conn_id);
+    if let Some(action) = &filter.action {
+        qb.push(" AND action = ").push_bind(action.clone());
+    }
+    if let Some(actor_id) = filter.actor_id {
+        qb.push(" AND actor_id = ").push_bind(actor_id);
+    }
+    if let Some(since) = filter.since {
+        qb.push(" AND created_at >= ").push_bind(since);
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PgAuditLogRepository {
+    async fn record(
+        &self,
+        conn_id: &Uuid,
+        actor_id: &Uuid,
+        action: &str,
+        table_name: Option<&str>,
+        details: Option<&serde_json::Value>,
+    ) -> anyhow::Result<AuditEntry> {
+        let entry = sqlx::query_as::<_, AuditEntry>(
+            r#"INSERT INTO audit_log (connection_id, actor_id, action, table_name, details)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING *"#,
+        )
+        .bind(conn_id)
+        .bind(actor_id)
+        .bind(action)
+        .bind(table_name)
+        .bind(details)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(entry)
+    }
+
+    async fn record_act_as(
+        &self,
+        admin_id: &Uuid,
+        target_id: &Uuid,
+        method: &str,
+        path: &str,
+    ) -> anyhow::Result<AuditEntry> {
+        let details = serde_json::json!({
+            "acting_as": target_id,
+            "method": method,
+            "path": path,
+        });
+        let entry = sqlx::query_as::<_, AuditEntry>(
+            r#"INSERT INTO audit_log (connection_id, actor_id, action, details)
+               VALUES (NULL, $1, 'act_as', $2)
+               RETURNING *"#,
+        )
+        .bind(admin_id)
+        .bind(details)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(entry)
+    }
+
+    async fn list(
+        &self,
+        conn_id: &Uuid,
+        filter: &AuditFilter,
+    ) -> anyhow::Result<Paginated<AuditEntry>> {
+        let mut count_qb = QueryBuilder::new("SELECT COUNT(*) FROM audit_log");
+        push_filters(&mut count_qb, conn_id, filter);
+        let total_count: i64 = count_qb.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let offset = (filter.page.saturating_sub(1) as i64) * filter.per_page as i64;
+        let mut qb = QueryBuilder::new("SELECT * FROM audit_log");
+        push_filters(&mut qb, conn_id, filter);
+        qb.push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(filter.per_page as i64)
+            .push(" OFFSET ")
+            .push_bind(offset);
+        let items = qb
+            .build_query_as::<AuditEntry>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Paginated {
+            items,
+            total_count,
+            page: filter.page,
+            per_page: filter.per_page,
+        })
+    }
+}
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::connection::{ConnectionInfo, SavedConnectionRow};
+use crate::domain::connection::{ConnectionInfo, ConnectionNameTaken, SavedConnectionRow};
 use crate::domain::repository::ConnectionRepository;
 use crate::infrastructure::crypto::Encryptor;
 
@@ -17,6 +17,27 @@ impl PgConnectionRepository {
     }
 }
 
+/// Report a violated name-uniqueness index as `ConnectionNameTaken`.
+fn map_name_taken(err: sqlx::Error, name: &str) -> anyhow::Error {
+    match &err {
+        sqlx::Error::Database(db_err)
+            if db_err
+                .constraint()
+                .is_some_and(|c| c.starts_with("uq_saved_connections_")) =>
+        {
+            ConnectionNameTaken(name.to_string()).into()
+        }
+        _ => err.into(),
+    }
+}
+
+/// Convert a setting for its INTEGER column, refusing values that would wrap.
+fn to_db_int(field: &str, value: Option<u32>) -> anyhow::Result<Option<i32>> {
+    value
+        .map(|v| i32::try_from(v).map_err(|_| anyhow::anyhow!("{} {} is out of range", field, v)))
+        .transpose()
+}
+
 #[async_trait]
 impl ConnectionRepository for PgConnectionRepository {
     async fn save(
@@ -27,11 +48,10 @@ impl ConnectionRepository for PgConnectionRepository {
     ) -> anyhow::Result<SavedConnectionRow> {
         let encrypted_password = self.encryptor.encrypt(&info.password)?;
         let row = sqlx::query_as::<_, SavedConnectionRow>(
-            r#"INSERT INTO saved_connections (id, organization_id, name, db_type, host, port, database_name, username, encrypted_password, created_by, owner_user_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            r#"INSERT INTO saved_connections (organization_id, name, db_type, host, port, database_name, username, encrypted_password, created_by, owner_user_id, tags, statement_timeout_ms, read_only, default_page_size, search_path, role, hidden_tables, replica_hosts, default_sort_order, pool_idle_timeout_ms, pool_max_lifetime_ms, default_schema)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22)
                RETURNING *"#,
         )
-        .bind(info.id)
         .bind(org_id)
         .bind(&info.name)
         .bind(&info.db_type)
@@ -42,8 +62,21 @@ impl ConnectionRepository for PgConnectionRepository {
         .bind(&encrypted_password)
         .bind::<Option<Uuid>>(None) // created_by
         .bind(owner_user_id)
+        .bind(&info.tags)
+        .bind(to_db_int("statement_timeout_ms", info.statement_timeout_ms)?)
+        .bind(info.read_only)
+        .bind(to_db_int("default_page_size", info.default_page_size)?)
+        .bind(&info.search_path)
+        .bind(&info.role)
+        .bind(&info.hidden_tables)
+        .bind(&info.replica_hosts)
+        .bind(&info.default_sort_order)
+        .bind(to_db_int("pool_idle_timeout_ms", info.pool_idle_timeout_ms)?)
+        .bind(to_db_int("pool_max_lifetime_ms", info.pool_max_lifetime_ms)?)
+        .bind(&info.default_schema)
         .fetch_one(&self.pool)
-        .await?;
+        .await
+        .map_err(|e| map_name_taken(e, &info.name))?;
         Ok(row)
     }
 
@@ -56,6 +89,16 @@ impl ConnectionRepository for PgConnectionRepository {
         Ok(rows)
     }
 
+    async fn get(&self, conn_id: &Uuid) -> anyhow::Result<Option<SavedConnectionRow>> {
+        let row = sqlx::query_as::<_, SavedConnectionRow>(
+            "SELECT * FROM saved_connections WHERE id = $1",
+        )
+        .bind(conn_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
     async fn delete(&self, conn_id: &Uuid) -> anyhow::Result<bool> {
         let result = sqlx::query("DELETE FROM saved_connections WHERE id = $1")
             .bind(conn_id)
@@ -76,4 +119,48 @@ impl ConnectionRepository for PgConnectionRepository {
         .await?;
         Ok(row)
     }
+
+    async fn set_tags(&self, conn_id: &Uuid, tags: &[String]) -> anyhow::Result<bool> {
+        let result =
+            sqlx::query("UPDATE saved_connections SET tags = $1, updated_at = NOW() WHERE id = $2")
+                .bind(tags)
+                .bind(conn_id)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_read_only(&self, conn_id: &Uuid, read_only: bool) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            "UPDATE saved_connections SET read_only = $1, updated_at = NOW() WHERE id = $2",
+        )
+        .bind(read_only)
+        .bind(conn_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_password(&self, conn_id: &Uuid, password: &str) -> anyhow::Result<bool> {
+        let encrypted_password = self.encryptor.encrypt(password)?;
+        let result = sqlx::query(
+            "UPDATE saved_connections SET encrypted_password = $1, updated_at = NOW() WHERE id = $2",
+        )
+        .bind(&encrypted_password)
+        .bind(conn_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_owner(&self, conn_id: &Uuid, owner_user_id: &Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            "UPDATE saved_connections SET owner_user_id = $1, updated_at = NOW() WHERE id = $2",
+        )
+        .bind(owner_user_id)
+        .bind(conn_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -45,15 +45,55 @@ impl GroupRepository for PgGroupRepository {
         Ok(groups)
     }
 
-    async fn add_member(&self, group_id: &Uuid, user_id: &Uuid) -> anyhow::Result<()> {
-        sqlx::query(
+    async fn update(
+        &self,
+        group_id: &Uuid,
+        name: Option<&str>,
+        description: Option<&str>,
+    ) -> anyhow::Result<Option<Group>> {
+        let group = sqlx::query_as::<_, Group>(
+            r#"UPDATE groups
+               SET name = COALESCE($2, name), description = COALESCE($3, description), updated_at = NOW()
+               WHERE id = $1
+               RETURNING *"#,
+        )
+        .bind(group_id)
+        .bind(name)
+        .bind(description)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(group)
+    }
+
+    async fn delete(&self, group_id: &Uuid) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        for sql in [
+            "DELETE FROM group_members WHERE group_id = $1",
+            "DELETE FROM group_connection_permissions WHERE group_id = $1",
+            "DELETE FROM group_table_permissions WHERE group_id = $1",
+        ] {
+            sqlx::query(sql).bind(group_id).execute(&mut *tx).await?;
+        }
+
+        let result = sqlx::query("DELETE FROM groups WHERE id = $1")
+            .bind(group_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn add_member(&self, group_id: &Uuid, user_id: &Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query(
             "INSERT INTO group_members (group_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
         )
         .bind(group_id)
         .bind(user_id)
         .execute(&self.pool)
         .await?;
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
     async fn remove_member(&self, group_id: &Uuid, user_id: &Uuid) -> anyhow::Result<bool> {
@@ -65,6 +105,27 @@ impl GroupRepository for PgGroupRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    async fn add_members_bulk(&self, group_id: &Uuid, user_ids: &[Uuid]) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            "INSERT INTO group_members (group_id, user_id) SELECT $1, UNNEST($2::uuid[]) ON CONFLICT DO NOTHING",
+        )
+        .bind(group_id)
+        .bind(user_ids)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn remove_members_bulk(&self, group_id: &Uuid, user_ids: &[Uuid]) -> anyhow::Result<u64> {
+        let result =
+            sqlx::query("DELETE FROM group_members WHERE group_id = $1 AND user_id = ANY($2)")
+                .bind(group_id)
+                .bind(user_ids)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected())
+    }
+
     async fn list_members(&self, group_id: &Uuid) -> anyhow::Result<Vec<AppUser>> {
         let users = sqlx::query_as::<_, AppUser>(
             "SELECT u.* FROM app_users u INNER JOIN group_members gm ON u.id = gm.user_id WHERE gm.group_id = $1 ORDER BY u.name",
@@ -75,6 +136,16 @@ impl GroupRepository for PgGroupRepository {
         Ok(users)
     }
 
+    async fn list_for_user(&self, user_id: &Uuid) -> anyhow::Result<Vec<Group>> {
+        let groups = sqlx::query_as::<_, Group>(
+            "SELECT g.* FROM groups g INNER JOIN group_members gm ON g.id = gm.group_id WHERE gm.user_id = $1 ORDER BY g.name",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(groups)
+    }
+
     async fn get_org_id(&self, group_id: &Uuid) -> anyhow::Result<Option<Uuid>> {
         let org_id =
             sqlx::query_scalar::<_, Uuid>("SELECT organization_id FROM groups WHERE id = $1")
@@ -83,4 +154,47 @@ impl GroupRepository for PgGroupRepository {
                 .await?;
         Ok(org_id)
     }
+
+    async fn set_parent(&self, group_id: &Uuid, parent_id: Option<&Uuid>) -> anyhow::Result<bool> {
+        let result =
+            sqlx::query("UPDATE groups SET parent_group_id = $2, updated_at = NOW() WHERE id = $1")
+                .bind(group_id)
+                .bind(parent_id)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_ancestor_ids(&self, group_id: &Uuid) -> anyhow::Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"WITH RECURSIVE ancestors(id, parent_group_id, depth) AS (
+                   SELECT id, parent_group_id, 0 FROM groups WHERE id = $1
+                   UNION
+                   SELECT g.id, g.parent_group_id, a.depth + 1 FROM groups g
+                   INNER JOIN ancestors a ON a.parent_group_id = g.id
+                   WHERE a.depth < 100
+               )
+               SELECT id FROM ancestors ORDER BY depth"#,
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ids)
+    }
+
+    async fn list_descendant_ids(&self, group_id: &Uuid) -> anyhow::Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"WITH RECURSIVE descendants(id) AS (
+                   SELECT id FROM groups WHERE id = $1
+                   UNION
+                   SELECT g.id FROM groups g
+                   INNER JOIN descendants d ON g.parent_group_id = d.id
+               )
+               SELECT id FROM descendants"#,
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ids)
+    }
 }
@@ -1,6 +1,12 @@
+pub mod api_token_repo;
+pub mod audit_log_repo;
 pub mod connection_repo;
 pub mod group_repo;
+pub mod org_email_domain_repo;
 pub mod organization_member_repo;
 pub mod organization_repo;
 pub mod permission_repo;
+pub mod permission_template_repo;
+pub mod query_log_repo;
+pub mod table_metadata_repo;
 pub mod user_repo;
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::organization::OrgEmailDomain;
+use crate::domain::repository::OrgEmailDomainRepository;
+
+pub struct PgOrgEmailDomainRepository {
+    pool: PgPool,
+}
+
+impl PgOrgEmailDomainRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OrgEmailDomainRepository for PgOrgEmailDomainRepository {
+    async fn create(
+        &self,
+        domain: &str,
+        org_id: &Uuid,
+        default_role: &str,
+    ) -> anyhow::Result<OrgEmailDomain> {
+        let mapping = sqlx::query_as::<_, OrgEmailDomain>(
+            r#"INSERT INTO org_email_domains (domain, organization_id, default_role)
+               VALUES ($1, $2, $3)
+               RETURNING *"#,
+        )
+        .bind(domain)
+        .bind(org_id)
+        .bind(default_role)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(mapping)
+    }
+
+    async fn find_by_domain(&self, domain: &str) -> anyhow::Result<Option<OrgEmailDomain>> {
+        let mapping = sqlx::query_as::<_, OrgEmailDomain>(
+            "SELECT * FROM org_email_domains WHERE domain = $1",
+        )
+        .bind(domain)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(mapping)
+    }
+}
@@ -38,13 +38,35 @@ impl OrganizationMemberRepository for PgOrganizationMemberRepository {
     }
 
     async fn remove_member(&self, org_id: &Uuid, user_id: &Uuid) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        for sql in [
+            r#"DELETE FROM user_connection_permissions
+               WHERE user_id = $2
+                 AND connection_id IN (SELECT id FROM saved_connections WHERE organization_id = $1)"#,
+            r#"DELETE FROM user_table_permissions
+               WHERE user_id = $2
+                 AND connection_id IN (SELECT id FROM saved_connections WHERE organization_id = $1)"#,
+            r#"DELETE FROM group_members
+               WHERE user_id = $2
+                 AND group_id IN (SELECT id FROM groups WHERE organization_id = $1)"#,
+        ] {
+            sqlx::query(sql)
+                .bind(org_id)
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
         let result = sqlx::query(
             "DELETE FROM organization_members WHERE organization_id = $1 AND user_id = $2",
         )
         .bind(org_id)
         .bind(user_id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+
+        tx.commit().await?;
         Ok(result.rows_affected() > 0)
     }
 
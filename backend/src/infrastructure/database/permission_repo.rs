@@ -1,7 +1,9 @@
 use async_trait::async_trait;
 use sqlx::PgPool;
+use sqlx::postgres::PgRow;
 use uuid::Uuid;
 
+use crate::domain::pagination::Paginated;
 use crate::domain::permission::*;
 use crate::domain::repository::PermissionRepository;
 use crate::domain::user::AppUser;
@@ -14,8 +16,184 @@ impl PgPermissionRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// One page of `table` rows matching `filter` (whose `$n` placeholders
+    /// take `ids` in order), sorted by `order`, plus the total count.
+    /// All three are fixed strings from this file, never caller input.
+    async fn list_page<T>(
+        &self,
+        table: &str,
+        filter: &str,
+        order: &str,
+        ids: &[&Uuid],
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<T>>
+    where
+        T: for<'r> sqlx::FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let count_sql = format!("SELECT COUNT(*) FROM {} WHERE {}", table, filter);
+        let mut count = sqlx::query_scalar::<_, i64>(&count_sql);
+        for id in ids {
+            count = count.bind(*id);
+        }
+        let total_count = count.fetch_one(&self.pool).await?;
+
+        let limit_idx = ids.len() + 1;
+        let items_sql = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY {} LIMIT ${} OFFSET ${}",
+            table,
+            filter,
+            order,
+            limit_idx,
+            limit_idx + 1
+        );
+        let mut items = sqlx::query_as::<_, T>(&items_sql);
+        for id in ids {
+            items = items.bind(*id);
+        }
+        let offset = (page.saturating_sub(1) as i64) * per_page as i64;
+        let items = items
+            .bind(per_page as i64)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Paginated {
+            items,
+            total_count,
+            page,
+            per_page,
+        })
+    }
+
+    /// Connection-level resolution: (level, all_tables, deciding rule).
+    async fn resolve_connection(
+        &self,
+        user: &AppUser,
+        conn_id: &Uuid,
+    ) -> anyhow::Result<(PermissionLevel, bool, PermissionSource)> {
+        // 0. API token → exactly the token's scope, nothing else
+        if let Some(scope) = &user.api_token_scope {
+            return Ok(if scope.connection_id == *conn_id {
+                (scope.permission.clone(), true, PermissionSource::ApiToken)
+            } else {
+                (PermissionLevel::None, false, PermissionSource::None)
+            });
+        }
+
+        // 1. SuperAdmin → full access
+        if user.is_super_admin() {
+            return Ok((PermissionLevel::Admin, true, PermissionSource::SuperAdmin));
+        }
+
+        // 1.5. Connection owner → full access
+        let is_owner = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM saved_connections WHERE id = $1 AND owner_user_id = $2)",
+        )
+        .bind(conn_id)
+        .bind(user.id)
+        .fetch_one(&self.pool)
+        .await?;
+        if is_owner {
+            return Ok((PermissionLevel::Admin, true, PermissionSource::Owner));
+        }
+
+        // 1.6. Org member → read access to org connections
+        let is_org_member = sqlx::query_scalar::<_, bool>(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM saved_connections sc
+                INNER JOIN organization_members om ON om.organization_id = sc.organization_id
+                WHERE sc.id = $1 AND om.user_id = $2 AND sc.organization_id IS NOT NULL
+            )"#,
+        )
+        .bind(conn_id)
+        .bind(user.id)
+        .fetch_one(&self.pool)
+        .await?;
+        if is_org_member {
+            return Ok((PermissionLevel::Read, true, PermissionSource::OrgMember));
+        }
+
+        // 2. Check user-level permission
+        let user_perm = sqlx::query_as::<_, UserConnectionPermission>(
+            "SELECT * FROM user_connection_permissions WHERE user_id = $1 AND connection_id = $2",
+        )
+        .bind(user.id)
+        .bind(conn_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if let Some(up) = user_perm {
+            let level = PermissionLevel::from_str(&up.permission);
+            return Ok((level, up.all_tables, PermissionSource::User));
+        }
+
+        // 3. Check group-level permissions (max of all groups and their ancestors)
+        let group_perms = sqlx::query_as::<_, GroupConnectionPermission>(&format!(
+            r#"{USER_GROUPS_CTE}
+               SELECT gcp.* FROM group_connection_permissions gcp
+               INNER JOIN user_groups ug ON ug.group_id = gcp.group_id
+               WHERE gcp.connection_id = $2"#
+        ))
+        .bind(user.id)
+        .bind(conn_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // 4. No connection-specific grant → org-wide group defaults for the connection's org
+        if group_perms.is_empty() {
+            let org_perms = sqlx::query_as::<_, GroupOrgPermission>(&format!(
+                r#"{USER_GROUPS_CTE}
+                   SELECT gop.* FROM group_org_permissions gop
+                   INNER JOIN user_groups ug ON ug.group_id = gop.group_id
+                   INNER JOIN saved_connections sc ON sc.organization_id = gop.organization_id
+                   WHERE sc.id = $2"#
+            ))
+            .bind(user.id)
+            .bind(conn_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+            return Ok(
+                match org_perms
+                    .iter()
+                    .map(|p| PermissionLevel::from_str(&p.permission))
+                    .max()
+                {
+                    Some(level) => (level, true, PermissionSource::OrgDefault),
+                    None => (PermissionLevel::None, false, PermissionSource::None),
+                },
+            );
+        }
+
+        let mut best_level = PermissionLevel::None;
+        let mut any_all_tables = false;
+        for gp in &group_perms {
+            let level = PermissionLevel::from_str(&gp.permission);
+            if level > best_level {
+                best_level = level;
+            }
+            if gp.all_tables {
+                any_all_tables = true;
+            }
+        }
+
+        Ok((best_level, any_all_tables, PermissionSource::Group))
+    }
 }
 
+/// Recursive CTE collecting every group the user (`$1`) belongs to, directly
+/// or through a descendant group. `UNION` de-duplicates, so a cycle that
+/// slipped past validation still terminates.
+const USER_GROUPS_CTE: &str = r#"WITH RECURSIVE user_groups(group_id) AS (
+    SELECT group_id FROM group_members WHERE user_id = $1
+    UNION
+    SELECT g.parent_group_id FROM groups g
+    INNER JOIN user_groups ug ON ug.group_id = g.id
+    WHERE g.parent_group_id IS NOT NULL
+)"#;
+
 #[async_trait]
 impl PermissionRepository for PgPermissionRepository {
     // ============================================================
@@ -62,14 +240,18 @@ impl PermissionRepository for PgPermissionRepository {
     async fn list_user_connection_permissions(
         &self,
         conn_id: &Uuid,
-    ) -> anyhow::Result<Vec<UserConnectionPermission>> {
-        let perms = sqlx::query_as::<_, UserConnectionPermission>(
-            "SELECT * FROM user_connection_permissions WHERE connection_id = $1",
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<UserConnectionPermission>> {
+        self.list_page(
+            "user_connection_permissions",
+            "connection_id = $1",
+            "granted_at, id",
+            &[conn_id],
+            page,
+            per_page,
         )
-        .bind(conn_id)
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(perms)
+        .await
     }
 
     // ============================================================
@@ -119,15 +301,78 @@ impl PermissionRepository for PgPermissionRepository {
         &self,
         conn_id: &Uuid,
         user_id: &Uuid,
-    ) -> anyhow::Result<Vec<UserTablePermission>> {
-        let perms = sqlx::query_as::<_, UserTablePermission>(
-            "SELECT * FROM user_table_permissions WHERE connection_id = $1 AND user_id = $2",
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<UserTablePermission>> {
+        self.list_page(
+            "user_table_permissions",
+            "connection_id = $1 AND user_id = $2",
+            "table_name",
+            &[conn_id, user_id],
+            page,
+            per_page,
+        )
+        .await
+    }
+
+    async fn copy_user_permissions(
+        &self,
+        conn_id: &Uuid,
+        from_user: &Uuid,
+        to_user: &Uuid,
+    ) -> anyhow::Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let conn_rows = sqlx::query(
+            r#"INSERT INTO user_connection_permissions (user_id, connection_id, permission, all_tables)
+               SELECT $3, connection_id, permission, all_tables
+               FROM user_connection_permissions
+               WHERE connection_id = $1 AND user_id = $2
+               ON CONFLICT (user_id, connection_id)
+               DO UPDATE SET permission = EXCLUDED.permission, all_tables = EXCLUDED.all_tables"#,
         )
         .bind(conn_id)
-        .bind(user_id)
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(perms)
+        .bind(from_user)
+        .bind(to_user)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let table_rows = sqlx::query(
+            r#"INSERT INTO user_table_permissions (user_id, connection_id, table_name, permission)
+               SELECT $3, connection_id, table_name, permission
+               FROM user_table_permissions
+               WHERE connection_id = $1 AND user_id = $2
+               ON CONFLICT (user_id, connection_id, table_name)
+               DO UPDATE SET permission = EXCLUDED.permission"#,
+        )
+        .bind(conn_id)
+        .bind(from_user)
+        .bind(to_user)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        Ok(conn_rows + table_rows)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: &Uuid) -> anyhow::Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let table_rows = sqlx::query("DELETE FROM user_table_permissions WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        let conn_rows = sqlx::query("DELETE FROM user_connection_permissions WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+        Ok(conn_rows + table_rows)
     }
 
     // ============================================================
@@ -174,14 +419,18 @@ impl PermissionRepository for PgPermissionRepository {
     async fn list_group_connection_permissions(
         &self,
         conn_id: &Uuid,
-    ) -> anyhow::Result<Vec<GroupConnectionPermission>> {
-        let perms = sqlx::query_as::<_, GroupConnectionPermission>(
-            "SELECT * FROM group_connection_permissions WHERE connection_id = $1",
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<GroupConnectionPermission>> {
+        self.list_page(
+            "group_connection_permissions",
+            "connection_id = $1",
+            "granted_at, id",
+            &[conn_id],
+            page,
+            per_page,
         )
-        .bind(conn_id)
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(perms)
+        .await
     }
 
     // ============================================================
@@ -231,118 +480,147 @@ impl PermissionRepository for PgPermissionRepository {
         &self,
         conn_id: &Uuid,
         group_id: &Uuid,
-    ) -> anyhow::Result<Vec<GroupTablePermission>> {
-        let perms = sqlx::query_as::<_, GroupTablePermission>(
-            "SELECT * FROM group_table_permissions WHERE connection_id = $1 AND group_id = $2",
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<GroupTablePermission>> {
+        self.list_page(
+            "group_table_permissions",
+            "connection_id = $1 AND group_id = $2",
+            "table_name",
+            &[conn_id, group_id],
+            page,
+            per_page,
         )
-        .bind(conn_id)
-        .bind(group_id)
-        .fetch_all(&self.pool)
-        .await?;
-        Ok(perms)
+        .await
     }
 
     // ============================================================
-    // Permission Resolution
+    // Group Org Permissions
     // ============================================================
 
-    async fn resolve_connection_permission(
+    async fn grant_group_org_permission(
         &self,
-        user: &AppUser,
-        conn_id: &Uuid,
-    ) -> anyhow::Result<(PermissionLevel, bool)> {
-        // 1. SuperAdmin → full access
-        if user.role == "super_admin" {
-            return Ok((PermissionLevel::Admin, true));
-        }
-
-        // 1.5. Connection owner → full access
-        let is_owner = sqlx::query_scalar::<_, bool>(
-            "SELECT EXISTS(SELECT 1 FROM saved_connections WHERE id = $1 AND owner_user_id = $2)",
+        org_id: &Uuid,
+        group_id: &Uuid,
+        permission: &str,
+    ) -> anyhow::Result<GroupOrgPermission> {
+        let perm = sqlx::query_as::<_, GroupOrgPermission>(
+            r#"INSERT INTO group_org_permissions (group_id, organization_id, permission)
+               VALUES ($1, $2, $3)
+               ON CONFLICT (group_id, organization_id) DO UPDATE SET permission = $3
+               RETURNING *"#,
         )
-        .bind(conn_id)
-        .bind(user.id)
+        .bind(group_id)
+        .bind(org_id)
+        .bind(permission)
         .fetch_one(&self.pool)
         .await?;
-        if is_owner {
-            return Ok((PermissionLevel::Admin, true));
-        }
+        Ok(perm)
+    }
 
-        // 1.6. Org member → read access to org connections
-        let is_org_member = sqlx::query_scalar::<_, bool>(
-            r#"SELECT EXISTS(
-                SELECT 1 FROM saved_connections sc
-                INNER JOIN organization_members om ON om.organization_id = sc.organization_id
-                WHERE sc.id = $1 AND om.user_id = $2 AND sc.organization_id IS NOT NULL
-            )"#,
+    async fn revoke_group_org_permission(
+        &self,
+        org_id: &Uuid,
+        group_id: &Uuid,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            "DELETE FROM group_org_permissions WHERE organization_id = $1 AND group_id = $2",
         )
-        .bind(conn_id)
-        .bind(user.id)
-        .fetch_one(&self.pool)
+        .bind(org_id)
+        .bind(group_id)
+        .execute(&self.pool)
         .await?;
-        if is_org_member {
-            return Ok((PermissionLevel::Read, true));
-        }
+        Ok(result.rows_affected() > 0)
+    }
 
-        // 2. Check user-level permission
-        let user_perm = sqlx::query_as::<_, UserConnectionPermission>(
-            "SELECT * FROM user_connection_permissions WHERE user_id = $1 AND connection_id = $2",
+    async fn list_group_org_permissions(
+        &self,
+        org_id: &Uuid,
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<GroupOrgPermission>> {
+        self.list_page(
+            "group_org_permissions",
+            "organization_id = $1",
+            "granted_at, id",
+            &[org_id],
+            page,
+            per_page,
         )
-        .bind(user.id)
-        .bind(conn_id)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some(up) = user_perm {
-            let level = PermissionLevel::from_str(&up.permission);
-            return Ok((level, up.all_tables));
-        }
+        .await
+    }
 
-        // 3. Check group-level permissions (max of all groups)
-        let group_perms = sqlx::query_as::<_, GroupConnectionPermission>(
-            r#"SELECT gcp.* FROM group_connection_permissions gcp
-               INNER JOIN group_members gm ON gm.group_id = gcp.group_id
-               WHERE gm.user_id = $1 AND gcp.connection_id = $2"#,
+    async fn count_connection_grants(
+        &self,
+        conn_id: &Uuid,
+    ) -> anyhow::Result<ConnectionDeletionImpact> {
+        let impact = sqlx::query_as::<_, ConnectionDeletionImpact>(
+            r#"SELECT
+                 (SELECT COUNT(*) FROM user_connection_permissions WHERE connection_id = $1) AS user_connection_permissions,
+                 (SELECT COUNT(*) FROM user_table_permissions WHERE connection_id = $1) AS user_table_permissions,
+                 (SELECT COUNT(*) FROM group_connection_permissions WHERE connection_id = $1) AS group_connection_permissions,
+                 (SELECT COUNT(*) FROM group_table_permissions WHERE connection_id = $1) AS group_table_permissions"#,
         )
-        .bind(user.id)
         .bind(conn_id)
-        .fetch_all(&self.pool)
+        .fetch_one(&self.pool)
         .await?;
+        Ok(impact)
+    }
 
-        if group_perms.is_empty() {
-            return Ok((PermissionLevel::None, false));
-        }
-
-        let mut best_level = PermissionLevel::None;
-        let mut any_all_tables = false;
-        for gp in &group_perms {
-            let level = PermissionLevel::from_str(&gp.permission);
-            if level > best_level {
-                best_level = level;
-            }
-            if gp.all_tables {
-                any_all_tables = true;
-            }
-        }
+    // ============================================================
+    // Permission Resolution
+    // ============================================================
 
-        Ok((best_level, any_all_tables))
+    async fn resolve_connection_permission(
+        &self,
+        user: &AppUser,
+        conn_id: &Uuid,
+    ) -> anyhow::Result<(PermissionLevel, bool)> {
+        let (level, all_tables, _) = self.resolve_connection(user, conn_id).await?;
+        Ok((level, all_tables))
     }
 
-    async fn resolve_table_permission(
+    async fn explain_table_permission(
         &self,
         user: &AppUser,
         conn_id: &Uuid,
         table_name: &str,
-    ) -> anyhow::Result<PermissionLevel> {
+    ) -> anyhow::Result<ResolvedPermission> {
+        let resolved = |level, source, table_level| ResolvedPermission {
+            level,
+            source,
+            table_level,
+            reason: None,
+        };
+        let denied = |source, table_level, reason: &str| ResolvedPermission {
+            level: PermissionLevel::None,
+            source,
+            table_level,
+            reason: Some(reason.to_string()),
+        };
+        const TABLE_GRANT_NONE: &str = "a table-level grant sets this table to none";
+
         // 1. SuperAdmin → full access
-        if user.role == "super_admin" {
-            return Ok(PermissionLevel::Admin);
+        if user.is_super_admin() {
+            return Ok(resolved(
+                PermissionLevel::Admin,
+                PermissionSource::SuperAdmin,
+                false,
+            ));
         }
 
         // 2. Check connection-level permission first
-        let (conn_level, all_tables) = self.resolve_connection_permission(user, conn_id).await?;
+        let (conn_level, all_tables, conn_source) = self.resolve_connection(user, conn_id).await?;
         if conn_level == PermissionLevel::None {
-            return Ok(PermissionLevel::None);
+            let reason = if user.api_token_scope.is_some() {
+                "the API token is scoped to a different connection"
+            } else {
+                "no connection-level grant exists for this user or their groups"
+            };
+            return Ok(denied(conn_source, false, reason));
+        }
+        if conn_source == PermissionSource::ApiToken {
+            return Ok(resolved(conn_level, conn_source, false));
         }
 
         // 3. If all_tables is true at connection level, check for table-level override
@@ -357,10 +635,13 @@ impl PermissionRepository for PgPermissionRepository {
             .await?;
 
             if let Some(utp) = user_table {
-                return Ok(PermissionLevel::from_str(&utp.permission));
+                return Ok(match PermissionLevel::from_str(&utp.permission) {
+                    PermissionLevel::None => denied(PermissionSource::User, true, TABLE_GRANT_NONE),
+                    level => resolved(level, PermissionSource::User, true),
+                });
             }
 
-            return Ok(conn_level);
+            return Ok(resolved(conn_level, conn_source, false));
         }
 
         // 4. all_tables = false → only allowed if explicit table permission exists
@@ -374,15 +655,19 @@ impl PermissionRepository for PgPermissionRepository {
         .await?;
 
         if let Some(utp) = user_table {
-            return Ok(PermissionLevel::from_str(&utp.permission));
+            return Ok(match PermissionLevel::from_str(&utp.permission) {
+                PermissionLevel::None => denied(PermissionSource::User, true, TABLE_GRANT_NONE),
+                level => resolved(level, PermissionSource::User, true),
+            });
         }
 
-        // Check group-level table permissions
-        let group_table_perms = sqlx::query_as::<_, GroupTablePermission>(
-            r#"SELECT gtp.* FROM group_table_permissions gtp
-               INNER JOIN group_members gm ON gm.group_id = gtp.group_id
-               WHERE gm.user_id = $1 AND gtp.connection_id = $2 AND gtp.table_name = $3"#,
-        )
+        // Check group-level table permissions (including ancestor groups)
+        let group_table_perms = sqlx::query_as::<_, GroupTablePermission>(&format!(
+            r#"{USER_GROUPS_CTE}
+               SELECT gtp.* FROM group_table_permissions gtp
+               INNER JOIN user_groups ug ON ug.group_id = gtp.group_id
+               WHERE gtp.connection_id = $2 AND gtp.table_name = $3"#
+        ))
         .bind(user.id)
         .bind(conn_id)
         .bind(table_name)
@@ -392,9 +677,16 @@ impl PermissionRepository for PgPermissionRepository {
         let best = group_table_perms
             .iter()
             .map(|p| PermissionLevel::from_str(&p.permission))
-            .max()
-            .unwrap_or(PermissionLevel::None);
-
-        Ok(best)
+            .max();
+
+        Ok(match best {
+            Some(PermissionLevel::None) => denied(PermissionSource::Group, true, TABLE_GRANT_NONE),
+            Some(level) => resolved(level, PermissionSource::Group, true),
+            None => denied(
+                PermissionSource::None,
+                false,
+                "connection grant is all_tables=false and no table-level grant exists",
+            ),
+        })
     }
 }
@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::permission::{PermissionTemplate, TemplateTableGrant};
+use crate::domain::repository::PermissionTemplateRepository;
+
+pub struct PgPermissionTemplateRepository {
+    pool: PgPool,
+}
+
+impl PgPermissionTemplateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_tables(&self, template: &mut PermissionTemplate) -> anyhow::Result<()> {
+        template.tables = sqlx::query_as::<_, TemplateTableGrant>(
+            "SELECT table_name, permission FROM permission_template_tables WHERE template_id = $1 ORDER BY table_name",
+        )
+        .bind(template.id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_tables(
+        tx: &mut Transaction<'_, Postgres>,
+        template_id: &Uuid,
+        tables: &[TemplateTableGrant],
+    ) -> anyhow::Result<()> {
+        let names: Vec<&str> = tables.iter().map(|t| t.table_name.as_str()).collect();
+        let permissions: Vec<&str> = tables.iter().map(|t| t.permission.as_str()).collect();
+        sqlx::query(
+            r#"INSERT INTO permission_template_tables (template_id, table_name, permission)
+               SELECT $1, t.table_name, t.permission
+               FROM UNNEST($2::text[], $3::text[]) AS t(table_name, permission)
+               ON CONFLICT (template_id, table_name) DO UPDATE SET permission = EXCLUDED.permission"#,
+        )
+        .bind(template_id)
+        .bind(&names)
+        .bind(&permissions)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PermissionTemplateRepository for PgPermissionTemplateRepository {
+    async fn create(
+        &self,
+        org_id: &Uuid,
+        name: &str,
+        description: Option<&str>,
+        tables: &[TemplateTableGrant],
+    ) -> anyhow::Result<PermissionTemplate> {
+        let mut tx = self.pool.begin().await?;
+        let mut template = sqlx::query_as::<_, PermissionTemplate>(
+            "INSERT INTO permission_templates (organization_id, name, description) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(org_id)
+        .bind(name)
+        .bind(description)
+        .fetch_one(&mut *tx)
+        .await?;
+        Self::insert_tables(&mut tx, &template.id, tables).await?;
+        tx.commit().await?;
+
+        self.load_tables(&mut template).await?;
+        Ok(template)
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> anyhow::Result<Option<PermissionTemplate>> {
+        let template = sqlx::query_as::<_, PermissionTemplate>(
+            "SELECT * FROM permission_templates WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        match template {
+            Some(mut template) => {
+                self.load_tables(&mut template).await?;
+                Ok(Some(template))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn list_by_org(&self, org_id: &Uuid) -> anyhow::Result<Vec<PermissionTemplate>> {
+        let mut templates = sqlx::query_as::<_, PermissionTemplate>(
+            "SELECT * FROM permission_templates WHERE organization_id = $1 ORDER BY name",
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await?;
+        for template in &mut templates {
+            self.load_tables(template).await?;
+        }
+        Ok(templates)
+    }
+
+    async fn update(
+        &self,
+        id: &Uuid,
+        name: &str,
+        description: Option<&str>,
+        tables: &[TemplateTableGrant],
+    ) -> anyhow::Result<Option<PermissionTemplate>> {
+        let mut tx = self.pool.begin().await?;
+        let template = sqlx::query_as::<_, PermissionTemplate>(
+            r#"UPDATE permission_templates SET name = $2, description = $3, updated_at = NOW()
+               WHERE id = $1
+               RETURNING *"#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(description)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(mut template) = template else {
+            return Ok(None);
+        };
+        sqlx::query("DELETE FROM permission_template_tables WHERE template_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        Self::insert_tables(&mut tx, id, tables).await?;
+        tx.commit().await?;
+
+        self.load_tables(&mut template).await?;
+        Ok(Some(template))
+    }
+
+    async fn delete(&self, id: &Uuid) -> anyhow::Result<bool> {
+        let result = sqlx::query("DELETE FROM permission_templates WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn apply_to_user(
+        &self,
+        template_id: &Uuid,
+        conn_id: &Uuid,
+        user_id: &Uuid,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"INSERT INTO user_table_permissions (user_id, connection_id, table_name, permission)
+               SELECT $3, $2, table_name, permission
+               FROM permission_template_tables
+               WHERE template_id = $1
+               ON CONFLICT (user_id, connection_id, table_name)
+               DO UPDATE SET permission = EXCLUDED.permission"#,
+        )
+        .bind(template_id)
+        .bind(conn_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn apply_to_group(
+        &self,
+        template_id: &Uuid,
+        conn_id: &Uuid,
+        group_id: &Uuid,
+    ) -> anyhow::Result<u64> {
+        let result = sqlx::query(
+            r#"INSERT INTO group_table_permissions (group_id, connection_id, table_name, permission)
+               SELECT $3, $2, table_name, permission
+               FROM permission_template_tables
+               WHERE template_id = $1
+               ON CONFLICT (group_id, connection_id, table_name)
+               DO UPDATE SET permission = EXCLUDED.permission"#,
+        )
+        .bind(template_id)
+        .bind(conn_id)
+        .bind(group_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::audit::QueryLogEntry;
+use crate::domain::pagination::Paginated;
+use crate::domain::repository::QueryLogRepository;
+
+pub struct PgQueryLogRepository {
+    pool: PgPool,
+}
+
+impl PgQueryLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl QueryLogRepository for PgQueryLogRepository {
+    async fn record(
+        &self,
+        conn_id: &Uuid,
+        user_id: &Uuid,
+        operation: &str,
+        table_name: Option<&str>,
+        duration_ms: i64,
+        success: bool,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"INSERT INTO query_log (connection_id, user_id, operation, table_name, duration_ms, success)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+        )
+        .bind(conn_id)
+        .bind(user_id)
+        .bind(operation)
+        .bind(table_name)
+        .bind(duration_ms)
+        .bind(success)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn prune(&self, cutoff: chrono::DateTime<chrono::Utc>) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM query_log WHERE created_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn list(
+        &self,
+        conn_id: &Uuid,
+        page: u32,
+        per_page: u32,
+    ) -> anyhow::Result<Paginated<QueryLogEntry>> {
+        let total_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM query_log WHERE connection_id = $1")
+                .bind(conn_id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        let offset = (page.saturating_sub(1) as i64) * per_page as i64;
+        let items = sqlx::query_as::<_, QueryLogEntry>(
+            r#"SELECT * FROM query_log WHERE connection_id = $1
+               ORDER BY created_at DESC, id DESC
+               LIMIT $2 OFFSET $3"#,
+        )
+        .bind(conn_id)
+        .bind(per_page as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Paginated {
+            items,
+            total_count,
+            page,
+            per_page,
+        })
+    }
+}
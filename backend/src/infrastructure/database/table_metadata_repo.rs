@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::data::{ColumnMetadata, TableMetadata};
+use crate::domain::repository::TableMetadataRepository;
+
+pub struct PgTableMetadataRepository {
+    pool: PgPool,
+}
+
+impl PgTableMetadataRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TableMetadataRepository for PgTableMetadataRepository {
+    async fn list(&self, conn_id: &Uuid) -> anyhow::Result<Vec<TableMetadata>> {
+        let rows = sqlx::query_as::<_, TableMetadata>(
+            r#"SELECT table_name, display_name, description FROM connection_table_metadata
+               WHERE connection_id = $1
+               ORDER BY table_name"#,
+        )
+        .bind(conn_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn get(&self, conn_id: &Uuid, table_name: &str) -> anyhow::Result<Option<TableMetadata>> {
+        let row = sqlx::query_as::<_, TableMetadata>(
+            r#"SELECT table_name, display_name, description FROM connection_table_metadata
+               WHERE connection_id = $1 AND table_name = $2"#,
+        )
+        .bind(conn_id)
+        .bind(table_name)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    async fn upsert(
+        &self,
+        conn_id: &Uuid,
+        table_name: &str,
+        display_name: Option<&str>,
+        description: Option<&str>,
+    ) -> anyhow::Result<TableMetadata> {
+        let row = sqlx::query_as::<_, TableMetadata>(
+            r#"INSERT INTO connection_table_metadata (connection_id, table_name, display_name, description)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (connection_id, table_name)
+               DO UPDATE SET display_name = EXCLUDED.display_name,
+                             description = EXCLUDED.description,
+                             updated_at = NOW()
+               RETURNING table_name, display_name, description"#,
+        )
+        .bind(conn_id)
+        .bind(table_name)
+        .bind(display_name)
+        .bind(description)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row)
+    }
+
+    async fn list_columns(
+        &self,
+        conn_id: &Uuid,
+        table_name: &str,
+    ) -> anyhow::Result<Vec<ColumnMetadata>> {
+        let rows = sqlx::query_as::<_, ColumnMetadata>(
+            r#"SELECT column_name, description, display_hint FROM connection_column_metadata
+               WHERE connection_id = $1 AND table_name = $2
+               ORDER BY column_name"#,
+        )
+        .bind(conn_id)
+        .bind(table_name)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    async fn upsert_column(
+        &self,
+        conn_id: &Uuid,
+        table_name: &str,
+        column_name: &str,
+        description: Option<&str>,
+        display_hint: Option<&str>,
+    ) -> anyhow::Result<ColumnMetadata> {
+        let row = sqlx::query_as::<_, ColumnMetadata>(
+            r#"INSERT INTO connection_column_metadata (connection_id, table_name, column_name, description, display_hint)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (connection_id, table_name, column_name)
+               DO UPDATE SET description = EXCLUDED.description,
+                             display_hint = EXCLUDED.display_hint,
+                             updated_at = NOW()
+               RETURNING column_name, description, display_hint"#,
+        )
+        .bind(conn_id)
+        .bind(table_name)
+        .bind(column_name)
+        .bind(description)
+        .bind(display_hint)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row)
+    }
+}
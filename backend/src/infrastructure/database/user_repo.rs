@@ -49,4 +49,27 @@ impl UserRepository for PgUserRepository {
             .await?;
         Ok(user)
     }
+
+    async fn delete(&self, user_id: &Uuid) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        for sql in [
+            "DELETE FROM user_connection_permissions WHERE user_id = $1",
+            "DELETE FROM user_table_permissions WHERE user_id = $1",
+            "DELETE FROM group_members WHERE user_id = $1",
+            "DELETE FROM organization_members WHERE user_id = $1",
+            // Org connections outlive their creator; only personal ones cascade.
+            "UPDATE saved_connections SET owner_user_id = NULL WHERE owner_user_id = $1 AND organization_id IS NOT NULL",
+        ] {
+            sqlx::query(sql).bind(user_id).execute(&mut *tx).await?;
+        }
+
+        let result = sqlx::query("DELETE FROM app_users WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use sqlx::postgres::PgListener;
+use tokio::sync::{Mutex, broadcast};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::domain::data::RowChange;
+
+/// Buffered changes per table before slow subscribers start skipping events.
+const CHANGE_CHANNEL_CAPACITY: usize = 256;
+
+/// NOTIFY channel the row-change trigger for `table` publishes on.
+pub fn notify_channel(table: &str) -> String {
+    format!("dbworks_{}", table)
+}
+
+/// Turn a NOTIFY payload (the inserted row as JSON) into a `RowChange`.
+/// Payloads that are not JSON are passed through as a string.
+pub fn parse_notification(table: &str, payload: &str) -> RowChange {
+    RowChange {
+        table: table.to_string(),
+        operation: "insert".to_string(),
+        row: serde_json::from_str(payload)
+            .unwrap_or_else(|_| serde_json::Value::String(payload.to_string())),
+    }
+}
+
+/// Fans out PostgreSQL notifications to subscribers. Each subscribed
+/// (connection, table) pair gets one dedicated listener connection, which
+/// stops once its last subscriber has gone away.
+#[derive(Default)]
+pub struct ChangeFeed {
+    listeners: Mutex<HashMap<(Uuid, String), Listener>>,
+}
+
+/// A running listener task. Only the task holds a strong sender, so
+/// subscriber streams end as soon as the task does.
+struct Listener {
+    sender: broadcast::WeakSender<RowChange>,
+    task: JoinHandle<()>,
+}
+
+impl ChangeFeed {
+    /// Subscribe to changes on `table`, starting a listener on
+    /// `connection_string` if nobody is subscribed yet.
+    pub async fn subscribe(
+        &self,
+        conn_id: Uuid,
+        connection_string: &str,
+        table: &str,
+    ) -> anyhow::Result<broadcast::Receiver<RowChange>> {
+        let key = (conn_id, table.to_string());
+        let mut listeners = self.listeners.lock().await;
+        if let Some(tx) = listeners.get(&key).and_then(|l| l.sender.upgrade())
+            && tx.receiver_count() > 0
+        {
+            return Ok(tx.subscribe());
+        }
+
+        let mut listener = PgListener::connect(connection_string).await?;
+        listener.listen(&notify_channel(table)).await?;
+        let (tx, rx) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        let sender = tx.downgrade();
+
+        let table = table.to_string();
+        let task = tokio::spawn(async move {
+            tracing::info!(connection_id = %conn_id, table = %table, "Change listener started");
+            loop {
+                match listener.recv().await {
+                    Ok(notification) => {
+                        let change = parse_notification(&table, notification.payload());
+                        if tx.send(change).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(connection_id = %conn_id, table = %table, error = %e, "Change listener failed");
+                        break;
+                    }
+                }
+            }
+            tracing::info!(connection_id = %conn_id, table = %table, "Change listener stopped");
+        });
+        listeners.insert(key, Listener { sender, task });
+
+        Ok(rx)
+    }
+
+    /// Stop every listener for a connection, ending its subscribers' streams.
+    pub async fn remove_connection(&self, conn_id: &Uuid) {
+        self.listeners.lock().await.retain(|(id, _), listener| {
+            if id == conn_id {
+                listener.task.abort();
+            }
+            id != conn_id
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_payload_is_parsed_as_json_row() {
+        let change = parse_notification("orders", r#"{"id":1,"total":9.5}"#);
+        assert_eq!(change.table, "orders");
+        assert_eq!(change.operation, "insert");
+        assert_eq!(change.row, serde_json::json!({ "id": 1, "total": 9.5 }));
+    }
+
+    #[test]
+    fn non_json_payload_is_kept_as_string() {
+        let change = parse_notification("orders", "42");
+        assert_eq!(change.row, serde_json::json!(42));
+        let change = parse_notification("orders", "not json");
+        assert_eq!(change.row, serde_json::json!("not json"));
+    }
+}
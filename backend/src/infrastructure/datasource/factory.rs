@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::connection::ConnectionInfo;
+
+use super::mysql::MySqlDataSource;
+use super::postgres::PostgresDataSource;
+use super::{
+    DataSource, HiddenTablesDataSource, PoolLimits, RetryPolicy, RetryingDataSource,
+    pool_warm_up_connections, statement_timeout,
+};
+
+/// Builds a live `DataSource` for a connection. Injected into
+/// `ConnectionManager` so it never constructs concrete backends itself.
+#[async_trait]
+pub trait DataSourceFactory: Send + Sync {
+    async fn build(&self, info: &ConnectionInfo) -> anyhow::Result<Arc<dyn DataSource>>;
+}
+
+/// Connection URL for a saved connection, e.g. `postgres://user:pw@host:5432/db`.
+pub fn connection_string(info: &ConnectionInfo) -> String {
+    format!(
+        "{}://{}:{}@{}:{}/{}",
+        info.db_type, info.user, info.password, info.host, info.port, info.database
+    )
+}
+
+/// Connection URLs for a connection's read replicas: the primary's URL with
+/// each `host` or `host:port` from `replica_hosts` swapped in.
+pub fn replica_connection_strings(info: &ConnectionInfo) -> anyhow::Result<Vec<String>> {
+    info.replica_hosts
+        .iter()
+        .map(|replica| {
+            let (host, port) = match replica.rsplit_once(':') {
+                Some((host, port)) => (
+                    host,
+                    port.parse::<u16>()
+                        .map_err(|_| anyhow::anyhow!("Invalid replica port in '{}'", replica))?,
+                ),
+                None => (replica.as_str(), info.port),
+            };
+            Ok(connection_string(&ConnectionInfo {
+                host: host.to_string(),
+                port,
+                ..info.clone()
+            }))
+        })
+        .collect()
+}
+
+/// Default factory: connects to PostgreSQL, or MySQL when `db_type` is "mysql",
+/// then warms the pool up (see `pool_warm_up_connections`) and wraps it so
+/// transient read failures are retried (see `RetryPolicy`) and the
+/// connection's `hidden_tables` are unreachable.
+pub struct DefaultDataSourceFactory;
+
+#[async_trait]
+impl DataSourceFactory for DefaultDataSourceFactory {
+    async fn build(&self, info: &ConnectionInfo) -> anyhow::Result<Arc<dyn DataSource>> {
+        let timeout = statement_timeout(info.statement_timeout_ms);
+        let conn_string = connection_string(info);
+        let limits =
+            PoolLimits::for_connection(info.pool_idle_timeout_ms, info.pool_max_lifetime_ms);
+        let mut ds: Arc<dyn DataSource> = match info.db_type.as_str() {
+            "postgres" => Arc::new(
+                PostgresDataSource::with_session(
+                    &conn_string,
+                    timeout,
+                    info.session_search_path().as_deref(),
+                    info.role.as_deref(),
+                    limits,
+                    &replica_connection_strings(info)?,
+                )
+                .await?,
+            ),
+            "mysql" => Arc::new(MySqlDataSource::new(&conn_string, timeout, limits).await?),
+            other => anyhow::bail!("Unsupported db_type: {}", other),
+        };
+        let warm_up = pool_warm_up_connections();
+        if warm_up > 0 {
+            ds.warm_up(warm_up).await;
+        }
+        let retry = RetryPolicy::from_env();
+        if retry.max_retries > 0 {
+            ds = Arc::new(RetryingDataSource::new(ds, retry));
+        }
+        if !info.hidden_tables.is_empty() {
+            ds = Arc::new(HiddenTablesDataSource::new(ds, info.hidden_tables.clone()));
+        }
+        Ok(ds)
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::data::{AggregateRow, AggregateSpec, RowsResponse, TableInfo, TableSchema};
+use crate::presentation::request::RowsQuery;
+
+use super::{DataSource, DataSourceError, DataSourceTransaction, PoolStatus};
+
+/// Wraps a `DataSource` so a connection's denylisted tables don't exist as
+/// far as the app is concerned: `list_tables` leaves them out and every
+/// table operation on one fails with `DataSourceError::TableNotFound`.
+pub struct HiddenTablesDataSource {
+    inner: Arc<dyn DataSource>,
+    hidden: Vec<String>,
+}
+
+impl HiddenTablesDataSource {
+    pub fn new(inner: Arc<dyn DataSource>, hidden: Vec<String>) -> Self {
+        Self { inner, hidden }
+    }
+
+    fn check(&self, table_name: &str) -> anyhow::Result<()> {
+        if self.hidden.iter().any(|t| t == table_name) {
+            return Err(anyhow::Error::new(DataSourceError::TableNotFound));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DataSource for HiddenTablesDataSource {
+    async fn list_tables(&self) -> anyhow::Result<Vec<TableInfo>> {
+        let mut tables = self.inner.list_tables().await?;
+        tables.retain(|t| !self.hidden.contains(&t.table_name));
+        Ok(tables)
+    }
+
+    async fn get_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema> {
+        self.check(table_name)?;
+        self.inner.get_table_schema(table_name).await
+    }
+
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool> {
+        self.check(table_name)?;
+        self.inner.is_view(table_name).await
+    }
+
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse> {
+        self.check(table_name)?;
+        self.inner.list_rows(table_name, query, tx).await
+    }
+
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64> {
+        self.check(table_name)?;
+        self.inner.count_rows(table_name, filter).await
+    }
+
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.check(table_name)?;
+        self.inner.distinct_values(table_name, column, limit).await
+    }
+
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>> {
+        self.check(table_name)?;
+        self.inner.aggregate(table_name, spec).await
+    }
+
+    async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value> {
+        self.check(table_name)?;
+        self.inner.get_row(table_name, pk_value).await
+    }
+
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.check(table_name)?;
+        self.inner.explain_rows(table_name, query).await
+    }
+
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.check(table_name)?;
+        self.inner.get_rows(table_name, pk_values).await
+    }
+
+    async fn insert_row(
+        &self,
+        table_name: &str,
+        data: &serde_json::Value,
+        returning: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.check(table_name)?;
+        self.inner.insert_row(table_name, data, returning, tx).await
+    }
+
+    async fn update_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        data: &serde_json::Value,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.check(table_name)?;
+        self.inner.update_row(table_name, pk_value, data, tx).await
+    }
+
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()> {
+        self.check(table_name)?;
+        self.inner.delete_row(table_name, pk_value, tx).await
+    }
+
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64> {
+        self.check(table_name)?;
+        self.inner.count_by_pk(table_name, pk_value).await
+    }
+
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64> {
+        self.check(table_name)?;
+        self.inner.bulk_update(table_name, filter, data).await
+    }
+
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64> {
+        self.check(table_name)?;
+        self.inner.bulk_delete(table_name, filter).await
+    }
+
+    async fn begin(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin().await
+    }
+
+    async fn begin_snapshot(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin_snapshot().await
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        self.inner.pool_status()
+    }
+
+    async fn warm_up(&self, connections: u32) {
+        self.inner.warm_up(connections).await
+    }
+
+    async fn invalidate_schema_cache(&self) {
+        self.inner.invalidate_schema_cache().await
+    }
+
+    async fn close(&self) {
+        self.inner.close().await
+    }
+}
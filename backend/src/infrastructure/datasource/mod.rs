@@ -1,11 +1,317 @@
+mod change_feed;
+mod factory;
+mod hidden_tables;
 pub mod mysql;
 pub mod postgres;
+mod profile;
+mod query_log;
+mod retry;
+mod transaction;
+
+use std::fmt;
+use std::time::Duration;
 
 use async_trait::async_trait;
 
-use crate::domain::data::{RowsResponse, TableInfo, TableSchema};
+use crate::domain::data::{AggregateRow, AggregateSpec, RowsResponse, TableInfo, TableSchema};
 use crate::presentation::request::RowsQuery;
 
+pub use change_feed::{ChangeFeed, notify_channel, parse_notification};
+pub use factory::{
+    DataSourceFactory, DefaultDataSourceFactory, connection_string, replica_connection_strings,
+};
+pub use hidden_tables::HiddenTablesDataSource;
+pub use profile::{DEFAULT_SLOW_QUERY_BUFFER_SIZE, ProfilingDataSource, QueryProfile};
+pub use query_log::{
+    DEFAULT_QUERY_LOG_RETENTION_DAYS, QueryLogWriter, QueryLoggingDataSource,
+    query_log_retention_days,
+};
+pub use retry::{RetryPolicy, RetryingDataSource};
+pub use transaction::{
+    DEFAULT_MAX_OPEN_TRANSACTIONS, DEFAULT_TRANSACTION_TIMEOUT_MS, DataSourceTransaction,
+    max_open_transactions, transaction_timeout,
+};
+
+/// Statement timeout applied when neither the connection nor the
+/// `STATEMENT_TIMEOUT_MS` environment variable specify one.
+pub const DEFAULT_STATEMENT_TIMEOUT_MS: u32 = 30_000;
+
+/// Resolve the statement timeout for a connection.
+/// Priority: per-connection override > `STATEMENT_TIMEOUT_MS` env > built-in default.
+pub fn statement_timeout(override_ms: Option<u32>) -> Duration {
+    let ms = override_ms.unwrap_or_else(|| {
+        std::env::var("STATEMENT_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_STATEMENT_TIMEOUT_MS)
+    });
+    Duration::from_millis(ms as u64)
+}
+
+/// Upper bound on connections in each datasource pool.
+pub const POOL_MAX_CONNECTIONS: u32 = 5;
+
+/// How long a call waits for a free pool connection before failing with
+/// `DataSourceError::PoolTimeout`, unless `POOL_ACQUIRE_TIMEOUT_MS` overrides it.
+pub const DEFAULT_POOL_ACQUIRE_TIMEOUT_MS: u64 = 10_000;
+
+/// How long a pooled connection may sit idle before it is closed, unless
+/// `POOL_IDLE_TIMEOUT_MS` overrides it.
+pub const DEFAULT_POOL_IDLE_TIMEOUT_MS: u32 = 600_000;
+
+/// How long a pooled connection may live before it is replaced, unless
+/// `POOL_MAX_LIFETIME_MS` overrides it.
+pub const DEFAULT_POOL_MAX_LIFETIME_MS: u32 = 1_800_000;
+
+/// Size of a datasource pool, how long a call waits for a connection, and
+/// when pooled connections are retired. Retiring connections before a
+/// managed database kills them server-side avoids stale-connection errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolLimits {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    /// `None` keeps idle connections open indefinitely.
+    pub idle_timeout: Option<Duration>,
+    /// `None` lets connections live indefinitely.
+    pub max_lifetime: Option<Duration>,
+}
+
+impl PoolLimits {
+    /// `POOL_MAX_CONNECTIONS` connections, waiting `POOL_ACQUIRE_TIMEOUT_MS`
+    /// (default 10s) for one to become free, closed after
+    /// `POOL_IDLE_TIMEOUT_MS` idle (default 10 min) or `POOL_MAX_LIFETIME_MS`
+    /// in total (default 30 min). `0` disables either limit.
+    pub fn from_env() -> Self {
+        Self::for_connection(None, None)
+    }
+
+    /// Like `from_env`, with a connection's own idle timeout and max
+    /// lifetime taking precedence over the environment.
+    pub fn for_connection(idle_timeout_ms: Option<u32>, max_lifetime_ms: Option<u32>) -> Self {
+        let acquire_timeout_ms = std::env::var("POOL_ACQUIRE_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_POOL_ACQUIRE_TIMEOUT_MS);
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse().ok());
+        let idle_timeout_ms = idle_timeout_ms
+            .or_else(|| var("POOL_IDLE_TIMEOUT_MS"))
+            .unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT_MS);
+        let max_lifetime_ms = max_lifetime_ms
+            .or_else(|| var("POOL_MAX_LIFETIME_MS"))
+            .unwrap_or(DEFAULT_POOL_MAX_LIFETIME_MS);
+        let limit = |ms: u32| (ms > 0).then(|| Duration::from_millis(ms as u64));
+        Self {
+            max_connections: POOL_MAX_CONNECTIONS,
+            acquire_timeout: Duration::from_millis(acquire_timeout_ms),
+            idle_timeout: limit(idle_timeout_ms),
+            max_lifetime: limit(max_lifetime_ms),
+        }
+    }
+
+    /// Pool options carrying these limits, for either backend.
+    pub fn pool_options<DB: sqlx::Database>(&self) -> sqlx::pool::PoolOptions<DB> {
+        sqlx::pool::PoolOptions::new()
+            .max_connections(self.max_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+}
+
+/// Connections opened eagerly when a datasource is registered, unless
+/// `POOL_WARM_UP_CONNECTIONS` overrides it.
+pub const DEFAULT_POOL_WARM_UP_CONNECTIONS: u32 = 2;
+
+/// Resolve how many pool connections to open up front for a new datasource.
+/// `POOL_WARM_UP_CONNECTIONS=0` skips warm-up; values above the pool size are capped.
+pub fn pool_warm_up_connections() -> u32 {
+    std::env::var("POOL_WARM_UP_CONNECTIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_POOL_WARM_UP_CONNECTIONS)
+        .min(POOL_MAX_CONNECTIONS)
+}
+
+/// Whether connections with the same target and pool options share one
+/// live pool. Off unless `SHARE_CONNECTION_POOLS=true`, since each connection
+/// sharing a pool reports that pool's gauges as its own.
+pub fn share_connection_pools() -> bool {
+    std::env::var("SHARE_CONNECTION_POOLS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(false)
+}
+
+/// Classified datasource failures that callers may want to handle
+/// differently from a generic query error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataSourceError {
+    /// The statement was cancelled because it exceeded the statement timeout.
+    Timeout,
+    /// The connection dropped or could not be obtained; retrying may succeed.
+    Transient,
+    /// Connecting failed because the server rejected the credentials.
+    AuthenticationFailed,
+    /// Connecting failed because the server could not be reached.
+    HostUnreachable,
+    /// Connecting failed because the named database does not exist.
+    DatabaseNotFound,
+    /// The query named a table that does not exist.
+    TableNotFound,
+    /// Every pool connection stayed busy for the whole acquire timeout; the
+    /// server is saturated rather than the query failing.
+    PoolTimeout,
+    /// A write targeted a table in a system schema (see `is_system_schema`).
+    SystemTable,
+}
+
+impl fmt::Display for DataSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "Query exceeded the statement timeout"),
+            Self::Transient => write!(f, "Transient connection failure"),
+            Self::AuthenticationFailed => {
+                write!(f, "Authentication failed: check the user name and password")
+            }
+            Self::HostUnreachable => write!(
+                f,
+                "Host unreachable: check the host, port and that the database server is running"
+            ),
+            Self::DatabaseNotFound => write!(f, "Database does not exist on this server"),
+            Self::TableNotFound => write!(f, "Table does not exist"),
+            Self::PoolTimeout => {
+                write!(f, "Server busy: no database connection became free in time")
+            }
+            Self::SystemTable => write!(f, "System tables cannot be modified"),
+        }
+    }
+}
+
+impl std::error::Error for DataSourceError {}
+
+impl DataSourceError {
+    /// Short, stable label for this error kind (used as a metrics label).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Transient => "transient",
+            Self::AuthenticationFailed => "authentication_failed",
+            Self::HostUnreachable => "host_unreachable",
+            Self::DatabaseNotFound => "database_not_found",
+            Self::TableNotFound => "table_not_found",
+            Self::PoolTimeout => "pool_timeout",
+            Self::SystemTable => "system_table",
+        }
+    }
+
+    /// Inspect an error returned by a `DataSource` and classify it, if it is
+    /// one of the known failure kinds. Returns `None` for everything else.
+    pub fn classify(err: &anyhow::Error) -> Option<Self> {
+        // Also finds a kind attached with `.context(...)`, which `chain()` can't downcast
+        if let Some(e) = err.downcast_ref::<DataSourceError>() {
+            return Some(e.clone());
+        }
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<DataSourceError>() {
+                return Some(e.clone());
+            }
+            if let Some(sqlx::Error::PoolTimedOut) = cause.downcast_ref::<sqlx::Error>() {
+                return Some(Self::PoolTimeout);
+            }
+            if let Some(sqlx::Error::Io(_) | sqlx::Error::WorkerCrashed) =
+                cause.downcast_ref::<sqlx::Error>()
+            {
+                return Some(Self::Transient);
+            }
+            if let Some(sqlx::Error::Database(db_err)) = cause.downcast_ref::<sqlx::Error>() {
+                // PostgreSQL: class 08 connection_exception
+                if db_err.code().is_some_and(|c| c.starts_with("08")) {
+                    return Some(Self::Transient);
+                }
+                // PostgreSQL: 57014 query_canceled (raised by statement_timeout)
+                if db_err.code().as_deref() == Some("57014") {
+                    return Some(Self::Timeout);
+                }
+                // PostgreSQL: 42P01 undefined_table
+                if db_err.code().as_deref() == Some("42P01") {
+                    return Some(Self::TableNotFound);
+                }
+                if let Some(my_err) = db_err.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+                    match my_err.number() {
+                        // MySQL: 3024 ER_QUERY_TIMEOUT (raised by max_execution_time)
+                        3024 => return Some(Self::Timeout),
+                        // MySQL: 1146 ER_NO_SUCH_TABLE
+                        1146 => return Some(Self::TableNotFound),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Classify an error raised while opening a connection. Returns `None`
+    /// for failures that don't fit a known kind.
+    pub fn classify_connect(err: &sqlx::Error) -> Option<Self> {
+        match err {
+            sqlx::Error::Io(_) | sqlx::Error::Tls(_) | sqlx::Error::PoolTimedOut => {
+                Some(Self::HostUnreachable)
+            }
+            sqlx::Error::Database(db_err) => match db_err.code().as_deref() {
+                // PostgreSQL: 28P01 invalid_password, 28000 invalid_authorization_specification
+                Some("28P01" | "28000") => Some(Self::AuthenticationFailed),
+                // PostgreSQL: 3D000 invalid_catalog_name
+                Some("3D000") => Some(Self::DatabaseNotFound),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Wrap a connect failure so the classified kind is the top-level message
+/// while the original sqlx error stays in the chain.
+pub(crate) fn connect_error(err: sqlx::Error) -> anyhow::Error {
+    match DataSourceError::classify_connect(&err) {
+        Some(kind) => anyhow::Error::new(err).context(kind),
+        None => err.into(),
+    }
+}
+
+/// Whether `schema` holds the server's own catalogs (PostgreSQL or MySQL).
+/// Writes into these are refused no matter what the caller is granted.
+pub fn is_system_schema(schema: &str) -> bool {
+    let schema = schema.to_ascii_lowercase();
+    matches!(
+        schema.as_str(),
+        "pg_catalog" | "information_schema" | "mysql" | "performance_schema" | "sys"
+    ) || schema.starts_with("pg_toast")
+}
+
+// Logging never includes bound values (filter values, search terms, primary
+// keys or row data), only the parameterized SQL and identifiers.
+
+/// Column of a `column:op:value` filter, for logging without its value.
+pub fn filter_column(filter: &str) -> &str {
+    filter.split(':').next().unwrap_or_default()
+}
+
+/// Keys of a row payload, for logging without its values.
+pub(crate) fn payload_columns(data: &serde_json::Value) -> Vec<&str> {
+    data.as_object()
+        .map(|obj| obj.keys().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Snapshot of a datasource's connection pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatus {
+    /// Connections currently open (idle + in use)
+    pub size: u32,
+    /// Connections currently idle
+    pub idle: usize,
+}
+
 /// Trait abstracting database operations.
 /// Implement this for each data source (PostgreSQL, MySQL, NoSQL, etc.)
 #[async_trait]
@@ -16,27 +322,196 @@ pub trait DataSource: Send + Sync {
     /// Get schema information for a specific table
     async fn get_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema>;
 
-    /// List rows with pagination, sorting, and filtering
-    async fn list_rows(&self, table_name: &str, query: &RowsQuery) -> anyhow::Result<RowsResponse>;
+    /// Whether the name refers to a view rather than a base table
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool>;
+
+    /// List rows with pagination, sorting, and filtering; inside `tx` when given
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse>;
+
+    /// Count rows matching an optional `column:op:value` filter
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64>;
+
+    /// Distinct values of a column in ascending order, at most `limit` of them
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>>;
+
+    /// Group rows by a column and aggregate a metric per group, ordered by group
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>>;
 
     /// Get a single row by its primary key value
     async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value>;
 
-    /// Insert a new row
+    /// Run the query `list_rows` would issue for `query` under EXPLAIN and
+    /// return the database's JSON plan instead of the rows.
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value>;
+
+    /// Get the rows matching any of the given primary key values, in request order.
+    /// Keys with no matching row are skipped.
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>>;
+
+    /// Insert a new row, inside `tx` when given. The inserted row is
+    /// returned narrowed to `returning` (empty means every column).
     async fn insert_row(
         &self,
         table_name: &str,
         data: &serde_json::Value,
+        returning: &[String],
+        tx: Option<&DataSourceTransaction>,
     ) -> anyhow::Result<serde_json::Value>;
 
-    /// Update an existing row by primary key
+    /// Update an existing row by primary key, inside `tx` when given
     async fn update_row(
         &self,
         table_name: &str,
         pk_value: &str,
         data: &serde_json::Value,
+        tx: Option<&DataSourceTransaction>,
     ) -> anyhow::Result<serde_json::Value>;
 
-    /// Delete a row by primary key
-    async fn delete_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<()>;
+    /// Delete a row by primary key, inside `tx` when given
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()>;
+
+    /// Count the rows `delete_row` would remove for this primary key value
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64>;
+
+    /// Apply a partial update to every row matching a `column:op:value` filter
+    /// in a single statement. Returns the number of rows affected.
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64>;
+
+    /// Delete every row matching a `column:op:value` filter in a single
+    /// statement. Returns the number of rows affected.
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64>;
+
+    /// Open a transaction on a dedicated connection for writes that must
+    /// succeed or fail together. Datasources without support refuse.
+    async fn begin(&self) -> anyhow::Result<DataSourceTransaction> {
+        anyhow::bail!("Transactions are not supported on this connection")
+    }
+
+    /// Open a read-only `REPEATABLE READ` transaction, so every read done in
+    /// it sees the same snapshot. Datasources without support refuse.
+    async fn begin_snapshot(&self) -> anyhow::Result<DataSourceTransaction> {
+        anyhow::bail!("Snapshots are not supported on this connection")
+    }
+
+    /// Current pool utilization, if the datasource is pool-backed
+    fn pool_status(&self) -> Option<PoolStatus> {
+        None
+    }
+
+    /// Eagerly open up to `connections` pooled connections so the first
+    /// user queries don't pay the connect cost. Failures are logged, not fatal.
+    async fn warm_up(&self, _connections: u32) {}
+
+    /// Drop any cached table schemas so the next lookup reads the catalog again.
+    /// Call after DDL changes; datasources without a cache do nothing.
+    async fn invalidate_schema_cache(&self) {}
+
+    /// Close the underlying pool, waiting for checked-out connections to be returned
+    async fn close(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statement_timeout_prefers_override() {
+        assert_eq!(statement_timeout(Some(1500)), Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn pool_options_carry_connection_idle_timeout_and_lifetime() {
+        let limits = PoolLimits::for_connection(Some(5_000), Some(0));
+        let options = limits.pool_options::<sqlx::Postgres>();
+        assert_eq!(options.get_idle_timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(options.get_max_lifetime(), None);
+        assert_eq!(options.get_max_connections(), POOL_MAX_CONNECTIONS);
+    }
+
+    #[test]
+    fn system_schemas_cover_both_backends() {
+        assert!(is_system_schema("pg_catalog"));
+        assert!(is_system_schema("INFORMATION_SCHEMA"));
+        assert!(is_system_schema("pg_toast"));
+        assert!(is_system_schema("performance_schema"));
+        assert!(!is_system_schema("public"));
+        assert!(!is_system_schema("analytics"));
+    }
+
+    #[test]
+    fn classify_unrelated_error_is_none() {
+        let err = anyhow::anyhow!("boom");
+        assert_eq!(DataSourceError::classify(&err), None);
+    }
+
+    #[test]
+    fn classify_passes_through_datasource_error() {
+        let err = anyhow::Error::new(DataSourceError::Timeout);
+        assert_eq!(
+            DataSourceError::classify(&err),
+            Some(DataSourceError::Timeout)
+        );
+    }
+
+    #[test]
+    fn classify_io_as_transient_and_pool_timeout_separately() {
+        let reset = anyhow::Error::new(sqlx::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset,
+        )));
+        let pool = anyhow::Error::new(sqlx::Error::PoolTimedOut);
+        assert_eq!(
+            DataSourceError::classify(&reset),
+            Some(DataSourceError::Transient)
+        );
+        assert_eq!(
+            DataSourceError::classify(&pool),
+            Some(DataSourceError::PoolTimeout)
+        );
+    }
+
+    #[test]
+    fn connect_error_puts_classified_kind_first() {
+        let refused = sqlx::Error::Io(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+        let err = connect_error(refused);
+        assert_eq!(
+            DataSourceError::classify(&err),
+            Some(DataSourceError::HostUnreachable)
+        );
+        assert_eq!(
+            err.to_string(),
+            DataSourceError::HostUnreachable.to_string()
+        );
+    }
 }
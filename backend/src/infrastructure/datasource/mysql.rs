@@ -1,10 +1,14 @@
 use async_trait::async_trait;
-use sqlx::mysql::MySqlPoolOptions;
-use sqlx::{MySqlPool, Row};
+use sqlx::{MySql, MySqlPool, Row};
 use std::time::Duration;
 
-use crate::domain::data::{ColumnInfo, RowsResponse, TableInfo, TableSchema};
-use crate::infrastructure::datasource::DataSource;
+use crate::domain::data::{
+    AggregateRow, AggregateSpec, ColumnInfo, RowsResponse, TableInfo, TableSchema,
+};
+use crate::infrastructure::datasource::{
+    DataSource, DataSourceError, DataSourceTransaction, PoolLimits, PoolStatus, filter_column,
+    is_system_schema,
+};
 use crate::presentation::request::RowsQuery;
 
 pub struct MySqlDataSource {
@@ -20,6 +24,36 @@ fn get_string(row: &sqlx::mysql::MySqlRow, col: &str) -> String {
     })
 }
 
+/// Helper: extract the variants from an `enum('a','b')` column type.
+/// Returns `None` for any other column type.
+fn parse_enum_values(column_type: &str) -> Option<Vec<String>> {
+    let inner = column_type
+        .strip_prefix("enum(")
+        .and_then(|s| s.strip_suffix(')'))?;
+    let mut values = Vec::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                // A doubled quote is an escaped quote inside the value
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    value.push('\'');
+                    continue;
+                }
+                break;
+            }
+            value.push(c);
+        }
+        values.push(value);
+    }
+    Some(values)
+}
+
 /// Helper: decode an optional string column.
 fn get_opt_string(row: &sqlx::mysql::MySqlRow, col: &str) -> Option<String> {
     match row.try_get::<Option<String>, _>(col) {
@@ -32,13 +66,29 @@ fn get_opt_string(row: &sqlx::mysql::MySqlRow, col: &str) -> Option<String> {
 }
 
 impl MySqlDataSource {
-    pub async fn new(connection_string: &str) -> anyhow::Result<Self> {
+    pub async fn new(
+        connection_string: &str,
+        statement_timeout: Duration,
+        limits: PoolLimits,
+    ) -> anyhow::Result<Self> {
         let safe_conn = connection_string.split('@').next_back().unwrap_or("***");
         tracing::info!(target = %safe_conn, "Creating MySQL connection pool...");
 
-        let pool = MySqlPoolOptions::new()
-            .max_connections(5)
-            .acquire_timeout(Duration::from_secs(10))
+        // max_execution_time bounds SELECT statements for every session on the pool
+        let statement_timeout_ms = statement_timeout.as_millis() as u64;
+        let pool = limits
+            .pool_options::<MySql>()
+            .after_connect(move |conn, _meta| {
+                Box::pin(async move {
+                    sqlx::query(&format!(
+                        "SET SESSION max_execution_time = {}",
+                        statement_timeout_ms
+                    ))
+                    .execute(conn)
+                    .await?;
+                    Ok(())
+                })
+            })
             .connect(connection_string)
             .await
             .map_err(|e| {
@@ -61,7 +111,8 @@ impl MySqlDataSource {
 
         tracing::info!(
             target = %safe_conn,
-            max_connections = 5,
+            max_connections = limits.max_connections,
+            statement_timeout_ms = statement_timeout_ms,
             "MySQL connection pool created and verified"
         );
         Ok(Self { pool })
@@ -89,10 +140,114 @@ impl MySqlDataSource {
         Ok(columns)
     }
 
+    /// Refuse a write when the connection's database is a system schema;
+    /// unqualified table names always resolve there.
+    async fn ensure_not_system_table(&self, table_name: &str) -> anyhow::Result<()> {
+        let row = sqlx::query("SELECT COALESCE(DATABASE(), '') AS db")
+            .fetch_one(&self.pool)
+            .await?;
+        let database = get_string(&row, "db");
+        if is_system_schema(&database) {
+            tracing::warn!(table = %table_name, database = %database, "Refusing write to system table (MySQL)");
+            return Err(DataSourceError::SystemTable.into());
+        }
+        Ok(())
+    }
+
     /// Build a safe identifier (prevents SQL injection for table/column names)
     fn quote_ident(name: &str) -> String {
         format!("`{}`", name.replace('`', "``"))
     }
+
+    /// `WHERE` predicate matching a row by its primary key (bound as `?`).
+    /// Shared by `delete_row` and `count_by_pk`.
+    async fn pk_predicate(&self, table_name: &str) -> anyhow::Result<String> {
+        let pk_columns = self.get_primary_key_columns(table_name).await?;
+        let pk_col = pk_columns
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
+        Ok(format!("CAST({} AS CHAR) = ?", Self::quote_ident(pk_col)))
+    }
+
+    /// Build the WHERE clause and bind values for a `column:op:value` filter.
+    /// Shared by `list_rows` and `count_rows` so both apply the same predicate.
+    fn build_where_clause(filter: Option<&str>) -> (String, Vec<String>) {
+        let Some(filter_str) = filter else {
+            return (String::new(), Vec::new());
+        };
+        let parts: Vec<&str> = filter_str.splitn(3, ':').collect();
+        if parts.len() != 3 {
+            return (String::new(), Vec::new());
+        }
+        let col = Self::quote_ident(parts[0]);
+        let op = match parts[1] {
+            "eq" => "=",
+            "neq" => "!=",
+            "gt" => ">",
+            "gte" => ">=",
+            "lt" => "<",
+            "lte" => "<=",
+            "like" => "LIKE",
+            _ => "=",
+        };
+        let value = if parts[1] == "like" {
+            format!("%{}%", parts[2])
+        } else {
+            parts[2].to_string()
+        };
+        tracing::debug!(column = parts[0], operator = op, "Filter applied");
+        (
+            format!(" WHERE CAST({} AS CHAR) {} ?", col, op),
+            vec![value],
+        )
+    }
+
+    /// Build the page query for `list_rows` with its bind values.
+    /// `explain_rows` runs the same SQL under EXPLAIN.
+    async fn list_rows_sql(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        per_page: u32,
+        offset: u32,
+    ) -> anyhow::Result<(String, Vec<String>)> {
+        // Get all columns for JSON construction, narrowed to `fields` if given
+        let schema = self.get_table_schema(table_name).await?;
+        let fields = query.field_list();
+        let json_cols: String = schema
+            .columns
+            .iter()
+            .filter(|c| fields.is_empty() || fields.contains(&c.column_name))
+            .map(|c| format!("'{}', {}", c.column_name, Self::quote_ident(&c.column_name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let (where_clause, filter_values) = Self::build_where_clause(query.filter.as_deref());
+
+        // Build ORDER BY
+        let order_terms: Vec<String> = query
+            .sort_keys()
+            .iter()
+            .map(|k| format!("{} {}", Self::quote_ident(&k.column), k.direction()))
+            .collect();
+        let order_clause = if order_terms.is_empty() {
+            String::new()
+        } else {
+            format!(" ORDER BY {}", order_terms.join(", "))
+        };
+
+        // CONCAT forces MySQL to return VARCHAR instead of JSON type
+        let data_sql = format!(
+            "SELECT CONCAT(JSON_OBJECT({})) as row_data FROM {}{}{} LIMIT {} OFFSET {}",
+            json_cols,
+            Self::quote_ident(table_name),
+            where_clause,
+            order_clause,
+            per_page,
+            offset
+        );
+        Ok((data_sql, filter_values))
+    }
 }
 
 #[async_trait]
@@ -120,6 +275,8 @@ impl DataSource for MySqlDataSource {
             .map(|r| TableInfo {
                 table_name: get_string(r, "TABLE_NAME"),
                 table_schema: get_string(r, "TABLE_SCHEMA"),
+                display_name: None,
+                description: None,
             })
             .collect();
 
@@ -136,9 +293,11 @@ impl DataSource for MySqlDataSource {
             SELECT
                 COLUMN_NAME,
                 DATA_TYPE,
+                COLUMN_TYPE,
                 IS_NULLABLE,
                 COLUMN_DEFAULT,
-                CHARACTER_MAXIMUM_LENGTH
+                CHARACTER_MAXIMUM_LENGTH,
+                EXTRA
             FROM INFORMATION_SCHEMA.COLUMNS
             WHERE TABLE_NAME = ?
               AND TABLE_SCHEMA = DATABASE()
@@ -167,10 +326,22 @@ impl DataSource for MySqlDataSource {
                         .try_get::<Option<i64>, _>("CHARACTER_MAXIMUM_LENGTH")
                         .unwrap_or(None)
                         .map(|v| v as i32),
+                    is_generated: {
+                        let extra = get_string(r, "EXTRA").to_ascii_lowercase();
+                        extra.contains("auto_increment") || extra.contains("generated")
+                    },
+                    description: None,
+                    display_hint: None,
+                    enum_values: parse_enum_values(&get_string(r, "COLUMN_TYPE")),
+                    element_type: None,
                 }
             })
             .collect();
 
+        if columns.is_empty() {
+            return Err(DataSourceError::TableNotFound.into());
+        }
+
         tracing::info!(table = %table_name, column_count = columns.len(), pk = ?pk_columns, "Schema retrieved (MySQL)");
         Ok(TableSchema {
             table_name: table_name.to_string(),
@@ -179,7 +350,28 @@ impl DataSource for MySqlDataSource {
         })
     }
 
-    async fn list_rows(&self, table_name: &str, query: &RowsQuery) -> anyhow::Result<RowsResponse> {
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool> {
+        let views = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM INFORMATION_SCHEMA.TABLES
+            WHERE TABLE_NAME = ?
+              AND TABLE_SCHEMA = DATABASE()
+              AND TABLE_TYPE = 'VIEW'
+            "#,
+        )
+        .bind(table_name)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(views > 0)
+    }
+
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        _tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse> {
         let page = query.page.unwrap_or(1).max(1);
         let per_page = query.per_page.unwrap_or(20).min(100);
         let offset = (page - 1) * per_page;
@@ -190,88 +382,21 @@ impl DataSource for MySqlDataSource {
             per_page = per_page,
             sort_by = ?query.sort_by,
             sort_order = ?query.sort_order,
-            filter = ?query.filter,
+            filter = ?query.filter.as_deref().map(filter_column),
             "Listing rows (MySQL)"
         );
 
-        let table = Self::quote_ident(table_name);
-
-        // Get all columns for JSON construction
-        let schema = self.get_table_schema(table_name).await?;
-        let json_cols: String = schema
-            .columns
-            .iter()
-            .map(|c| format!("'{}', {}", c.column_name, Self::quote_ident(&c.column_name)))
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        // Build WHERE clause from filter
-        let mut where_clause = String::new();
-        let mut filter_values: Vec<String> = Vec::new();
-        if let Some(ref filter_str) = query.filter {
-            let parts: Vec<&str> = filter_str.splitn(3, ':').collect();
-            if parts.len() == 3 {
-                let col = Self::quote_ident(parts[0]);
-                let op = match parts[1] {
-                    "eq" => "=",
-                    "neq" => "!=",
-                    "gt" => ">",
-                    "gte" => ">=",
-                    "lt" => "<",
-                    "lte" => "<=",
-                    "like" => "LIKE",
-                    _ => "=",
-                };
-                if parts[1] == "like" {
-                    filter_values.push(format!("%{}%", parts[2]));
-                } else {
-                    filter_values.push(parts[2].to_string());
-                }
-                where_clause = format!(" WHERE CAST({} AS CHAR) {} ?", col, op);
-            }
-        }
-
-        // Count query
-        let count_sql = format!("SELECT COUNT(*) as cnt FROM {}{}", table, where_clause);
-        let total_count: i64 = if filter_values.is_empty() {
-            sqlx::query(&count_sql)
-                .fetch_one(&self.pool)
-                .await?
-                .get("cnt")
-        } else {
-            sqlx::query(&count_sql)
-                .bind(&filter_values[0])
-                .fetch_one(&self.pool)
-                .await?
-                .get("cnt")
-        };
-
-        // Build ORDER BY
-        let order_clause = if let Some(ref sort_by) = query.sort_by {
-            let direction = match query.sort_order.as_deref() {
-                Some("desc") | Some("DESC") => "DESC",
-                _ => "ASC",
-            };
-            format!(" ORDER BY {} {}", Self::quote_ident(sort_by), direction)
-        } else {
-            String::new()
-        };
+        let (data_sql, filter_values) = self
+            .list_rows_sql(table_name, query, per_page, offset)
+            .await?;
+        let total_count = self.count_rows(table_name, query.filter.as_deref()).await?;
 
-        // Build data query — CONCAT forces MySQL to return VARCHAR instead of JSON type
-        let data_sql = format!(
-            "SELECT CONCAT(JSON_OBJECT({})) as row_data FROM {}{}{} LIMIT {} OFFSET {}",
-            json_cols, table, where_clause, order_clause, per_page, offset
-        );
         tracing::debug!(sql = %data_sql, "Executing data query (MySQL)");
-
-        let rows = if filter_values.is_empty() {
-            sqlx::query(&data_sql).fetch_all(&self.pool).await?
-        } else {
-            sqlx::query(&data_sql)
-                .bind(&filter_values[0])
-                .fetch_all(&self.pool)
-                .await?
-        };
+        let mut data_query = sqlx::query(&data_sql);
+        for v in &filter_values {
+            data_query = data_query.bind(v);
+        }
+        let rows = data_query.fetch_all(&self.pool).await?;
 
         let json_rows: Vec<serde_json::Value> = rows
             .iter()
@@ -297,8 +422,116 @@ impl DataSource for MySqlDataSource {
         })
     }
 
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query.per_page.unwrap_or(20).min(100);
+        let offset = (page - 1) * per_page;
+
+        let (data_sql, filter_values) = self
+            .list_rows_sql(table_name, query, per_page, offset)
+            .await?;
+        let explain_sql = format!("EXPLAIN FORMAT=JSON {}", data_sql);
+        tracing::debug!(sql = %explain_sql, "Explaining data query (MySQL)");
+
+        let mut explain = sqlx::query(&explain_sql);
+        for v in &filter_values {
+            explain = explain.bind(v);
+        }
+        let row = explain.fetch_one(&self.pool).await?;
+        Ok(serde_json::from_str(&get_string(&row, "EXPLAIN"))?)
+    }
+
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64> {
+        let (where_clause, filter_values) = Self::build_where_clause(filter);
+        let count_sql = format!(
+            "SELECT COUNT(*) as cnt FROM {}{}",
+            Self::quote_ident(table_name),
+            where_clause
+        );
+        tracing::debug!(sql = %count_sql, "Executing count query (MySQL)");
+
+        let mut query = sqlx::query(&count_sql);
+        for v in &filter_values {
+            query = query.bind(v);
+        }
+        Ok(query.fetch_one(&self.pool).await?.get("cnt"))
+    }
+
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        tracing::info!(table = %table_name, column = %column, limit = limit, "Listing distinct values (MySQL)");
+        // JSON_ARRAY wraps each value so NULLs survive the round trip; CONCAT forces VARCHAR
+        let sql = format!(
+            "SELECT CONCAT(JSON_ARRAY(d.v)) AS value FROM (SELECT DISTINCT {} AS v FROM {} ORDER BY 1 LIMIT {}) AS d ORDER BY d.v",
+            Self::quote_ident(column),
+            Self::quote_ident(table_name),
+            limit
+        );
+        tracing::debug!(sql = %sql, "Executing distinct query (MySQL)");
+
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let raw = get_string(r, "value");
+                serde_json::from_str::<serde_json::Value>(&raw)
+                    .ok()
+                    .and_then(|v| v.get(0).cloned())
+                    .unwrap_or(serde_json::Value::Null)
+            })
+            .collect())
+    }
+
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>> {
+        tracing::info!(
+            table = %table_name,
+            group_by = %spec.group_by,
+            metric = ?spec.metric_col,
+            agg = spec.agg_fn.as_sql(),
+            "Running aggregate (MySQL)"
+        );
+        let metric = match &spec.metric_col {
+            Some(col) => format!("{}({})", spec.agg_fn.as_sql(), Self::quote_ident(col)),
+            None => "COUNT(*)".to_string(),
+        };
+        // JSON_ARRAY keeps group/value types intact; CONCAT forces VARCHAR
+        let sql = format!(
+            "SELECT CONCAT(JSON_ARRAY(a.g, a.v)) AS row_data FROM (SELECT {} AS g, {} AS v FROM {} GROUP BY 1) AS a ORDER BY a.g",
+            Self::quote_ident(&spec.group_by),
+            metric,
+            Self::quote_ident(table_name)
+        );
+        tracing::debug!(sql = %sql, "Executing aggregate query (MySQL)");
+
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let raw = get_string(r, "row_data");
+                let pair: Vec<serde_json::Value> = serde_json::from_str(&raw).unwrap_or_default();
+                let mut pair = pair.into_iter();
+                AggregateRow {
+                    group: pair.next().unwrap_or(serde_json::Value::Null),
+                    value: pair.next().unwrap_or(serde_json::Value::Null),
+                }
+            })
+            .collect())
+    }
+
     async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value> {
-        tracing::info!(table = %table_name, pk = %pk_value, "Getting single row (MySQL)");
+        tracing::info!(table = %table_name, "Getting single row (MySQL)");
         let pk_columns = self.get_primary_key_columns(table_name).await?;
         let pk_col = pk_columns
             .first()
@@ -325,22 +558,76 @@ impl DataSource for MySqlDataSource {
             .fetch_one(&self.pool)
             .await
             .map_err(|e| {
-                tracing::error!(table = %table_name, pk = %pk_value, error = %e, "Failed to get row (MySQL)");
+                tracing::error!(table = %table_name, error = %e, "Failed to get row (MySQL)");
                 e
             })?;
 
         let raw = get_string(&row, "row_data");
         let json: serde_json::Value = serde_json::from_str(&raw)?;
-        tracing::debug!(table = %table_name, pk = %pk_value, "Row retrieved (MySQL)");
+        tracing::debug!(table = %table_name, "Row retrieved (MySQL)");
         Ok(json)
     }
 
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        tracing::info!(table = %table_name, count = pk_values.len(), "Getting rows by primary key (MySQL)");
+        if pk_values.is_empty() {
+            return Ok(vec![]);
+        }
+        let pk_columns = self.get_primary_key_columns(table_name).await?;
+        let pk_col = pk_columns
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
+
+        let schema = self.get_table_schema(table_name).await?;
+        let json_cols: String = schema
+            .columns
+            .iter()
+            .map(|c| format!("'{}', {}", c.column_name, Self::quote_ident(&c.column_name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let pk = format!("CAST({} AS CHAR)", Self::quote_ident(pk_col));
+        let placeholders = vec!["?"; pk_values.len()].join(", ");
+        // FIELD() returns the position of the key in the list, preserving request order
+        let sql = format!(
+            "SELECT CONCAT(JSON_OBJECT({})) as row_data FROM {} WHERE {} IN ({}) ORDER BY FIELD({}, {})",
+            json_cols,
+            Self::quote_ident(table_name),
+            pk,
+            placeholders,
+            pk,
+            placeholders
+        );
+        tracing::debug!(sql = %sql, "Executing batch get (MySQL)");
+
+        let mut query = sqlx::query(&sql);
+        for v in pk_values.iter().chain(pk_values) {
+            query = query.bind(v);
+        }
+        let rows = query.fetch_all(&self.pool).await.map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to get rows (MySQL)");
+            e
+        })?;
+
+        rows.iter()
+            .map(|r| Ok(serde_json::from_str(&get_string(r, "row_data"))?))
+            .collect()
+    }
+
+    // `begin` is not supported here, so no transaction can ever be passed in.
     async fn insert_row(
         &self,
         table_name: &str,
         data: &serde_json::Value,
+        returning: &[String],
+        _tx: Option<&DataSourceTransaction>,
     ) -> anyhow::Result<serde_json::Value> {
         tracing::info!(table = %table_name, "Inserting new row (MySQL)");
+        self.ensure_not_system_table(table_name).await?;
 
         let obj = data
             .as_object()
@@ -381,23 +668,30 @@ impl DataSource for MySqlDataSource {
             e
         })?;
 
-        // Fetch the inserted row using LAST_INSERT_ID if available
+        // Fetch the inserted row using LAST_INSERT_ID if available; MySQL has
+        // no RETURNING, so `returning` narrows the fetched row instead
         let pk_columns = self.get_primary_key_columns(table_name).await?;
         if let Some(pk_col) = pk_columns.first() {
             // Check if the PK was provided in the input
-            if let Some(pk_val) = obj.get(pk_col) {
-                let pk_str = match pk_val {
-                    serde_json::Value::String(s) => s.clone(),
-                    other => other.to_string(),
-                };
-                return self.get_row(table_name, &pk_str).await;
+            let pk_str = match obj.get(pk_col) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                // Otherwise use LAST_INSERT_ID
+                None => {
+                    let last_id: u64 = sqlx::query("SELECT LAST_INSERT_ID() as id")
+                        .fetch_one(&self.pool)
+                        .await?
+                        .get("id");
+                    last_id.to_string()
+                }
+            };
+            let mut row = self.get_row(table_name, &pk_str).await?;
+            if !returning.is_empty()
+                && let Some(fields) = row.as_object_mut()
+            {
+                fields.retain(|name, _| returning.contains(name));
             }
-            // Otherwise use LAST_INSERT_ID
-            let last_id: u64 = sqlx::query("SELECT LAST_INSERT_ID() as id")
-                .fetch_one(&self.pool)
-                .await?
-                .get("id");
-            return self.get_row(table_name, &last_id.to_string()).await;
+            return Ok(row);
         }
 
         tracing::info!(table = %table_name, "Row inserted successfully (MySQL)");
@@ -409,8 +703,10 @@ impl DataSource for MySqlDataSource {
         table_name: &str,
         pk_value: &str,
         data: &serde_json::Value,
+        _tx: Option<&DataSourceTransaction>,
     ) -> anyhow::Result<serde_json::Value> {
-        tracing::info!(table = %table_name, pk = %pk_value, "Updating row (MySQL)");
+        tracing::info!(table = %table_name, "Updating row (MySQL)");
+        self.ensure_not_system_table(table_name).await?;
 
         let pk_columns = self.get_primary_key_columns(table_name).await?;
         let pk_col = pk_columns
@@ -460,41 +756,172 @@ impl DataSource for MySqlDataSource {
         }
 
         query.execute(&self.pool).await.map_err(|e| {
-            tracing::error!(table = %table_name, pk = %pk_value, error = %e, "Failed to update row (MySQL)");
+            tracing::error!(table = %table_name, error = %e, "Failed to update row (MySQL)");
             e
         })?;
 
-        tracing::info!(table = %table_name, pk = %pk_value, "Row updated successfully (MySQL)");
+        tracing::info!(table = %table_name, "Row updated successfully (MySQL)");
         self.get_row(table_name, pk_value).await
     }
 
-    async fn delete_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<()> {
-        tracing::info!(table = %table_name, pk = %pk_value, "Deleting row (MySQL)");
-        let pk_columns = self.get_primary_key_columns(table_name).await?;
-        let pk_col = pk_columns
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
-
-        let table = Self::quote_ident(table_name);
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        _tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(table = %table_name, "Deleting row (MySQL)");
+        self.ensure_not_system_table(table_name).await?;
+        let predicate = self.pk_predicate(table_name).await?;
         let sql = format!(
-            "DELETE FROM {} WHERE CAST({} AS CHAR) = ?",
-            table,
-            Self::quote_ident(pk_col)
+            "DELETE FROM {} WHERE {}",
+            Self::quote_ident(table_name),
+            predicate
         );
-        tracing::debug!(sql = %sql, pk = %pk_value, "Executing delete (MySQL)");
+        tracing::debug!(sql = %sql, "Executing delete (MySQL)");
 
         sqlx::query(&sql)
             .bind(pk_value)
             .execute(&self.pool)
             .await
             .map_err(|e| {
-                tracing::error!(table = %table_name, pk = %pk_value, error = %e, "Failed to delete row (MySQL)");
+                tracing::error!(table = %table_name, error = %e, "Failed to delete row (MySQL)");
                 e
             })?;
 
-        tracing::info!(table = %table_name, pk = %pk_value, "Row deleted successfully (MySQL)");
+        tracing::info!(table = %table_name, "Row deleted successfully (MySQL)");
         Ok(())
     }
+
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64> {
+        let predicate = self.pk_predicate(table_name).await?;
+        let sql = format!(
+            "SELECT COUNT(*) as cnt FROM {} WHERE {}",
+            Self::quote_ident(table_name),
+            predicate
+        );
+        tracing::debug!(sql = %sql, "Executing count by primary key (MySQL)");
+        let row = sqlx::query(&sql)
+            .bind(pk_value)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.get("cnt"))
+    }
+
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64> {
+        tracing::info!(table = %table_name, filter = filter_column(filter), "Bulk updating rows (MySQL)");
+        self.ensure_not_system_table(table_name).await?;
+        let (where_clause, filter_values) = Self::build_where_clause(Some(filter));
+        if where_clause.is_empty() {
+            anyhow::bail!("A valid filter is required for bulk update");
+        }
+        let obj = data
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("Data must be a JSON object"))?;
+
+        let mut set_clauses = Vec::new();
+        let mut values: Vec<String> = Vec::new();
+        for (key, val) in obj.iter() {
+            match val {
+                serde_json::Value::Null => {
+                    set_clauses.push(format!("{} = NULL", Self::quote_ident(key)));
+                }
+                serde_json::Value::String(s) => {
+                    set_clauses.push(format!("{} = ?", Self::quote_ident(key)));
+                    values.push(s.clone());
+                }
+                other => {
+                    set_clauses.push(format!("{} = ?", Self::quote_ident(key)));
+                    values.push(other.to_string());
+                }
+            }
+        }
+        if set_clauses.is_empty() {
+            anyhow::bail!("Data must contain at least one column");
+        }
+        // Positional placeholders: SET values come before the filter value
+        values.extend(filter_values);
+
+        let sql = format!(
+            "UPDATE {} SET {}{}",
+            Self::quote_ident(table_name),
+            set_clauses.join(", "),
+            where_clause
+        );
+        tracing::debug!(sql = %sql, "Executing bulk update (MySQL)");
+
+        let mut query = sqlx::query(&sql);
+        for v in &values {
+            query = query.bind(v);
+        }
+        let result = query.execute(&self.pool).await.map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to bulk update rows (MySQL)");
+            e
+        })?;
+
+        tracing::info!(table = %table_name, affected = result.rows_affected(), "Bulk update complete (MySQL)");
+        Ok(result.rows_affected())
+    }
+
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64> {
+        tracing::info!(table = %table_name, filter = filter_column(filter), "Bulk deleting rows (MySQL)");
+        self.ensure_not_system_table(table_name).await?;
+        let (where_clause, values) = Self::build_where_clause(Some(filter));
+        if where_clause.is_empty() {
+            anyhow::bail!("A valid filter is required for bulk delete");
+        }
+
+        let sql = format!(
+            "DELETE FROM {}{}",
+            Self::quote_ident(table_name),
+            where_clause
+        );
+        tracing::debug!(sql = %sql, "Executing bulk delete (MySQL)");
+
+        let mut query = sqlx::query(&sql);
+        for v in &values {
+            query = query.bind(v);
+        }
+        let result = query.execute(&self.pool).await.map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to bulk delete rows (MySQL)");
+            e
+        })?;
+
+        tracing::info!(table = %table_name, affected = result.rows_affected(), "Bulk delete complete (MySQL)");
+        Ok(result.rows_affected())
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        Some(PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle(),
+        })
+    }
+
+    async fn warm_up(&self, connections: u32) {
+        let connections = connections.min(self.pool.options().get_max_connections());
+        // Hold each connection until all are open so the pool can't hand back the same one
+        let mut held = Vec::with_capacity(connections as usize);
+        for _ in 0..connections {
+            match self.pool.acquire().await {
+                Ok(conn) => held.push(conn),
+                Err(e) => {
+                    tracing::warn!(error = %e, opened = held.len(), "Pool warm-up stopped early (MySQL)");
+                    break;
+                }
+            }
+        }
+        tracing::debug!(opened = held.len(), "Pool warmed up (MySQL)");
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
 }
 
 #[cfg(test)]
@@ -511,6 +938,19 @@ mod tests {
         assert_eq!(MySqlDataSource::quote_ident("my`table"), "`my``table`");
     }
 
+    #[test]
+    fn parse_enum_values_keeps_declaration_order() {
+        assert_eq!(
+            parse_enum_values("enum('sad','ok','it''s fine')"),
+            Some(vec![
+                "sad".to_string(),
+                "ok".to_string(),
+                "it's fine".to_string()
+            ])
+        );
+        assert_eq!(parse_enum_values("varchar(255)"), None);
+    }
+
     #[test]
     fn quote_ident_empty() {
         assert_eq!(MySqlDataSource::quote_ident(""), "``");
@@ -1,34 +1,143 @@
 use async_trait::async_trait;
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{PgPool, Row};
+use sqlx::postgres::{PgConnectOptions, PgQueryResult, PgRow};
+use sqlx::{Connection, Executor, PgConnection, PgPool, Postgres, Row};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
-use crate::domain::data::{ColumnInfo, RowsResponse, TableInfo, TableSchema};
-use crate::infrastructure::datasource::DataSource;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+
+use crate::domain::connection::search_path_schemas;
+use crate::domain::data::{
+    AggregateRow, AggregateSpec, BYTEA_TYPE_MARKER, ColumnInfo, RowsResponse, TableInfo,
+    TableSchema,
+};
+use crate::infrastructure::datasource::transaction::transaction_finished;
+use crate::infrastructure::datasource::{
+    DataSource, DataSourceError, DataSourceTransaction, PoolLimits, PoolStatus, connect_error,
+    filter_column, is_system_schema, payload_columns,
+};
 use crate::presentation::request::RowsQuery;
 
 pub struct PostgresDataSource {
+    /// Primary: takes every write
     pool: PgPool,
+    /// Read replica pools, used in turn for reads; empty means reads go to `pool`
+    read_pools: Vec<PgPool>,
+    next_read: AtomicUsize,
+    /// Table schemas by name, kept until `invalidate_schema_cache`
+    schema_cache: RwLock<HashMap<String, TableSchema>>,
 }
 
 impl PostgresDataSource {
-    pub async fn new(connection_string: &str) -> anyhow::Result<Self> {
+    pub async fn new(connection_string: &str, statement_timeout: Duration) -> anyhow::Result<Self> {
+        Self::with_session(
+            connection_string,
+            statement_timeout,
+            None,
+            None,
+            PoolLimits::from_env(),
+            &[],
+        )
+        .await
+    }
+
+    /// Like `new`, but every pooled session first sets `search_path` (a
+    /// comma-separated schema list) and assumes `role`, when given. Tables
+    /// are then listed from the first schema on the path.
+    ///
+    /// Each of `replicas` (connection strings for read replicas of the same
+    /// database) gets its own pool with the same session settings; reads are
+    /// spread across them and writes always go to `connection_string`.
+    pub async fn with_session(
+        connection_string: &str,
+        statement_timeout: Duration,
+        search_path: Option<&str>,
+        role: Option<&str>,
+        limits: PoolLimits,
+        replicas: &[String],
+    ) -> anyhow::Result<Self> {
+        // Only these two SET statements are ever built, from quoted identifiers
+        let mut session_sql = Vec::new();
+        if let Some(search_path) = search_path {
+            let schemas: Vec<String> = search_path_schemas(search_path)
+                .into_iter()
+                .map(Self::quote_ident)
+                .collect();
+            session_sql.push(format!("SET search_path TO {}", schemas.join(", ")));
+        }
+        if let Some(role) = role {
+            session_sql.push(format!("SET ROLE {}", Self::quote_ident(role)));
+        }
+
+        let pool =
+            Self::connect_pool(connection_string, statement_timeout, &session_sql, limits).await?;
+        let mut read_pools = Vec::with_capacity(replicas.len());
+        for replica in replicas {
+            read_pools
+                .push(Self::connect_pool(replica, statement_timeout, &session_sql, limits).await?);
+        }
+        Ok(Self {
+            pool,
+            read_pools,
+            next_read: AtomicUsize::new(0),
+            schema_cache: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Open and verify one pool whose sessions run `session_sql` after connecting.
+    async fn connect_pool(
+        connection_string: &str,
+        statement_timeout: Duration,
+        session_sql: &[String],
+        limits: PoolLimits,
+    ) -> anyhow::Result<PgPool> {
         // Mask password in logs
         let safe_conn = connection_string.split('@').next_back().unwrap_or("***");
         tracing::info!(target = %safe_conn, "Creating PostgreSQL connection pool...");
 
-        let pool = PgPoolOptions::new()
-            .max_connections(5)
-            .acquire_timeout(Duration::from_secs(10))
-            .connect(connection_string)
+        // statement_timeout is set per session so every query on the pool is bounded
+        let statement_timeout_ms = statement_timeout.as_millis().to_string();
+        let connect_options = PgConnectOptions::from_str(connection_string)?
+            .options([("statement_timeout", statement_timeout_ms.as_str())]);
+
+        // Probe with a single connection first: the pool retries refused
+        // connections until its acquire timeout, which hides the real cause.
+        let probe = PgConnection::connect_with(&connect_options)
             .await
             .map_err(|e| {
                 tracing::error!(
                     error = %e,
                     target = %safe_conn,
-                    "Failed to create connection pool. Check that the database is running and credentials are correct."
+                    "Failed to connect. Check that the database is running and credentials are correct."
                 );
-                e
+                connect_error(e)
+            })?;
+        probe.close().await.ok();
+
+        let session_sql = session_sql.to_vec();
+        let pool = limits
+            .pool_options::<Postgres>()
+            .after_connect(move |conn, _meta| {
+                let session_sql = session_sql.clone();
+                Box::pin(async move {
+                    for sql in &session_sql {
+                        conn.execute(sql.as_str()).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect_with(connect_options)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    error = %e,
+                    target = %safe_conn,
+                    "Failed to create connection pool."
+                );
+                connect_error(e)
             })?;
 
         // Verify the connection actually works
@@ -43,10 +152,176 @@ impl PostgresDataSource {
 
         tracing::info!(
             target = %safe_conn,
-            max_connections = 5,
+            max_connections = limits.max_connections,
+            statement_timeout_ms = %statement_timeout_ms,
             "PostgreSQL connection pool created and verified"
         );
-        Ok(Self { pool })
+        Ok(pool)
+    }
+
+    /// Run a write returning one row on the primary, or inside `tx` when given.
+    async fn fetch_one_write(
+        &self,
+        sql: &str,
+        values: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<PgRow> {
+        let mut query = sqlx::query(sql);
+        for v in values {
+            query = query.bind(v);
+        }
+        Ok(match tx {
+            Some(tx) => {
+                let mut guard = tx.lock_postgres().await;
+                let conn = guard.as_mut().ok_or_else(transaction_finished)?;
+                query.fetch_one(&mut **conn).await?
+            }
+            None => query.fetch_one(&self.pool).await?,
+        })
+    }
+
+    /// Run a write on the primary, or inside `tx` when given.
+    async fn execute_write(
+        &self,
+        sql: &str,
+        values: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<PgQueryResult> {
+        let mut query = sqlx::query(sql);
+        for v in values {
+            query = query.bind(v);
+        }
+        Ok(match tx {
+            Some(tx) => {
+                let mut guard = tx.lock_postgres().await;
+                let conn = guard.as_mut().ok_or_else(transaction_finished)?;
+                query.execute(&mut **conn).await?
+            }
+            None => query.execute(&self.pool).await?,
+        })
+    }
+
+    /// Run a read on the next reader pool, or inside `tx` when given.
+    async fn fetch_all_read(
+        &self,
+        sql: &str,
+        values: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<Vec<PgRow>> {
+        let mut query = sqlx::query(sql);
+        for v in values {
+            query = query.bind(v);
+        }
+        Ok(match tx {
+            Some(tx) => {
+                let mut guard = tx.lock_postgres().await;
+                let conn = guard.as_mut().ok_or_else(transaction_finished)?;
+                query.fetch_all(&mut **conn).await?
+            }
+            None => query.fetch_all(self.reader()).await?,
+        })
+    }
+
+    /// Pool for a read: the next replica in turn, or the primary without replicas.
+    fn reader(&self) -> &PgPool {
+        if self.read_pools.is_empty() {
+            return &self.pool;
+        }
+        let i = self.next_read.fetch_add(1, Ordering::Relaxed);
+        &self.read_pools[i % self.read_pools.len()]
+    }
+
+    /// Read a table's columns from `information_schema`, bypassing the cache.
+    async fn fetch_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema> {
+        tracing::info!(table = %table_name, "Getting table schema");
+        let pk_columns = self.get_primary_key_columns(table_name).await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                c.column_name,
+                c.data_type,
+                c.is_nullable,
+                c.column_default,
+                c.character_maximum_length,
+                (c.is_identity = 'YES' OR c.is_generated = 'ALWAYS') AS is_generated,
+                (
+                    SELECT array_agg(e.enumlabel::text ORDER BY e.enumsortorder)
+                    FROM pg_type t
+                    JOIN pg_namespace n ON n.oid = t.typnamespace
+                    JOIN pg_enum e ON e.enumtypid = t.oid
+                    WHERE t.typname = c.udt_name
+                      AND n.nspname = c.udt_schema
+                ) AS enum_values,
+                (
+                    SELECT format_type(t.typelem, NULL)
+                    FROM pg_type t
+                    JOIN pg_namespace n ON n.oid = t.typnamespace
+                    WHERE t.typname = c.udt_name
+                      AND n.nspname = c.udt_schema
+                      AND c.data_type = 'ARRAY'
+                ) AS element_type
+            FROM information_schema.columns c
+            WHERE c.table_name = $1
+              AND c.table_schema = current_schema()
+            ORDER BY c.ordinal_position
+            "#,
+        )
+        .bind(table_name)
+        .fetch_all(self.reader())
+        .await
+        .map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to get table schema");
+            e
+        })?;
+
+        let columns: Vec<ColumnInfo> = rows
+            .iter()
+            .map(|r| {
+                let col_name: String = r.get("column_name");
+                ColumnInfo {
+                    is_primary_key: pk_columns.contains(&col_name),
+                    column_name: col_name,
+                    data_type: r.get("data_type"),
+                    is_nullable: r.get::<String, _>("is_nullable") == "YES",
+                    column_default: r.get("column_default"),
+                    max_length: r.get::<Option<i32>, _>("character_maximum_length"),
+                    is_generated: r.get("is_generated"),
+                    description: None,
+                    display_hint: None,
+                    enum_values: r.get("enum_values"),
+                    element_type: r.get("element_type"),
+                }
+            })
+            .collect();
+
+        // No columns usually means no such table; a zero-column table is legal but rare
+        if columns.is_empty() && !self.table_exists(table_name).await? {
+            return Err(DataSourceError::TableNotFound.into());
+        }
+
+        tracing::info!(table = %table_name, column_count = columns.len(), pk = ?pk_columns, "Schema retrieved");
+        Ok(TableSchema {
+            table_name: table_name.to_string(),
+            columns,
+            primary_key_columns: pk_columns,
+        })
+    }
+
+    /// Whether a table or view with this name exists in the current schema
+    async fn table_exists(&self, table_name: &str) -> anyhow::Result<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM information_schema.tables
+                WHERE table_name = $1 AND table_schema = current_schema()
+            )
+            "#,
+        )
+        .bind(table_name)
+        .fetch_one(self.reader())
+        .await?;
+        Ok(exists)
     }
 
     /// Resolve the primary key column(s) for a given table
@@ -61,12 +336,12 @@ impl PostgresDataSource {
               AND tc.table_schema = kcu.table_schema
             WHERE tc.constraint_type = 'PRIMARY KEY'
               AND tc.table_name = $1
-              AND tc.table_schema = 'public'
+              AND tc.table_schema = current_schema()
             ORDER BY kcu.ordinal_position
             "#,
         )
         .bind(table_name)
-        .fetch_all(&self.pool)
+        .fetch_all(self.reader())
         .await?;
 
         let columns: Vec<String> = rows
@@ -77,11 +352,415 @@ impl PostgresDataSource {
         Ok(columns)
     }
 
+    /// Refuse a write when `table_name` resolves to a system table. The
+    /// lookup follows the session's `search_path`, which implicitly starts
+    /// with `pg_catalog`, exactly as the write statement would.
+    async fn ensure_not_system_table(&self, table_name: &str) -> anyhow::Result<()> {
+        let schema = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT n.nspname::text
+            FROM pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            WHERE c.oid = to_regclass($1)
+            "#,
+        )
+        .bind(Self::quote_ident(table_name))
+        .fetch_optional(&self.pool)
+        .await?;
+        if schema.as_deref().is_some_and(is_system_schema) {
+            tracing::warn!(table = %table_name, schema = ?schema, "Refusing write to system table");
+            return Err(DataSourceError::SystemTable.into());
+        }
+        Ok(())
+    }
+
     /// Build a safe identifier (prevents SQL injection for table/column names)
     fn quote_ident(name: &str) -> String {
         // Double-quote and escape any existing double quotes
         format!("\"{}\"", name.replace('"', "\"\""))
     }
+
+    /// JSON expression for one row of `alias`, limited to `fields` when given.
+    /// Timestamp columns are rendered through `timestamp_text` so every row
+    /// carries them in one fixed format, whatever the session's settings.
+    fn row_json_expr(schema: &TableSchema, alias: &str, fields: &[String]) -> String {
+        let timestamp_type = |name: &str| {
+            schema
+                .columns
+                .iter()
+                .find(|c| c.column_name == name)
+                .map(|c| c.data_type.as_str())
+                .filter(|t| t.starts_with("timestamp"))
+        };
+        let names: Vec<&str> = if fields.is_empty() {
+            schema
+                .columns
+                .iter()
+                .map(|c| c.column_name.as_str())
+                .collect()
+        } else {
+            fields.iter().map(String::as_str).collect()
+        };
+        if names.is_empty() || !names.iter().any(|n| timestamp_type(n).is_some()) {
+            // Sparse fieldsets build the object explicitly; keys are escaped literals
+            return if fields.is_empty() {
+                format!("row_to_json({}.*)", alias)
+            } else {
+                let pairs: Vec<String> = fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "'{}', {}.{}",
+                            f.replace('\'', "''"),
+                            alias,
+                            Self::quote_ident(f)
+                        )
+                    })
+                    .collect();
+                format!("json_build_object({})", pairs.join(", "))
+            };
+        }
+        let projection: Vec<String> = names
+            .iter()
+            .map(|name| {
+                let column = format!("{}.{}", alias, Self::quote_ident(name));
+                match timestamp_type(name) {
+                    Some(data_type) => format!(
+                        "{} AS {}",
+                        timestamp_text(&column, data_type),
+                        Self::quote_ident(name)
+                    ),
+                    None => column,
+                }
+            })
+            .collect();
+        format!(
+            "(SELECT row_to_json(r) FROM (SELECT {}) AS r)",
+            projection.join(", ")
+        )
+    }
+
+    /// `WHERE` predicate matching a row by its primary key (bound as `$1`).
+    /// Shared by `delete_row` and `count_by_pk` so a dry run counts exactly
+    /// what the delete would remove.
+    async fn pk_predicate(&self, table_name: &str) -> anyhow::Result<String> {
+        let pk_columns = self.get_primary_key_columns(table_name).await?;
+        let pk_col = pk_columns
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
+        Ok(format!("{}::text = $1", Self::quote_ident(pk_col)))
+    }
+
+    /// Names of the text-like columns of a table, used as `q` search targets
+    async fn text_columns(&self, table_name: &str) -> anyhow::Result<Vec<String>> {
+        let schema = self.get_table_schema(table_name).await?;
+        Ok(schema
+            .columns
+            .into_iter()
+            .filter(|c| {
+                matches!(
+                    c.data_type.as_str(),
+                    "text" | "character varying" | "character"
+                )
+            })
+            .map(|c| c.column_name)
+            .collect())
+    }
+
+    /// Rewrite bytea values in rows of a table into the `{"$type": "bytea",
+    /// "base64": ...}` form, since `row_to_json` renders them as `\x` hex.
+    async fn encode_bytea_columns(
+        &self,
+        table_name: &str,
+        rows: &mut [serde_json::Value],
+    ) -> anyhow::Result<()> {
+        let schema = self.get_table_schema(table_name).await?;
+        let columns: Vec<&str> = schema
+            .columns
+            .iter()
+            .filter(|c| c.data_type == "bytea")
+            .map(|c| c.column_name.as_str())
+            .collect();
+        if columns.is_empty() {
+            return Ok(());
+        }
+        for row in rows.iter_mut() {
+            let Some(obj) = row.as_object_mut() else {
+                continue;
+            };
+            for column in &columns {
+                if let Some(value) = obj.get_mut(*column)
+                    && let Some(encoded) = value.as_str().and_then(bytea_from_hex)
+                {
+                    *value = encoded;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Escape `\`, `%` and `_` so `term` matches itself in a LIKE pattern
+    /// using `ESCAPE '\'`.
+    fn escape_like(term: &str) -> String {
+        let mut escaped = String::with_capacity(term.len());
+        for c in term.chars() {
+            if matches!(c, '\\' | '%' | '_') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    /// Build the WHERE clause and bind values for a `column:op:value` filter
+    /// and an optional `(term, columns)` search, ANDed together.
+    /// Shared by `list_rows` and `count_rows` so both apply the same predicate.
+    fn build_where_clause(
+        filter: Option<&str>,
+        search: Option<(&str, &[String])>,
+    ) -> (String, Vec<String>) {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<String> = Vec::new();
+
+        if let Some(filter_str) = filter {
+            let parts: Vec<&str> = filter_str.splitn(3, ':').collect();
+            if parts.len() == 3 {
+                let col = Self::quote_ident(parts[0]);
+                let op = match parts[1] {
+                    "eq" => "=",
+                    "neq" => "!=",
+                    "gt" => ">",
+                    "gte" => ">=",
+                    "lt" => "<",
+                    "lte" => "<=",
+                    "like" => "ILIKE",
+                    _ => "=",
+                };
+                if parts[1] == "like" {
+                    values.push(format!("%{}%", parts[2]));
+                } else {
+                    values.push(parts[2].to_string());
+                }
+                conditions.push(format!("{}::text {} ${}", col, op, values.len()));
+                tracing::debug!(column = parts[0], operator = op, "Filter applied");
+            }
+        }
+
+        if let Some((term, columns)) = search {
+            // One bound parameter, reused for every column. The term is matched
+            // literally, so its own `%` and `_` are not wildcards.
+            values.push(format!("%{}%", Self::escape_like(term)));
+            let placeholder = format!("${}", values.len());
+            let matches: Vec<String> = columns
+                .iter()
+                .map(|c| format!("{} ILIKE {} ESCAPE '\\'", Self::quote_ident(c), placeholder))
+                .collect();
+            if matches.is_empty() {
+                // Nothing searchable: a search term can never match
+                conditions.push("FALSE".to_string());
+            } else {
+                conditions.push(format!("({})", matches.join(" OR ")));
+            }
+            tracing::debug!(columns = ?columns, "Search applied");
+        }
+
+        if conditions.is_empty() {
+            (String::new(), values)
+        } else {
+            (format!(" WHERE {}", conditions.join(" AND ")), values)
+        }
+    }
+
+    /// Count rows matching a pre-built WHERE clause
+    async fn count_where(
+        &self,
+        table_name: &str,
+        where_clause: &str,
+        values: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<i64> {
+        let count_sql = format!(
+            "SELECT COUNT(*) as cnt FROM {}{}",
+            Self::quote_ident(table_name),
+            where_clause
+        );
+        tracing::debug!(sql = %count_sql, "Executing count query");
+
+        let rows = self.fetch_all_read(&count_sql, values, tx).await?;
+        let row = rows
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("COUNT returned no row"))?;
+        Ok(row.get("cnt"))
+    }
+
+    /// Build the page query for `list_rows`. `explain_rows` runs the same
+    /// SQL under EXPLAIN, so the two can never drift apart.
+    async fn list_rows_sql(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        per_page: u32,
+        offset: u32,
+    ) -> anyhow::Result<ListRowsSql> {
+        let search_term = query.q.as_deref().filter(|q| !q.is_empty());
+        let search_columns = match search_term {
+            Some(_) => self.text_columns(table_name).await?,
+            None => Vec::new(),
+        };
+        let search = search_term.map(|term| (term, search_columns.as_slice()));
+
+        let (where_clause, values) = Self::build_where_clause(query.filter.as_deref(), search);
+
+        // Build ORDER BY
+        // Without an explicit choice Postgres puts NULLs last for ASC, first for DESC
+        let nulls = match query.nulls.as_deref() {
+            Some(n) if n.eq_ignore_ascii_case("first") => " NULLS FIRST",
+            Some(n) if n.eq_ignore_ascii_case("last") => " NULLS LAST",
+            _ => "",
+        };
+        let order_terms: Vec<String> = query
+            .sort_keys()
+            .iter()
+            .map(|k| {
+                format!(
+                    "{} {}{}",
+                    Self::quote_ident(&k.column),
+                    k.direction(),
+                    nulls
+                )
+            })
+            .collect();
+        let order_clause = if order_terms.is_empty() {
+            String::new()
+        } else {
+            format!(" ORDER BY {}", order_terms.join(", "))
+        };
+
+        let schema = self.get_table_schema(table_name).await?;
+        let row_expr = Self::row_json_expr(&schema, "t", &query.field_list());
+
+        let data_sql = format!(
+            "SELECT {} as row_data FROM {} AS t{}{} LIMIT {} OFFSET {}",
+            row_expr,
+            Self::quote_ident(table_name),
+            where_clause,
+            order_clause,
+            per_page,
+            offset
+        );
+        Ok(ListRowsSql {
+            data_sql,
+            where_clause,
+            values,
+        })
+    }
+}
+
+/// Placeholder and bound text for a written column value at `$idx`.
+/// Strings bind as-is and other JSON as its text; the bytea marker object
+/// binds its base64 and decodes it in SQL, as text does not cast to bytea.
+/// A JSON array written to an array column (`element_type` set) binds as an
+/// array literal cast to `element_type[]`.
+fn write_param(
+    value: &serde_json::Value,
+    idx: usize,
+    element_type: Option<&str>,
+) -> anyhow::Result<(String, String)> {
+    if let (serde_json::Value::Array(items), Some(element_type)) = (value, element_type) {
+        return Ok((
+            format!("${}::{}[]", idx, element_type),
+            array_literal(items),
+        ));
+    }
+    if let Some(obj) = value.as_object()
+        && obj.get("$type").and_then(|t| t.as_str()) == Some(BYTEA_TYPE_MARKER)
+    {
+        let encoded = obj
+            .get("base64")
+            .and_then(|b| b.as_str())
+            .ok_or_else(|| anyhow::anyhow!("bytea value is missing its base64 field"))?;
+        BASE64
+            .decode(encoded)
+            .map_err(|e| anyhow::anyhow!("Invalid base64 in bytea value: {}", e))?;
+        return Ok((format!("decode(${}, 'base64')", idx), encoded.to_string()));
+    }
+    let text = match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    Ok((format!("${}", idx), text))
+}
+
+/// PostgreSQL array literal for JSON array elements, e.g. `{"a","b c",NULL}`.
+/// Every element is quoted and escaped so commas, braces and quotes inside
+/// values survive; JSON `null` becomes `NULL` and nested arrays nest.
+fn array_literal(items: &[serde_json::Value]) -> String {
+    let elements: Vec<String> = items
+        .iter()
+        .map(|item| match item {
+            serde_json::Value::Null => "NULL".to_string(),
+            serde_json::Value::Array(inner) => array_literal(inner),
+            serde_json::Value::String(s) => quote_array_element(s),
+            other => quote_array_element(&other.to_string()),
+        })
+        .collect();
+    format!("{{{}}}", elements.join(","))
+}
+
+fn quote_array_element(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Element type of `column` if it is an array column of `schema`.
+fn element_type<'a>(schema: &'a TableSchema, column: &str) -> Option<&'a str> {
+    schema
+        .columns
+        .iter()
+        .find(|c| c.column_name == column)
+        .and_then(|c| c.element_type.as_deref())
+}
+
+/// Render a timestamp column as text in a fixed format: `timestamptz` as
+/// RFC 3339 in UTC (`2024-03-01T03:30:00.000000Z`), and `timestamp` as the
+/// same layout without an offset, since it carries no zone. Infinite values
+/// keep their `infinity` / `-infinity` text.
+fn timestamp_text(column: &str, data_type: &str) -> String {
+    let (value, format) = if data_type == "timestamp with time zone" {
+        (
+            format!("{} AT TIME ZONE 'UTC'", column),
+            r#"YYYY-MM-DD"T"HH24:MI:SS.US"Z""#,
+        )
+    } else {
+        (column.to_string(), r#"YYYY-MM-DD"T"HH24:MI:SS.US"#)
+    };
+    format!(
+        "CASE WHEN isfinite({}) THEN to_char({}, '{}') ELSE {}::text END",
+        column, value, format, column
+    )
+}
+
+/// Convert Postgres's `\x...` hex output into the bytea marker object.
+/// Returns `None` if the text is not hex-escaped.
+fn bytea_from_hex(text: &str) -> Option<serde_json::Value> {
+    let hex = text.strip_prefix("\\x")?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Some(serde_json::json!({
+        "$type": BYTEA_TYPE_MARKER,
+        "base64": BASE64.encode(bytes),
+    }))
+}
+
+/// SQL and bind values for one page of `list_rows`
+struct ListRowsSql {
+    data_sql: String,
+    where_clause: String,
+    values: Vec<String>,
 }
 
 #[async_trait]
@@ -92,12 +771,12 @@ impl DataSource for PostgresDataSource {
             r#"
             SELECT table_name, table_schema
             FROM information_schema.tables
-            WHERE table_schema = 'public'
+            WHERE table_schema = current_schema()
               AND table_type = 'BASE TABLE'
             ORDER BY table_name
             "#,
         )
-        .fetch_all(&self.pool)
+        .fetch_all(self.reader())
         .await
         .map_err(|e| {
             tracing::error!(error = %e, "Failed to list tables");
@@ -109,6 +788,8 @@ impl DataSource for PostgresDataSource {
             .map(|r| TableInfo {
                 table_name: r.get("table_name"),
                 table_schema: r.get("table_schema"),
+                display_name: None,
+                description: None,
             })
             .collect();
 
@@ -120,55 +801,45 @@ impl DataSource for PostgresDataSource {
     }
 
     async fn get_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema> {
-        tracing::info!(table = %table_name, "Getting table schema");
-        let pk_columns = self.get_primary_key_columns(table_name).await?;
+        if let Some(schema) = self.schema_cache.read().unwrap().get(table_name) {
+            tracing::debug!(table = %table_name, "Schema cache hit");
+            return Ok(schema.clone());
+        }
+        let schema = self.fetch_table_schema(table_name).await?;
+        // An empty column list means the table doesn't exist (yet); don't pin that
+        if !schema.columns.is_empty() {
+            self.schema_cache
+                .write()
+                .unwrap()
+                .insert(table_name.to_string(), schema.clone());
+        }
+        Ok(schema)
+    }
 
-        let rows = sqlx::query(
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool> {
+        let is_view = sqlx::query_scalar::<_, bool>(
             r#"
-            SELECT
-                c.column_name,
-                c.data_type,
-                c.is_nullable,
-                c.column_default,
-                c.character_maximum_length
-            FROM information_schema.columns c
-            WHERE c.table_name = $1
-              AND c.table_schema = 'public'
-            ORDER BY c.ordinal_position
+            SELECT EXISTS(
+                SELECT 1 FROM pg_class c
+                JOIN pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relname = $1
+                  AND n.nspname = current_schema()
+                  AND c.relkind IN ('v', 'm')
+            )
             "#,
         )
         .bind(table_name)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| {
-            tracing::error!(table = %table_name, error = %e, "Failed to get table schema");
-            e
-        })?;
-
-        let columns: Vec<ColumnInfo> = rows
-            .iter()
-            .map(|r| {
-                let col_name: String = r.get("column_name");
-                ColumnInfo {
-                    is_primary_key: pk_columns.contains(&col_name),
-                    column_name: col_name,
-                    data_type: r.get("data_type"),
-                    is_nullable: r.get::<String, _>("is_nullable") == "YES",
-                    column_default: r.get("column_default"),
-                    max_length: r.get::<Option<i32>, _>("character_maximum_length"),
-                }
-            })
-            .collect();
-
-        tracing::info!(table = %table_name, column_count = columns.len(), pk = ?pk_columns, "Schema retrieved");
-        Ok(TableSchema {
-            table_name: table_name.to_string(),
-            columns,
-            primary_key_columns: pk_columns,
-        })
+        .fetch_one(self.reader())
+        .await?;
+        Ok(is_view)
     }
 
-    async fn list_rows(&self, table_name: &str, query: &RowsQuery) -> anyhow::Result<RowsResponse> {
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse> {
         let page = query.page.unwrap_or(1).max(1);
         let per_page = query.per_page.unwrap_or(20).min(100);
         let offset = (page - 1) * per_page;
@@ -179,91 +850,27 @@ impl DataSource for PostgresDataSource {
             per_page = per_page,
             sort_by = ?query.sort_by,
             sort_order = ?query.sort_order,
-            filter = ?query.filter,
+            filter = ?query.filter.as_deref().map(filter_column),
+            search = query.q.is_some(),
             "Listing rows"
         );
 
-        let table = Self::quote_ident(table_name);
+        let sql = self
+            .list_rows_sql(table_name, query, per_page, offset)
+            .await?;
+        let total_count = self
+            .count_where(table_name, &sql.where_clause, &sql.values, tx)
+            .await?;
 
-        // Build WHERE clause from filter
-        let mut where_clause = String::new();
-        let mut filter_values: Vec<String> = Vec::new();
-        if let Some(ref filter_str) = query.filter {
-            let parts: Vec<&str> = filter_str.splitn(3, ':').collect();
-            if parts.len() == 3 {
-                let col = Self::quote_ident(parts[0]);
-                let op = match parts[1] {
-                    "eq" => "=",
-                    "neq" => "!=",
-                    "gt" => ">",
-                    "gte" => ">=",
-                    "lt" => "<",
-                    "lte" => "<=",
-                    "like" => "ILIKE",
-                    _ => "=",
-                };
-                if parts[1] == "like" {
-                    filter_values.push(format!("%{}%", parts[2]));
-                } else {
-                    filter_values.push(parts[2].to_string());
-                }
-                where_clause = format!(" WHERE {}::text {} $1", col, op);
-                tracing::debug!(
-                    column = parts[0],
-                    operator = op,
-                    value = parts[2],
-                    "Filter applied"
-                );
-            }
-        }
+        tracing::debug!(sql = %sql.data_sql, "Executing data query");
+        let rows = self.fetch_all_read(&sql.data_sql, &sql.values, tx).await?;
 
-        // Count query
-        let count_sql = format!("SELECT COUNT(*) as cnt FROM {}{}", table, where_clause);
-        tracing::debug!(sql = %count_sql, "Executing count query");
-        let total_count: i64 = if filter_values.is_empty() {
-            sqlx::query(&count_sql)
-                .fetch_one(&self.pool)
-                .await?
-                .get("cnt")
-        } else {
-            sqlx::query(&count_sql)
-                .bind(&filter_values[0])
-                .fetch_one(&self.pool)
-                .await?
-                .get("cnt")
-        };
-
-        // Build ORDER BY
-        let order_clause = if let Some(ref sort_by) = query.sort_by {
-            let direction = match query.sort_order.as_deref() {
-                Some("desc") | Some("DESC") => "DESC",
-                _ => "ASC",
-            };
-            format!(" ORDER BY {} {}", Self::quote_ident(sort_by), direction)
-        } else {
-            String::new()
-        };
-
-        // Build data query
-        let data_sql = format!(
-            "SELECT row_to_json(t.*) as row_data FROM {} AS t{}{} LIMIT {} OFFSET {}",
-            table, where_clause, order_clause, per_page, offset
-        );
-        tracing::debug!(sql = %data_sql, "Executing data query");
-
-        let rows = if filter_values.is_empty() {
-            sqlx::query(&data_sql).fetch_all(&self.pool).await?
-        } else {
-            sqlx::query(&data_sql)
-                .bind(&filter_values[0])
-                .fetch_all(&self.pool)
-                .await?
-        };
-
-        let json_rows: Vec<serde_json::Value> = rows
+        let mut json_rows: Vec<serde_json::Value> = rows
             .iter()
             .map(|r| r.get::<serde_json::Value, _>("row_data"))
             .collect();
+        self.encode_bytea_columns(table_name, &mut json_rows)
+            .await?;
 
         tracing::info!(
             table = %table_name,
@@ -281,46 +888,189 @@ impl DataSource for PostgresDataSource {
         })
     }
 
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query.per_page.unwrap_or(20).min(100);
+        let offset = (page - 1) * per_page;
+
+        let sql = self
+            .list_rows_sql(table_name, query, per_page, offset)
+            .await?;
+        let explain_sql = format!("EXPLAIN (FORMAT JSON) {}", sql.data_sql);
+        tracing::debug!(sql = %explain_sql, "Explaining data query");
+
+        let mut explain = sqlx::query(&explain_sql);
+        for v in &sql.values {
+            explain = explain.bind(v);
+        }
+        Ok(explain.fetch_one(self.reader()).await?.get(0))
+    }
+
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64> {
+        let (where_clause, filter_values) = Self::build_where_clause(filter, None);
+        self.count_where(table_name, &where_clause, &filter_values, None)
+            .await
+    }
+
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        tracing::info!(table = %table_name, column = %column, limit = limit, "Listing distinct values");
+        let sql = format!(
+            "SELECT to_jsonb(d.v) AS value FROM (SELECT DISTINCT {} AS v FROM {} ORDER BY 1 LIMIT {}) AS d ORDER BY d.v",
+            Self::quote_ident(column),
+            Self::quote_ident(table_name),
+            limit
+        );
+        tracing::debug!(sql = %sql, "Executing distinct query");
+
+        let rows = sqlx::query(&sql).fetch_all(self.reader()).await?;
+        Ok(rows
+            .iter()
+            .map(|r| {
+                r.get::<Option<serde_json::Value>, _>("value")
+                    .unwrap_or(serde_json::Value::Null)
+            })
+            .collect())
+    }
+
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>> {
+        tracing::info!(
+            table = %table_name,
+            group_by = %spec.group_by,
+            metric = ?spec.metric_col,
+            agg = spec.agg_fn.as_sql(),
+            "Running aggregate"
+        );
+        let metric = match &spec.metric_col {
+            Some(col) => format!("{}({})", spec.agg_fn.as_sql(), Self::quote_ident(col)),
+            None => "COUNT(*)".to_string(),
+        };
+        let sql = format!(
+            "SELECT to_jsonb(a.g) AS grp, to_jsonb(a.v) AS val FROM (SELECT {} AS g, {} AS v FROM {} GROUP BY 1) AS a ORDER BY a.g",
+            Self::quote_ident(&spec.group_by),
+            metric,
+            Self::quote_ident(table_name)
+        );
+        tracing::debug!(sql = %sql, "Executing aggregate query");
+
+        let rows = sqlx::query(&sql).fetch_all(self.reader()).await?;
+        Ok(rows
+            .iter()
+            .map(|r| AggregateRow {
+                group: r
+                    .get::<Option<serde_json::Value>, _>("grp")
+                    .unwrap_or(serde_json::Value::Null),
+                value: r
+                    .get::<Option<serde_json::Value>, _>("val")
+                    .unwrap_or(serde_json::Value::Null),
+            })
+            .collect())
+    }
+
     async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value> {
-        tracing::info!(table = %table_name, pk = %pk_value, "Getting single row");
+        tracing::info!(table = %table_name, "Getting single row");
         let pk_columns = self.get_primary_key_columns(table_name).await?;
         let pk_col = pk_columns
             .first()
             .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
 
-        let table = Self::quote_ident(table_name);
+        let schema = self.get_table_schema(table_name).await?;
         let sql = format!(
-            "SELECT row_to_json(t.*) as row_data FROM {} AS t WHERE {}::text = $1",
-            table,
+            "SELECT {} as row_data FROM {} AS t WHERE {}::text = $1",
+            Self::row_json_expr(&schema, "t", &[]),
+            Self::quote_ident(table_name),
             Self::quote_ident(pk_col)
         );
 
         let row = sqlx::query(&sql)
             .bind(pk_value)
-            .fetch_one(&self.pool)
+            .fetch_one(self.reader())
+            .await
+            .map_err(|e| {
+                tracing::error!(table = %table_name, error = %e, "Failed to get row");
+                e
+            })?;
+
+        tracing::debug!(table = %table_name, "Row retrieved");
+        let mut rows = [row.get::<serde_json::Value, _>("row_data")];
+        self.encode_bytea_columns(table_name, &mut rows).await?;
+        let [row] = rows;
+        Ok(row)
+    }
+
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        tracing::info!(table = %table_name, count = pk_values.len(), "Getting rows by primary key");
+        if pk_values.is_empty() {
+            return Ok(vec![]);
+        }
+        let pk_columns = self.get_primary_key_columns(table_name).await?;
+        let pk_col = pk_columns
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
+
+        let pk = Self::quote_ident(pk_col);
+        let schema = self.get_table_schema(table_name).await?;
+        let sql = format!(
+            "SELECT {} as row_data FROM {} AS t WHERE {}::text = ANY($1) ORDER BY array_position($1, {}::text)",
+            Self::row_json_expr(&schema, "t", &[]),
+            Self::quote_ident(table_name),
+            pk,
+            pk
+        );
+        tracing::debug!(sql = %sql, "Executing batch get");
+
+        let rows = sqlx::query(&sql)
+            .bind(pk_values)
+            .fetch_all(self.reader())
             .await
             .map_err(|e| {
-                tracing::error!(table = %table_name, pk = %pk_value, error = %e, "Failed to get row");
+                tracing::error!(table = %table_name, error = %e, "Failed to get rows");
                 e
             })?;
 
-        tracing::debug!(table = %table_name, pk = %pk_value, "Row retrieved");
-        Ok(row.get::<serde_json::Value, _>("row_data"))
+        tracing::debug!(table = %table_name, found = rows.len(), "Rows retrieved by primary key");
+        let mut json_rows: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|r| r.get::<serde_json::Value, _>("row_data"))
+            .collect();
+        self.encode_bytea_columns(table_name, &mut json_rows)
+            .await?;
+        Ok(json_rows)
     }
 
     async fn insert_row(
         &self,
         table_name: &str,
         data: &serde_json::Value,
+        returning: &[String],
+        tx: Option<&DataSourceTransaction>,
     ) -> anyhow::Result<serde_json::Value> {
         tracing::info!(table = %table_name, "Inserting new row");
-        tracing::debug!(table = %table_name, data = %data, "Insert data");
+        self.ensure_not_system_table(table_name).await?;
+        tracing::debug!(table = %table_name, columns = ?payload_columns(data), "Insert data");
 
         let obj = data
             .as_object()
             .ok_or_else(|| anyhow::anyhow!("Data must be a JSON object"))?;
 
         let table = Self::quote_ident(table_name);
+        let schema = self.get_table_schema(table_name).await?;
         let mut columns = Vec::new();
         let mut placeholders = Vec::new();
         let mut values: Vec<String> = Vec::new();
@@ -330,36 +1080,32 @@ impl DataSource for PostgresDataSource {
             if val.is_null() {
                 continue;
             }
+            let (placeholder, value) = write_param(val, idx, element_type(&schema, key))?;
             columns.push(Self::quote_ident(key));
-            placeholders.push(format!("${}", idx));
-            values.push(match val {
-                serde_json::Value::String(s) => s.clone(),
-                other => other.to_string(),
-            });
+            placeholders.push(placeholder);
+            values.push(value);
             idx += 1;
         }
 
         let sql = format!(
-            "INSERT INTO {} ({}) VALUES ({}) RETURNING row_to_json({}.*)  as row_data",
+            "INSERT INTO {} ({}) VALUES ({}) RETURNING {} as row_data",
             table,
             columns.join(", "),
             placeholders.join(", "),
-            table
+            Self::row_json_expr(&schema, &table, returning)
         );
         tracing::debug!(sql = %sql, "Executing insert");
 
-        let mut query = sqlx::query(&sql);
-        for v in &values {
-            query = query.bind(v);
-        }
-
-        let row = query.fetch_one(&self.pool).await.map_err(|e| {
+        let row = self.fetch_one_write(&sql, &values, tx).await.map_err(|e| {
             tracing::error!(table = %table_name, error = %e, "Failed to insert row");
             e
         })?;
 
         tracing::info!(table = %table_name, "Row inserted successfully");
-        Ok(row.get::<serde_json::Value, _>("row_data"))
+        let mut rows = [row.get::<serde_json::Value, _>("row_data")];
+        self.encode_bytea_columns(table_name, &mut rows).await?;
+        let [row] = rows;
+        Ok(row)
     }
 
     async fn update_row(
@@ -367,9 +1113,11 @@ impl DataSource for PostgresDataSource {
         table_name: &str,
         pk_value: &str,
         data: &serde_json::Value,
+        tx: Option<&DataSourceTransaction>,
     ) -> anyhow::Result<serde_json::Value> {
-        tracing::info!(table = %table_name, pk = %pk_value, "Updating row");
-        tracing::debug!(table = %table_name, pk = %pk_value, data = %data, "Update data");
+        tracing::info!(table = %table_name, "Updating row");
+        self.ensure_not_system_table(table_name).await?;
+        tracing::debug!(table = %table_name, columns = ?payload_columns(data), "Update data");
 
         let pk_columns = self.get_primary_key_columns(table_name).await?;
         let pk_col = pk_columns
@@ -381,6 +1129,7 @@ impl DataSource for PostgresDataSource {
             .ok_or_else(|| anyhow::anyhow!("Data must be a JSON object"))?;
 
         let table = Self::quote_ident(table_name);
+        let schema = self.get_table_schema(table_name).await?;
         let mut set_clauses = Vec::new();
         let mut values: Vec<String> = Vec::new();
         let mut idx = 1;
@@ -389,16 +1138,13 @@ impl DataSource for PostgresDataSource {
             if key == pk_col {
                 continue;
             }
-            set_clauses.push(format!("{} = ${}", Self::quote_ident(key), idx));
-            values.push(match val {
-                serde_json::Value::Null => {
-                    set_clauses.pop();
-                    set_clauses.push(format!("{} = NULL", Self::quote_ident(key)));
-                    continue;
-                }
-                serde_json::Value::String(s) => s.clone(),
-                other => other.to_string(),
-            });
+            if val.is_null() {
+                set_clauses.push(format!("{} = NULL", Self::quote_ident(key)));
+                continue;
+            }
+            let (placeholder, value) = write_param(val, idx, element_type(&schema, key))?;
+            set_clauses.push(format!("{} = {}", Self::quote_ident(key), placeholder));
+            values.push(value);
             idx += 1;
         }
 
@@ -406,54 +1152,201 @@ impl DataSource for PostgresDataSource {
         values.push(pk_value.to_string());
 
         let sql = format!(
-            "UPDATE {} SET {} WHERE {}::text = {} RETURNING row_to_json({}.*)  as row_data",
+            "UPDATE {} SET {} WHERE {}::text = {} RETURNING {} as row_data",
             table,
             set_clauses.join(", "),
             Self::quote_ident(pk_col),
             pk_placeholder,
-            table
+            Self::row_json_expr(&schema, &table, &[])
         );
         tracing::debug!(sql = %sql, "Executing update");
 
-        let mut query = sqlx::query(&sql);
-        for v in &values {
-            query = query.bind(v);
-        }
-
-        let row = query.fetch_one(&self.pool).await.map_err(|e| {
-            tracing::error!(table = %table_name, pk = %pk_value, error = %e, "Failed to update row");
+        let row = self.fetch_one_write(&sql, &values, tx).await.map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to update row");
             e
         })?;
 
-        tracing::info!(table = %table_name, pk = %pk_value, "Row updated successfully");
-        Ok(row.get::<serde_json::Value, _>("row_data"))
+        tracing::info!(table = %table_name, "Row updated successfully");
+        let mut rows = [row.get::<serde_json::Value, _>("row_data")];
+        self.encode_bytea_columns(table_name, &mut rows).await?;
+        let [row] = rows;
+        Ok(row)
     }
 
-    async fn delete_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<()> {
-        tracing::info!(table = %table_name, pk = %pk_value, "Deleting row");
-        let pk_columns = self.get_primary_key_columns(table_name).await?;
-        let pk_col = pk_columns
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("No primary key found for table {}", table_name))?;
-
-        let table = Self::quote_ident(table_name);
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(table = %table_name, "Deleting row");
+        self.ensure_not_system_table(table_name).await?;
+        let predicate = self.pk_predicate(table_name).await?;
         let sql = format!(
-            "DELETE FROM {} WHERE {}::text = $1",
-            table,
-            Self::quote_ident(pk_col)
+            "DELETE FROM {} WHERE {}",
+            Self::quote_ident(table_name),
+            predicate
         );
-        tracing::debug!(sql = %sql, pk = %pk_value, "Executing delete");
+        tracing::debug!(sql = %sql, "Executing delete");
 
-        sqlx::query(&sql)
-            .bind(pk_value)
-            .execute(&self.pool)
+        self.execute_write(&sql, &[pk_value.to_string()], tx)
             .await
             .map_err(|e| {
-                tracing::error!(table = %table_name, pk = %pk_value, error = %e, "Failed to delete row");
+                tracing::error!(table = %table_name, error = %e, "Failed to delete row");
                 e
             })?;
 
-        tracing::info!(table = %table_name, pk = %pk_value, "Row deleted successfully");
+        tracing::info!(table = %table_name, "Row deleted successfully");
         Ok(())
     }
+
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64> {
+        let predicate = self.pk_predicate(table_name).await?;
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            Self::quote_ident(table_name),
+            predicate
+        );
+        tracing::debug!(sql = %sql, "Executing count by primary key");
+        let row = sqlx::query(&sql)
+            .bind(pk_value)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.get::<i64, _>(0))
+    }
+
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64> {
+        tracing::info!(table = %table_name, filter = filter_column(filter), "Bulk updating rows");
+        self.ensure_not_system_table(table_name).await?;
+        let (where_clause, mut values) = Self::build_where_clause(Some(filter), None);
+        if where_clause.is_empty() {
+            anyhow::bail!("A valid filter is required for bulk update");
+        }
+        let obj = data
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("Data must be a JSON object"))?;
+
+        // Filter values take $1..$n; SET values are numbered after them
+        let schema = self.get_table_schema(table_name).await?;
+        let mut set_clauses = Vec::new();
+        for (key, val) in obj.iter() {
+            match val {
+                serde_json::Value::Null => {
+                    set_clauses.push(format!("{} = NULL", Self::quote_ident(key)));
+                }
+                other => {
+                    let (placeholder, value) =
+                        write_param(other, values.len() + 1, element_type(&schema, key))?;
+                    values.push(value);
+                    set_clauses.push(format!("{} = {}", Self::quote_ident(key), placeholder));
+                }
+            }
+        }
+        if set_clauses.is_empty() {
+            anyhow::bail!("Data must contain at least one column");
+        }
+
+        let sql = format!(
+            "UPDATE {} SET {}{}",
+            Self::quote_ident(table_name),
+            set_clauses.join(", "),
+            where_clause
+        );
+        tracing::debug!(sql = %sql, "Executing bulk update");
+
+        let mut query = sqlx::query(&sql);
+        for v in &values {
+            query = query.bind(v);
+        }
+        let result = query.execute(&self.pool).await.map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to bulk update rows");
+            e
+        })?;
+
+        tracing::info!(table = %table_name, affected = result.rows_affected(), "Bulk update complete");
+        Ok(result.rows_affected())
+    }
+
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64> {
+        tracing::info!(table = %table_name, filter = filter_column(filter), "Bulk deleting rows");
+        self.ensure_not_system_table(table_name).await?;
+        let (where_clause, values) = Self::build_where_clause(Some(filter), None);
+        if where_clause.is_empty() {
+            anyhow::bail!("A valid filter is required for bulk delete");
+        }
+
+        let sql = format!(
+            "DELETE FROM {}{}",
+            Self::quote_ident(table_name),
+            where_clause
+        );
+        tracing::debug!(sql = %sql, "Executing bulk delete");
+
+        let mut query = sqlx::query(&sql);
+        for v in &values {
+            query = query.bind(v);
+        }
+        let result = query.execute(&self.pool).await.map_err(|e| {
+            tracing::error!(table = %table_name, error = %e, "Failed to bulk delete rows");
+            e
+        })?;
+
+        tracing::info!(table = %table_name, affected = result.rows_affected(), "Bulk delete complete");
+        Ok(result.rows_affected())
+    }
+
+    async fn begin(&self) -> anyhow::Result<DataSourceTransaction> {
+        tracing::info!("Beginning transaction");
+        Ok(DataSourceTransaction::postgres(self.pool.begin().await?))
+    }
+
+    async fn begin_snapshot(&self) -> anyhow::Result<DataSourceTransaction> {
+        tracing::info!("Beginning snapshot");
+        let mut tx = self.reader().begin().await?;
+        // Must precede any query for the isolation level to take effect
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+        Ok(DataSourceTransaction::postgres(tx))
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        Some(PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle(),
+        })
+    }
+
+    async fn warm_up(&self, connections: u32) {
+        let connections = connections.min(self.pool.options().get_max_connections());
+        // Hold each connection until all are open so the pool can't hand back the same one
+        let mut held = Vec::with_capacity(connections as usize);
+        for _ in 0..connections {
+            match self.pool.acquire().await {
+                Ok(conn) => held.push(conn),
+                Err(e) => {
+                    tracing::warn!(error = %e, opened = held.len(), "Pool warm-up stopped early");
+                    break;
+                }
+            }
+        }
+        tracing::debug!(opened = held.len(), "Pool warmed up");
+    }
+
+    async fn invalidate_schema_cache(&self) {
+        self.schema_cache.write().unwrap().clear();
+        tracing::info!("Schema cache cleared");
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+        for pool in &self.read_pools {
+            pool.close().await;
+        }
+    }
 }
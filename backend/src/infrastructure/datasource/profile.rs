@@ -0,0 +1,326 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use crate::domain::connection::SlowQuery;
+use crate::domain::data::{AggregateRow, AggregateSpec, RowsResponse, TableInfo, TableSchema};
+use crate::presentation::request::RowsQuery;
+
+use super::{DataSource, DataSourceTransaction, PoolStatus};
+
+/// Calls kept per connection, unless `SLOW_QUERY_BUFFER_SIZE` overrides it.
+pub const DEFAULT_SLOW_QUERY_BUFFER_SIZE: usize = 100;
+
+/// The last few datasource calls of one connection with their durations,
+/// kept in memory only. Once full, each new call evicts the oldest.
+pub struct QueryProfile {
+    capacity: usize,
+    entries: Mutex<VecDeque<SlowQuery>>,
+}
+
+impl Default for QueryProfile {
+    /// Sized by `SLOW_QUERY_BUFFER_SIZE`.
+    fn default() -> Self {
+        let capacity = std::env::var("SLOW_QUERY_BUFFER_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SLOW_QUERY_BUFFER_SIZE);
+        Self::with_capacity(capacity)
+    }
+}
+
+impl QueryProfile {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn record(&self, query: SlowQuery) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(query);
+    }
+
+    /// The buffered calls, slowest first.
+    pub fn slowest(&self) -> Vec<SlowQuery> {
+        let mut queries: Vec<SlowQuery> = self.entries.lock().unwrap().iter().cloned().collect();
+        queries.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+        queries
+    }
+}
+
+/// Wraps a `DataSource` and times every call into a `QueryProfile`.
+pub struct ProfilingDataSource {
+    inner: Arc<dyn DataSource>,
+    profile: Arc<QueryProfile>,
+}
+
+impl ProfilingDataSource {
+    pub fn new(inner: Arc<dyn DataSource>, profile: Arc<QueryProfile>) -> Self {
+        Self { inner, profile }
+    }
+
+    async fn timed<T, Fut>(
+        &self,
+        operation: &'static str,
+        table_name: Option<&str>,
+        call: Fut,
+    ) -> anyhow::Result<T>
+    where
+        Fut: Future<Output = anyhow::Result<T>> + Send,
+    {
+        let started = Instant::now();
+        let result = call.await;
+        self.profile.record(SlowQuery {
+            operation: operation.to_string(),
+            table_name: table_name.map(str::to_string),
+            duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            success: result.is_ok(),
+            finished_at: chrono::Utc::now(),
+        });
+        result
+    }
+}
+
+#[async_trait]
+impl DataSource for ProfilingDataSource {
+    async fn list_tables(&self) -> anyhow::Result<Vec<TableInfo>> {
+        self.timed("list_tables", None, self.inner.list_tables())
+            .await
+    }
+
+    async fn get_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema> {
+        self.timed(
+            "get_table_schema",
+            Some(table_name),
+            self.inner.get_table_schema(table_name),
+        )
+        .await
+    }
+
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool> {
+        self.timed("is_view", Some(table_name), self.inner.is_view(table_name))
+            .await
+    }
+
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse> {
+        self.timed(
+            "list_rows",
+            Some(table_name),
+            self.inner.list_rows(table_name, query, tx),
+        )
+        .await
+    }
+
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64> {
+        self.timed(
+            "count_rows",
+            Some(table_name),
+            self.inner.count_rows(table_name, filter),
+        )
+        .await
+    }
+
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.timed(
+            "distinct_values",
+            Some(table_name),
+            self.inner.distinct_values(table_name, column, limit),
+        )
+        .await
+    }
+
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>> {
+        self.timed(
+            "aggregate",
+            Some(table_name),
+            self.inner.aggregate(table_name, spec),
+        )
+        .await
+    }
+
+    async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value> {
+        self.timed(
+            "get_row",
+            Some(table_name),
+            self.inner.get_row(table_name, pk_value),
+        )
+        .await
+    }
+
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.timed(
+            "explain_rows",
+            Some(table_name),
+            self.inner.explain_rows(table_name, query),
+        )
+        .await
+    }
+
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.timed(
+            "get_rows",
+            Some(table_name),
+            self.inner.get_rows(table_name, pk_values),
+        )
+        .await
+    }
+
+    async fn insert_row(
+        &self,
+        table_name: &str,
+        data: &serde_json::Value,
+        returning: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.timed(
+            "insert_row",
+            Some(table_name),
+            self.inner.insert_row(table_name, data, returning, tx),
+        )
+        .await
+    }
+
+    async fn update_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        data: &serde_json::Value,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.timed(
+            "update_row",
+            Some(table_name),
+            self.inner.update_row(table_name, pk_value, data, tx),
+        )
+        .await
+    }
+
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()> {
+        self.timed(
+            "delete_row",
+            Some(table_name),
+            self.inner.delete_row(table_name, pk_value, tx),
+        )
+        .await
+    }
+
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64> {
+        self.timed(
+            "count_by_pk",
+            Some(table_name),
+            self.inner.count_by_pk(table_name, pk_value),
+        )
+        .await
+    }
+
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64> {
+        self.timed(
+            "bulk_update",
+            Some(table_name),
+            self.inner.bulk_update(table_name, filter, data),
+        )
+        .await
+    }
+
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64> {
+        self.timed(
+            "bulk_delete",
+            Some(table_name),
+            self.inner.bulk_delete(table_name, filter),
+        )
+        .await
+    }
+
+    async fn begin(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin().await
+    }
+
+    async fn begin_snapshot(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin_snapshot().await
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        self.inner.pool_status()
+    }
+
+    async fn warm_up(&self, connections: u32) {
+        self.inner.warm_up(connections).await
+    }
+
+    async fn invalidate_schema_cache(&self) {
+        self.inner.invalidate_schema_cache().await
+    }
+
+    async fn close(&self) {
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(operation: &str, duration_ms: f64) -> SlowQuery {
+        SlowQuery {
+            operation: operation.to_string(),
+            table_name: None,
+            duration_ms,
+            success: true,
+            finished_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn full_buffer_evicts_the_oldest_call() {
+        let profile = QueryProfile::with_capacity(2);
+        profile.record(query("first", 30.0));
+        profile.record(query("second", 10.0));
+        profile.record(query("third", 20.0));
+
+        let ops: Vec<String> = profile.slowest().into_iter().map(|q| q.operation).collect();
+        assert_eq!(ops, vec!["third", "second"]);
+    }
+}
@@ -0,0 +1,347 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::domain::data::{AggregateRow, AggregateSpec, RowsResponse, TableInfo, TableSchema};
+use crate::domain::repository::QueryLogRepository;
+use crate::presentation::request::RowsQuery;
+
+use super::{DataSource, DataSourceTransaction, PoolStatus};
+
+/// Entries waiting to be written beyond this many are dropped, so a slow
+/// app database never holds up datasource calls.
+pub const QUERY_LOG_QUEUE_CAPACITY: usize = 1024;
+
+/// How long query log entries are kept, unless `QUERY_LOG_RETENTION_DAYS`
+/// overrides it (0 keeps them forever).
+pub const DEFAULT_QUERY_LOG_RETENTION_DAYS: u32 = 30;
+
+/// How often entries past the retention period are deleted.
+const QUERY_LOG_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Resolve the query log retention from `QUERY_LOG_RETENTION_DAYS`.
+pub fn query_log_retention_days() -> u32 {
+    std::env::var("QUERY_LOG_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_QUERY_LOG_RETENTION_DAYS)
+}
+
+/// One datasource call, queued for the background writer.
+struct PendingQueryLogEntry {
+    conn_id: Uuid,
+    user_id: Uuid,
+    operation: &'static str,
+    table_name: Option<String>,
+    duration_ms: i64,
+    success: bool,
+}
+
+/// Hands query log entries to a background task that writes them, so a
+/// datasource call never waits on the app database. The task also deletes
+/// entries older than the retention period.
+#[derive(Clone)]
+pub struct QueryLogWriter {
+    tx: mpsc::Sender<PendingQueryLogEntry>,
+}
+
+impl QueryLogWriter {
+    /// Start the writer task; it stops once every writer handle is dropped.
+    pub fn spawn(log: Arc<dyn QueryLogRepository>, retention_days: u32) -> Self {
+        let (tx, mut rx) = mpsc::channel::<PendingQueryLogEntry>(QUERY_LOG_QUEUE_CAPACITY);
+        tokio::spawn(async move {
+            let mut prune = tokio::time::interval(QUERY_LOG_PRUNE_INTERVAL);
+            loop {
+                tokio::select! {
+                    entry = rx.recv() => {
+                        let Some(entry) = entry else { break };
+                        if let Err(e) = log
+                            .record(
+                                &entry.conn_id,
+                                &entry.user_id,
+                                entry.operation,
+                                entry.table_name.as_deref(),
+                                entry.duration_ms,
+                                entry.success,
+                            )
+                            .await
+                        {
+                            tracing::warn!(connection_id = %entry.conn_id, operation = entry.operation, error = %e, "Failed to record query log entry");
+                        }
+                    }
+                    _ = prune.tick(), if retention_days > 0 => {
+                        let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days.into());
+                        match log.prune(cutoff).await {
+                            Ok(0) => {}
+                            Ok(n) => tracing::info!(deleted = n, "Pruned expired query log entries"),
+                            Err(e) => tracing::warn!(error = %e, "Failed to prune query log"),
+                        }
+                    }
+                }
+            }
+        });
+        Self { tx }
+    }
+
+    fn send(&self, entry: PendingQueryLogEntry) {
+        if let Err(mpsc::error::TrySendError::Full(entry)) = self.tx.try_send(entry) {
+            tracing::warn!(connection_id = %entry.conn_id, operation = entry.operation, "Query log queue full; dropping entry");
+        }
+    }
+}
+
+/// Wraps a `DataSource` for a single caller and records each data operation
+/// in the query log: the operation, table, duration, and whether it
+/// succeeded. Catalog lookups (`get_table_schema`, `is_view`) are what
+/// usecases do around the operation a request asked for, so they are passed
+/// through unlogged. Entries are written in the background; a failure to
+/// write one is only warned about, never surfaced.
+pub struct QueryLoggingDataSource {
+    inner: Arc<dyn DataSource>,
+    log: QueryLogWriter,
+    conn_id: Uuid,
+    user_id: Uuid,
+}
+
+impl QueryLoggingDataSource {
+    pub fn new(
+        inner: Arc<dyn DataSource>,
+        log: QueryLogWriter,
+        conn_id: Uuid,
+        user_id: Uuid,
+    ) -> Self {
+        Self {
+            inner,
+            log,
+            conn_id,
+            user_id,
+        }
+    }
+
+    async fn logged<T, Fut>(
+        &self,
+        operation: &'static str,
+        table_name: Option<&str>,
+        call: Fut,
+    ) -> anyhow::Result<T>
+    where
+        Fut: Future<Output = anyhow::Result<T>> + Send,
+    {
+        let started = Instant::now();
+        let result = call.await;
+        self.log.send(PendingQueryLogEntry {
+            conn_id: self.conn_id,
+            user_id: self.user_id,
+            operation,
+            table_name: table_name.map(str::to_string),
+            duration_ms: started.elapsed().as_millis() as i64,
+            success: result.is_ok(),
+        });
+        result
+    }
+}
+
+#[async_trait]
+impl DataSource for QueryLoggingDataSource {
+    async fn list_tables(&self) -> anyhow::Result<Vec<TableInfo>> {
+        self.logged("list_tables", None, self.inner.list_tables())
+            .await
+    }
+
+    async fn get_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema> {
+        self.inner.get_table_schema(table_name).await
+    }
+
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool> {
+        self.inner.is_view(table_name).await
+    }
+
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse> {
+        self.logged(
+            "list_rows",
+            Some(table_name),
+            self.inner.list_rows(table_name, query, tx),
+        )
+        .await
+    }
+
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64> {
+        self.logged(
+            "count_rows",
+            Some(table_name),
+            self.inner.count_rows(table_name, filter),
+        )
+        .await
+    }
+
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.logged(
+            "distinct_values",
+            Some(table_name),
+            self.inner.distinct_values(table_name, column, limit),
+        )
+        .await
+    }
+
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>> {
+        self.logged(
+            "aggregate",
+            Some(table_name),
+            self.inner.aggregate(table_name, spec),
+        )
+        .await
+    }
+
+    async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value> {
+        self.logged(
+            "get_row",
+            Some(table_name),
+            self.inner.get_row(table_name, pk_value),
+        )
+        .await
+    }
+
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.logged(
+            "explain_rows",
+            Some(table_name),
+            self.inner.explain_rows(table_name, query),
+        )
+        .await
+    }
+
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.logged(
+            "get_rows",
+            Some(table_name),
+            self.inner.get_rows(table_name, pk_values),
+        )
+        .await
+    }
+
+    async fn insert_row(
+        &self,
+        table_name: &str,
+        data: &serde_json::Value,
+        returning: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.logged(
+            "insert_row",
+            Some(table_name),
+            self.inner.insert_row(table_name, data, returning, tx),
+        )
+        .await
+    }
+
+    async fn update_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        data: &serde_json::Value,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.logged(
+            "update_row",
+            Some(table_name),
+            self.inner.update_row(table_name, pk_value, data, tx),
+        )
+        .await
+    }
+
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()> {
+        self.logged(
+            "delete_row",
+            Some(table_name),
+            self.inner.delete_row(table_name, pk_value, tx),
+        )
+        .await
+    }
+
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64> {
+        self.logged(
+            "count_by_pk",
+            Some(table_name),
+            self.inner.count_by_pk(table_name, pk_value),
+        )
+        .await
+    }
+
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64> {
+        self.logged(
+            "bulk_update",
+            Some(table_name),
+            self.inner.bulk_update(table_name, filter, data),
+        )
+        .await
+    }
+
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64> {
+        self.logged(
+            "bulk_delete",
+            Some(table_name),
+            self.inner.bulk_delete(table_name, filter),
+        )
+        .await
+    }
+
+    async fn begin(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin().await
+    }
+
+    async fn begin_snapshot(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin_snapshot().await
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        self.inner.pool_status()
+    }
+
+    async fn warm_up(&self, connections: u32) {
+        self.inner.warm_up(connections).await
+    }
+
+    async fn invalidate_schema_cache(&self) {
+        self.inner.invalidate_schema_cache().await
+    }
+
+    async fn close(&self) {
+        self.inner.close().await
+    }
+}
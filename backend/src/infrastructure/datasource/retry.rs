@@ -0,0 +1,429 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::domain::data::{AggregateRow, AggregateSpec, RowsResponse, TableInfo, TableSchema};
+use crate::presentation::request::RowsQuery;
+
+use super::{DataSource, DataSourceError, DataSourceTransaction, PoolStatus};
+
+/// Retries used when `DATASOURCE_RETRIES` is unset.
+pub const DEFAULT_DATASOURCE_RETRIES: u32 = 2;
+
+/// First backoff delay used when `DATASOURCE_RETRY_BASE_MS` is unset.
+pub const DEFAULT_DATASOURCE_RETRY_BASE_MS: u64 = 50;
+
+/// How often and how patiently to retry a read after a transient failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Extra attempts after the first one; 0 disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each following one.
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_env() -> Self {
+        let max_retries = std::env::var("DATASOURCE_RETRIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DATASOURCE_RETRIES);
+        let base_ms = std::env::var("DATASOURCE_RETRY_BASE_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DATASOURCE_RETRY_BASE_MS);
+        Self {
+            max_retries,
+            base_delay: Duration::from_millis(base_ms),
+        }
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay.saturating_mul(2u32.saturating_pow(attempt))
+    }
+}
+
+/// Wraps a `DataSource` and retries its read-only methods with exponential
+/// backoff when the failure is classified as `DataSourceError::Transient` or
+/// `DataSourceError::PoolTimeout`.
+/// Writes are passed straight through and never retried.
+pub struct RetryingDataSource {
+    inner: Arc<dyn DataSource>,
+    policy: RetryPolicy,
+}
+
+impl RetryingDataSource {
+    pub fn new(inner: Arc<dyn DataSource>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+
+    async fn retry<T, F, Fut>(&self, op: &'static str, mut call: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut + Send,
+        Fut: Future<Output = anyhow::Result<T>> + Send,
+        T: Send,
+    {
+        let mut attempt = 0;
+        loop {
+            match call().await {
+                Err(e)
+                    if attempt < self.policy.max_retries
+                        && matches!(
+                            DataSourceError::classify(&e),
+                            Some(DataSourceError::Transient | DataSourceError::PoolTimeout)
+                        ) =>
+                {
+                    let delay = self.policy.delay(attempt);
+                    attempt += 1;
+                    tracing::warn!(op, attempt, delay_ms = delay.as_millis() as u64, error = %e, "Transient datasource error, retrying");
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSource for RetryingDataSource {
+    async fn list_tables(&self) -> anyhow::Result<Vec<TableInfo>> {
+        self.retry("list_tables", || self.inner.list_tables()).await
+    }
+
+    async fn get_table_schema(&self, table_name: &str) -> anyhow::Result<TableSchema> {
+        self.retry("get_table_schema", || {
+            self.inner.get_table_schema(table_name)
+        })
+        .await
+    }
+
+    async fn is_view(&self, table_name: &str) -> anyhow::Result<bool> {
+        self.retry("is_view", || self.inner.is_view(table_name))
+            .await
+    }
+
+    async fn list_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<RowsResponse> {
+        // A failed statement aborts the transaction, so there is nothing to retry in
+        if tx.is_some() {
+            return self.inner.list_rows(table_name, query, tx).await;
+        }
+        self.retry("list_rows", || {
+            self.inner.list_rows(table_name, query, None)
+        })
+        .await
+    }
+
+    async fn count_rows(&self, table_name: &str, filter: Option<&str>) -> anyhow::Result<i64> {
+        self.retry("count_rows", || self.inner.count_rows(table_name, filter))
+            .await
+    }
+
+    async fn distinct_values(
+        &self,
+        table_name: &str,
+        column: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.retry("distinct_values", || {
+            self.inner.distinct_values(table_name, column, limit)
+        })
+        .await
+    }
+
+    async fn aggregate(
+        &self,
+        table_name: &str,
+        spec: &AggregateSpec,
+    ) -> anyhow::Result<Vec<AggregateRow>> {
+        self.retry("aggregate", || self.inner.aggregate(table_name, spec))
+            .await
+    }
+
+    async fn get_row(&self, table_name: &str, pk_value: &str) -> anyhow::Result<serde_json::Value> {
+        self.retry("get_row", || self.inner.get_row(table_name, pk_value))
+            .await
+    }
+
+    async fn explain_rows(
+        &self,
+        table_name: &str,
+        query: &RowsQuery,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.retry("explain_rows", || {
+            self.inner.explain_rows(table_name, query)
+        })
+        .await
+    }
+
+    async fn get_rows(
+        &self,
+        table_name: &str,
+        pk_values: &[String],
+    ) -> anyhow::Result<Vec<serde_json::Value>> {
+        self.retry("get_rows", || self.inner.get_rows(table_name, pk_values))
+            .await
+    }
+
+    async fn insert_row(
+        &self,
+        table_name: &str,
+        data: &serde_json::Value,
+        returning: &[String],
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.inner.insert_row(table_name, data, returning, tx).await
+    }
+
+    async fn update_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        data: &serde_json::Value,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.inner.update_row(table_name, pk_value, data, tx).await
+    }
+
+    async fn delete_row(
+        &self,
+        table_name: &str,
+        pk_value: &str,
+        tx: Option<&DataSourceTransaction>,
+    ) -> anyhow::Result<()> {
+        self.inner.delete_row(table_name, pk_value, tx).await
+    }
+
+    async fn count_by_pk(&self, table_name: &str, pk_value: &str) -> anyhow::Result<i64> {
+        self.retry("count_by_pk", || {
+            self.inner.count_by_pk(table_name, pk_value)
+        })
+        .await
+    }
+
+    async fn bulk_update(
+        &self,
+        table_name: &str,
+        filter: &str,
+        data: &serde_json::Value,
+    ) -> anyhow::Result<u64> {
+        self.inner.bulk_update(table_name, filter, data).await
+    }
+
+    async fn bulk_delete(&self, table_name: &str, filter: &str) -> anyhow::Result<u64> {
+        self.inner.bulk_delete(table_name, filter).await
+    }
+
+    async fn begin(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin().await
+    }
+
+    async fn begin_snapshot(&self) -> anyhow::Result<DataSourceTransaction> {
+        self.inner.begin_snapshot().await
+    }
+
+    fn pool_status(&self) -> Option<PoolStatus> {
+        self.inner.pool_status()
+    }
+
+    async fn warm_up(&self, connections: u32) {
+        self.inner.warm_up(connections).await
+    }
+
+    async fn invalidate_schema_cache(&self) {
+        self.inner.invalidate_schema_cache().await
+    }
+
+    async fn close(&self) {
+        self.inner.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` calls of each method with `error()`.
+    struct FlakyDataSource {
+        failures: u32,
+        error: fn() -> anyhow::Error,
+        calls: AtomicU32,
+    }
+
+    impl FlakyDataSource {
+        fn attempt(&self) -> anyhow::Result<()> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err((self.error)());
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl DataSource for FlakyDataSource {
+        async fn list_tables(&self) -> anyhow::Result<Vec<TableInfo>> {
+            self.attempt()?;
+            Ok(vec![])
+        }
+        async fn get_table_schema(&self, _: &str) -> anyhow::Result<TableSchema> {
+            anyhow::bail!("mock")
+        }
+        async fn is_view(&self, _: &str) -> anyhow::Result<bool> {
+            anyhow::bail!("mock")
+        }
+        async fn list_rows(
+            &self,
+            _: &str,
+            _: &RowsQuery,
+            _: Option<&DataSourceTransaction>,
+        ) -> anyhow::Result<RowsResponse> {
+            anyhow::bail!("mock")
+        }
+        async fn count_rows(&self, _: &str, _: Option<&str>) -> anyhow::Result<i64> {
+            anyhow::bail!("mock")
+        }
+        async fn distinct_values(
+            &self,
+            _: &str,
+            _: &str,
+            _: u32,
+        ) -> anyhow::Result<Vec<serde_json::Value>> {
+            anyhow::bail!("mock")
+        }
+        async fn aggregate(&self, _: &str, _: &AggregateSpec) -> anyhow::Result<Vec<AggregateRow>> {
+            anyhow::bail!("mock")
+        }
+        async fn get_row(&self, _: &str, _: &str) -> anyhow::Result<serde_json::Value> {
+            self.attempt()?;
+            Ok(serde_json::json!({ "id": 1 }))
+        }
+        async fn explain_rows(&self, _: &str, _: &RowsQuery) -> anyhow::Result<serde_json::Value> {
+            anyhow::bail!("mock")
+        }
+        async fn get_rows(&self, _: &str, _: &[String]) -> anyhow::Result<Vec<serde_json::Value>> {
+            anyhow::bail!("mock")
+        }
+        async fn insert_row(
+            &self,
+            _: &str,
+            _: &serde_json::Value,
+            _: &[String],
+            _: Option<&DataSourceTransaction>,
+        ) -> anyhow::Result<serde_json::Value> {
+            self.attempt()?;
+            Ok(serde_json::json!({ "id": 1 }))
+        }
+        async fn update_row(
+            &self,
+            _: &str,
+            _: &str,
+            _: &serde_json::Value,
+            _: Option<&DataSourceTransaction>,
+        ) -> anyhow::Result<serde_json::Value> {
+            anyhow::bail!("mock")
+        }
+        async fn delete_row(
+            &self,
+            _: &str,
+            _: &str,
+            _: Option<&DataSourceTransaction>,
+        ) -> anyhow::Result<()> {
+            anyhow::bail!("mock")
+        }
+        async fn count_by_pk(&self, _: &str, _: &str) -> anyhow::Result<i64> {
+            anyhow::bail!("mock")
+        }
+        async fn bulk_update(
+            &self,
+            _: &str,
+            _: &str,
+            _: &serde_json::Value,
+        ) -> anyhow::Result<u64> {
+            anyhow::bail!("mock")
+        }
+        async fn bulk_delete(&self, _: &str, _: &str) -> anyhow::Result<u64> {
+            anyhow::bail!("mock")
+        }
+    }
+
+    fn wrap(failures: u32, max_retries: u32) -> (Arc<FlakyDataSource>, RetryingDataSource) {
+        wrap_failing_with(
+            || anyhow::Error::new(DataSourceError::Transient),
+            failures,
+            max_retries,
+        )
+    }
+
+    fn wrap_failing_with(
+        error: fn() -> anyhow::Error,
+        failures: u32,
+        max_retries: u32,
+    ) -> (Arc<FlakyDataSource>, RetryingDataSource) {
+        let flaky = Arc::new(FlakyDataSource {
+            failures,
+            error,
+            calls: AtomicU32::new(0),
+        });
+        let policy = RetryPolicy {
+            max_retries,
+            base_delay: Duration::ZERO,
+        };
+        (flaky.clone(), RetryingDataSource::new(flaky, policy))
+    }
+
+    #[tokio::test]
+    async fn read_succeeds_after_one_transient_failure() {
+        let (flaky, ds) = wrap(1, 2);
+        assert_eq!(
+            ds.get_row("t", "1").await.unwrap(),
+            serde_json::json!({ "id": 1 })
+        );
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn read_is_retried_after_a_pool_timeout() {
+        let (flaky, ds) = wrap_failing_with(|| anyhow::Error::new(sqlx::Error::PoolTimedOut), 1, 2);
+        assert!(ds.get_row("t", "1").await.is_ok());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn read_gives_up_after_max_retries() {
+        let (flaky, ds) = wrap(5, 2);
+        let err = ds.list_tables().await.unwrap_err();
+        assert_eq!(
+            DataSourceError::classify(&err),
+            Some(DataSourceError::Transient)
+        );
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn writes_are_never_retried() {
+        let (flaky, ds) = wrap(1, 2);
+        assert!(
+            ds.insert_row("t", &serde_json::json!({}), &[], None)
+                .await
+                .is_err()
+        );
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn backoff_doubles_each_attempt() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(50),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(50));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use sqlx::{Postgres, Transaction};
+use tokio::sync::{Mutex, MutexGuard};
+
+/// How long a transaction may stay open between requests before it is
+/// rolled back, unless `TRANSACTION_TIMEOUT_MS` overrides it.
+pub const DEFAULT_TRANSACTION_TIMEOUT_MS: u64 = 60_000;
+
+/// Resolve the lifetime of a held transaction from `TRANSACTION_TIMEOUT_MS`.
+pub fn transaction_timeout() -> Duration {
+    let ms = std::env::var("TRANSACTION_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_MS);
+    Duration::from_millis(ms)
+}
+
+/// How many transactions (and snapshots) one user may hold open on one
+/// connection at a time, unless `MAX_OPEN_TRANSACTIONS` overrides it.
+pub const DEFAULT_MAX_OPEN_TRANSACTIONS: usize = 2;
+
+/// Resolve the per-user, per-connection cap on held transactions from
+/// `MAX_OPEN_TRANSACTIONS`.
+pub fn max_open_transactions() -> usize {
+    std::env::var("MAX_OPEN_TRANSACTIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_OPEN_TRANSACTIONS)
+}
+
+/// A database transaction held open across requests. Writes given this
+/// handle run inside it and stay invisible to other sessions until `commit`.
+/// Only PostgreSQL datasources can open one (see `DataSource::begin`).
+pub struct DataSourceTransaction {
+    tx: Mutex<Option<Transaction<'static, Postgres>>>,
+}
+
+impl DataSourceTransaction {
+    pub(crate) fn postgres(tx: Transaction<'static, Postgres>) -> Self {
+        Self {
+            tx: Mutex::new(Some(tx)),
+        }
+    }
+
+    /// Lock the transaction for one statement; statements on the same
+    /// transaction run one at a time. `None` once committed or rolled back.
+    pub(crate) async fn lock_postgres(
+        &self,
+    ) -> MutexGuard<'_, Option<Transaction<'static, Postgres>>> {
+        self.tx.lock().await
+    }
+
+    /// Make every write done in the transaction permanent.
+    pub async fn commit(&self) -> anyhow::Result<()> {
+        let tx = self.tx.lock().await.take();
+        match tx {
+            Some(tx) => Ok(tx.commit().await?),
+            None => Err(transaction_finished()),
+        }
+    }
+
+    /// Discard every write done in the transaction.
+    pub async fn rollback(&self) -> anyhow::Result<()> {
+        let tx = self.tx.lock().await.take();
+        match tx {
+            Some(tx) => Ok(tx.rollback().await?),
+            None => Err(transaction_finished()),
+        }
+    }
+}
+
+/// Error for a statement, commit or rollback on a finished transaction.
+pub(crate) fn transaction_finished() -> anyhow::Error {
+    anyhow::anyhow!("Transaction is already finished")
+}
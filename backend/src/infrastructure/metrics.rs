@@ -0,0 +1,93 @@
+// Thin facade over the `metrics` crate.
+// Call sites record unconditionally; when the `metrics` feature is disabled
+// every function here compiles to a no-op.
+
+use uuid::Uuid;
+
+use crate::infrastructure::datasource::PoolStatus;
+
+#[cfg(feature = "metrics")]
+mod recorder {
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+    use metrics_util::MetricKindMask;
+
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+    /// Gauges not set for this long are dropped from the output. Live
+    /// connections are reported before every render, so this only drops the
+    /// pool gauges of removed connections.
+    const GAUGE_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Install the global Prometheus recorder (idempotent) and return its handle.
+    pub fn install() -> &'static PrometheusHandle {
+        HANDLE.get_or_init(|| {
+            PrometheusBuilder::new()
+                .idle_timeout(MetricKindMask::GAUGE, Some(GAUGE_IDLE_TIMEOUT))
+                .install_recorder()
+                .expect("Failed to install Prometheus recorder")
+        })
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render() -> String {
+        install().render()
+    }
+}
+
+#[cfg(feature = "metrics")]
+pub use recorder::{install, render};
+
+/// Count a handled HTTP request by method, matched route and status code.
+#[allow(unused_variables)]
+pub fn record_request(method: &str, route: &str, status: u16) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "dbworks_http_requests_total",
+        "method" => method.to_string(),
+        "route" => route.to_string(),
+        "status" => status.to_string()
+    )
+    .increment(1);
+}
+
+/// Count a failed datasource call by error kind (e.g. `timeout`, `other`).
+#[allow(unused_variables)]
+pub fn record_datasource_error(kind: &'static str) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("dbworks_datasource_errors_total", "kind" => kind).increment(1);
+}
+
+/// Report the number of live connections held by the `ConnectionManager`.
+#[allow(unused_variables)]
+pub fn set_live_connections(count: usize) {
+    #[cfg(feature = "metrics")]
+    metrics::gauge!("dbworks_live_connections").set(count as f64);
+}
+
+/// Report pool size and idle connections for a single live connection.
+#[allow(unused_variables)]
+pub fn set_pool_status(conn_id: &Uuid, status: PoolStatus) {
+    #[cfg(feature = "metrics")]
+    {
+        let id = conn_id.to_string();
+        metrics::gauge!("dbworks_pool_connections", "connection_id" => id.clone())
+            .set(status.size as f64);
+        metrics::gauge!("dbworks_pool_idle_connections", "connection_id" => id)
+            .set(status.idle as f64);
+    }
+}
+
+/// Clear the pool gauges of a removed connection. They read zero until the
+/// recorder's idle timeout drops the label set, since nothing reports them again.
+#[allow(unused_variables)]
+pub fn clear_pool_status(conn_id: &Uuid) {
+    #[cfg(feature = "metrics")]
+    {
+        let id = conn_id.to_string();
+        metrics::gauge!("dbworks_pool_connections", "connection_id" => id.clone()).set(0.0);
+        metrics::gauge!("dbworks_pool_idle_connections", "connection_id" => id).set(0.0);
+    }
+}
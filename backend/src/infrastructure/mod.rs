@@ -1,4 +1,7 @@
 pub mod auth;
+pub mod connections_config;
 pub mod crypto;
 pub mod database;
 pub mod datasource;
+pub mod metrics;
+pub mod notifier;
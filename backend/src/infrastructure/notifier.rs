@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::domain::notification::Notifier;
+
+/// Port used when `SMTP_PORT` is unset (submission with STARTTLS).
+pub const DEFAULT_SMTP_PORT: u16 = 587;
+
+/// Sends notifications as plain-text email over SMTP with STARTTLS.
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpNotifier {
+    /// Configure from `SMTP_HOST`, `SMTP_PORT`, `SMTP_USERNAME`,
+    /// `SMTP_PASSWORD` and `SMTP_FROM`. Fails when the host or sender is unset.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let host = std::env::var("SMTP_HOST")
+            .map_err(|_| anyhow::anyhow!("SMTP_HOST environment variable not set"))?;
+        let from: Mailbox = std::env::var("SMTP_FROM")
+            .map_err(|_| anyhow::anyhow!("SMTP_FROM environment variable not set"))?
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid SMTP_FROM: {}", e))?;
+        let port = std::env::var("SMTP_PORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_SMTP_PORT);
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)?.port(port);
+        if let (Ok(user), Ok(password)) = (
+            std::env::var("SMTP_USERNAME"),
+            std::env::var("SMTP_PASSWORD"),
+        ) {
+            builder = builder.credentials(Credentials::new(user, password));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> anyhow::Result<()> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to.parse()?)
+            .subject(subject)
+            .body(body.to_string())?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use axum::http::{HeaderValue, Method};
+use tower_http::cors::{AllowHeaders, Any, CorsLayer};
+
+/// Build the CORS layer from a comma-separated origin allowlist.
+/// With an allowlist, credentials are allowed and only those origins are
+/// echoed back; without one every origin is allowed (dev mode). An allowlist
+/// that is set but names no valid origin, or contains `*`, is an error
+/// rather than a silent fallback to any origin.
+pub fn cors_layer(allowed_origins: Option<&str>) -> anyhow::Result<CorsLayer> {
+    let Some(allowed_origins) = allowed_origins else {
+        tracing::warn!("CORS_ALLOWED_ORIGINS not set; allowing any origin");
+        return Ok(CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods(Any)
+            .allow_headers(Any));
+    };
+
+    let origins = allowed_origins
+        .split(',')
+        .map(str::trim)
+        .filter(|o| !o.is_empty())
+        .map(parse_origin)
+        .collect::<anyhow::Result<Vec<HeaderValue>>>()?;
+    if origins.is_empty() {
+        anyhow::bail!("CORS_ALLOWED_ORIGINS is set but lists no origins");
+    }
+
+    // Wildcards are not allowed alongside credentials, so list methods
+    // explicitly and mirror the requested headers.
+    Ok(CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
+        .allow_headers(AllowHeaders::mirror_request())
+        .allow_credentials(true))
+}
+
+/// Parse one allowlist entry, which must be a `scheme://host[:port]` origin.
+fn parse_origin(origin: &str) -> anyhow::Result<HeaderValue> {
+    if origin == "*" {
+        anyhow::bail!("CORS_ALLOWED_ORIGINS cannot contain '*'; unset it to allow any origin");
+    }
+    let host = origin
+        .strip_prefix("https://")
+        .or_else(|| origin.strip_prefix("http://"))
+        .ok_or_else(|| {
+            anyhow::anyhow!("Invalid CORS origin '{}': expected http(s)://host", origin)
+        })?;
+    if host.is_empty() || host.contains(['/', '*']) {
+        anyhow::bail!("Invalid CORS origin '{}': expected http(s)://host", origin);
+    }
+    HeaderValue::from_str(origin).map_err(|_| anyhow::anyhow!("Invalid CORS origin '{}'", origin))
+}
+
+/// Build the CORS layer from `CORS_ALLOWED_ORIGINS`.
+pub fn cors_layer_from_env() -> anyhow::Result<CorsLayer> {
+    cors_layer(std::env::var("CORS_ALLOWED_ORIGINS").ok().as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, http::Request, routing::get};
+    use tower::ServiceExt;
+
+    async fn preflight(layer: CorsLayer, origin: &str) -> axum::http::HeaderMap {
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(layer);
+        let req = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/")
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", "POST")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(req).await.unwrap().headers().clone()
+    }
+
+    #[tokio::test]
+    async fn allowlist_rejects_unknown_origin() {
+        let layer = cors_layer(Some("https://app.example.com, https://admin.example.com")).unwrap();
+        let headers = preflight(layer.clone(), "https://evil.example.com").await;
+        assert!(headers.get("access-control-allow-origin").is_none());
+
+        let headers = preflight(layer, "https://admin.example.com").await;
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://admin.example.com"
+        );
+        assert_eq!(headers["access-control-allow-credentials"], "true");
+    }
+
+    #[tokio::test]
+    async fn missing_allowlist_allows_any_origin() {
+        let headers = preflight(cors_layer(None).unwrap(), "https://evil.example.com").await;
+        assert_eq!(headers["access-control-allow-origin"], "*");
+    }
+
+    #[test]
+    fn wildcard_origin_is_a_startup_error() {
+        let err = cors_layer(Some("https://app.example.com,*")).unwrap_err();
+        assert!(err.to_string().contains("'*'"));
+    }
+
+    #[test]
+    fn invalid_origins_are_a_startup_error() {
+        for value in [
+            "app.example.com",
+            "https://app.example.com/path",
+            "https://*.example.com",
+            "https://bad\norigin",
+            " , ",
+        ] {
+            assert!(cors_layer(Some(value)).is_err(), "{value:?} was accepted");
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::CreateApiTokenRequest;
+use crate::presentation::state::AppState;
+use crate::usecase;
+
+use super::into_response;
+
+pub async fn create_api_token(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Json(req): Json<CreateApiTokenRequest>,
+) -> impl IntoResponse {
+    tracing::info!(conn_id = %conn_id, name = %req.name, permission = %req.permission, "POST /api/connections/:conn_id/api-tokens");
+
+    match usecase::api_token::create_api_token(
+        &*state.api_token_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        &req.name,
+        &req.permission,
+        req.expires_at,
+    )
+    .await
+    {
+        Ok(created) => (StatusCode::CREATED, Json(serde_json::json!(created))).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn list_api_tokens(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::api_token::list_api_tokens(
+        &*state.api_token_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(tokens) => Json(serde_json::json!(tokens)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn revoke_api_token(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, token_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    tracing::info!(conn_id = %conn_id, token_id = %token_id, "DELETE /api/connections/:conn_id/api-tokens/:token_id");
+
+    match usecase::api_token::revoke_api_token(&*state.api_token_repo, &caller, &conn_id, &token_id)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{Uri, header},
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::{AuditQuery, QueryLogQuery};
+use crate::presentation::state::AppState;
+use crate::usecase;
+
+use super::{into_response, pagination_links};
+
+pub async fn list_audit_log(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Query(query): Query<AuditQuery>,
+    uri: Uri,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "GET /api/connections/:conn_id/audit");
+
+    match usecase::audit::list_audit_log(
+        &*state.audit_repo,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        &query,
+    )
+    .await
+    {
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn list_query_log(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Query(query): Query<QueryLogQuery>,
+    uri: Uri,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "GET /api/connections/:conn_id/query-log");
+
+    match usecase::audit::list_query_log(&*state.query_log_repo, &caller, &conn_id, &query).await {
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
+        Err(e) => into_response(e),
+    }
+}
@@ -1,42 +1,37 @@
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    http::StatusCode,
     response::IntoResponse,
 };
+use schemars::JsonSchema;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::presentation::middleware::get_current_user;
-use crate::presentation::request::ConnectionRequest;
+use crate::domain::connection::ConnectionSettings;
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::{
+    ConnectionRequest, ImportConnectionsRequest, PreviewConnectionRequest, RotatePasswordRequest,
+    SetConnectionReadOnlyRequest, SetConnectionTagsRequest, TransferConnectionRequest,
+};
 use crate::presentation::state::AppState;
 use crate::usecase;
 
 use super::into_response;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ConnectionListParams {
     pub scope: Option<String>,
+    pub tag: Option<String>,
+    pub search: Option<String>,
 }
 
 pub async fn create_connection(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Json(req): Json<ConnectionRequest>,
 ) -> impl IntoResponse {
-    let default_port = match req.db_type.as_str() {
-        "mysql" => 3306,
-        _ => 5432,
-    };
-    let port = req.port.unwrap_or(default_port);
-    tracing::info!(name = %req.name, db_type = %req.db_type, host = %req.host, port = port, database = %req.database, scope = ?req.scope, "POST /api/connections");
-
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
+    tracing::info!(name = %req.name, db_type = %req.db_type, host = %req.host, port = ?req.port, database = %req.database, scope = ?req.scope, "POST /api/connections");
 
     // Parse scope to determine organization_id
     let organization_id = match req.scope.as_deref() {
@@ -60,11 +55,26 @@ pub async fn create_connection(
         req.name,
         req.db_type,
         req.host,
-        port,
+        req.port,
         req.database,
         req.user,
         req.password,
         organization_id,
+        ConnectionSettings {
+            tags: req.tags,
+            statement_timeout_ms: req.statement_timeout_ms,
+            pool_idle_timeout_ms: req.pool_idle_timeout_ms,
+            pool_max_lifetime_ms: req.pool_max_lifetime_ms,
+            read_only: req.read_only,
+            default_page_size: req.default_page_size,
+            default_sort_order: req.default_sort_order,
+            search_path: req.search_path,
+            default_schema: req.default_schema,
+            role: req.role,
+            hidden_tables: req.hidden_tables,
+            replica_hosts: req.replica_hosts,
+        },
+        req.default_member_permission.as_deref(),
     )
     .await
     {
@@ -78,22 +88,18 @@ pub async fn create_connection(
 
 pub async fn list_connections(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Query(params): Query<ConnectionListParams>,
 ) -> impl IntoResponse {
-    tracing::debug!(scope = ?params.scope, "GET /api/connections");
-
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
+    tracing::debug!(scope = ?params.scope, tag = ?params.tag, search = ?params.search, "GET /api/connections");
 
     match usecase::connection::list_connections(
         &state.connection_manager,
+        &*state.permission_repo,
         &caller,
         params.scope.as_deref(),
+        params.tag.as_deref(),
+        params.search.as_deref(),
     )
     .await
     {
@@ -104,18 +110,11 @@ pub async fn list_connections(
 
 pub async fn delete_connection(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(conn_id): Path<Uuid>,
 ) -> impl IntoResponse {
     tracing::info!(connection_id = %conn_id, "DELETE /api/connections/:conn_id");
 
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
-
     match usecase::connection::delete_connection(
         &state.connection_manager,
         &*state.org_member_repo,
@@ -129,3 +128,231 @@ pub async fn delete_connection(
         Err(e) => into_response(e),
     }
 }
+
+pub async fn get_connection_stats(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "GET /api/connections/:conn_id/stats");
+
+    match usecase::connection::get_connection_stats(
+        &state.connection_manager,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(stats) => Json(serde_json::json!(stats)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn get_deletion_impact(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "GET /api/connections/:conn_id/deletion-impact");
+
+    match usecase::connection::get_deletion_impact(
+        &*state.conn_repo,
+        &*state.permission_repo,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(impact) => Json(serde_json::json!(impact)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn list_slow_queries(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "GET /api/connections/:conn_id/slow-queries");
+
+    match usecase::connection::list_slow_queries(
+        &state.connection_manager,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(queries) => Json(serde_json::json!(queries)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn set_connection_tags(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Json(req): Json<SetConnectionTagsRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, tags = ?req.tags, "PUT /api/connections/:conn_id/tags");
+
+    match usecase::connection::set_connection_tags(
+        &state.connection_manager,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        req.tags,
+    )
+    .await
+    {
+        Ok(tags) => Json(serde_json::json!({ "tags": tags })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn set_connection_read_only(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Json(req): Json<SetConnectionReadOnlyRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, read_only = req.read_only, "PUT /api/connections/:conn_id/read-only");
+
+    match usecase::connection::set_connection_read_only(
+        &state.connection_manager,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        req.read_only,
+    )
+    .await
+    {
+        Ok(read_only) => Json(serde_json::json!({ "read_only": read_only })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn rotate_connection_password(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Json(req): Json<RotatePasswordRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "POST /api/connections/:conn_id/rotate-password");
+
+    match usecase::connection::rotate_connection_password(
+        &state.connection_manager,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        req.password,
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn transfer_connection(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Json(req): Json<TransferConnectionRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, new_owner = %req.new_owner, "POST /api/connections/:conn_id/transfer");
+
+    match usecase::connection::transfer_connection(
+        &state.connection_manager,
+        &*state.user_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        &req.new_owner,
+    )
+    .await
+    {
+        Ok(owner_user_id) => {
+            Json(serde_json::json!({ "owner_user_id": owner_user_id })).into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+/// List a database's tables without saving a connection to it.
+pub async fn preview_connection_tables(
+    State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
+    Json(req): Json<PreviewConnectionRequest>,
+) -> impl IntoResponse {
+    tracing::info!(db_type = %req.db_type, host = %req.host, port = ?req.port, database = %req.database, "POST /api/connections/test/tables");
+
+    match usecase::connection::preview_connection_tables(
+        &state.connection_manager,
+        req.db_type,
+        req.host,
+        req.port,
+        req.database,
+        req.user,
+        req.password,
+        req.search_path,
+        req.role,
+    )
+    .await
+    {
+        Ok(tables) => Json(serde_json::json!(tables)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn export_connections(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+) -> impl IntoResponse {
+    tracing::info!("GET /api/connections/export");
+
+    match usecase::connection::export_connections(&*state.conn_repo, &caller).await {
+        Ok(connections) => Json(serde_json::json!(connections)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn reload_connections(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+) -> impl IntoResponse {
+    tracing::info!("POST /api/admin/connections/reload");
+
+    match usecase::connection::reload_connections(&state.connection_manager, &caller).await {
+        Ok(results) => Json(serde_json::json!(results)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn import_connections(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Json(req): Json<ImportConnectionsRequest>,
+) -> impl IntoResponse {
+    tracing::info!(
+        count = req.connections.len(),
+        "POST /api/connections/import"
+    );
+
+    match usecase::connection::import_connections(
+        &state.connection_manager,
+        &caller,
+        req.connections,
+    )
+    .await
+    {
+        Ok(results) => Json(serde_json::json!(results)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
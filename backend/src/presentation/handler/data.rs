@@ -1,17 +1,33 @@
+use std::convert::Infallible;
+
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode, Uri, header},
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
+use sha2::{Digest, Sha256};
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::BroadcastStream;
 use uuid::Uuid;
 
-use crate::presentation::middleware::get_current_user;
-use crate::presentation::request::RowsQuery;
+use crate::domain::audit::PendingAuditEntry;
+use crate::domain::data::RowChange;
+use crate::domain::user::AppUser;
+use crate::infrastructure::datasource::{filter_column, transaction_timeout};
+use crate::presentation::idempotency::{Claim, IdempotencyStore};
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::{
+    AggregateRequest, BulkDeleteQuery, BulkUpdateRequest, ColumnMetadataRequest, CountQuery,
+    CreateRowQuery, DistinctQuery, DryRunQuery, RowsQuery, TableMetadataRequest,
+};
 use crate::presentation::state::AppState;
 use crate::usecase;
 
-use super::into_response;
+use super::{into_response, pagination_links};
 
 // ============================================================
 // Table Introspection
@@ -19,20 +35,14 @@ use super::into_response;
 
 pub async fn list_tables(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(conn_id): Path<Uuid>,
 ) -> impl IntoResponse {
     tracing::info!(connection_id = %conn_id, "GET /api/connections/:conn_id/tables");
 
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
-
     match usecase::data::list_tables(
         &*state.permission_repo,
+        &*state.table_metadata_repo,
         &state.connection_manager,
         &caller,
         &conn_id,
@@ -44,22 +54,127 @@ pub async fn list_tables(
     }
 }
 
+pub async fn refresh_schema(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "POST refresh-schema");
+
+    match usecase::data::refresh_schema(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn get_table_capabilities(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET capabilities");
+
+    match usecase::data::get_table_capabilities(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+    )
+    .await
+    {
+        Ok(capabilities) => Json(serde_json::json!(capabilities)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn get_table_metadata(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET metadata");
+
+    match usecase::data::get_table_metadata(
+        &*state.permission_repo,
+        &*state.table_metadata_repo,
+        &caller,
+        &conn_id,
+        &table,
+    )
+    .await
+    {
+        Ok(metadata) => Json(serde_json::json!(metadata)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn set_table_metadata(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Json(req): Json<TableMetadataRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "PUT metadata");
+
+    match usecase::data::set_table_metadata(
+        &*state.permission_repo,
+        &*state.table_metadata_repo,
+        &caller,
+        &conn_id,
+        &table,
+        req.display_name.as_deref(),
+        req.description.as_deref(),
+    )
+    .await
+    {
+        Ok(metadata) => Json(serde_json::json!(metadata)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn set_column_metadata(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table, column)): Path<(Uuid, String, String)>,
+    Json(req): Json<ColumnMetadataRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, column = %column, "PUT column metadata");
+
+    match usecase::data::set_column_metadata(
+        &*state.permission_repo,
+        &*state.table_metadata_repo,
+        &caller,
+        &conn_id,
+        &table,
+        &column,
+        req.description.as_deref(),
+        req.display_hint.as_deref(),
+    )
+    .await
+    {
+        Ok(metadata) => Json(serde_json::json!(metadata)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
 pub async fn get_table_schema(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, table)): Path<(Uuid, String)>,
 ) -> impl IntoResponse {
     tracing::info!(connection_id = %conn_id, table = %table, "GET schema");
 
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
-
     match usecase::data::get_table_schema(
         &*state.permission_repo,
+        &*state.table_metadata_repo,
         &state.connection_manager,
         &caller,
         &conn_id,
@@ -72,23 +187,103 @@ pub async fn get_table_schema(
     }
 }
 
+pub async fn get_table_json_schema(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET json-schema");
+
+    match usecase::data::get_table_schema(
+        &*state.permission_repo,
+        &*state.table_metadata_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+    )
+    .await
+    {
+        Ok(schema) => Json(schema.to_json_schema()).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn get_table_insert_defaults(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET insert-defaults");
+
+    match usecase::data::get_table_schema(
+        &*state.permission_repo,
+        &*state.table_metadata_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+    )
+    .await
+    {
+        Ok(schema) => Json(schema.insert_defaults()).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+/// SSE frame for a row change: the operation is the event type and the row is the data.
+fn row_change_event(change: &RowChange) -> Event {
+    Event::default()
+        .event(&change.operation)
+        .data(change.row.to_string())
+}
+
+pub async fn subscribe_table(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET subscribe");
+
+    match usecase::data::subscribe_table(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+    )
+    .await
+    {
+        Ok(rx) => {
+            // Lagging subscribers skip the events they missed
+            let events = BroadcastStream::new(rx)
+                .filter_map(|change| change.ok())
+                .map(|change| Ok::<_, Infallible>(row_change_event(&change)));
+            Sse::new(events)
+                .keep_alive(KeepAlive::default())
+                .into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
 // ============================================================
 // Row CRUD
 // ============================================================
 
 pub async fn list_rows(
     State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
     headers: HeaderMap,
     Path((conn_id, table)): Path<(Uuid, String)>,
     Query(query): Query<RowsQuery>,
+    uri: Uri,
 ) -> impl IntoResponse {
     tracing::info!(connection_id = %conn_id, table = %table, "GET rows");
 
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
+    let transaction = match transaction_header(&headers) {
+        Ok(token) => token,
+        Err(e) => return into_response(e),
     };
 
     match usecase::data::list_rows(
@@ -98,29 +293,173 @@ pub async fn list_rows(
         &conn_id,
         &table,
         &query,
+        transaction.as_ref(),
+    )
+    .await
+    {
+        Ok(response) => {
+            let mut resp = Json(serde_json::json!(response)).into_response();
+            if let Some(link) =
+                pagination_links(&uri, response.page, response.per_page, response.total_count)
+            {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn explain_rows(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Query(query): Query<RowsQuery>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET explain");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::explain_rows(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        &query,
+    )
+    .await
+    {
+        Ok(plan) => Json(serde_json::json!({ "plan": plan })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn count_rows(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Query(query): Query<CountQuery>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "GET count");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::count_rows(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        query.filter.as_deref(),
+    )
+    .await
+    {
+        Ok(count) => Json(serde_json::json!({ "count": count })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn distinct_values(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table, column)): Path<(Uuid, String, String)>,
+    Query(query): Query<DistinctQuery>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, column = %column, "GET distinct");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::distinct_values(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        &column,
+        query.limit,
+    )
+    .await
+    {
+        Ok(values) => Json(serde_json::json!(values)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn aggregate(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Json(req): Json<AggregateRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, "POST aggregate");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::aggregate(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        &req,
     )
     .await
     {
-        Ok(response) => Json(serde_json::json!(response)).into_response(),
+        Ok(rows) => Json(serde_json::json!(rows)).into_response(),
         Err(e) => into_response(e),
     }
 }
 
 pub async fn create_row(
     State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
     headers: HeaderMap,
     Path((conn_id, table)): Path<(Uuid, String)>,
+    Query(query): Query<CreateRowQuery>,
     Json(data): Json<serde_json::Value>,
 ) -> impl IntoResponse {
     tracing::info!(connection_id = %conn_id, table = %table, "POST row");
 
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
+    let transaction = match transaction_header(&headers) {
+        Ok(token) => token,
+        Err(e) => return into_response(e),
     };
 
+    // A repeated Idempotency-Key replays the original row instead of inserting again.
+    let idempotency_key = headers
+        .get("Idempotency-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(|key| IdempotencyStore::scoped_key(&caller.id, &conn_id, &table, key));
+    let mut claim = None;
+    if let Some(key) = &idempotency_key {
+        match state.idempotency.claim(key) {
+            Claim::New(guard) => claim = Some(guard),
+            Claim::Replay(row) => return (StatusCode::CREATED, Json(row)).into_response(),
+            Claim::InProgress => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(serde_json::json!({
+                        "error": "A request with this Idempotency-Key is still in progress"
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
     match usecase::data::create_row(
         &*state.permission_repo,
         &state.connection_manager,
@@ -128,25 +467,42 @@ pub async fn create_row(
         &conn_id,
         &table,
         &data,
+        query.validate,
+        &query.returning_list(),
+        transaction.as_ref(),
     )
     .await
     {
-        Ok(row) => (StatusCode::CREATED, Json(row)).into_response(),
+        Ok(row) => {
+            if let Some(guard) = claim {
+                guard.complete(row.clone());
+            }
+            record_audit(
+                &state,
+                &caller,
+                &conn_id,
+                transaction.as_ref(),
+                "insert",
+                &table,
+                None,
+            )
+            .await;
+            (StatusCode::CREATED, Json(row)).into_response()
+        }
+        // Dropping the claim releases the key so that a retry runs again.
         Err(e) => into_response(e),
     }
 }
 
 pub async fn get_row(
     State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
     headers: HeaderMap,
     Path((conn_id, table, pk)): Path<(Uuid, String, String)>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
 
     match usecase::data::get_row(
         &*state.permission_repo,
@@ -158,22 +514,84 @@ pub async fn get_row(
     )
     .await
     {
-        Ok(row) => Json(row).into_response(),
+        Ok(row) => {
+            let body = match serde_json::to_vec(&row) {
+                Ok(body) => body,
+                Err(e) => return into_response(usecase::UsecaseError::Internal(e.to_string())),
+            };
+            let etag = row_etag(&body);
+            if if_none_match(&headers, &etag) {
+                return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+            }
+            (
+                [
+                    (header::CONTENT_TYPE, "application/json".to_string()),
+                    (header::ETAG, etag),
+                ],
+                body,
+            )
+                .into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+/// Strong ETag for a serialized row: a SHA-256 of the exact response body,
+/// so it changes whenever any returned value does.
+fn row_etag(body: &[u8]) -> String {
+    format!("\"{:x}\"", Sha256::digest(body))
+}
+
+/// Whether `If-None-Match` lists `etag` (or `*`). Weak validators compare
+/// equal to their strong form, as GET allows.
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag)
+}
+
+pub async fn get_rows(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Json(pks): Json<Vec<String>>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, count = pks.len(), "POST batch-get");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::get_rows(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        &pks,
+    )
+    .await
+    {
+        Ok(rows) => Json(serde_json::json!(rows)).into_response(),
         Err(e) => into_response(e),
     }
 }
 
 pub async fn update_row(
     State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
     headers: HeaderMap,
     Path((conn_id, table, pk)): Path<(Uuid, String, String)>,
     Json(data): Json<serde_json::Value>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
+    let transaction = match transaction_header(&headers) {
+        Ok(token) => token,
+        Err(e) => return into_response(e),
     };
 
     match usecase::data::update_row(
@@ -184,24 +602,38 @@ pub async fn update_row(
         &table,
         &pk,
         &data,
+        transaction.as_ref(),
     )
     .await
     {
-        Ok(row) => Json(row).into_response(),
+        Ok(row) => {
+            let details = serde_json::json!({ "pk": pk });
+            record_audit(
+                &state,
+                &caller,
+                &conn_id,
+                transaction.as_ref(),
+                "update",
+                &table,
+                Some(&details),
+            )
+            .await;
+            Json(row).into_response()
+        }
         Err(e) => into_response(e),
     }
 }
 
 pub async fn delete_row(
     State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
     headers: HeaderMap,
     Path((conn_id, table, pk)): Path<(Uuid, String, String)>,
+    Query(query): Query<DryRunQuery>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
+    let transaction = match transaction_header(&headers) {
+        Ok(token) => token,
+        Err(e) => return into_response(e),
     };
 
     match usecase::data::delete_row(
@@ -211,10 +643,349 @@ pub async fn delete_row(
         &conn_id,
         &table,
         &pk,
+        query.dry_run,
+        transaction.as_ref(),
     )
     .await
+    {
+        Ok(Some(affected)) => Json(serde_json::json!({
+            "dry_run": true,
+            "affected_rows": affected,
+        }))
+        .into_response(),
+        Ok(None) => {
+            let details = serde_json::json!({ "pk": pk });
+            record_audit(
+                &state,
+                &caller,
+                &conn_id,
+                transaction.as_ref(),
+                "delete",
+                &table,
+                Some(&details),
+            )
+            .await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn bulk_update_rows(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Query(query): Query<DryRunQuery>,
+    Json(req): Json<BulkUpdateRequest>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, filter = ?req.filter.as_deref().map(filter_column), dry_run = query.dry_run, "PATCH rows");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::bulk_update_rows(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        req.filter.as_deref(),
+        &req.data,
+        query.dry_run,
+    )
+    .await
+    {
+        Ok(affected) => {
+            if !query.dry_run {
+                let details =
+                    serde_json::json!({ "filter": req.filter, "affected_rows": affected });
+                record_audit(
+                    &state,
+                    &caller,
+                    &conn_id,
+                    None,
+                    "bulk_update",
+                    &table,
+                    Some(&details),
+                )
+                .await;
+            }
+            Json(serde_json::json!({
+                "dry_run": query.dry_run,
+                "affected_rows": affected,
+            }))
+            .into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn bulk_delete_rows(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    headers: HeaderMap,
+    Path((conn_id, table)): Path<(Uuid, String)>,
+    Query(query): Query<BulkDeleteQuery>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, table = %table, filter = ?query.filter.as_deref().map(filter_column), dry_run = query.dry_run, "DELETE rows");
+
+    if let Err(e) = refuse_transaction_header(&headers) {
+        return into_response(e);
+    }
+
+    match usecase::data::bulk_delete_rows(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+        &table,
+        query.filter.as_deref(),
+        query.dry_run,
+    )
+    .await
+    {
+        Ok(affected) => {
+            if !query.dry_run {
+                let details =
+                    serde_json::json!({ "filter": query.filter, "affected_rows": affected });
+                record_audit(
+                    &state,
+                    &caller,
+                    &conn_id,
+                    None,
+                    "bulk_delete",
+                    &table,
+                    Some(&details),
+                )
+                .await;
+            }
+            Json(serde_json::json!({
+                "dry_run": query.dry_run,
+                "affected_rows": affected,
+            }))
+            .into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+// ============================================================
+// Transactions
+// ============================================================
+
+pub async fn begin_transaction(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "POST /api/connections/:conn_id/transactions");
+
+    match usecase::data::begin_transaction(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(token) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "transaction_id": token,
+                "expires_in_ms": transaction_timeout().as_millis() as u64,
+            })),
+        )
+            .into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn begin_snapshot(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(connection_id = %conn_id, "POST /api/connections/:conn_id/snapshots");
+
+    match usecase::data::begin_snapshot(
+        &*state.permission_repo,
+        &state.connection_manager,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(token) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "transaction_id": token,
+                "expires_in_ms": transaction_timeout().as_millis() as u64,
+            })),
+        )
+            .into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn commit_transaction(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, tx_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match usecase::data::commit_transaction(&state.connection_manager, &caller, &conn_id, &tx_id)
+        .await
+    {
+        Ok(audit) => {
+            for entry in &audit {
+                write_audit(&state, entry).await;
+            }
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn rollback_transaction(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, tx_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match usecase::data::rollback_transaction(&state.connection_manager, &caller, &conn_id, &tx_id)
+        .await
     {
         Ok(()) => StatusCode::NO_CONTENT.into_response(),
         Err(e) => into_response(e),
     }
 }
+
+/// The `X-Transaction-Id` a row read or write should run in, if any.
+fn transaction_header(headers: &HeaderMap) -> Result<Option<Uuid>, usecase::UsecaseError> {
+    headers
+        .get("X-Transaction-Id")
+        .map(|v| {
+            v.to_str()
+                .ok()
+                .and_then(|v| Uuid::parse_str(v.trim()).ok())
+                .ok_or_else(|| {
+                    usecase::UsecaseError::BadRequest(
+                        "X-Transaction-Id must be a transaction UUID".to_string(),
+                    )
+                })
+        })
+        .transpose()
+}
+
+/// Refuse `X-Transaction-Id` on a route that cannot run inside a held
+/// transaction, rather than silently running (and committing) outside it.
+fn refuse_transaction_header(headers: &HeaderMap) -> Result<(), usecase::UsecaseError> {
+    if headers.contains_key("X-Transaction-Id") {
+        return Err(usecase::UsecaseError::BadRequest(
+            "X-Transaction-Id is not supported on this route".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Append a data change to the audit log. A change made in a held
+/// transaction is only deferred to it, and recorded when it commits.
+async fn record_audit(
+    state: &AppState,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    transaction: Option<&Uuid>,
+    action: &str,
+    table: &str,
+    details: Option<&serde_json::Value>,
+) {
+    // The real admin or token issuer is the actor; the impersonated user or
+    // the token goes in the details
+    let via = if caller.acting_admin_id.is_some() {
+        Some("acting_as")
+    } else if caller.api_token_scope.is_some() {
+        Some("api_token_id")
+    } else {
+        None
+    };
+    let details = match via {
+        Some(key) => {
+            let mut details = details.cloned().unwrap_or_else(|| serde_json::json!({}));
+            if let Some(obj) = details.as_object_mut() {
+                obj.insert(key.to_string(), serde_json::json!(caller.id));
+            }
+            Some(details)
+        }
+        None => details.cloned(),
+    };
+    let entry = PendingAuditEntry {
+        connection_id: *conn_id,
+        actor_id: caller.actor_id(),
+        action: action.to_string(),
+        table_name: table.to_string(),
+        details,
+    };
+    match transaction {
+        Some(token) => state.connection_manager.defer_audit(token, entry),
+        None => write_audit(state, &entry).await,
+    }
+}
+
+/// Write an audit entry. Failures are logged, not surfaced: the change
+/// itself has already been committed.
+async fn write_audit(state: &AppState, entry: &PendingAuditEntry) {
+    if let Err(e) = state
+        .audit_repo
+        .record(
+            &entry.connection_id,
+            &entry.actor_id,
+            &entry.action,
+            Some(&entry.table_name),
+            entry.details.as_ref(),
+        )
+        .await
+    {
+        tracing::error!(error = %e, action = %entry.action, "Failed to record audit entry");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::datasource::parse_notification;
+    use http_body_util::BodyExt;
+
+    #[test]
+    fn if_none_match_accepts_lists_weak_tags_and_wildcard() {
+        let etag = row_etag(br#"{"id":1}"#);
+        let with = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::IF_NONE_MATCH, value.parse().unwrap());
+            headers
+        };
+
+        assert!(if_none_match(&with(&etag), &etag));
+        assert!(if_none_match(
+            &with(&format!("\"other\", W/{}", etag)),
+            &etag
+        ));
+        assert!(if_none_match(&with("*"), &etag));
+        assert!(!if_none_match(&with("\"other\""), &etag));
+        assert!(!if_none_match(&HeaderMap::new(), &etag));
+        assert_ne!(etag, row_etag(br#"{"id":2}"#));
+    }
+
+    #[tokio::test]
+    async fn notification_is_framed_as_sse_event() {
+        let change = parse_notification("orders", r#"{"id":1,"note":"hi"}"#);
+        let events = tokio_stream::iter([Ok::<_, Infallible>(row_change_event(&change))]);
+        let resp = Sse::new(events).into_response();
+        assert_eq!(resp.headers()["content-type"], "text/event-stream");
+
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(
+            std::str::from_utf8(&body).unwrap(),
+            "event: insert\ndata: {\"id\":1,\"note\":\"hi\"}\n\n"
+        );
+    }
+}
@@ -1,13 +1,16 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    http::StatusCode,
     response::IntoResponse,
 };
 use uuid::Uuid;
 
-use crate::presentation::middleware::get_current_user;
-use crate::presentation::request::{AddGroupMemberRequest, CreateGroupRequest};
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::{
+    AddGroupMemberRequest, BulkGroupMembersRequest, CreateGroupRequest, SetGroupParentRequest,
+    UpdateGroupRequest,
+};
 use crate::presentation::state::AppState;
 use crate::usecase;
 
@@ -15,17 +18,11 @@ use super::into_response;
 
 pub async fn create_group(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(org_id): Path<Uuid>,
     Json(req): Json<CreateGroupRequest>,
 ) -> impl IntoResponse {
     tracing::info!(org_id = %org_id, name = %req.name, "POST /api/organizations/:org_id/groups");
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
 
     match usecase::group::create_group(
         &*state.group_repo,
@@ -44,6 +41,7 @@ pub async fn create_group(
 
 pub async fn list_groups(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
     Path(org_id): Path<Uuid>,
 ) -> impl IntoResponse {
     match usecase::group::list_groups(&*state.group_repo, &org_id).await {
@@ -52,19 +50,78 @@ pub async fn list_groups(
     }
 }
 
+pub async fn update_group(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(group_id): Path<Uuid>,
+    Json(req): Json<UpdateGroupRequest>,
+) -> impl IntoResponse {
+    tracing::info!(group_id = %group_id, "PATCH /api/groups/:group_id");
+
+    match usecase::group::update_group(
+        &*state.group_repo,
+        &*state.org_member_repo,
+        &caller,
+        &group_id,
+        req.name.as_deref(),
+        req.description.as_deref(),
+    )
+    .await
+    {
+        Ok(group) => Json(serde_json::json!(group)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn delete_group(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(group_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(group_id = %group_id, "DELETE /api/groups/:group_id");
+
+    match usecase::group::delete_group(
+        &*state.group_repo,
+        &*state.org_member_repo,
+        &caller,
+        &group_id,
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn set_group_parent(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(group_id): Path<Uuid>,
+    Json(req): Json<SetGroupParentRequest>,
+) -> impl IntoResponse {
+    tracing::info!(group_id = %group_id, parent_group_id = ?req.parent_group_id, "PUT /api/groups/:group_id/parent");
+
+    match usecase::group::set_group_parent(
+        &*state.group_repo,
+        &*state.org_member_repo,
+        &caller,
+        &group_id,
+        req.parent_group_id.as_ref(),
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
 pub async fn add_group_member(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(group_id): Path<Uuid>,
     Json(req): Json<AddGroupMemberRequest>,
 ) -> impl IntoResponse {
     tracing::info!(group_id = %group_id, user_id = %req.user_id, "POST /api/groups/:group_id/members");
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
 
     match usecase::group::add_group_member(
         &*state.group_repo,
@@ -75,23 +132,60 @@ pub async fn add_group_member(
     )
     .await
     {
-        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Ok(true) => StatusCode::CREATED.into_response(),
+        Ok(false) => StatusCode::OK.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn add_group_members_bulk(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(group_id): Path<Uuid>,
+    Json(req): Json<BulkGroupMembersRequest>,
+) -> impl IntoResponse {
+    tracing::info!(group_id = %group_id, count = req.user_ids.len(), "POST /api/groups/:group_id/members/bulk");
+
+    match usecase::group::add_group_members_bulk(
+        &*state.group_repo,
+        &caller,
+        &group_id,
+        &req.user_ids,
+    )
+    .await
+    {
+        Ok(added) => Json(serde_json::json!({ "added": added })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn remove_group_members_bulk(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(group_id): Path<Uuid>,
+    Json(req): Json<BulkGroupMembersRequest>,
+) -> impl IntoResponse {
+    tracing::info!(group_id = %group_id, count = req.user_ids.len(), "DELETE /api/groups/:group_id/members/bulk");
+
+    match usecase::group::remove_group_members_bulk(
+        &*state.group_repo,
+        &caller,
+        &group_id,
+        &req.user_ids,
+    )
+    .await
+    {
+        Ok(removed) => Json(serde_json::json!({ "removed": removed })).into_response(),
         Err(e) => into_response(e),
     }
 }
 
 pub async fn remove_group_member(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((group_id, user_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
     tracing::info!(group_id = %group_id, user_id = %user_id, "DELETE /api/groups/:group_id/members/:user_id");
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
 
     match usecase::group::remove_group_member(
         &*state.group_repo,
@@ -114,6 +208,7 @@ pub async fn remove_group_member(
 
 pub async fn list_group_members(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
     Path(group_id): Path<Uuid>,
 ) -> impl IntoResponse {
     match usecase::group::list_group_members(&*state.group_repo, &group_id).await {
@@ -121,3 +216,24 @@ pub async fn list_group_members(
         Err(e) => into_response(e),
     }
 }
+
+pub async fn list_user_groups(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    tracing::info!(org_id = %org_id, user_id = %user_id, "GET /api/organizations/:org_id/users/:user_id/groups");
+
+    match usecase::group::list_user_groups(
+        &*state.group_repo,
+        &*state.org_member_repo,
+        &caller,
+        &org_id,
+        &user_id,
+    )
+    .await
+    {
+        Ok(groups) => Json(serde_json::json!(groups)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
@@ -0,0 +1,25 @@
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::presentation::state::AppState;
+
+/// GET /healthz — liveness probe. Always 200 while the process is serving.
+pub async fn healthz() -> impl IntoResponse {
+    (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
+}
+
+/// GET /readyz — readiness probe. 503 when the app database is unreachable.
+pub async fn readyz(State(state): State<AppState>) -> impl IntoResponse {
+    match sqlx::query("SELECT 1").execute(&state.pool).await {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "status": "ready", "database": "ok" })),
+        ),
+        Err(e) => {
+            tracing::warn!(error = %e, "Readiness check failed: app database unreachable");
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({ "status": "unavailable", "database": "unreachable" })),
+            )
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use axum::{
+    extract::State,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+
+use crate::infrastructure::metrics;
+use crate::presentation::state::AppState;
+
+/// GET /metrics — Prometheus text exposition format.
+/// Unauthenticated so scrapers can reach it; keep it off public ingress.
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    state.connection_manager.report_metrics().await;
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::render(),
+    )
+}
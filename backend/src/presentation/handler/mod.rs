@@ -1,25 +1,107 @@
-use axum::{Json, http::StatusCode, response::IntoResponse};
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, Uri, header},
+    response::IntoResponse,
+};
 
 use crate::usecase::UsecaseError;
 
+pub mod api_token;
+pub mod audit;
 pub mod connection;
 pub mod data;
 pub mod group;
+pub mod health;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod openapi;
 pub mod organization;
 pub mod permission;
+pub mod permission_template;
 pub mod user;
 
+/// Seconds a client is asked to wait (`Retry-After`) before retrying a 503.
+const RETRY_AFTER_SECS: u64 = 1;
+
 /// Map a `UsecaseError` to an HTTP response.
 pub fn into_response(err: UsecaseError) -> axum::response::Response {
-    let (status, message) = match &err {
-        UsecaseError::Unauthorized => (StatusCode::UNAUTHORIZED, err.to_string()),
-        UsecaseError::Forbidden(_) => (StatusCode::FORBIDDEN, err.to_string()),
-        UsecaseError::NotFound(_) => (StatusCode::NOT_FOUND, err.to_string()),
-        UsecaseError::BadRequest(_) => (StatusCode::BAD_REQUEST, err.to_string()),
-        UsecaseError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    let status = match &err {
+        UsecaseError::Unauthorized => StatusCode::UNAUTHORIZED,
+        UsecaseError::Forbidden(_) | UsecaseError::Denied { .. } => StatusCode::FORBIDDEN,
+        UsecaseError::NotFound(_) => StatusCode::NOT_FOUND,
+        UsecaseError::BadRequest(_) | UsecaseError::Invalid(_) => StatusCode::BAD_REQUEST,
+        UsecaseError::Conflict(_) => StatusCode::CONFLICT,
+        UsecaseError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+        UsecaseError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        UsecaseError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    tracing::error!(error = %err, "Usecase error");
+    let body = match &err {
+        UsecaseError::Invalid(problems) => {
+            serde_json::json!({ "error": "Validation failed", "problems": problems })
+        }
+        UsecaseError::Denied { message, reason } => {
+            serde_json::json!({ "error": message, "reason": reason })
+        }
+        _ => serde_json::json!({ "error": err.to_string() }),
+    };
+    if matches!(err, UsecaseError::Unavailable(_)) {
+        return (
+            status,
+            [(header::RETRY_AFTER, RETRY_AFTER_SECS.to_string())],
+            Json(body),
+        )
+            .into_response();
+    }
+    (status, Json(body)).into_response()
+}
+
+/// Build an RFC 5988 `Link` header (`first`, `prev`, `next`, `last`) for a
+/// paginated response, keeping the request's other query parameters.
+/// `prev` is omitted on the first page and `next` on the last.
+pub fn pagination_links(
+    uri: &Uri,
+    page: u32,
+    per_page: u32,
+    total_count: i64,
+) -> Option<HeaderValue> {
+    if per_page == 0 {
+        return None;
+    }
+    let last = ((total_count.max(0) as u64).div_ceil(per_page as u64)).max(1) as u32;
+    let params: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|p| {
+            let key = p.split('=').next().unwrap_or_default();
+            !p.is_empty() && key != "page" && key != "per_page"
+        })
+        .collect();
+    let link = |target: u32, rel: &str| {
+        let mut query = params.join("&");
+        if !query.is_empty() {
+            query.push('&');
+        }
+        format!(
+            "<{}?{}page={}&per_page={}>; rel=\"{}\"",
+            uri.path(),
+            query,
+            target,
+            per_page,
+            rel
+        )
     };
-    tracing::error!(error = %message, "Usecase error");
-    (status, Json(serde_json::json!({ "error": message }))).into_response()
+
+    let mut links = vec![link(1, "first")];
+    if page > 1 {
+        links.push(link(page.min(last + 1) - 1, "prev"));
+    }
+    if page < last {
+        links.push(link(page + 1, "next"));
+    }
+    links.push(link(last, "last"));
+    HeaderValue::from_str(&links.join(", ")).ok()
 }
 
 #[cfg(test)]
@@ -50,6 +132,18 @@ mod tests {
         assert_eq!(status, StatusCode::FORBIDDEN);
     }
 
+    #[tokio::test]
+    async fn denied_includes_reason_in_403_body() {
+        let err = || UsecaseError::Denied {
+            message: "No access to this table".into(),
+            reason: "no grant".into(),
+        };
+        assert_eq!(response_status(err()).await, StatusCode::FORBIDDEN);
+        let body = response_body(err()).await;
+        assert_eq!(body["error"], "No access to this table");
+        assert_eq!(body["reason"], "no grant");
+    }
+
     #[tokio::test]
     async fn not_found_maps_to_404() {
         let status = response_status(UsecaseError::NotFound("gone".into())).await;
@@ -62,6 +156,25 @@ mod tests {
         assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn conflict_maps_to_409() {
+        let status = response_status(UsecaseError::Conflict("busy".into())).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn timeout_maps_to_504() {
+        let status = response_status(UsecaseError::Timeout("slow".into())).await;
+        assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn unavailable_maps_to_503_with_retry_after() {
+        let resp = into_response(UsecaseError::Unavailable("busy".into()));
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(resp.headers()[header::RETRY_AFTER], "1");
+    }
+
     #[tokio::test]
     async fn internal_maps_to_500() {
         let status = response_status(UsecaseError::Internal("oops".into())).await;
@@ -79,4 +192,53 @@ mod tests {
         let body = response_body(UsecaseError::Unauthorized).await;
         assert_eq!(body["error"], "Unauthorized");
     }
+
+    #[tokio::test]
+    async fn invalid_lists_each_problem() {
+        let err = UsecaseError::Invalid(vec!["Missing required column 'a'".into()]);
+        assert_eq!(
+            response_status(UsecaseError::Invalid(vec![])).await,
+            StatusCode::BAD_REQUEST
+        );
+        let body = response_body(err).await;
+        assert_eq!(body["error"], "Validation failed");
+        assert_eq!(body["problems"][0], "Missing required column 'a'");
+    }
+
+    #[test]
+    fn pagination_links_for_middle_page() {
+        let uri: Uri = "/api/connections/c/tables/t/rows?filter=a:eq:1&page=3&per_page=10"
+            .parse()
+            .unwrap();
+        let header = pagination_links(&uri, 3, 10, 95).unwrap();
+        assert_eq!(
+            header.to_str().unwrap(),
+            "</api/connections/c/tables/t/rows?filter=a:eq:1&page=1&per_page=10>; rel=\"first\", \
+             </api/connections/c/tables/t/rows?filter=a:eq:1&page=2&per_page=10>; rel=\"prev\", \
+             </api/connections/c/tables/t/rows?filter=a:eq:1&page=4&per_page=10>; rel=\"next\", \
+             </api/connections/c/tables/t/rows?filter=a:eq:1&page=10&per_page=10>; rel=\"last\""
+        );
+    }
+
+    #[test]
+    fn pagination_links_omit_prev_and_next_at_the_edges() {
+        let uri: Uri = "/rows".parse().unwrap();
+        let first = pagination_links(&uri, 1, 10, 25).unwrap();
+        assert!(!first.to_str().unwrap().contains("rel=\"prev\""));
+        assert!(
+            first
+                .to_str()
+                .unwrap()
+                .contains("</rows?page=2&per_page=10>; rel=\"next\"")
+        );
+
+        let last = pagination_links(&uri, 3, 10, 25).unwrap();
+        assert!(!last.to_str().unwrap().contains("rel=\"next\""));
+
+        let empty = pagination_links(&uri, 1, 10, 0).unwrap();
+        assert_eq!(
+            empty.to_str().unwrap(),
+            "</rows?page=1&per_page=10>; rel=\"first\", </rows?page=1&per_page=10>; rel=\"last\""
+        );
+    }
 }
@@ -0,0 +1,33 @@
+use std::sync::LazyLock;
+
+use axum::{Json, response::Html};
+
+use crate::presentation::openapi::openapi_spec;
+
+static SPEC: LazyLock<serde_json::Value> = LazyLock::new(openapi_spec);
+
+/// Swagger UI page, loaded from a CDN and pointed at `/api/openapi.json`.
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>DBWorks API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##;
+
+pub async fn openapi_json() -> Json<serde_json::Value> {
+    Json(SPEC.clone())
+}
+
+pub async fn swagger_ui() -> Html<&'static str> {
+    Html(SWAGGER_UI)
+}
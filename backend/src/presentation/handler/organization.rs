@@ -1,12 +1,13 @@
 use axum::{
     Json,
-    extract::State,
-    http::{HeaderMap, StatusCode},
+    extract::{Path, State},
+    http::StatusCode,
     response::IntoResponse,
 };
+use uuid::Uuid;
 
-use crate::presentation::middleware::get_current_user;
-use crate::presentation::request::CreateOrganizationRequest;
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::{CreateOrganizationRequest, RegisterEmailDomainRequest};
 use crate::presentation::state::AppState;
 use crate::usecase;
 
@@ -14,18 +15,11 @@ use super::into_response;
 
 pub async fn create_organization(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Json(req): Json<CreateOrganizationRequest>,
 ) -> impl IntoResponse {
     tracing::info!(name = %req.name, "POST /api/organizations");
 
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
-
     match usecase::organization::create_organization(&*state.organization_repo, &req.name).await {
         Ok(org) => {
             // Auto-add creator as owner
@@ -44,17 +38,33 @@ pub async fn create_organization(
 
 pub async fn list_organizations(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
-
     match usecase::organization::list_organizations(&*state.organization_repo, &caller).await {
         Ok(orgs) => Json(serde_json::json!(orgs)).into_response(),
         Err(e) => into_response(e),
     }
 }
+
+pub async fn register_email_domain(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<RegisterEmailDomainRequest>,
+) -> impl IntoResponse {
+    tracing::info!(org_id = %org_id, domain = %req.domain, "POST /api/organizations/{org_id}/email-domains");
+
+    match usecase::organization::register_email_domain(
+        &*state.organization_repo,
+        &*state.org_email_domain_repo,
+        &caller,
+        &org_id,
+        &req.domain,
+        &req.default_role,
+    )
+    .await
+    {
+        Ok(mapping) => (StatusCode::CREATED, Json(serde_json::json!(mapping))).into_response(),
+        Err(e) => into_response(e),
+    }
+}
@@ -1,17 +1,17 @@
 use axum::{
     Json,
-    extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    extract::{Path, Query, State},
+    http::{StatusCode, Uri, header},
     response::IntoResponse,
 };
 use uuid::Uuid;
 
-use crate::presentation::middleware::get_current_user;
+use crate::presentation::middleware::CurrentUser;
 use crate::presentation::request::*;
 use crate::presentation::state::AppState;
 use crate::usecase;
 
-use super::into_response;
+use super::{into_response, pagination_links};
 
 // ============================================================
 // User Connection Permissions
@@ -19,20 +19,16 @@ use super::into_response;
 
 pub async fn grant_user_conn_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(conn_id): Path<Uuid>,
     Json(req): Json<GrantUserConnectionPermissionRequest>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::grant_user_connection_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
         &*state.conn_repo,
+        &*state.user_repo,
+        state.notifier.as_ref(),
         &caller,
         &conn_id,
         &req.user_id,
@@ -48,15 +44,9 @@ pub async fn grant_user_conn_permission(
 
 pub async fn revoke_user_conn_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, user_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::revoke_user_connection_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
@@ -74,12 +64,113 @@ pub async fn revoke_user_conn_permission(
 
 pub async fn list_user_conn_permissions(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Query(query): Query<PermissionPageQuery>,
+    uri: Uri,
+) -> impl IntoResponse {
+    match usecase::permission::list_user_connection_permissions(
+        &*state.permission_repo,
+        &conn_id,
+        &query,
+    )
+    .await
+    {
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn list_connection_access(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::permission::list_connection_access(
+        &*state.permission_repo,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &*state.user_repo,
+        &*state.group_repo,
+        &caller,
+        &conn_id,
+    )
+    .await
+    {
+        Ok(entries) => Json(serde_json::json!(entries)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn resolve_permission(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
     Path(conn_id): Path<Uuid>,
+    Query(query): Query<ResolvePermissionQuery>,
 ) -> impl IntoResponse {
-    match usecase::permission::list_user_connection_permissions(&*state.permission_repo, &conn_id)
-        .await
+    match usecase::permission::resolve_permission(
+        &*state.permission_repo,
+        &*state.user_repo,
+        &caller,
+        &conn_id,
+        &query.user_id,
+        &query.table,
+    )
+    .await
+    {
+        Ok(resolved) => Json(serde_json::json!({
+            "user_id": query.user_id,
+            "table": query.table,
+            "level": resolved.level,
+            "source": resolved.source,
+            "table_level": resolved.table_level,
+        }))
+        .into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn copy_user_permissions(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(conn_id): Path<Uuid>,
+    Json(req): Json<CopyUserPermissionsRequest>,
+) -> impl IntoResponse {
+    match usecase::permission::copy_user_permissions(
+        &*state.permission_repo,
+        &caller,
+        &conn_id,
+        &req.from_user,
+        &req.to_user,
+    )
+    .await
+    {
+        Ok(copied) => Json(serde_json::json!({ "copied": copied })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn revoke_all_user_permissions(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(user_id = %user_id, "DELETE /api/users/:user_id/permissions");
+    match usecase::permission::revoke_all_user_permissions(
+        &*state.permission_repo,
+        &*state.user_repo,
+        &caller,
+        &user_id,
+    )
+    .await
     {
-        Ok(perms) => Json(serde_json::json!(perms)).into_response(),
+        Ok(revoked) => Json(serde_json::json!({ "revoked": revoked })).into_response(),
         Err(e) => into_response(e),
     }
 }
@@ -90,20 +181,16 @@ pub async fn list_user_conn_permissions(
 
 pub async fn grant_user_table_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, user_id)): Path<(Uuid, Uuid)>,
     Json(req): Json<GrantUserTablePermissionRequest>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::grant_user_table_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
         &*state.conn_repo,
+        &*state.user_repo,
+        state.notifier.as_ref(),
         &caller,
         &conn_id,
         &user_id,
@@ -119,15 +206,9 @@ pub async fn grant_user_table_permission(
 
 pub async fn revoke_user_table_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, user_id, table)): Path<(Uuid, Uuid, String)>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::revoke_user_table_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
@@ -146,16 +227,26 @@ pub async fn revoke_user_table_permission(
 
 pub async fn list_user_table_permissions(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
     Path((conn_id, user_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<PermissionPageQuery>,
+    uri: Uri,
 ) -> impl IntoResponse {
     match usecase::permission::list_user_table_permissions(
         &*state.permission_repo,
         &conn_id,
         &user_id,
+        &query,
     )
     .await
     {
-        Ok(perms) => Json(serde_json::json!(perms)).into_response(),
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
         Err(e) => into_response(e),
     }
 }
@@ -166,16 +257,10 @@ pub async fn list_user_table_permissions(
 
 pub async fn grant_group_conn_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(conn_id): Path<Uuid>,
     Json(req): Json<GrantGroupConnectionPermissionRequest>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::grant_group_connection_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
@@ -195,15 +280,9 @@ pub async fn grant_group_conn_permission(
 
 pub async fn revoke_group_conn_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, group_id)): Path<(Uuid, Uuid)>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::revoke_group_connection_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
@@ -221,12 +300,97 @@ pub async fn revoke_group_conn_permission(
 
 pub async fn list_group_conn_permissions(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
     Path(conn_id): Path<Uuid>,
+    Query(query): Query<PermissionPageQuery>,
+    uri: Uri,
 ) -> impl IntoResponse {
-    match usecase::permission::list_group_connection_permissions(&*state.permission_repo, &conn_id)
-        .await
+    match usecase::permission::list_group_connection_permissions(
+        &*state.permission_repo,
+        &conn_id,
+        &query,
+    )
+    .await
     {
-        Ok(perms) => Json(serde_json::json!(perms)).into_response(),
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
+        Err(e) => into_response(e),
+    }
+}
+
+// ============================================================
+// Group Org Permissions
+// ============================================================
+
+pub async fn grant_group_org_permission(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<GrantGroupOrgPermissionRequest>,
+) -> impl IntoResponse {
+    match usecase::permission::grant_group_org_permission(
+        &*state.permission_repo,
+        &*state.org_member_repo,
+        &*state.group_repo,
+        &caller,
+        &org_id,
+        &req.group_id,
+        &req.permission,
+    )
+    .await
+    {
+        Ok(p) => (StatusCode::CREATED, Json(serde_json::json!(p))).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn revoke_group_org_permission(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((org_id, group_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match usecase::permission::revoke_group_org_permission(
+        &*state.permission_repo,
+        &*state.org_member_repo,
+        &caller,
+        &org_id,
+        &group_id,
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn list_group_org_permissions(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(org_id): Path<Uuid>,
+    Query(query): Query<PermissionPageQuery>,
+    uri: Uri,
+) -> impl IntoResponse {
+    match usecase::permission::list_group_org_permissions(
+        &*state.permission_repo,
+        &*state.org_member_repo,
+        &caller,
+        &org_id,
+        &query,
+    )
+    .await
+    {
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
         Err(e) => into_response(e),
     }
 }
@@ -237,16 +401,10 @@ pub async fn list_group_conn_permissions(
 
 pub async fn grant_group_table_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, group_id)): Path<(Uuid, Uuid)>,
     Json(req): Json<GrantGroupTablePermissionRequest>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::grant_group_table_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
@@ -266,15 +424,9 @@ pub async fn grant_group_table_permission(
 
 pub async fn revoke_group_table_permission(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path((conn_id, group_id, table)): Path<(Uuid, Uuid, String)>,
 ) -> impl IntoResponse {
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
     match usecase::permission::revoke_group_table_permission(
         &*state.permission_repo,
         &*state.org_member_repo,
@@ -293,16 +445,26 @@ pub async fn revoke_group_table_permission(
 
 pub async fn list_group_table_permissions(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
     Path((conn_id, group_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<PermissionPageQuery>,
+    uri: Uri,
 ) -> impl IntoResponse {
     match usecase::permission::list_group_table_permissions(
         &*state.permission_repo,
         &conn_id,
         &group_id,
+        &query,
     )
     .await
     {
-        Ok(perms) => Json(serde_json::json!(perms)).into_response(),
+        Ok(page) => {
+            let mut resp = Json(serde_json::json!(page)).into_response();
+            if let Some(link) = pagination_links(&uri, page.page, page.per_page, page.total_count) {
+                resp.headers_mut().insert(header::LINK, link);
+            }
+            resp
+        }
         Err(e) => into_response(e),
     }
 }
@@ -0,0 +1,125 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::presentation::middleware::CurrentUser;
+use crate::presentation::request::{ApplyTemplateRequest, PermissionTemplateRequest};
+use crate::presentation::state::AppState;
+use crate::usecase;
+
+use super::into_response;
+
+pub async fn create_template(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<PermissionTemplateRequest>,
+) -> impl IntoResponse {
+    tracing::info!(org_id = %org_id, name = %req.name, "POST /api/organizations/:org_id/permission-templates");
+
+    match usecase::permission_template::create_template(
+        &*state.template_repo,
+        &*state.org_member_repo,
+        &caller,
+        &org_id,
+        &req.name,
+        req.description.as_deref(),
+        &req.tables,
+    )
+    .await
+    {
+        Ok(template) => (StatusCode::CREATED, Json(serde_json::json!(template))).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn list_templates(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(org_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match usecase::permission_template::list_templates(
+        &*state.template_repo,
+        &*state.org_member_repo,
+        &caller,
+        &org_id,
+    )
+    .await
+    {
+        Ok(templates) => Json(serde_json::json!(templates)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn update_template(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(template_id): Path<Uuid>,
+    Json(req): Json<PermissionTemplateRequest>,
+) -> impl IntoResponse {
+    tracing::info!(template_id = %template_id, "PUT /api/permission-templates/:template_id");
+
+    match usecase::permission_template::update_template(
+        &*state.template_repo,
+        &*state.org_member_repo,
+        &caller,
+        &template_id,
+        &req.name,
+        req.description.as_deref(),
+        &req.tables,
+    )
+    .await
+    {
+        Ok(template) => Json(serde_json::json!(template)).into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn delete_template(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path(template_id): Path<Uuid>,
+) -> impl IntoResponse {
+    tracing::info!(template_id = %template_id, "DELETE /api/permission-templates/:template_id");
+
+    match usecase::permission_template::delete_template(
+        &*state.template_repo,
+        &*state.org_member_repo,
+        &caller,
+        &template_id,
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
+
+pub async fn apply_template(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((conn_id, template_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<ApplyTemplateRequest>,
+) -> impl IntoResponse {
+    tracing::info!(conn_id = %conn_id, template_id = %template_id, "POST /api/connections/:conn_id/permission-templates/:template_id/apply");
+
+    match usecase::permission_template::apply_template(
+        &*state.template_repo,
+        &*state.org_member_repo,
+        &*state.conn_repo,
+        &caller,
+        &conn_id,
+        &template_id,
+        req.user_id.as_ref(),
+        req.group_id.as_ref(),
+    )
+    .await
+    {
+        Ok(applied) => Json(serde_json::json!({ "applied": applied })).into_response(),
+        Err(e) => into_response(e),
+    }
+}
@@ -1,12 +1,12 @@
 use axum::{
     Json,
     extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    http::StatusCode,
     response::IntoResponse,
 };
 use uuid::Uuid;
 
-use crate::presentation::middleware::get_current_user;
+use crate::presentation::middleware::CurrentUser;
 use crate::presentation::request::CreateUserRequest;
 use crate::presentation::state::AppState;
 use crate::usecase;
@@ -15,17 +15,11 @@ use super::into_response;
 
 pub async fn create_user(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    CurrentUser(caller): CurrentUser,
     Path(org_id): Path<Uuid>,
     Json(req): Json<CreateUserRequest>,
 ) -> impl IntoResponse {
     tracing::info!(org_id = %org_id, name = %req.name, "POST /api/organizations/:org_id/users");
-    let caller = match get_current_user(&*state.user_repo, &state.jwt_secret, &headers).await {
-        Ok(u) => u,
-        Err(status) => {
-            return (status, Json(serde_json::json!({ "error": "Unauthorized" }))).into_response();
-        }
-    };
 
     match usecase::user::create_user(
         &*state.user_repo,
@@ -45,6 +39,7 @@ pub async fn create_user(
 
 pub async fn list_users(
     State(state): State<AppState>,
+    CurrentUser(_): CurrentUser,
     Path(org_id): Path<Uuid>,
 ) -> impl IntoResponse {
     match usecase::user::list_users(&*state.user_repo, &org_id).await {
@@ -52,3 +47,25 @@ pub async fn list_users(
         Err(e) => into_response(e),
     }
 }
+
+pub async fn delete_user(
+    State(state): State<AppState>,
+    CurrentUser(caller): CurrentUser,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    tracing::info!(org_id = %org_id, user_id = %user_id, "DELETE /api/organizations/:org_id/users/:user_id");
+
+    match usecase::user::delete_user(
+        &state.connection_manager,
+        &*state.user_repo,
+        &*state.org_member_repo,
+        &caller,
+        &org_id,
+        &user_id,
+    )
+    .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => into_response(e),
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// How long a completed result is replayed when neither the
+/// `IDEMPOTENCY_TTL_SECS` environment variable nor the caller specify one.
+pub const DEFAULT_IDEMPOTENCY_TTL_SECS: u64 = 600;
+
+/// Outcome of claiming an `Idempotency-Key`.
+#[derive(Debug)]
+pub enum Claim<'a> {
+    /// First time this key is seen; the caller should run the request and
+    /// then `complete` the guard. Dropping the guard instead releases the key,
+    /// so a failed or cancelled request never leaves it stuck in progress.
+    New(ClaimGuard<'a>),
+    /// A request with this key already finished; replay its result.
+    Replay(serde_json::Value),
+    /// A request with this key is still running.
+    InProgress,
+}
+
+enum Slot {
+    Pending,
+    Done {
+        result: serde_json::Value,
+        expires_at: Instant,
+    },
+}
+
+/// In-memory store of recently-seen idempotency keys and their results.
+/// Keys are scoped per user, connection and table so that two callers
+/// reusing the same header value never see each other's rows.
+pub struct IdempotencyStore {
+    ttl: Duration,
+    slots: Mutex<HashMap<String, Slot>>,
+}
+
+impl IdempotencyStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let secs = std::env::var("IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_IDEMPOTENCY_TTL_SECS);
+        Self::new(Duration::from_secs(secs))
+    }
+
+    pub fn scoped_key(user_id: &Uuid, conn_id: &Uuid, table: &str, key: &str) -> String {
+        format!("{}:{}:{}:{}", user_id, conn_id, table, key)
+    }
+
+    /// Claim `key`, reserving it if unseen. Expired results are pruned first.
+    pub fn claim(&self, key: &str) -> Claim<'_> {
+        let mut slots = self.slots.lock().unwrap();
+        let now = Instant::now();
+        slots.retain(|_, slot| match slot {
+            Slot::Pending => true,
+            Slot::Done { expires_at, .. } => *expires_at > now,
+        });
+        match slots.get(key) {
+            Some(Slot::Pending) => Claim::InProgress,
+            Some(Slot::Done { result, .. }) => Claim::Replay(result.clone()),
+            None => {
+                slots.insert(key.to_string(), Slot::Pending);
+                Claim::New(ClaimGuard {
+                    store: self,
+                    key: key.to_string(),
+                    completed: false,
+                })
+            }
+        }
+    }
+
+    fn complete(&self, key: &str, result: serde_json::Value) {
+        self.slots.lock().unwrap().insert(
+            key.to_string(),
+            Slot::Done {
+                result,
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+    }
+
+    fn release(&self, key: &str) {
+        self.slots.lock().unwrap().remove(key);
+    }
+}
+
+/// A key reserved by `IdempotencyStore::claim`. Releases the key when dropped
+/// unless `complete` was called first.
+pub struct ClaimGuard<'a> {
+    store: &'a IdempotencyStore,
+    key: String,
+    completed: bool,
+}
+
+impl ClaimGuard<'_> {
+    /// Record the result for the claimed key so repeats within the TTL replay it.
+    pub fn complete(mut self, result: serde_json::Value) {
+        self.store.complete(&self.key, result);
+        self.completed = true;
+    }
+}
+
+impl std::fmt::Debug for ClaimGuard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClaimGuard")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl Drop for ClaimGuard<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            self.store.release(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim_new<'a>(store: &'a IdempotencyStore, key: &str) -> ClaimGuard<'a> {
+        match store.claim(key) {
+            Claim::New(guard) => guard,
+            other => panic!("expected a new claim, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn claim_complete_and_replay() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+        let guard = claim_new(&store, "k");
+        assert!(matches!(store.claim("k"), Claim::InProgress));
+        guard.complete(serde_json::json!({ "id": 1 }));
+        match store.claim("k") {
+            Claim::Replay(row) => assert_eq!(row, serde_json::json!({ "id": 1 })),
+            other => panic!("expected a replay, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn dropping_a_claim_allows_retry() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+        drop(claim_new(&store, "k"));
+        claim_new(&store, "k");
+    }
+
+    #[test]
+    fn expired_results_are_forgotten() {
+        let store = IdempotencyStore::new(Duration::ZERO);
+        claim_new(&store, "k").complete(serde_json::json!({ "id": 1 }));
+        claim_new(&store, "k");
+    }
+}
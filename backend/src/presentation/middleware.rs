@@ -1,25 +1,149 @@
-use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+#[cfg(feature = "metrics")]
+use axum::extract::Request;
+use axum::extract::{FromRequestParts, MatchedPath};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, StatusCode, header};
+#[cfg(feature = "metrics")]
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
 use uuid::Uuid;
 
-use crate::domain::repository::UserRepository;
+use crate::domain::api_token::ApiTokenScope;
+use crate::domain::repository::{ApiTokenRepository, UserRepository};
 use crate::domain::user::AppUser;
-use crate::infrastructure::auth::jwt::{Claims, extract_bearer_token};
+use crate::infrastructure::auth::api_token;
+use crate::infrastructure::auth::jwt::{Claims, JwtConfig, extract_bearer_token};
+#[cfg(feature = "metrics")]
+use crate::infrastructure::metrics;
+use crate::presentation::state::AppState;
 
-/// Authenticate user from JWT, falling back to X-User-Id for backward compat.
+/// Whether the `X-User-Id` impersonation header is honored.
+/// Only unit tests and builds with the `test-auth` feature accept it;
+/// release builds authenticate exclusively via JWT.
+const X_USER_ID_ENABLED: bool = cfg!(any(test, feature = "test-auth"));
+
+/// Header a super_admin sets to act as another user for a single request.
+const ACT_AS_HEADER: &str = "x-act-as";
+
+/// Route templates an API token may call, for its own connection only.
+const API_TOKEN_ROUTES: &[&str] = &[
+    "/api/connections/{conn_id}/tables",
+    "/api/connections/{conn_id}/refresh-schema",
+    "/api/connections/{conn_id}/transactions",
+    "/api/connections/{conn_id}/snapshots",
+];
+
+/// Why a request could not be resolved to a caller.
+///
+/// Every 401 carries `WWW-Authenticate: Bearer` and a machine-readable `code`
+/// so clients can tell a missing login from a stale one; 403 is reserved for
+/// an authenticated caller who is not allowed to do what they asked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthError {
+    /// No credentials were presented.
+    MissingToken,
+    /// A bearer token was presented but is malformed, expired, revoked or unknown.
+    InvalidToken,
+    /// The credentials are well-formed but name a user that does not exist.
+    UnknownUser,
+    /// An identity header (`X-User-Id`, `X-Act-As`) is not a UUID.
+    BadRequest,
+    /// The `X-Act-As` target does not exist.
+    ActAsNotFound,
+    /// The `X-Act-As` target may not be impersonated.
+    Forbidden,
+    /// An API token was used outside the data routes of its connection.
+    OutOfScope,
+    Internal,
+}
+
+impl AuthError {
+    pub fn status(self) -> StatusCode {
+        match self {
+            AuthError::MissingToken | AuthError::InvalidToken | AuthError::UnknownUser => {
+                StatusCode::UNAUTHORIZED
+            }
+            AuthError::BadRequest => StatusCode::BAD_REQUEST,
+            AuthError::ActAsNotFound => StatusCode::NOT_FOUND,
+            AuthError::Forbidden | AuthError::OutOfScope => StatusCode::FORBIDDEN,
+            AuthError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            AuthError::MissingToken => "missing_token",
+            AuthError::InvalidToken => "invalid_token",
+            AuthError::UnknownUser => "unknown_user",
+            AuthError::BadRequest => "invalid_user_id",
+            AuthError::ActAsNotFound => "act_as_not_found",
+            AuthError::Forbidden => "act_as_forbidden",
+            AuthError::OutOfScope => "api_token_out_of_scope",
+            AuthError::Internal => "internal",
+        }
+    }
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let message = match status {
+            StatusCode::UNAUTHORIZED => "Unauthorized",
+            StatusCode::FORBIDDEN => "Forbidden",
+            StatusCode::NOT_FOUND => "User not found",
+            StatusCode::BAD_REQUEST => "Invalid user id",
+            _ => "Internal server error",
+        };
+        let body = Json(serde_json::json!({ "error": message, "code": self.code() }));
+        let challenge = match self {
+            AuthError::MissingToken => "Bearer",
+            AuthError::InvalidToken | AuthError::UnknownUser => "Bearer error=\"invalid_token\"",
+            _ => return (status, body).into_response(),
+        };
+        (status, [(header::WWW_AUTHENTICATE, challenge)], body).into_response()
+    }
+}
+
+/// Authenticate user from an API token or JWT, falling back to X-User-Id in test builds.
 pub async fn authenticate_user(
     user_repo: &dyn UserRepository,
-    jwt_secret: &str,
+    api_token_repo: &dyn ApiTokenRepository,
+    jwt: &JwtConfig,
+    headers: &HeaderMap,
+) -> Result<AppUser, AuthError> {
+    authenticate(user_repo, api_token_repo, jwt, headers, X_USER_ID_ENABLED).await
+}
+
+async fn authenticate(
+    user_repo: &dyn UserRepository,
+    api_token_repo: &dyn ApiTokenRepository,
+    jwt: &JwtConfig,
     headers: &HeaderMap,
-) -> Result<AppUser, StatusCode> {
-    // Try JWT first
+    allow_x_user_id: bool,
+) -> Result<AppUser, AuthError> {
     if let Some(token) = extract_bearer_token(headers) {
-        let claims = Claims::decode(&token, jwt_secret).map_err(|_| StatusCode::UNAUTHORIZED)?;
-        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::UNAUTHORIZED)?;
+        // API tokens resolve to a synthetic principal limited to the token's scope
+        if api_token::is_api_token(&token) {
+            return api_token_repo
+                .find_active_by_hash(&api_token::hash(&token))
+                .await
+                .map_err(|_| AuthError::Internal)?
+                .map(|t| t.principal())
+                .ok_or(AuthError::InvalidToken);
+        }
+
+        let claims = Claims::decode(&token, jwt).map_err(|_| AuthError::InvalidToken)?;
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| AuthError::InvalidToken)?;
         return user_repo
             .get(&user_id)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-            .ok_or(StatusCode::UNAUTHORIZED);
+            .map_err(|_| AuthError::Internal)?
+            .ok_or(AuthError::UnknownUser);
+    }
+
+    if !allow_x_user_id {
+        return Err(AuthError::MissingToken);
     }
 
     // Fallback: X-User-Id header (for dev/testing)
@@ -28,19 +152,415 @@ pub async fn authenticate_user(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("00000000-0000-0000-0000-000000000001");
 
-    let user_id = Uuid::parse_str(user_id_str).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let user_id = Uuid::parse_str(user_id_str).map_err(|_| AuthError::BadRequest)?;
 
     user_repo
         .get(&user_id)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::UNAUTHORIZED)
+        .map_err(|_| AuthError::Internal)?
+        .ok_or(AuthError::UnknownUser)
 }
 
 pub async fn get_current_user(
     user_repo: &dyn UserRepository,
-    jwt_secret: &str,
+    api_token_repo: &dyn ApiTokenRepository,
+    jwt: &JwtConfig,
+    headers: &HeaderMap,
+) -> Result<AppUser, AuthError> {
+    let user = authenticate_user(user_repo, api_token_repo, jwt, headers).await?;
+    resolve_act_as(user_repo, user, headers).await
+}
+
+/// The caller of a request, as resolved by `get_current_user`.
+///
+/// API token principals are only accepted on the data routes of their own
+/// connection (`API_TOKEN_ROUTES`); every other route refuses them with 403.
+/// Every request honoring `X-Act-As` is written to the audit log.
+pub struct CurrentUser(pub AppUser);
+
+impl FromRequestParts<AppState> for CurrentUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, AuthError> {
+        let user = get_current_user(
+            &*state.user_repo,
+            &*state.api_token_repo,
+            &state.jwt,
+            &parts.headers,
+        )
+        .await?;
+        if let Some(admin_id) = user.acting_admin_id {
+            // Impersonation is always audited; a request that cannot be is refused
+            state
+                .audit_repo
+                .record_act_as(&admin_id, &user.id, parts.method.as_str(), parts.uri.path())
+                .await
+                .map_err(|e| {
+                    tracing::error!(error = %e, "Failed to record X-Act-As audit entry");
+                    AuthError::Internal
+                })?;
+        }
+        if let Some(scope) = &user.api_token_scope {
+            let route = parts
+                .extensions
+                .get::<MatchedPath>()
+                .map(MatchedPath::as_str);
+            if !token_may_call(scope, route, parts.uri.path()) {
+                tracing::warn!(token_id = %user.id, path = %parts.uri.path(), "API token used outside its scope");
+                return Err(AuthError::OutOfScope);
+            }
+        }
+        Ok(CurrentUser(user))
+    }
+}
+
+/// Whether a token with `scope` may call the route matched as `route` for
+/// the request `path`: only the data routes, and only on its connection.
+fn token_may_call(scope: &ApiTokenScope, route: Option<&str>, path: &str) -> bool {
+    let Some(route) = route else {
+        return false;
+    };
+    if !API_TOKEN_ROUTES
+        .iter()
+        .any(|prefix| route == *prefix || route.starts_with(&format!("{}/", prefix)))
+    {
+        return false;
+    }
+    // Same segment position as `{conn_id}` in the template
+    let position = route.split('/').position(|s| s == "{conn_id}");
+    position
+        .and_then(|i| path.split('/').nth(i))
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .is_some_and(|id| id == scope.connection_id)
+}
+
+/// Swap in the `X-Act-As` target when the authenticated user is a super_admin.
+/// The header is ignored for everyone else, and acting as another super_admin
+/// is refused so impersonation can never escalate privileges.
+async fn resolve_act_as(
+    user_repo: &dyn UserRepository,
+    user: AppUser,
     headers: &HeaderMap,
-) -> Result<AppUser, StatusCode> {
-    authenticate_user(user_repo, jwt_secret, headers).await
+) -> Result<AppUser, AuthError> {
+    let Some(value) = headers.get(ACT_AS_HEADER) else {
+        return Ok(user);
+    };
+    if !user.is_super_admin() {
+        tracing::warn!(user_id = %user.id, "Ignoring X-Act-As from a non-super_admin");
+        return Ok(user);
+    }
+
+    let target_id = value
+        .to_str()
+        .ok()
+        .and_then(|v| Uuid::parse_str(v.trim()).ok())
+        .ok_or(AuthError::BadRequest)?;
+    if target_id == user.id {
+        return Ok(user);
+    }
+    let mut target = user_repo
+        .get(&target_id)
+        .await
+        .map_err(|_| AuthError::Internal)?
+        .ok_or(AuthError::ActAsNotFound)?;
+    if target.is_super_admin() {
+        return Err(AuthError::Forbidden);
+    }
+
+    tracing::warn!(admin_id = %user.id, acting_as = %target.id, "Super admin acting as another user");
+    target.acting_admin_id = Some(user.id);
+    Ok(target)
+}
+
+/// Count every routed request by method, matched route template and status.
+#[cfg(feature = "metrics")]
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+    metrics::record_request(&method, &route, response.status().as_u16());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::api_token::ApiToken;
+    use async_trait::async_trait;
+
+    /// Repository that knows exactly one user.
+    struct SingleUserRepo(AppUser);
+
+    #[async_trait]
+    impl UserRepository for SingleUserRepo {
+        async fn create(&self, _: &str, _: &str, _: &str) -> anyhow::Result<AppUser> {
+            anyhow::bail!("mock")
+        }
+        async fn list_by_org(&self, _: &Uuid) -> anyhow::Result<Vec<AppUser>> {
+            Ok(vec![])
+        }
+        async fn get(&self, user_id: &Uuid) -> anyhow::Result<Option<AppUser>> {
+            Ok((*user_id == self.0.id).then(|| self.0.clone()))
+        }
+        async fn delete(&self, _: &Uuid) -> anyhow::Result<bool> {
+            anyhow::bail!("mock")
+        }
+    }
+
+    /// Repository that knows at most one API token, by its plaintext.
+    struct SingleTokenRepo(Option<(String, ApiToken)>);
+
+    #[async_trait]
+    impl ApiTokenRepository for SingleTokenRepo {
+        async fn create(
+            &self,
+            _: &Uuid,
+            _: &str,
+            _: &str,
+            _: &str,
+            _: &Uuid,
+            _: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> anyhow::Result<ApiToken> {
+            anyhow::bail!("mock")
+        }
+        async fn find_active_by_hash(&self, token_hash: &str) -> anyhow::Result<Option<ApiToken>> {
+            Ok(self
+                .0
+                .as_ref()
+                .filter(|(plain, _)| api_token::hash(plain) == token_hash)
+                .map(|(_, t)| t.clone()))
+        }
+        async fn list_by_connection(&self, _: &Uuid) -> anyhow::Result<Vec<ApiToken>> {
+            Ok(vec![])
+        }
+        async fn revoke(&self, _: &Uuid, _: &Uuid) -> anyhow::Result<bool> {
+            anyhow::bail!("mock")
+        }
+    }
+
+    fn bearer_headers(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        headers
+    }
+
+    async fn response_code(resp: Response) -> String {
+        let body = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["code"].as_str().unwrap().to_string()
+    }
+
+    fn user() -> AppUser {
+        AppUser {
+            id: Uuid::new_v4(),
+            email: "alice@example.com".to_string(),
+            name: "Alice".to_string(),
+            auth_provider: None,
+            provider_id: None,
+            role: "member".to_string(),
+            avatar_url: None,
+            created_at: None,
+            updated_at: None,
+            acting_admin_id: None,
+            api_token_scope: None,
+        }
+    }
+
+    fn x_user_id_headers(id: &Uuid) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-user-id", id.to_string().parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn token_may_only_call_data_routes_of_its_connection() {
+        let scope = ApiTokenScope {
+            connection_id: Uuid::new_v4(),
+            permission: crate::domain::permission::PermissionLevel::Read,
+            created_by: Uuid::new_v4(),
+        };
+        let rows = "/api/connections/{conn_id}/tables/{table}/rows";
+        let own = format!("/api/connections/{}/tables/notes/rows", scope.connection_id);
+        let other = format!("/api/connections/{}/tables/notes/rows", Uuid::new_v4());
+        assert!(token_may_call(&scope, Some(rows), &own));
+        assert!(!token_may_call(&scope, Some(rows), &other));
+        assert!(!token_may_call(&scope, None, &own));
+
+        let tokens = format!("/api/connections/{}/api-tokens", scope.connection_id);
+        assert!(!token_may_call(
+            &scope,
+            Some("/api/connections/{conn_id}/api-tokens"),
+            &tokens
+        ));
+        assert!(!token_may_call(
+            &scope,
+            Some("/api/organizations"),
+            "/api/organizations"
+        ));
+    }
+
+    #[tokio::test]
+    async fn x_user_id_ignored_when_disabled() {
+        let repo = SingleUserRepo(user());
+        let jwt = JwtConfig::hs256("secret");
+        let headers = x_user_id_headers(&repo.0.id);
+
+        let result = authenticate(&repo, &SingleTokenRepo(None), &jwt, &headers, false).await;
+        assert_eq!(result.unwrap_err(), AuthError::MissingToken);
+    }
+
+    #[tokio::test]
+    async fn missing_credentials_rejected_when_disabled() {
+        let repo = SingleUserRepo(user());
+        let jwt = JwtConfig::hs256("secret");
+
+        let result = authenticate(
+            &repo,
+            &SingleTokenRepo(None),
+            &jwt,
+            &HeaderMap::new(),
+            false,
+        )
+        .await;
+        let err = result.unwrap_err();
+        assert_eq!(err, AuthError::MissingToken);
+
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(resp.headers()[header::WWW_AUTHENTICATE], "Bearer");
+        assert_eq!(response_code(resp).await, "missing_token");
+    }
+
+    #[tokio::test]
+    async fn jwt_for_unknown_user_rejected_with_distinct_code() {
+        let repo = SingleUserRepo(user());
+        let jwt = JwtConfig::hs256("secret");
+        let token = Claims::generate_token(&user(), &jwt).unwrap();
+
+        let result = authenticate(
+            &repo,
+            &SingleTokenRepo(None),
+            &jwt,
+            &bearer_headers(&token),
+            false,
+        )
+        .await;
+        let err = result.unwrap_err();
+        assert_eq!(err, AuthError::UnknownUser);
+
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            resp.headers()[header::WWW_AUTHENTICATE],
+            "Bearer error=\"invalid_token\""
+        );
+        assert_eq!(response_code(resp).await, "unknown_user");
+    }
+
+    #[tokio::test]
+    async fn jwt_accepted_when_x_user_id_disabled() {
+        let repo = SingleUserRepo(user());
+        let jwt = JwtConfig::hs256("secret");
+        let token = Claims::generate_token(&repo.0, &jwt).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+
+        let result = authenticate(&repo, &SingleTokenRepo(None), &jwt, &headers, false).await;
+        assert_eq!(result.unwrap().id, repo.0.id);
+    }
+
+    fn act_as_headers(id: &Uuid) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACT_AS_HEADER, id.to_string().parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn act_as_super_admin_is_refused() {
+        let mut target = user();
+        target.role = "super_admin".to_string();
+        let repo = SingleUserRepo(target);
+        let mut admin = user();
+        admin.role = "super_admin".to_string();
+
+        let result = resolve_act_as(&repo, admin, &act_as_headers(&repo.0.id)).await;
+        let resp = result.unwrap_err().into_response();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert!(resp.headers().get(header::WWW_AUTHENTICATE).is_none());
+    }
+
+    #[tokio::test]
+    async fn act_as_records_real_admin() {
+        let repo = SingleUserRepo(user());
+        let mut admin = user();
+        admin.role = "super_admin".to_string();
+        let admin_id = admin.id;
+
+        let acting = resolve_act_as(&repo, admin, &act_as_headers(&repo.0.id))
+            .await
+            .unwrap();
+        assert_eq!(acting.id, repo.0.id);
+        assert_eq!(acting.acting_admin_id, Some(admin_id));
+    }
+
+    #[tokio::test]
+    async fn x_user_id_honored_when_enabled() {
+        let repo = SingleUserRepo(user());
+        let jwt = JwtConfig::hs256("secret");
+        let headers = x_user_id_headers(&repo.0.id);
+
+        let result = authenticate(&repo, &SingleTokenRepo(None), &jwt, &headers, true).await;
+        assert_eq!(result.unwrap().id, repo.0.id);
+    }
+
+    #[tokio::test]
+    async fn api_token_resolves_to_scoped_principal() {
+        let plain = api_token::generate();
+        let token = ApiToken {
+            id: Uuid::new_v4(),
+            connection_id: Uuid::new_v4(),
+            name: "etl".to_string(),
+            permission: "read".to_string(),
+            token_hash: api_token::hash(&plain),
+            created_by: Uuid::new_v4(),
+            expires_at: None,
+            revoked_at: None,
+            created_at: chrono::Utc::now(),
+        };
+        let tokens = SingleTokenRepo(Some((plain.clone(), token.clone())));
+        let users = SingleUserRepo(user());
+        let jwt = JwtConfig::hs256("secret");
+
+        let principal = authenticate(&users, &tokens, &jwt, &bearer_headers(&plain), false)
+            .await
+            .unwrap();
+        assert_eq!(principal.id, token.id);
+        assert_eq!(principal.acting_admin_id, None);
+        assert_eq!(
+            principal.api_token_scope.map(|s| s.connection_id),
+            Some(token.connection_id)
+        );
+
+        // Unknown (or revoked/expired, which the repository filters out) tokens are refused
+        let result = authenticate(
+            &users,
+            &tokens,
+            &jwt,
+            &bearer_headers(&api_token::generate()),
+            false,
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), AuthError::InvalidToken);
+    }
 }
@@ -1,5 +1,9 @@
+pub mod cors;
 pub mod handler;
+pub mod idempotency;
 pub mod middleware;
+pub mod openapi;
 pub mod request;
 pub mod routes;
+pub mod shutdown;
 pub mod state;
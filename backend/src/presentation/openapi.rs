@@ -0,0 +1,812 @@
+//! OpenAPI 3 description of the HTTP API, served at `/api/openapi.json`.
+//!
+//! Paths mirror `routes.rs` and are listed by hand; request bodies and query
+//! parameters are generated from the DTOs in `request.rs`, so their doc
+//! comments are the single source of field documentation.
+
+use schemars::JsonSchema;
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use serde_json::{Map, Value, json};
+
+use crate::presentation::handler::connection::ConnectionListParams;
+use crate::presentation::request::*;
+
+struct Spec {
+    generator: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+/// One operation being described; chain `body`, `query` or `public` onto it.
+struct Operation<'a> {
+    generator: &'a mut SchemaGenerator,
+    op: &'a mut Map<String, Value>,
+}
+
+impl Spec {
+    fn new() -> Self {
+        Self {
+            generator: SchemaGenerator::new(SchemaSettings::openapi3()),
+            paths: Map::new(),
+        }
+    }
+
+    fn op(&mut self, method: &str, path: &str, tag: &str, summary: &str) -> Operation<'_> {
+        let item = self
+            .paths
+            .entry(path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .unwrap();
+        item.insert(
+            method.to_string(),
+            json!({
+                "tags": [tag],
+                "summary": summary,
+                "parameters": path_params(path),
+                "responses": {
+                    "2XX": { "description": "Success" },
+                    "4XX": {
+                        "description": "Client error",
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/Error" }
+                            }
+                        }
+                    }
+                }
+            }),
+        );
+        Operation {
+            generator: &mut self.generator,
+            op: item.get_mut(method).unwrap().as_object_mut().unwrap(),
+        }
+    }
+
+    fn finish(mut self) -> Value {
+        let mut schemas = self.generator.take_definitions(true);
+        schemas.insert(
+            "Error".to_string(),
+            json!({
+                "type": "object",
+                "properties": { "error": { "type": "string" } },
+                "required": ["error"]
+            }),
+        );
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "DBWorks API",
+                "version": env!("CARGO_PKG_VERSION")
+            },
+            "paths": self.paths,
+            "components": {
+                "schemas": schemas,
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }
+                }
+            },
+            "security": [{ "bearerAuth": [] }]
+        })
+    }
+}
+
+impl Operation<'_> {
+    /// JSON request body described by `T`.
+    fn body<T: JsonSchema>(self) -> Self {
+        let schema = self.generator.subschema_for::<T>();
+        self.op.insert(
+            "requestBody".to_string(),
+            json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema } }
+            }),
+        );
+        self
+    }
+
+    /// One query parameter per field of `T`.
+    fn query<T: JsonSchema>(self) -> Self {
+        let root = self.generator.root_schema_for::<T>().to_value();
+        let required = root["required"].as_array().cloned().unwrap_or_default();
+        let params = self.op["parameters"].as_array_mut().unwrap();
+        for (name, schema) in root["properties"].as_object().into_iter().flatten() {
+            let mut schema = schema.clone();
+            let description = schema.as_object_mut().and_then(|s| s.remove("description"));
+            let mut param = json!({
+                "name": name,
+                "in": "query",
+                "required": required.contains(&json!(name)),
+                "schema": schema,
+            });
+            if let Some(description) = description {
+                param["description"] = description;
+            }
+            params.push(param);
+        }
+        self
+    }
+
+    /// Reachable without authentication.
+    fn public(self) -> Self {
+        self.op.insert("security".to_string(), json!([]));
+        self
+    }
+}
+
+/// Parameters for every `{name}` segment of a route; `*_id` segments are UUIDs.
+fn path_params(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|seg| seg.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            let schema = if name.ends_with("_id") {
+                json!({ "type": "string", "format": "uuid" })
+            } else {
+                json!({ "type": "string" })
+            };
+            json!({ "name": name, "in": "path", "required": true, "schema": schema })
+        })
+        .collect()
+}
+
+/// Build the OpenAPI document for every route in `create_router`.
+pub fn openapi_spec() -> Value {
+    let mut spec = Spec::new();
+
+    // Health and auth
+    spec.op("get", "/healthz", "health", "Liveness probe")
+        .public();
+    spec.op("get", "/readyz", "health", "Readiness probe")
+        .public();
+    spec.op(
+        "get",
+        "/api/auth/google",
+        "auth",
+        "Start Google OAuth login",
+    )
+    .public();
+    spec.op(
+        "get",
+        "/api/auth/google/callback",
+        "auth",
+        "Google OAuth callback",
+    )
+    .public();
+    spec.op(
+        "get",
+        "/api/auth/github",
+        "auth",
+        "Start GitHub OAuth login",
+    )
+    .public();
+    spec.op(
+        "get",
+        "/api/auth/github/callback",
+        "auth",
+        "GitHub OAuth callback",
+    )
+    .public();
+    spec.op("get", "/api/auth/me", "auth", "Current user");
+
+    // Organizations and users
+    spec.op(
+        "post",
+        "/api/organizations",
+        "organizations",
+        "Create an organization",
+    )
+    .body::<CreateOrganizationRequest>();
+    spec.op(
+        "get",
+        "/api/organizations",
+        "organizations",
+        "List organizations",
+    );
+    spec.op(
+        "post",
+        "/api/organizations/{org_id}/email-domains",
+        "organizations",
+        "Register an email domain for automatic membership",
+    )
+    .body::<RegisterEmailDomainRequest>();
+    spec.op(
+        "post",
+        "/api/organizations/{org_id}/users",
+        "users",
+        "Create a user",
+    )
+    .body::<CreateUserRequest>();
+    spec.op(
+        "get",
+        "/api/organizations/{org_id}/users",
+        "users",
+        "List users",
+    );
+    spec.op(
+        "delete",
+        "/api/organizations/{org_id}/users/{user_id}",
+        "users",
+        "Delete a user",
+    );
+    spec.op(
+        "get",
+        "/api/organizations/{org_id}/users/{user_id}/groups",
+        "groups",
+        "Groups a user belongs to in an organization",
+    );
+
+    // Groups
+    spec.op(
+        "post",
+        "/api/organizations/{org_id}/groups",
+        "groups",
+        "Create a group",
+    )
+    .body::<CreateGroupRequest>();
+    spec.op(
+        "get",
+        "/api/organizations/{org_id}/groups",
+        "groups",
+        "List groups",
+    );
+    spec.op(
+        "patch",
+        "/api/groups/{group_id}",
+        "groups",
+        "Update a group",
+    )
+    .body::<UpdateGroupRequest>();
+    spec.op(
+        "delete",
+        "/api/groups/{group_id}",
+        "groups",
+        "Delete a group",
+    );
+    spec.op(
+        "put",
+        "/api/groups/{group_id}/parent",
+        "groups",
+        "Set a group's parent",
+    )
+    .body::<SetGroupParentRequest>();
+    spec.op(
+        "post",
+        "/api/groups/{group_id}/members",
+        "groups",
+        "Add a group member",
+    )
+    .body::<AddGroupMemberRequest>();
+    spec.op(
+        "get",
+        "/api/groups/{group_id}/members",
+        "groups",
+        "List group members",
+    );
+    spec.op(
+        "delete",
+        "/api/groups/{group_id}/members/{user_id}",
+        "groups",
+        "Remove a group member",
+    );
+    spec.op(
+        "post",
+        "/api/groups/{group_id}/members/bulk",
+        "groups",
+        "Add several group members",
+    )
+    .body::<BulkGroupMembersRequest>();
+    spec.op(
+        "delete",
+        "/api/groups/{group_id}/members/bulk",
+        "groups",
+        "Remove several group members",
+    )
+    .body::<BulkGroupMembersRequest>();
+
+    // Connections
+    spec.op(
+        "post",
+        "/api/connections",
+        "connections",
+        "Create a connection",
+    )
+    .body::<ConnectionRequest>();
+    spec.op(
+        "get",
+        "/api/connections",
+        "connections",
+        "List visible connections",
+    )
+    .query::<ConnectionListParams>();
+    spec.op(
+        "post",
+        "/api/connections/test/tables",
+        "connections",
+        "Preview a database's tables without saving the connection",
+    )
+    .body::<PreviewConnectionRequest>();
+    spec.op(
+        "get",
+        "/api/connections/export",
+        "connections",
+        "Export connections",
+    );
+    spec.op(
+        "post",
+        "/api/connections/import",
+        "connections",
+        "Import connections",
+    )
+    .body::<ImportConnectionsRequest>();
+    spec.op(
+        "post",
+        "/api/admin/connections/reload",
+        "connections",
+        "Reload saved connections",
+    );
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}",
+        "connections",
+        "Delete a connection",
+    );
+    spec.op(
+        "put",
+        "/api/connections/{conn_id}/tags",
+        "connections",
+        "Replace tags",
+    )
+    .body::<SetConnectionTagsRequest>();
+    spec.op(
+        "put",
+        "/api/connections/{conn_id}/read-only",
+        "connections",
+        "Set the read-only flag",
+    )
+    .body::<SetConnectionReadOnlyRequest>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/rotate-password",
+        "connections",
+        "Rotate the stored password",
+    )
+    .body::<RotatePasswordRequest>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/transfer",
+        "connections",
+        "Transfer ownership",
+    )
+    .body::<TransferConnectionRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/stats",
+        "connections",
+        "Usage and pool stats",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/deletion-impact",
+        "connections",
+        "Grants that deleting the connection would remove",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/slow-queries",
+        "connections",
+        "Recent datasource calls, slowest first",
+    );
+
+    // Audit
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/audit",
+        "audit",
+        "List audit entries",
+    )
+    .query::<AuditQuery>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/query-log",
+        "audit",
+        "List query log entries",
+    )
+    .query::<QueryLogQuery>();
+
+    // Permissions
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/access",
+        "permissions",
+        "List everyone with access",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/resolve",
+        "permissions",
+        "Resolve a user's effective permission",
+    )
+    .query::<ResolvePermissionQuery>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/user-permissions",
+        "permissions",
+        "Grant a user connection permission",
+    )
+    .body::<GrantUserConnectionPermissionRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/user-permissions",
+        "permissions",
+        "List user connection permissions",
+    )
+    .query::<PermissionPageQuery>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/user-permissions/copy",
+        "permissions",
+        "Copy one user's permissions to another",
+    )
+    .body::<CopyUserPermissionsRequest>();
+    spec.op(
+        "delete",
+        "/api/users/{user_id}/permissions",
+        "permissions",
+        "Revoke a user's grants on every connection",
+    );
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/user-permissions/{user_id}",
+        "permissions",
+        "Revoke a user connection permission",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/user-permissions/{user_id}/tables",
+        "permissions",
+        "Grant a user table permission",
+    )
+    .body::<GrantUserTablePermissionRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/user-permissions/{user_id}/tables",
+        "permissions",
+        "List user table permissions",
+    )
+    .query::<PermissionPageQuery>();
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/user-permissions/{user_id}/tables/{table}",
+        "permissions",
+        "Revoke a user table permission",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/group-permissions",
+        "permissions",
+        "Grant a group connection permission",
+    )
+    .body::<GrantGroupConnectionPermissionRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/group-permissions",
+        "permissions",
+        "List group connection permissions",
+    )
+    .query::<PermissionPageQuery>();
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/group-permissions/{group_id}",
+        "permissions",
+        "Revoke a group connection permission",
+    );
+    spec.op(
+        "post",
+        "/api/organizations/{org_id}/group-permissions",
+        "permissions",
+        "Grant an org-wide group permission",
+    )
+    .body::<GrantGroupOrgPermissionRequest>();
+    spec.op(
+        "get",
+        "/api/organizations/{org_id}/group-permissions",
+        "permissions",
+        "List org-wide group permissions",
+    )
+    .query::<PermissionPageQuery>();
+    spec.op(
+        "delete",
+        "/api/organizations/{org_id}/group-permissions/{group_id}",
+        "permissions",
+        "Revoke an org-wide group permission",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/group-permissions/{group_id}/tables",
+        "permissions",
+        "Grant a group table permission",
+    )
+    .body::<GrantGroupTablePermissionRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/group-permissions/{group_id}/tables",
+        "permissions",
+        "List group table permissions",
+    )
+    .query::<PermissionPageQuery>();
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/group-permissions/{group_id}/tables/{table}",
+        "permissions",
+        "Revoke a group table permission",
+    );
+
+    // Permission templates
+    spec.op(
+        "post",
+        "/api/organizations/{org_id}/permission-templates",
+        "permission-templates",
+        "Create a permission template",
+    )
+    .body::<PermissionTemplateRequest>();
+    spec.op(
+        "get",
+        "/api/organizations/{org_id}/permission-templates",
+        "permission-templates",
+        "List permission templates",
+    );
+    spec.op(
+        "put",
+        "/api/permission-templates/{template_id}",
+        "permission-templates",
+        "Replace a permission template",
+    )
+    .body::<PermissionTemplateRequest>();
+    spec.op(
+        "delete",
+        "/api/permission-templates/{template_id}",
+        "permission-templates",
+        "Delete a permission template",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/permission-templates/{template_id}/apply",
+        "permission-templates",
+        "Apply a template to a user or group",
+    )
+    .body::<ApplyTemplateRequest>();
+
+    // API tokens
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/api-tokens",
+        "api-tokens",
+        "Issue a connection-scoped API token",
+    )
+    .body::<CreateApiTokenRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/api-tokens",
+        "api-tokens",
+        "List API tokens for a connection",
+    );
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/api-tokens/{token_id}",
+        "api-tokens",
+        "Revoke an API token",
+    );
+
+    // Tables
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables",
+        "data",
+        "List tables",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/refresh-schema",
+        "data",
+        "Drop cached table schemas",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/schema",
+        "data",
+        "Table schema",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/json-schema",
+        "data",
+        "Table schema as JSON Schema",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/insert-defaults",
+        "data",
+        "Column defaults to show on insert forms",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/subscribe",
+        "data",
+        "Stream inserted rows as Server-Sent Events",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/capabilities",
+        "data",
+        "What the caller can do with a table",
+    );
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/metadata",
+        "data",
+        "Table display metadata",
+    );
+    spec.op(
+        "put",
+        "/api/connections/{conn_id}/tables/{table}/metadata",
+        "data",
+        "Set table display metadata",
+    )
+    .body::<TableMetadataRequest>();
+    spec.op(
+        "put",
+        "/api/connections/{conn_id}/tables/{table}/columns/{column}/metadata",
+        "data",
+        "Set column display metadata",
+    )
+    .body::<ColumnMetadataRequest>();
+
+    // Rows
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/rows",
+        "data",
+        "List rows",
+    )
+    .query::<RowsQuery>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/tables/{table}/rows",
+        "data",
+        "Insert a row",
+    )
+    .query::<CreateRowQuery>()
+    .body::<Map<String, Value>>();
+    spec.op(
+        "patch",
+        "/api/connections/{conn_id}/tables/{table}/rows",
+        "data",
+        "Update every row matching a filter",
+    )
+    .query::<DryRunQuery>()
+    .body::<BulkUpdateRequest>();
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/tables/{table}/rows",
+        "data",
+        "Delete every row matching a filter",
+    )
+    .query::<BulkDeleteQuery>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/tables/{table}/rows/batch-get",
+        "data",
+        "Fetch rows by primary key",
+    )
+    .body::<Vec<String>>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/explain",
+        "data",
+        "Query plan for a row listing",
+    )
+    .query::<RowsQuery>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/count",
+        "data",
+        "Count rows",
+    )
+    .query::<CountQuery>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/columns/{column}/distinct",
+        "data",
+        "Distinct values of a column",
+    )
+    .query::<DistinctQuery>();
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/tables/{table}/aggregate",
+        "data",
+        "Group-by aggregate",
+    )
+    .body::<AggregateRequest>();
+    spec.op(
+        "get",
+        "/api/connections/{conn_id}/tables/{table}/rows/{pk}",
+        "data",
+        "Get a row",
+    );
+    spec.op(
+        "put",
+        "/api/connections/{conn_id}/tables/{table}/rows/{pk}",
+        "data",
+        "Update a row",
+    )
+    .body::<Map<String, Value>>();
+    spec.op(
+        "delete",
+        "/api/connections/{conn_id}/tables/{table}/rows/{pk}",
+        "data",
+        "Delete a row",
+    )
+    .query::<DryRunQuery>();
+
+    // Transactions
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/transactions",
+        "data",
+        "Begin a transaction for row writes",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/snapshots",
+        "data",
+        "Begin a read-only snapshot for stable paging",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/transactions/{tx_id}/commit",
+        "data",
+        "Commit a transaction",
+    );
+    spec.op(
+        "post",
+        "/api/connections/{conn_id}/transactions/{tx_id}/rollback",
+        "data",
+        "Roll back a transaction",
+    );
+
+    // Documentation itself
+    spec.op("get", "/api/openapi.json", "docs", "This document")
+        .public();
+    spec.op("get", "/api/docs", "docs", "Swagger UI").public();
+
+    spec.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_params_marks_ids_as_uuids() {
+        let params = path_params("/api/connections/{conn_id}/tables/{table}");
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0]["name"], "conn_id");
+        assert_eq!(params[0]["schema"]["format"], "uuid");
+        assert_eq!(params[1]["name"], "table");
+        assert!(params[1]["schema"].get("format").is_none());
+    }
+
+    #[test]
+    fn query_structs_become_query_parameters() {
+        let spec = openapi_spec();
+        let params =
+            spec["paths"]["/api/connections/{conn_id}/tables/{table}/rows"]["get"]["parameters"]
+                .as_array()
+                .unwrap();
+        let names: Vec<&str> = params.iter().filter_map(|p| p["name"].as_str()).collect();
+        for name in [
+            "conn_id", "table", "page", "per_page", "sort_by", "filter", "fields",
+        ] {
+            assert!(names.contains(&name), "missing {}", name);
+        }
+    }
+}
@@ -1,20 +1,32 @@
+use schemars::JsonSchema;
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::domain::data::SortKey;
+use crate::domain::permission::TemplateTableGrant;
+
 // ============================================================
 // Organization
 // ============================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateOrganizationRequest {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RegisterEmailDomainRequest {
+    pub domain: String,
+    /// Role granted to users auto-joined via this domain
+    #[serde(default = "default_role")]
+    pub default_role: String,
+}
+
 // ============================================================
 // User
 // ============================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateUserRequest {
     pub name: String,
     pub email: String,
@@ -30,34 +42,177 @@ fn default_role() -> String {
 // Group
 // ============================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateGroupRequest {
     pub name: String,
     pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Partial update; omitted fields are left unchanged
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct UpdateGroupRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+/// `null` detaches the group from its parent
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetGroupParentRequest {
+    pub parent_group_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct AddGroupMemberRequest {
     pub user_id: Uuid,
 }
 
+/// Body for adding or removing several group members at once
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BulkGroupMembersRequest {
+    pub user_ids: Vec<Uuid>,
+}
+
 // ============================================================
 // Connection
 // ============================================================
 
 /// Request body when creating a new connection
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct ConnectionRequest {
     pub name: String,
     #[serde(default = "default_db_type")]
     pub db_type: String,
+    /// Empty for file-based kinds such as SQLite
+    #[serde(default)]
     pub host: String,
-    pub port: Option<u16>,
+    /// 1-65535; omit for the kind's default port
+    pub port: Option<u32>,
     pub database: String,
     pub user: String,
     pub password: String,
     /// Optional scope: "personal" or "org:<uuid>"
     pub scope: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Optional statement timeout override in milliseconds
+    pub statement_timeout_ms: Option<u32>,
+    /// Close pooled connections idle this long (ms); `0` never does
+    pub pool_idle_timeout_ms: Option<u32>,
+    /// Replace pooled connections after this long (ms); `0` never does
+    pub pool_max_lifetime_ms: Option<u32>,
+    /// Refuse all writes through the app on this connection
+    #[serde(default)]
+    pub read_only: bool,
+    /// Rows per page when a listing omits `per_page`; still capped at 100
+    pub default_page_size: Option<u32>,
+    /// Sort direction (`asc` or `desc`) when a listing sets `sort_by` but
+    /// omits `sort_order`; ascending if unset
+    pub default_sort_order: Option<String>,
+    /// Comma-separated schemas to use as `search_path` (PostgreSQL only)
+    pub search_path: Option<String>,
+    /// Schema to list tables from and resolve them in, ahead of
+    /// `search_path` (PostgreSQL only); the server default if unset
+    pub default_schema: Option<String>,
+    /// Role to assume with `SET ROLE` on each session (PostgreSQL only)
+    pub role: Option<String>,
+    /// Tables to hide: left out of table listings and 404 on direct access
+    #[serde(default)]
+    pub hidden_tables: Vec<String>,
+    /// Read replicas as `host` or `host:port`, using this connection's
+    /// database and credentials (PostgreSQL only)
+    #[serde(default)]
+    pub replica_hosts: Vec<String>,
+    /// What org members get on a new org connection: `none` (default) or `read`
+    pub default_member_permission: Option<String>,
+}
+
+/// Request body for previewing the tables of a connection that is not saved
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PreviewConnectionRequest {
+    #[serde(default = "default_db_type")]
+    pub db_type: String,
+    #[serde(default)]
+    pub host: String,
+    /// 1-65535; omit for the kind's default port
+    pub port: Option<u32>,
+    pub database: String,
+    pub user: String,
+    pub password: String,
+    /// Comma-separated schemas to use as `search_path` (PostgreSQL only)
+    pub search_path: Option<String>,
+    /// Role to assume with `SET ROLE` on each session (PostgreSQL only)
+    pub role: Option<String>,
+}
+
+/// Body for `PUT .../tables/{table}/metadata`. Omitted or blank fields are cleared.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TableMetadataRequest {
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Body for `PUT .../columns/{column}/metadata`. Omitted or blank fields are cleared.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ColumnMetadataRequest {
+    pub description: Option<String>,
+    pub display_hint: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetConnectionTagsRequest {
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetConnectionReadOnlyRequest {
+    pub read_only: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RotatePasswordRequest {
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TransferConnectionRequest {
+    pub new_owner: Uuid,
+}
+
+/// One connection in an import body. Mirrors the export format
+/// (`SavedConnectionResponse`) plus the password, which export never includes.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ImportConnectionEntry {
+    pub name: String,
+    #[serde(default = "default_db_type")]
+    pub db_type: String,
+    pub host: String,
+    pub port: u16,
+    pub database_name: String,
+    pub username: String,
+    pub password: Option<String>,
+    pub organization_id: Option<Uuid>,
+    pub owner_user_id: Option<Uuid>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub statement_timeout_ms: Option<u32>,
+    pub pool_idle_timeout_ms: Option<u32>,
+    pub pool_max_lifetime_ms: Option<u32>,
+    #[serde(default)]
+    pub read_only: bool,
+    pub default_page_size: Option<u32>,
+    pub default_sort_order: Option<String>,
+    pub search_path: Option<String>,
+    pub default_schema: Option<String>,
+    pub role: Option<String>,
+    #[serde(default)]
+    pub hidden_tables: Vec<String>,
+    #[serde(default)]
+    pub replica_hosts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ImportConnectionsRequest {
+    pub connections: Vec<ImportConnectionEntry>,
 }
 
 fn default_db_type() -> String {
@@ -68,7 +223,7 @@ fn default_db_type() -> String {
 // User Permissions
 // ============================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct GrantUserConnectionPermissionRequest {
     pub user_id: Uuid,
     pub permission: String,
@@ -76,7 +231,20 @@ pub struct GrantUserConnectionPermissionRequest {
     pub all_tables: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CopyUserPermissionsRequest {
+    pub from_user: Uuid,
+    pub to_user: Uuid,
+}
+
+/// Query parameters for `GET /api/connections/{conn_id}/resolve`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResolvePermissionQuery {
+    pub user_id: Uuid,
+    pub table: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct GrantUserTablePermissionRequest {
     pub table_name: String,
     pub permission: String,
@@ -86,7 +254,13 @@ pub struct GrantUserTablePermissionRequest {
 // Group Permissions
 // ============================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GrantGroupOrgPermissionRequest {
+    pub group_id: Uuid,
+    pub permission: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct GrantGroupConnectionPermissionRequest {
     pub group_id: Uuid,
     pub permission: String,
@@ -94,12 +268,47 @@ pub struct GrantGroupConnectionPermissionRequest {
     pub all_tables: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct GrantGroupTablePermissionRequest {
     pub table_name: String,
     pub permission: String,
 }
 
+// ============================================================
+// Permission Templates
+// ============================================================
+
+/// Body for creating or replacing a permission template
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PermissionTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tables: Vec<TemplateTableGrant>,
+}
+
+/// Target of a template application; exactly one field must be set
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ApplyTemplateRequest {
+    pub user_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+}
+
+// ============================================================
+// API tokens
+// ============================================================
+
+/// Body for issuing a connection-scoped API token
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateApiTokenRequest {
+    /// Label shown when listing tokens
+    pub name: String,
+    /// `read` or `write`
+    pub permission: String,
+    /// When the token stops working; omit for a token that never expires
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -109,19 +318,211 @@ fn default_true() -> bool {
 // ============================================================
 
 /// Query parameters for listing rows
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
 pub struct RowsQuery {
     pub page: Option<u32>,
     pub per_page: Option<u32>,
+    /// Column, or comma-separated `col:asc|desc` list (e.g. `last_name:asc,created_at:desc`)
     pub sort_by: Option<String>,
     pub sort_order: Option<String>,
+    /// Null placement for the sort: `first` or `last` (database default if unset)
+    pub nulls: Option<String>,
+    pub filter: Option<String>,
+    /// Case-insensitive substring search across all text columns (PostgreSQL).
+    /// This is a sequential scan over the table; results are still capped by
+    /// `per_page`.
+    pub q: Option<String>,
+    /// Comma-separated columns to return (e.g. `id,name`); all columns if unset
+    pub fields: Option<String>,
+}
+
+/// Most columns `fields` may name, since PostgreSQL's `json_build_object`
+/// takes at most 100 arguments (a key and a value per column).
+pub const MAX_FIELDS: usize = 50;
+
+/// Default cap on `sort_by` terms, unless `ROWS_MAX_SORT_COLUMNS` overrides it.
+pub const DEFAULT_MAX_SORT_COLUMNS: usize = 8;
+
+/// Default cap on the characters in a `filter` or `q`, unless
+/// `ROWS_MAX_FILTER_LENGTH` overrides it.
+pub const DEFAULT_MAX_FILTER_LENGTH: usize = 4096;
+
+/// Per-request caps on how many columns a rows query may name and how long
+/// its filter and search may be, so a client cannot make the server build an
+/// arbitrarily large query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowsQueryLimits {
+    pub max_fields: usize,
+    pub max_sort_columns: usize,
+    pub max_filter_length: usize,
+}
+
+impl Default for RowsQueryLimits {
+    fn default() -> Self {
+        Self {
+            max_fields: MAX_FIELDS,
+            max_sort_columns: DEFAULT_MAX_SORT_COLUMNS,
+            max_filter_length: DEFAULT_MAX_FILTER_LENGTH,
+        }
+    }
+}
+
+impl RowsQueryLimits {
+    /// Read `ROWS_MAX_FIELDS` (never above `MAX_FIELDS`),
+    /// `ROWS_MAX_SORT_COLUMNS` and `ROWS_MAX_FILTER_LENGTH`, keeping the
+    /// defaults for unset or invalid values.
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().and_then(|v| v.parse().ok());
+        let defaults = Self::default();
+        Self {
+            max_fields: var("ROWS_MAX_FIELDS")
+                .unwrap_or(defaults.max_fields)
+                .min(MAX_FIELDS),
+            max_sort_columns: var("ROWS_MAX_SORT_COLUMNS").unwrap_or(defaults.max_sort_columns),
+            max_filter_length: var("ROWS_MAX_FILTER_LENGTH").unwrap_or(defaults.max_filter_length),
+        }
+    }
+}
+
+/// Split a comma-separated column list, dropping blanks and repeats.
+fn column_list(list: Option<&str>) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for column in list.unwrap_or_default().split(',') {
+        let column = column.trim();
+        if !column.is_empty() && !columns.iter().any(|c| c == column) {
+            columns.push(column.to_string());
+        }
+    }
+    columns
+}
+
+impl RowsQuery {
+    /// Parsed `fields` list, trimmed and deduplicated (empty means all columns)
+    pub fn field_list(&self) -> Vec<String> {
+        column_list(self.fields.as_deref())
+    }
+
+    /// Parsed `sort_by` terms, in order (empty when unsorted)
+    pub fn sort_keys(&self) -> Vec<SortKey> {
+        self.sort_by
+            .as_deref()
+            .map(|s| SortKey::parse_list(s, self.sort_order.as_deref()))
+            .unwrap_or_default()
+    }
+}
+
+/// `?dry_run=true` on destructive row operations: report how many rows would
+/// be affected without changing anything.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Body for `PATCH .../rows`: set `data` on every row matching `filter`
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BulkUpdateRequest {
+    pub filter: Option<String>,
+    pub data: serde_json::Value,
+}
+
+/// Query parameters for `DELETE .../rows`
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct BulkDeleteQuery {
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Query parameters for inserting a row
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateRowQuery {
+    /// Check the payload against the table schema before inserting.
+    /// Pass `validate=false` to send it straight to the database.
+    #[serde(default = "default_true")]
+    pub validate: bool,
+    /// Comma-separated columns to return from the inserted row (all when omitted)
+    pub returning: Option<String>,
+}
+
+impl Default for CreateRowQuery {
+    fn default() -> Self {
+        Self {
+            validate: true,
+            returning: None,
+        }
+    }
+}
+
+impl CreateRowQuery {
+    /// Parsed `returning` list, trimmed and deduplicated (empty means all columns)
+    pub fn returning_list(&self) -> Vec<String> {
+        column_list(self.returning.as_deref())
+    }
+}
+
+/// Query parameters for counting rows
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct CountQuery {
     pub filter: Option<String>,
 }
 
+/// Query parameters for listing distinct column values
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct DistinctQuery {
+    pub limit: Option<u32>,
+}
+
+/// Body for a group-by aggregate query
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AggregateRequest {
+    pub group_by: String,
+    pub metric_col: Option<String>,
+    pub agg_fn: String,
+}
+
+// ============================================================
+// Audit log query
+// ============================================================
+
+/// Query parameters for listing audit entries
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct AuditQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    pub action: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Query parameters for listing query log entries
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct QueryLogQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
+/// Query parameters for paginated permission listings
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct PermissionPageQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn field_list_trims_and_dedupes() {
+        let query = RowsQuery {
+            fields: Some(" id,name,,id ".to_string()),
+            ..RowsQuery::default()
+        };
+        assert_eq!(query.field_list(), vec!["id", "name"]);
+        assert!(RowsQuery::default().field_list().is_empty());
+    }
+
     #[test]
     fn create_user_default_role() {
         let json = r#"{"name": "Alice", "email": "alice@example.com"}"#;
@@ -155,6 +556,14 @@ mod tests {
         let json = r#"{"name": "test", "host": "localhost", "database": "db", "user": "u", "password": "p"}"#;
         let req: ConnectionRequest = serde_json::from_str(json).unwrap();
         assert!(req.port.is_none());
+        assert!(req.tags.is_empty());
+    }
+
+    #[test]
+    fn connection_request_with_tags() {
+        let json = r#"{"name": "test", "host": "localhost", "database": "db", "user": "u", "password": "p", "tags": ["prod", "analytics"]}"#;
+        let req: ConnectionRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.tags, vec!["prod", "analytics"]);
     }
 
     #[test]
@@ -1,12 +1,49 @@
 use axum::Router;
-use axum::routing::{delete, get, post, put};
+use axum::extract::DefaultBodyLimit;
+use axum::routing::{delete, get, patch, post, put};
 
 use crate::infrastructure::auth::oauth;
-use crate::presentation::handler::{connection, data, group, organization, permission, user};
+#[cfg(feature = "metrics")]
+use crate::presentation::handler::metrics;
+use crate::presentation::handler::{
+    api_token, audit, connection, data, group, health, openapi, organization, permission,
+    permission_template, user,
+};
+#[cfg(feature = "metrics")]
+use crate::presentation::middleware::track_requests;
 use crate::presentation::state::AppState;
 
+/// Body size cap for single-row writes, unless `ROW_BODY_LIMIT_BYTES` overrides it.
+pub const DEFAULT_ROW_BODY_LIMIT_BYTES: usize = 1024 * 1024;
+
+/// Body size cap for bulk writes, unless `BULK_BODY_LIMIT_BYTES` overrides it.
+pub const DEFAULT_BULK_BODY_LIMIT_BYTES: usize = 8 * 1024 * 1024;
+
+fn body_limit(var: &str, default: usize) -> usize {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 pub fn create_router() -> Router<AppState> {
-    Router::new()
+    // Oversized bodies are rejected with 413 before they are buffered
+    let row_limit = DefaultBodyLimit::max(body_limit(
+        "ROW_BODY_LIMIT_BYTES",
+        DEFAULT_ROW_BODY_LIMIT_BYTES,
+    ));
+    let bulk_limit = DefaultBodyLimit::max(body_limit(
+        "BULK_BODY_LIMIT_BYTES",
+        DEFAULT_BULK_BODY_LIMIT_BYTES,
+    ));
+
+    let router = Router::new()
+        // Health probes (unauthenticated)
+        .route("/healthz", get(health::healthz))
+        .route("/readyz", get(health::readyz))
+        // API description (unauthenticated)
+        .route("/api/openapi.json", get(openapi::openapi_json))
+        .route("/api/docs", get(openapi::swagger_ui))
         // Auth routes
         .route("/api/auth/google", get(oauth::google_login))
         .route("/api/auth/google/callback", get(oauth::google_callback))
@@ -19,9 +56,21 @@ pub fn create_router() -> Router<AppState> {
             post(organization::create_organization),
         )
         .route("/api/organizations", get(organization::list_organizations))
+        .route(
+            "/api/organizations/{org_id}/email-domains",
+            post(organization::register_email_domain),
+        )
         // User management
         .route("/api/organizations/{org_id}/users", post(user::create_user))
         .route("/api/organizations/{org_id}/users", get(user::list_users))
+        .route(
+            "/api/organizations/{org_id}/users/{user_id}",
+            delete(user::delete_user),
+        )
+        .route(
+            "/api/organizations/{org_id}/users/{user_id}/groups",
+            get(group::list_user_groups),
+        )
         // Group management
         .route(
             "/api/organizations/{org_id}/groups",
@@ -31,6 +80,12 @@ pub fn create_router() -> Router<AppState> {
             "/api/organizations/{org_id}/groups",
             get(group::list_groups),
         )
+        .route("/api/groups/{group_id}", patch(group::update_group))
+        .route("/api/groups/{group_id}", delete(group::delete_group))
+        .route(
+            "/api/groups/{group_id}/parent",
+            put(group::set_group_parent),
+        )
         .route(
             "/api/groups/{group_id}/members",
             post(group::add_group_member),
@@ -43,13 +98,77 @@ pub fn create_router() -> Router<AppState> {
             "/api/groups/{group_id}/members/{user_id}",
             delete(group::remove_group_member),
         )
+        .route(
+            "/api/groups/{group_id}/members/bulk",
+            post(group::add_group_members_bulk).delete(group::remove_group_members_bulk),
+        )
         // Connection management
         .route("/api/connections", post(connection::create_connection))
         .route("/api/connections", get(connection::list_connections))
+        .route(
+            "/api/connections/test/tables",
+            post(connection::preview_connection_tables),
+        )
+        .route(
+            "/api/connections/export",
+            get(connection::export_connections),
+        )
+        .route(
+            "/api/connections/import",
+            post(connection::import_connections),
+        )
+        .route(
+            "/api/admin/connections/reload",
+            post(connection::reload_connections),
+        )
         .route(
             "/api/connections/{conn_id}",
             delete(connection::delete_connection),
         )
+        .route(
+            "/api/connections/{conn_id}/tags",
+            put(connection::set_connection_tags),
+        )
+        .route(
+            "/api/connections/{conn_id}/read-only",
+            put(connection::set_connection_read_only),
+        )
+        .route(
+            "/api/connections/{conn_id}/rotate-password",
+            post(connection::rotate_connection_password),
+        )
+        .route(
+            "/api/connections/{conn_id}/transfer",
+            post(connection::transfer_connection),
+        )
+        .route(
+            "/api/connections/{conn_id}/stats",
+            get(connection::get_connection_stats),
+        )
+        .route(
+            "/api/connections/{conn_id}/deletion-impact",
+            get(connection::get_deletion_impact),
+        )
+        .route(
+            "/api/connections/{conn_id}/slow-queries",
+            get(connection::list_slow_queries),
+        )
+        .route(
+            "/api/connections/{conn_id}/access",
+            get(permission::list_connection_access),
+        )
+        .route(
+            "/api/connections/{conn_id}/resolve",
+            get(permission::resolve_permission),
+        )
+        .route(
+            "/api/connections/{conn_id}/audit",
+            get(audit::list_audit_log),
+        )
+        .route(
+            "/api/connections/{conn_id}/query-log",
+            get(audit::list_query_log),
+        )
         // User connection permissions
         .route(
             "/api/connections/{conn_id}/user-permissions",
@@ -59,10 +178,18 @@ pub fn create_router() -> Router<AppState> {
             "/api/connections/{conn_id}/user-permissions",
             get(permission::list_user_conn_permissions),
         )
+        .route(
+            "/api/connections/{conn_id}/user-permissions/copy",
+            post(permission::copy_user_permissions),
+        )
         .route(
             "/api/connections/{conn_id}/user-permissions/{user_id}",
             delete(permission::revoke_user_conn_permission),
         )
+        .route(
+            "/api/users/{user_id}/permissions",
+            delete(permission::revoke_all_user_permissions),
+        )
         // User table permissions
         .route(
             "/api/connections/{conn_id}/user-permissions/{user_id}/tables",
@@ -89,6 +216,19 @@ pub fn create_router() -> Router<AppState> {
             "/api/connections/{conn_id}/group-permissions/{group_id}",
             delete(permission::revoke_group_conn_permission),
         )
+        // Org-wide group permissions
+        .route(
+            "/api/organizations/{org_id}/group-permissions",
+            post(permission::grant_group_org_permission),
+        )
+        .route(
+            "/api/organizations/{org_id}/group-permissions",
+            get(permission::list_group_org_permissions),
+        )
+        .route(
+            "/api/organizations/{org_id}/group-permissions/{group_id}",
+            delete(permission::revoke_group_org_permission),
+        )
         // Group table permissions
         .route(
             "/api/connections/{conn_id}/group-permissions/{group_id}/tables",
@@ -102,12 +242,78 @@ pub fn create_router() -> Router<AppState> {
             "/api/connections/{conn_id}/group-permissions/{group_id}/tables/{table}",
             delete(permission::revoke_group_table_permission),
         )
+        // Permission templates
+        .route(
+            "/api/organizations/{org_id}/permission-templates",
+            post(permission_template::create_template),
+        )
+        .route(
+            "/api/organizations/{org_id}/permission-templates",
+            get(permission_template::list_templates),
+        )
+        .route(
+            "/api/permission-templates/{template_id}",
+            put(permission_template::update_template),
+        )
+        .route(
+            "/api/permission-templates/{template_id}",
+            delete(permission_template::delete_template),
+        )
+        .route(
+            "/api/connections/{conn_id}/permission-templates/{template_id}/apply",
+            post(permission_template::apply_template),
+        )
+        // API tokens
+        .route(
+            "/api/connections/{conn_id}/api-tokens",
+            post(api_token::create_api_token),
+        )
+        .route(
+            "/api/connections/{conn_id}/api-tokens",
+            get(api_token::list_api_tokens),
+        )
+        .route(
+            "/api/connections/{conn_id}/api-tokens/{token_id}",
+            delete(api_token::revoke_api_token),
+        )
         // Table introspection
         .route("/api/connections/{conn_id}/tables", get(data::list_tables))
+        .route(
+            "/api/connections/{conn_id}/refresh-schema",
+            post(data::refresh_schema),
+        )
         .route(
             "/api/connections/{conn_id}/tables/{table}/schema",
             get(data::get_table_schema),
         )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/json-schema",
+            get(data::get_table_json_schema),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/insert-defaults",
+            get(data::get_table_insert_defaults),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/subscribe",
+            get(data::subscribe_table),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/capabilities",
+            get(data::get_table_capabilities),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/metadata",
+            get(data::get_table_metadata),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/metadata",
+            put(data::set_table_metadata),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/columns/{column}/metadata",
+            put(data::set_column_metadata),
+        )
         // Row CRUD
         .route(
             "/api/connections/{conn_id}/tables/{table}/rows",
@@ -115,7 +321,35 @@ pub fn create_router() -> Router<AppState> {
         )
         .route(
             "/api/connections/{conn_id}/tables/{table}/rows",
-            post(data::create_row),
+            post(data::create_row).layer(row_limit),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/rows",
+            patch(data::bulk_update_rows).layer(bulk_limit),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/rows",
+            delete(data::bulk_delete_rows),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/rows/batch-get",
+            post(data::get_rows),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/explain",
+            get(data::explain_rows),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/count",
+            get(data::count_rows),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/columns/{column}/distinct",
+            get(data::distinct_values),
+        )
+        .route(
+            "/api/connections/{conn_id}/tables/{table}/aggregate",
+            post(data::aggregate),
         )
         .route(
             "/api/connections/{conn_id}/tables/{table}/rows/{pk}",
@@ -123,10 +357,34 @@ pub fn create_router() -> Router<AppState> {
         )
         .route(
             "/api/connections/{conn_id}/tables/{table}/rows/{pk}",
-            put(data::update_row),
+            put(data::update_row).layer(row_limit),
         )
         .route(
             "/api/connections/{conn_id}/tables/{table}/rows/{pk}",
             delete(data::delete_row),
         )
+        // Transactions
+        .route(
+            "/api/connections/{conn_id}/transactions",
+            post(data::begin_transaction),
+        )
+        .route(
+            "/api/connections/{conn_id}/snapshots",
+            post(data::begin_snapshot),
+        )
+        .route(
+            "/api/connections/{conn_id}/transactions/{tx_id}/commit",
+            post(data::commit_transaction),
+        )
+        .route(
+            "/api/connections/{conn_id}/transactions/{tx_id}/rollback",
+            post(data::rollback_transaction),
+        );
+
+    #[cfg(feature = "metrics")]
+    let router = router
+        .route("/metrics", get(metrics::metrics))
+        .route_layer(axum::middleware::from_fn(track_requests));
+
+    router
 }
@@ -0,0 +1,56 @@
+use std::future::Future;
+
+use tokio::signal;
+
+/// Resolve once `signal` does, logging which signal it was.
+/// Passed to `axum::serve(...).with_graceful_shutdown` so in-flight requests drain.
+pub async fn shutdown_signal(signal: impl Future<Output = &'static str>) {
+    let name = signal.await;
+    tracing::info!("Received {}, shutting down", name);
+}
+
+/// Resolve with the signal's name when the process receives SIGINT (Ctrl+C)
+/// or SIGTERM.
+pub async fn os_signal() -> &'static str {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => "SIGINT",
+        _ = terminate => "SIGTERM",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn resolves_when_the_signal_fires() {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let handle = tokio::spawn(shutdown_signal(async { rx.await.unwrap() }));
+        tokio::task::yield_now().await;
+        assert!(!handle.is_finished());
+
+        tx.send("SIGTERM").unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("shutdown_signal did not resolve")
+            .unwrap();
+    }
+}
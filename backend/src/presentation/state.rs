@@ -1,32 +1,59 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::{RwLock, broadcast};
 use uuid::Uuid;
 
 use sqlx::PgPool;
 
-use crate::domain::connection::ConnectionInfo;
+use crate::domain::audit::PendingAuditEntry;
+use crate::domain::connection::{
+    ConnectionImportResult, ConnectionInfo, ConnectionLoadResult, ConnectionNameTaken,
+    ConnectionSettings, ConnectionStats, PoolKey, SlowQuery, normalize_sort_order,
+};
+use crate::domain::data::RowChange;
+use crate::domain::notification::Notifier;
 use crate::domain::repository::{
-    ConnectionRepository, GroupRepository, OrganizationMemberRepository, OrganizationRepository,
-    PermissionRepository, UserRepository,
+    ApiTokenRepository, AuditLogRepository, ConnectionRepository, GroupRepository,
+    OrgEmailDomainRepository, OrganizationMemberRepository, OrganizationRepository,
+    PermissionRepository, PermissionTemplateRepository, QueryLogRepository,
+    TableMetadataRepository, UserRepository,
 };
+use crate::infrastructure::auth::jwt::JwtConfig;
 use crate::infrastructure::auth::oauth::OAuthClients;
+use crate::infrastructure::connections_config::ConnectionsConfig;
 use crate::infrastructure::crypto::Encryptor;
-use crate::infrastructure::datasource::DataSource;
-use crate::infrastructure::datasource::mysql::MySqlDataSource;
-use crate::infrastructure::datasource::postgres::PostgresDataSource;
+use crate::infrastructure::datasource::{
+    ChangeFeed, DataSource, DataSourceFactory, DataSourceTransaction, DefaultDataSourceFactory,
+    ProfilingDataSource, QueryLogWriter, QueryLoggingDataSource, QueryProfile, connection_string,
+    query_log_retention_days,
+};
+use crate::infrastructure::metrics;
+use crate::presentation::idempotency::IdempotencyStore;
 
 pub struct AppStateInner {
     pub connection_manager: ConnectionManager,
     pub pool: PgPool,
     pub oauth_clients: OAuthClients,
-    pub jwt_secret: String,
+    pub jwt: JwtConfig,
     pub organization_repo: Arc<dyn OrganizationRepository>,
     pub user_repo: Arc<dyn UserRepository>,
     pub group_repo: Arc<dyn GroupRepository>,
     pub permission_repo: Arc<dyn PermissionRepository>,
     pub org_member_repo: Arc<dyn OrganizationMemberRepository>,
     pub conn_repo: Arc<dyn ConnectionRepository>,
+    pub org_email_domain_repo: Arc<dyn OrgEmailDomainRepository>,
+    pub audit_repo: Arc<dyn AuditLogRepository>,
+    pub query_log_repo: Arc<dyn QueryLogRepository>,
+    pub template_repo: Arc<dyn PermissionTemplateRepository>,
+    pub table_metadata_repo: Arc<dyn TableMetadataRepository>,
+    pub api_token_repo: Arc<dyn ApiTokenRepository>,
+    pub idempotency: IdempotencyStore,
+    /// Emails users about new grants; `None` when SMTP is not configured
+    pub notifier: Option<Arc<dyn Notifier>>,
 }
 
 pub type AppState = Arc<AppStateInner>;
@@ -39,11 +66,54 @@ pub struct ConnectionManager {
     connections: RwLock<HashMap<Uuid, ConnectionEntry>>,
     connection_repo: Option<Arc<dyn ConnectionRepository>>,
     encryptor: Option<Encryptor>,
+    factory: Arc<dyn DataSourceFactory>,
+    change_feed: ChangeFeed,
+    query_log: Option<QueryLogWriter>,
+    /// Open transactions by token, shared with their timeout tasks
+    transactions: Arc<std::sync::Mutex<HashMap<Uuid, HeldTransaction>>>,
+    /// Whether connections with the same `PoolKey` share one live pool
+    share_pools: bool,
+    pools: std::sync::Mutex<HashMap<PoolKey, SharedPool>>,
+}
+
+/// A live pool used by every registered connection with the same key;
+/// closed when the last of them lets go of it.
+struct SharedPool {
+    datasource: Arc<dyn DataSource>,
+    users: usize,
+}
+
+/// A transaction kept open between requests for the user who began it.
+struct HeldTransaction {
+    conn_id: Uuid,
+    user_id: Uuid,
+    tx: Arc<DataSourceTransaction>,
+    /// Audit entries for its writes, recorded on commit
+    audit: Vec<PendingAuditEntry>,
 }
 
 struct ConnectionEntry {
     pub info: ConnectionInfo,
     pub datasource: Arc<dyn DataSource>,
+    /// Set when `datasource` is shared through `pools`
+    pub pool_key: Option<PoolKey>,
+    pub usage: ConnectionUsage,
+}
+
+/// Access tracking for a registered connection, updated under the read lock.
+#[derive(Default)]
+struct ConnectionUsage {
+    query_count: AtomicU64,
+    last_accessed_at: std::sync::Mutex<Option<DateTime<Utc>>>,
+    /// Recent datasource calls with their durations
+    profile: Arc<QueryProfile>,
+}
+
+impl ConnectionUsage {
+    fn record(&self) {
+        self.query_count.fetch_add(1, Ordering::Relaxed);
+        *self.last_accessed_at.lock().unwrap() = Some(Utc::now());
+    }
 }
 
 impl ConnectionManager {
@@ -55,30 +125,68 @@ impl ConnectionManager {
             connections: RwLock::new(HashMap::new()),
             connection_repo,
             encryptor,
+            factory: Arc::new(DefaultDataSourceFactory),
+            change_feed: ChangeFeed::default(),
+            query_log: None,
+            transactions: Arc::default(),
+            share_pools: false,
+            pools: std::sync::Mutex::default(),
         }
     }
 
-    /// Load all saved connections from the database and establish live connections.
-    pub async fn load_saved_connections(&self) -> anyhow::Result<()> {
+    /// Let connections with the same target and pool options share one
+    /// live pool instead of opening their own.
+    pub fn with_shared_pools(mut self, enabled: bool) -> Self {
+        self.share_pools = enabled;
+        self
+    }
+
+    /// Replace the factory used to build datasources (e.g. a mock in tests).
+    pub fn with_factory(mut self, factory: Arc<dyn DataSourceFactory>) -> Self {
+        self.factory = factory;
+        self
+    }
+
+    /// Record datasource calls made through `get_datasource_for` in `query_log`,
+    /// written by a background task. Must be called inside a Tokio runtime.
+    pub fn with_query_log(mut self, query_log: Arc<dyn QueryLogRepository>) -> Self {
+        self.query_log = Some(QueryLogWriter::spawn(query_log, query_log_retention_days()));
+        self
+    }
+
+    /// Load saved connections from the database and establish live connections.
+    /// Connections already in memory are left alone, so this is safe to call
+    /// again at runtime; the outcome of every connection attempted is returned.
+    pub async fn load_saved_connections(&self) -> anyhow::Result<Vec<ConnectionLoadResult>> {
         let repo = match &self.connection_repo {
             Some(r) => r,
             None => {
                 tracing::warn!("No connection repository configured, skipping connection loading");
-                return Ok(());
+                return Ok(Vec::new());
             }
         };
         let encryptor = match &self.encryptor {
             Some(e) => e,
             None => {
                 tracing::warn!("No encryptor configured, skipping connection loading");
-                return Ok(());
+                return Ok(Vec::new());
             }
         };
 
         let saved = repo.list().await?;
         tracing::info!(count = saved.len(), "Loading saved connections from DB");
 
+        let mut results = Vec::new();
         for row in &saved {
+            if self.connections.read().await.contains_key(&row.id) {
+                continue;
+            }
+            let failed = |error: String| ConnectionLoadResult {
+                connection_id: row.id,
+                name: row.name.clone(),
+                error: Some(error),
+            };
+
             let password = match encryptor.decrypt(&row.encrypted_password) {
                 Ok(p) => p,
                 Err(e) => {
@@ -88,65 +196,67 @@ impl ConnectionManager {
                         error = %e,
                         "Failed to decrypt password, skipping"
                     );
+                    results.push(failed(format!("Failed to decrypt password: {}", e)));
                     continue;
                 }
             };
 
-            let db_type = row.db_type.as_str();
-            let conn_string = match db_type {
-                "mysql" => format!(
-                    "mysql://{}:{}@{}:{}/{}",
-                    row.username, password, row.host, row.port, row.database_name
-                ),
-                "postgres" => format!(
-                    "postgres://{}:{}@{}:{}/{}",
-                    row.username, password, row.host, row.port, row.database_name
-                ),
-                other => {
-                    tracing::error!(
-                        connection_id = %row.id,
-                        db_type = %other,
-                        "Unsupported db_type in saved connection, skipping"
-                    );
-                    continue;
-                }
-            };
-
-            let datasource_result: Result<Arc<dyn DataSource>, _> = match db_type {
-                "mysql" => MySqlDataSource::new(&conn_string)
-                    .await
-                    .map(|ds| Arc::new(ds) as Arc<dyn DataSource>),
-                "postgres" => PostgresDataSource::new(&conn_string)
-                    .await
-                    .map(|ds| Arc::new(ds) as Arc<dyn DataSource>),
-                _ => unreachable!(), // already handled above
+            let info = ConnectionInfo {
+                id: row.id,
+                name: row.name.clone(),
+                db_type: row.db_type.clone(),
+                host: row.host.clone(),
+                port: row.port as u16,
+                database: row.database_name.clone(),
+                user: row.username.clone(),
+                password,
+                organization_id: row.organization_id,
+                owner_user_id: row.owner_user_id,
+                tags: row.tags.clone(),
+                statement_timeout_ms: row.statement_timeout_ms.map(|ms| ms as u32),
+                pool_idle_timeout_ms: row.pool_idle_timeout_ms.map(|ms| ms as u32),
+                pool_max_lifetime_ms: row.pool_max_lifetime_ms.map(|ms| ms as u32),
+                read_only: row.read_only,
+                default_page_size: row.default_page_size.map(|n| n as u32),
+                default_sort_order: row.default_sort_order.clone(),
+                search_path: row.search_path.clone(),
+                default_schema: row.default_schema.clone(),
+                role: row.role.clone(),
+                hidden_tables: row.hidden_tables.clone(),
+                replica_hosts: row.replica_hosts.clone(),
+                persisted: true,
             };
 
-            match datasource_result {
-                Ok(ds) => {
-                    let info = ConnectionInfo {
-                        id: row.id,
-                        name: row.name.clone(),
-                        db_type: row.db_type.clone(),
-                        host: row.host.clone(),
-                        port: row.port as u16,
-                        database: row.database_name.clone(),
-                        user: row.username.clone(),
-                        password,
-                        organization_id: row.organization_id,
-                        owner_user_id: row.owner_user_id,
-                    };
+            match self.acquire_pool(&info).await {
+                Ok((ds, pool_key)) => {
                     let entry = ConnectionEntry {
                         info,
                         datasource: ds,
+                        pool_key,
+                        usage: ConnectionUsage::default(),
                     };
-                    self.connections.write().await.insert(row.id, entry);
+                    // A concurrent add may have won the race; keep that one
+                    let raced = match self.connections.write().await.entry(row.id) {
+                        std::collections::hash_map::Entry::Occupied(_) => Some(entry),
+                        std::collections::hash_map::Entry::Vacant(slot) => {
+                            slot.insert(entry);
+                            None
+                        }
+                    };
+                    if let Some(entry) = raced {
+                        self.release_pool(entry.datasource, entry.pool_key).await;
+                    }
                     tracing::info!(
                         connection_id = %row.id,
                         name = %row.name,
                         db_type = %row.db_type,
                         "Loaded saved connection"
                     );
+                    results.push(ConnectionLoadResult {
+                        connection_id: row.id,
+                        name: row.name.clone(),
+                        error: None,
+                    });
                 }
                 Err(e) => {
                     tracing::error!(
@@ -155,14 +265,85 @@ impl ConnectionManager {
                         error = %e,
                         "Failed to connect to saved connection, skipping"
                     );
+                    results.push(failed(e.to_string()));
                 }
             }
         }
 
-        Ok(())
+        Ok(results)
+    }
+
+    /// Register the connections of a startup config. Ones whose name is
+    /// already taken (e.g. saved by an earlier boot) are skipped; every other
+    /// entry is attempted and its outcome returned.
+    pub async fn add_configured(&self, config: ConnectionsConfig) -> Vec<ConnectionImportResult> {
+        let mut results = Vec::new();
+        for template in config.connections {
+            if self.list().await.iter().any(|c| c.name == template.name) {
+                tracing::debug!(name = %template.name, "Configured connection already present");
+                continue;
+            }
+            let name = template.name.clone();
+            let checked = template.resolve_password().and_then(|password| {
+                let sort_order = normalize_sort_order(template.default_sort_order.clone())
+                    .map_err(anyhow::Error::msg)?;
+                Ok((password, sort_order))
+            });
+            let added = match checked {
+                Ok((password, sort_order)) => {
+                    let settings = ConnectionSettings {
+                        tags: template.tags,
+                        statement_timeout_ms: template.statement_timeout_ms,
+                        pool_idle_timeout_ms: template.pool_idle_timeout_ms,
+                        pool_max_lifetime_ms: template.pool_max_lifetime_ms,
+                        read_only: template.read_only,
+                        default_page_size: template.default_page_size,
+                        default_sort_order: sort_order,
+                        search_path: template.search_path,
+                        default_schema: template.default_schema,
+                        role: template.role,
+                        hidden_tables: template.hidden_tables,
+                        replica_hosts: template.replica_hosts,
+                    };
+                    self.add_postgres(
+                        template.name,
+                        template.host,
+                        template.port,
+                        template.database,
+                        template.user,
+                        password,
+                        template.organization_id,
+                        template.owner_user_id,
+                        settings,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            results.push(match added {
+                Ok(info) => ConnectionImportResult {
+                    name,
+                    connection_id: Some(info.id),
+                    error: None,
+                },
+                Err(e) => {
+                    tracing::error!(name = %name, error = %e, "Failed to add configured connection");
+                    ConnectionImportResult {
+                        name,
+                        connection_id: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            });
+        }
+        results
     }
 
     /// Register a new PostgreSQL connection and persist it.
+    /// `search_path`, `default_schema` and `role` are applied to every pooled
+    /// session.
+    /// `hidden_tables` are treated as nonexistent (see `HiddenTablesDataSource`)
+    /// and reads are spread across `replica_hosts` when any are given.
     #[allow(clippy::too_many_arguments)]
     pub async fn add_postgres(
         &self,
@@ -174,76 +355,39 @@ impl ConnectionManager {
         password: String,
         organization_id: Option<Uuid>,
         owner_user_id: Option<Uuid>,
+        settings: ConnectionSettings,
     ) -> anyhow::Result<ConnectionInfo> {
-        let conn_string = format!(
-            "postgres://{}:{}@{}:{}/{}",
-            user, password, host, port, database
-        );
-
-        tracing::info!(
-            name = %name,
-            host = %host,
-            port = %port,
-            database = %database,
-            user = %user,
-            "Attempting to connect to PostgreSQL..."
-        );
-
-        let datasource = match PostgresDataSource::new(&conn_string).await {
-            Ok(ds) => {
-                tracing::info!(name = %name, "Successfully connected to PostgreSQL");
-                ds
-            }
-            Err(e) => {
-                tracing::error!(
-                    name = %name,
-                    host = %host,
-                    port = %port,
-                    database = %database,
-                    error = %e,
-                    "Failed to connect to PostgreSQL"
-                );
-                return Err(e);
-            }
-        };
-
-        let id = Uuid::new_v4();
-        let info = ConnectionInfo {
-            id,
+        self.add(ConnectionInfo {
+            id: Uuid::new_v4(),
             name,
             db_type: "postgres".to_string(),
             host,
             port,
             database,
             user,
-            password: password.clone(),
+            password,
             organization_id,
             owner_user_id,
-        };
-
-        // Persist to DB if configured
-        if let Some(repo) = &self.connection_repo {
-            if let Err(e) = repo
-                .save(organization_id.as_ref(), owner_user_id.as_ref(), &info)
-                .await
-            {
-                tracing::error!(error = %e, "Failed to persist connection to DB");
-                return Err(e);
-            }
-            tracing::info!(connection_id = %id, "Connection persisted to DB");
-        }
-
-        let entry = ConnectionEntry {
-            info: info.clone(),
-            datasource: Arc::new(datasource),
-        };
-
-        self.connections.write().await.insert(id, entry);
-        tracing::info!(connection_id = %id, "Connection registered");
-        Ok(info)
+            tags: settings.tags,
+            statement_timeout_ms: settings.statement_timeout_ms,
+            pool_idle_timeout_ms: settings.pool_idle_timeout_ms,
+            pool_max_lifetime_ms: settings.pool_max_lifetime_ms,
+            read_only: settings.read_only,
+            default_page_size: settings.default_page_size,
+            default_sort_order: settings.default_sort_order,
+            search_path: settings.search_path,
+            default_schema: settings.default_schema,
+            role: settings.role,
+            hidden_tables: settings.hidden_tables,
+            replica_hosts: settings.replica_hosts,
+            persisted: false,
+        })
+        .await
     }
 
-    /// Register a new MySQL connection and persist it.
+    /// Register a new MySQL connection and persist it. The PostgreSQL-only
+    /// settings (`search_path`, `default_schema`, `role`, `replica_hosts`)
+    /// are ignored.
     #[allow(clippy::too_many_arguments)]
     pub async fn add_mysql(
         &self,
@@ -255,68 +399,118 @@ impl ConnectionManager {
         password: String,
         organization_id: Option<Uuid>,
         owner_user_id: Option<Uuid>,
+        settings: ConnectionSettings,
     ) -> anyhow::Result<ConnectionInfo> {
-        let conn_string = format!(
-            "mysql://{}:{}@{}:{}/{}",
-            user, password, host, port, database
-        );
+        self.add(ConnectionInfo {
+            id: Uuid::new_v4(),
+            name,
+            db_type: "mysql".to_string(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            organization_id,
+            owner_user_id,
+            tags: settings.tags,
+            statement_timeout_ms: settings.statement_timeout_ms,
+            pool_idle_timeout_ms: settings.pool_idle_timeout_ms,
+            pool_max_lifetime_ms: settings.pool_max_lifetime_ms,
+            read_only: settings.read_only,
+            default_page_size: settings.default_page_size,
+            default_sort_order: settings.default_sort_order,
+            search_path: None,
+            default_schema: None,
+            role: None,
+            hidden_tables: settings.hidden_tables,
+            replica_hosts: vec![],
+            persisted: false,
+        })
+        .await
+    }
 
+    /// Connect through the factory, persist (when configured) and register.
+    /// A name already used in the same scope fails with `ConnectionNameTaken`
+    /// before anything is opened.
+    /// A connection that cannot be reached is never saved, and one that
+    /// cannot be saved is closed again without being registered. When
+    /// persisted, the id is the one generated for the saved row; otherwise
+    /// the returned info has `persisted: false`.
+    async fn add(&self, mut info: ConnectionInfo) -> anyhow::Result<ConnectionInfo> {
         tracing::info!(
-            name = %name,
-            host = %host,
-            port = %port,
-            database = %database,
-            user = %user,
-            "Attempting to connect to MySQL..."
+            name = %info.name,
+            db_type = %info.db_type,
+            host = %info.host,
+            port = %info.port,
+            database = %info.database,
+            user = %info.user,
+            "Attempting to connect..."
         );
 
-        let datasource = match MySqlDataSource::new(&conn_string).await {
+        if self
+            .connections
+            .read()
+            .await
+            .values()
+            .any(|entry| entry.info.name_conflicts_with(&info))
+        {
+            return Err(ConnectionNameTaken(info.name).into());
+        }
+
+        let (datasource, pool_key) = match self.acquire_pool(&info).await {
             Ok(ds) => {
-                tracing::info!(name = %name, "Successfully connected to MySQL");
+                tracing::info!(name = %info.name, db_type = %info.db_type, "Successfully connected");
                 ds
             }
             Err(e) => {
                 tracing::error!(
-                    name = %name,
-                    host = %host,
-                    port = %port,
-                    database = %database,
+                    name = %info.name,
+                    db_type = %info.db_type,
+                    host = %info.host,
+                    port = %info.port,
+                    database = %info.database,
                     error = %e,
-                    "Failed to connect to MySQL"
+                    "Failed to connect"
                 );
                 return Err(e);
             }
         };
 
-        let id = Uuid::new_v4();
-        let info = ConnectionInfo {
-            id,
-            name,
-            db_type: "mysql".to_string(),
-            host,
-            port,
-            database,
-            user,
-            password: password.clone(),
-            organization_id,
-            owner_user_id,
-        };
-
-        // Persist to DB if configured
+        // Persist to DB if configured. The row supplies the id, and nothing is
+        // registered in memory unless the insert succeeded.
         if let Some(repo) = &self.connection_repo {
-            if let Err(e) = repo
-                .save(organization_id.as_ref(), owner_user_id.as_ref(), &info)
+            match repo
+                .save(
+                    info.organization_id.as_ref(),
+                    info.owner_user_id.as_ref(),
+                    &info,
+                )
                 .await
             {
-                tracing::error!(error = %e, "Failed to persist connection to DB");
-                return Err(e);
+                Ok(row) => {
+                    info.id = row.id;
+                    info.persisted = true;
+                    tracing::info!(connection_id = %info.id, "Connection persisted to DB");
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to persist connection to DB");
+                    self.release_pool(datasource, pool_key).await;
+                    return Err(e);
+                }
             }
-            tracing::info!(connection_id = %id, "Connection persisted to DB");
+        } else {
+            tracing::warn!(
+                name = %info.name,
+                "No connection repository configured; connection will be lost on restart"
+            );
         }
 
+        let id = info.id;
         let entry = ConnectionEntry {
             info: info.clone(),
-            datasource: Arc::new(datasource),
+            datasource,
+            pool_key,
+            usage: ConnectionUsage::default(),
         };
 
         self.connections.write().await.insert(id, entry);
@@ -324,14 +518,207 @@ impl ConnectionManager {
         Ok(info)
     }
 
-    /// Get a datasource by connection ID
+    /// Build the datasource for `info`, or with shared pools reuse the live
+    /// one registered under the same key. Hand both back to `release_pool`
+    /// when the connection lets go of it.
+    async fn acquire_pool(
+        &self,
+        info: &ConnectionInfo,
+    ) -> anyhow::Result<(Arc<dyn DataSource>, Option<PoolKey>)> {
+        if !self.share_pools {
+            return Ok((self.factory.build(info).await?, None));
+        }
+        let key = info.pool_key();
+        let claim = |pools: &mut HashMap<PoolKey, SharedPool>| {
+            pools.get_mut(&key).map(|shared| {
+                shared.users += 1;
+                shared.datasource.clone()
+            })
+        };
+        if let Some(ds) = claim(&mut self.pools.lock().unwrap()) {
+            tracing::debug!(name = %info.name, "Reusing shared pool");
+            return Ok((ds, Some(key)));
+        }
+        let built = self.factory.build(info).await?;
+        // Another connection may have opened the same pool meanwhile
+        let existing = {
+            let mut pools = self.pools.lock().unwrap();
+            let existing = claim(&mut pools);
+            if existing.is_none() {
+                pools.insert(
+                    key.clone(),
+                    SharedPool {
+                        datasource: built.clone(),
+                        users: 1,
+                    },
+                );
+            }
+            existing
+        };
+        match existing {
+            Some(ds) => {
+                built.close().await;
+                Ok((ds, Some(key)))
+            }
+            None => Ok((built, Some(key))),
+        }
+    }
+
+    /// Let go of a datasource from `acquire_pool`, closing it unless another
+    /// connection still shares it.
+    async fn release_pool(&self, datasource: Arc<dyn DataSource>, pool_key: Option<PoolKey>) {
+        if let Some(key) = pool_key {
+            let mut pools = self.pools.lock().unwrap();
+            if let Some(shared) = pools.get_mut(&key) {
+                shared.users -= 1;
+                if shared.users > 0 {
+                    return;
+                }
+                pools.remove(&key);
+            }
+        }
+        datasource.close().await;
+    }
+
+    /// Connect through the factory without registering or persisting the
+    /// connection. The caller owns the datasource and must `close` it.
+    pub async fn connect_unregistered(
+        &self,
+        info: &ConnectionInfo,
+    ) -> anyhow::Result<Arc<dyn DataSource>> {
+        self.factory.build(info).await
+    }
+
+    /// Keep `tx` open for `user_id` on `conn_id` and return the token later
+    /// requests use to reach it. It is rolled back if still open after `timeout`.
+    /// Returns `None`, dropping (and so rolling back) `tx`, when the user
+    /// already holds `limit` transactions on the connection.
+    pub fn hold_transaction(
+        &self,
+        conn_id: Uuid,
+        user_id: Uuid,
+        tx: DataSourceTransaction,
+        timeout: Duration,
+        limit: usize,
+    ) -> Option<Uuid> {
+        let token = Uuid::new_v4();
+        {
+            let mut transactions = self.transactions.lock().unwrap();
+            let open = transactions
+                .values()
+                .filter(|held| held.conn_id == conn_id && held.user_id == user_id)
+                .count();
+            if open >= limit {
+                return None;
+            }
+            transactions.insert(
+                token,
+                HeldTransaction {
+                    conn_id,
+                    user_id,
+                    tx: Arc::new(tx),
+                    audit: Vec::new(),
+                },
+            );
+        }
+        let transactions = self.transactions.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            let expired = transactions.lock().unwrap().remove(&token);
+            if let Some(held) = expired {
+                tracing::warn!(transaction = %token, connection_id = %held.conn_id, "Rolling back abandoned transaction");
+                if let Err(e) = held.tx.rollback().await {
+                    tracing::warn!(transaction = %token, error = %e, "Failed to roll back abandoned transaction");
+                }
+            }
+        });
+        Some(token)
+    }
+
+    /// The open transaction `token`, if `user_id` began it on `conn_id`.
+    pub fn transaction(
+        &self,
+        token: &Uuid,
+        conn_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Option<Arc<DataSourceTransaction>> {
+        self.transactions
+            .lock()
+            .unwrap()
+            .get(token)
+            .filter(|held| held.conn_id == *conn_id && held.user_id == *user_id)
+            .map(|held| held.tx.clone())
+    }
+
+    /// Like `transaction`, but stops holding it so it can be committed or
+    /// rolled back. Also hands back the audit entries deferred to it.
+    pub fn release_transaction(
+        &self,
+        token: &Uuid,
+        conn_id: &Uuid,
+        user_id: &Uuid,
+    ) -> Option<(Arc<DataSourceTransaction>, Vec<PendingAuditEntry>)> {
+        let mut transactions = self.transactions.lock().unwrap();
+        let held = transactions.get(token)?;
+        if held.conn_id != *conn_id || held.user_id != *user_id {
+            return None;
+        }
+        transactions.remove(token).map(|held| (held.tx, held.audit))
+    }
+
+    /// Keep an audit entry for a write made in transaction `token` until it
+    /// is released. Dropped if the transaction is no longer held.
+    pub fn defer_audit(&self, token: &Uuid, entry: PendingAuditEntry) {
+        if let Some(held) = self.transactions.lock().unwrap().get_mut(token) {
+            held.audit.push(entry);
+        }
+    }
+
+    /// Close every live datasource pool and clear the registry.
+    /// Called once on server shutdown, after in-flight requests have drained.
+    pub async fn shutdown(&self) {
+        // Open transactions hold pool connections that `close` would wait for
+        let held: Vec<HeldTransaction> = self
+            .transactions
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, held)| held)
+            .collect();
+        for held in held {
+            held.tx.rollback().await.ok();
+        }
+
+        let entries: Vec<(Uuid, ConnectionEntry)> =
+            self.connections.write().await.drain().collect();
+        tracing::info!(count = entries.len(), "Closing live connections");
+        for (id, entry) in entries {
+            self.release_pool(entry.datasource, entry.pool_key).await;
+            tracing::debug!(connection_id = %id, "Connection closed");
+        }
+    }
+
+    /// Publish live connection count and per-connection pool gauges.
+    pub async fn report_metrics(&self) {
+        let connections = self.connections.read().await;
+        metrics::set_live_connections(connections.len());
+        for (id, entry) in connections.iter() {
+            if let Some(status) = entry.datasource.pool_status() {
+                metrics::set_pool_status(id, status);
+            }
+        }
+    }
+
+    /// Get a datasource by connection ID, recording the access in its usage
+    /// stats. Calls through it are timed into the connection's profile.
     pub async fn get_datasource(&self, id: &Uuid) -> Option<Arc<dyn DataSource>> {
-        let result = self
-            .connections
-            .read()
-            .await
-            .get(id)
-            .map(|e| e.datasource.clone());
+        let result = self.connections.read().await.get(id).map(|e| {
+            e.usage.record();
+            Arc::new(ProfilingDataSource::new(
+                e.datasource.clone(),
+                e.usage.profile.clone(),
+            )) as Arc<dyn DataSource>
+        });
 
         if result.is_none() {
             tracing::warn!(connection_id = %id, "Connection not found");
@@ -340,6 +727,43 @@ impl ConnectionManager {
         result
     }
 
+    /// Like `get_datasource`, but calls through the returned datasource are
+    /// recorded in the query log as made by `user_id`, if one is configured.
+    pub async fn get_datasource_for(
+        &self,
+        id: &Uuid,
+        user_id: &Uuid,
+    ) -> Option<Arc<dyn DataSource>> {
+        let ds = self.get_datasource(id).await?;
+        Some(match &self.query_log {
+            Some(log) => Arc::new(QueryLoggingDataSource::new(ds, log.clone(), *id, *user_id)),
+            None => ds,
+        })
+    }
+
+    /// Usage stats for a connection, or `None` if it is not registered
+    pub async fn stats(&self, id: &Uuid) -> Option<ConnectionStats> {
+        self.connections
+            .read()
+            .await
+            .get(id)
+            .map(|e| ConnectionStats {
+                connection_id: *id,
+                last_accessed_at: *e.usage.last_accessed_at.lock().unwrap(),
+                query_count: e.usage.query_count.load(Ordering::Relaxed),
+            })
+    }
+
+    /// The connection's recently profiled calls, slowest first, or `None`
+    /// if it is not registered
+    pub async fn slow_queries(&self, id: &Uuid) -> Option<Vec<SlowQuery>> {
+        self.connections
+            .read()
+            .await
+            .get(id)
+            .map(|e| e.usage.profile.slowest())
+    }
+
     /// List all connection infos
     pub async fn list(&self) -> Vec<ConnectionInfo> {
         let connections: Vec<ConnectionInfo> = self
@@ -375,21 +799,196 @@ impl ConnectionManager {
             .collect()
     }
 
-    /// Remove a connection (also deletes from DB)
-    pub async fn remove(&self, id: &Uuid) -> bool {
-        let removed = self.connections.write().await.remove(id).is_some();
-        if removed {
-            // Delete from DB
-            if let Some(repo) = &self.connection_repo
-                && let Err(e) = repo.delete(id).await
+    /// Replace the tags of a connection (persisted to DB when configured).
+    /// Returns false if the connection is not registered.
+    pub async fn set_tags(&self, id: &Uuid, tags: Vec<String>) -> anyhow::Result<bool> {
+        if !self.connections.read().await.contains_key(id) {
+            tracing::warn!(connection_id = %id, "Attempted to tag non-existent connection");
+            return Ok(false);
+        }
+
+        if let Some(repo) = &self.connection_repo {
+            repo.set_tags(id, &tags).await?;
+        }
+
+        let mut connections = self.connections.write().await;
+        match connections.get_mut(id) {
+            Some(entry) => {
+                entry.info.tags = tags;
+                tracing::info!(connection_id = %id, tags = ?entry.info.tags, "Connection tags updated");
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Turn the read-only guard on or off (persisted to DB when configured).
+    /// Returns false if the connection is not registered.
+    pub async fn set_read_only(&self, id: &Uuid, read_only: bool) -> anyhow::Result<bool> {
+        if !self.connections.read().await.contains_key(id) {
+            return Ok(false);
+        }
+
+        if let Some(repo) = &self.connection_repo {
+            repo.set_read_only(id, read_only).await?;
+        }
+
+        let mut connections = self.connections.write().await;
+        match connections.get_mut(id) {
+            Some(entry) => {
+                entry.info.read_only = read_only;
+                tracing::info!(connection_id = %id, read_only, "Connection read-only flag updated");
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Swap in a new password: connect with it first, then persist it and
+    /// replace the live pool. Returns false for unknown connections.
+    pub async fn rotate_password(&self, id: &Uuid, password: String) -> anyhow::Result<bool> {
+        let Some(mut info) = self.get_info(id).await else {
+            return Ok(false);
+        };
+        info.password = password;
+        let (datasource, pool_key) = self.acquire_pool(&info).await?;
+
+        if let Some(repo) = &self.connection_repo
+            && let Err(e) = repo.set_password(id, &info.password).await
+        {
+            self.release_pool(datasource, pool_key).await;
+            return Err(e);
+        }
+
+        let swapped = {
+            let mut connections = self.connections.write().await;
+            match connections.get_mut(id) {
+                Some(entry) => {
+                    entry.info.password = info.password;
+                    Ok((
+                        std::mem::replace(&mut entry.datasource, datasource),
+                        std::mem::replace(&mut entry.pool_key, pool_key),
+                    ))
+                }
+                None => Err((datasource, pool_key)),
+            }
+        };
+        let (old, old_key) = match swapped {
+            Ok(old) => old,
+            Err((datasource, pool_key)) => {
+                self.release_pool(datasource, pool_key).await;
+                return Ok(false);
+            }
+        };
+        self.release_pool(old, old_key).await;
+        tracing::info!(connection_id = %id, "Connection password rotated");
+        Ok(true)
+    }
+
+    /// Reassign a connection's personal owner, in the DB and in memory.
+    /// Fails with `ConnectionNameTaken` if the new owner already has a
+    /// connection of that name.
+    /// Returns false for unknown connections.
+    pub async fn set_owner(&self, id: &Uuid, owner_user_id: &Uuid) -> anyhow::Result<bool> {
+        {
+            let connections = self.connections.read().await;
+            let Some(entry) = connections.get(id) else {
+                return Ok(false);
+            };
+            let moved = ConnectionInfo {
+                owner_user_id: Some(*owner_user_id),
+                ..entry.info.clone()
+            };
+            if connections
+                .iter()
+                .any(|(other_id, other)| other_id != id && other.info.name_conflicts_with(&moved))
             {
-                tracing::error!(connection_id = %id, error = %e, "Failed to delete connection from DB");
+                return Err(ConnectionNameTaken(moved.name).into());
             }
-            tracing::info!(connection_id = %id, "Connection removed");
-        } else {
+        }
+
+        if let Some(repo) = &self.connection_repo {
+            repo.set_owner(id, owner_user_id).await?;
+        }
+
+        let mut connections = self.connections.write().await;
+        match connections.get_mut(id) {
+            Some(entry) => {
+                entry.info.owner_user_id = Some(*owner_user_id);
+                tracing::info!(connection_id = %id, owner_user_id = %owner_user_id, "Connection owner updated");
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Snapshot of a registered connection's info.
+    pub async fn get_info(&self, id: &Uuid) -> Option<ConnectionInfo> {
+        self.connections
+            .read()
+            .await
+            .get(id)
+            .map(|entry| entry.info.clone())
+    }
+
+    /// Subscribe to row changes on a table, sharing one listener
+    /// connection between all subscribers of that table.
+    pub async fn subscribe(
+        &self,
+        info: &ConnectionInfo,
+        table: &str,
+    ) -> anyhow::Result<broadcast::Receiver<RowChange>> {
+        self.change_feed
+            .subscribe(info.id, &connection_string(info), table)
+            .await
+    }
+
+    /// Whether the connection refuses writes. Unknown connections report false.
+    pub async fn is_read_only(&self, id: &Uuid) -> bool {
+        self.connections
+            .read()
+            .await
+            .get(id)
+            .is_some_and(|entry| entry.info.read_only)
+    }
+
+    /// The connection's page size for listings that omit `per_page`, if set.
+    pub async fn default_page_size(&self, id: &Uuid) -> Option<u32> {
+        self.connections
+            .read()
+            .await
+            .get(id)
+            .and_then(|entry| entry.info.default_page_size)
+    }
+
+    /// The connection's sort direction for listings that omit `sort_order`,
+    /// if set.
+    pub async fn default_sort_order(&self, id: &Uuid) -> Option<String> {
+        self.connections
+            .read()
+            .await
+            .get(id)
+            .and_then(|entry| entry.info.default_sort_order.clone())
+    }
+
+    /// Remove a connection (also deletes from DB) and close its pool
+    pub async fn remove(&self, id: &Uuid) -> bool {
+        let Some(entry) = self.connections.write().await.remove(id) else {
             tracing::warn!(connection_id = %id, "Attempted to remove non-existent connection");
+            return false;
+        };
+        self.change_feed.remove_connection(id).await;
+        metrics::clear_pool_status(id);
+        // A shared pool stays open for the connections still using it
+        self.release_pool(entry.datasource, entry.pool_key).await;
+        // Delete from DB
+        if let Some(repo) = &self.connection_repo
+            && let Err(e) = repo.delete(id).await
+        {
+            tracing::error!(connection_id = %id, error = %e, "Failed to delete connection from DB");
         }
-        removed
+        tracing::info!(connection_id = %id, "Connection removed");
+        true
     }
 }
 
@@ -398,7 +997,8 @@ mod tests {
     use super::*;
     use std::sync::Arc;
 
-    use crate::domain::data::{RowsResponse, TableInfo, TableSchema};
+    use crate::domain::connection::SavedConnectionRow;
+    use crate::domain::data::{AggregateRow, AggregateSpec, RowsResponse, TableInfo, TableSchema};
     use crate::presentation::request::RowsQuery;
     use async_trait::async_trait;
 
@@ -413,16 +1013,46 @@ mod tests {
         async fn get_table_schema(&self, _: &str) -> anyhow::Result<TableSchema> {
             anyhow::bail!("mock")
         }
-        async fn list_rows(&self, _: &str, _: &RowsQuery) -> anyhow::Result<RowsResponse> {
+        async fn is_view(&self, _: &str) -> anyhow::Result<bool> {
+            anyhow::bail!("mock")
+        }
+        async fn list_rows(
+            &self,
+            _: &str,
+            _: &RowsQuery,
+            _: Option<&DataSourceTransaction>,
+        ) -> anyhow::Result<RowsResponse> {
+            anyhow::bail!("mock")
+        }
+        async fn count_rows(&self, _: &str, _: Option<&str>) -> anyhow::Result<i64> {
+            anyhow::bail!("mock")
+        }
+        async fn distinct_values(
+            &self,
+            _: &str,
+            _: &str,
+            _: u32,
+        ) -> anyhow::Result<Vec<serde_json::Value>> {
+            anyhow::bail!("mock")
+        }
+        async fn aggregate(&self, _: &str, _: &AggregateSpec) -> anyhow::Result<Vec<AggregateRow>> {
             anyhow::bail!("mock")
         }
         async fn get_row(&self, _: &str, _: &str) -> anyhow::Result<serde_json::Value> {
             anyhow::bail!("mock")
         }
+        async fn explain_rows(&self, _: &str, _: &RowsQuery) -> anyhow::Result<serde_json::Value> {
+            anyhow::bail!("mock")
+        }
+        async fn get_rows(&self, _: &str, _: &[String]) -> anyhow::Result<Vec<serde_json::Value>> {
+            anyhow::bail!("mock")
+        }
         async fn insert_row(
             &self,
             _: &str,
             _: &serde_json::Value,
+            _: &[String],
+            _: Option<&DataSourceTransaction>,
         ) -> anyhow::Result<serde_json::Value> {
             anyhow::bail!("mock")
         }
@@ -431,14 +1061,135 @@ mod tests {
             _: &str,
             _: &str,
             _: &serde_json::Value,
+            _: Option<&DataSourceTransaction>,
         ) -> anyhow::Result<serde_json::Value> {
             anyhow::bail!("mock")
         }
-        async fn delete_row(&self, _: &str, _: &str) -> anyhow::Result<()> {
+        async fn delete_row(
+            &self,
+            _: &str,
+            _: &str,
+            _: Option<&DataSourceTransaction>,
+        ) -> anyhow::Result<()> {
+            anyhow::bail!("mock")
+        }
+        async fn count_by_pk(&self, _: &str, _: &str) -> anyhow::Result<i64> {
+            anyhow::bail!("mock")
+        }
+        async fn bulk_update(
+            &self,
+            _: &str,
+            _: &str,
+            _: &serde_json::Value,
+        ) -> anyhow::Result<u64> {
+            anyhow::bail!("mock")
+        }
+        async fn bulk_delete(&self, _: &str, _: &str) -> anyhow::Result<u64> {
             anyhow::bail!("mock")
         }
     }
 
+    /// Factory that hands out `MockDataSource`s without touching a database.
+    struct MockFactory;
+
+    #[async_trait]
+    impl DataSourceFactory for MockFactory {
+        async fn build(&self, info: &ConnectionInfo) -> anyhow::Result<Arc<dyn DataSource>> {
+            if info.host == "unreachable" {
+                anyhow::bail!("connection refused");
+            }
+            Ok(Arc::new(MockDataSource))
+        }
+    }
+
+    /// `MockFactory` that counts how many datasources it has built.
+    #[derive(Default)]
+    struct CountingFactory {
+        builds: AtomicU64,
+    }
+
+    #[async_trait]
+    impl DataSourceFactory for CountingFactory {
+        async fn build(&self, _info: &ConnectionInfo) -> anyhow::Result<Arc<dyn DataSource>> {
+            self.builds.fetch_add(1, Ordering::SeqCst);
+            Ok(Arc::new(MockDataSource))
+        }
+    }
+
+    /// Connection repository whose `save` either fails or returns a row with
+    /// a fixed id; nothing else is expected to be called.
+    struct StubConnectionRepository {
+        saved_id: Option<Uuid>,
+    }
+
+    #[async_trait]
+    impl ConnectionRepository for StubConnectionRepository {
+        async fn save(
+            &self,
+            org_id: Option<&Uuid>,
+            owner_user_id: Option<&Uuid>,
+            info: &ConnectionInfo,
+        ) -> anyhow::Result<SavedConnectionRow> {
+            let Some(id) = self.saved_id else {
+                anyhow::bail!("insert failed");
+            };
+            Ok(SavedConnectionRow {
+                id,
+                organization_id: org_id.copied(),
+                name: info.name.clone(),
+                db_type: info.db_type.clone(),
+                host: info.host.clone(),
+                port: info.port as i32,
+                database_name: info.database.clone(),
+                username: info.user.clone(),
+                encrypted_password: String::new(),
+                created_by: None,
+                owner_user_id: owner_user_id.copied(),
+                created_at: None,
+                updated_at: None,
+                tags: info.tags.clone(),
+                statement_timeout_ms: None,
+                pool_idle_timeout_ms: None,
+                pool_max_lifetime_ms: None,
+                read_only: info.read_only,
+                default_page_size: None,
+                default_sort_order: None,
+                search_path: None,
+                default_schema: None,
+                role: None,
+                hidden_tables: vec![],
+                replica_hosts: vec![],
+            })
+        }
+        async fn list(&self) -> anyhow::Result<Vec<SavedConnectionRow>> {
+            unreachable!()
+        }
+        async fn get(&self, _: &Uuid) -> anyhow::Result<Option<SavedConnectionRow>> {
+            unreachable!()
+        }
+        async fn delete(&self, _: &Uuid) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+        async fn get_ownership(
+            &self,
+            _: &Uuid,
+        ) -> anyhow::Result<Option<(Option<Uuid>, Option<Uuid>)>> {
+            unreachable!()
+        }
+        async fn set_tags(&self, _: &Uuid, _: &[String]) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+        async fn set_read_only(&self, _: &Uuid, _: bool) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+        async fn set_password(&self, _: &Uuid, _: &str) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+        async fn set_owner(&self, _: &Uuid, _: &Uuid) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+    }
+
     fn make_entry(org_id: Option<Uuid>, owner_id: Option<Uuid>) -> (Uuid, ConnectionEntry) {
         let id = Uuid::new_v4();
         let info = ConnectionInfo {
@@ -452,10 +1203,25 @@ mod tests {
             password: "pass".to_string(),
             organization_id: org_id,
             owner_user_id: owner_id,
+            tags: vec![],
+            statement_timeout_ms: None,
+            pool_idle_timeout_ms: None,
+            pool_max_lifetime_ms: None,
+            read_only: false,
+            default_page_size: None,
+            default_sort_order: None,
+            search_path: None,
+            default_schema: None,
+            role: None,
+            hidden_tables: vec![],
+            replica_hosts: vec![],
+            persisted: false,
         };
         let entry = ConnectionEntry {
             info,
             datasource: Arc::new(MockDataSource),
+            pool_key: None,
+            usage: ConnectionUsage::default(),
         };
         (id, entry)
     }
@@ -514,6 +1280,43 @@ mod tests {
         assert_eq!(personal[0].owner_user_id, Some(user_a));
     }
 
+    #[tokio::test]
+    async fn set_tags_updates_in_memory_info() {
+        let cm = ConnectionManager::new(None, None);
+        let (id, entry) = make_entry(None, None);
+        cm.connections.write().await.insert(id, entry);
+
+        assert!(cm.set_tags(&id, vec!["prod".to_string()]).await.unwrap());
+        let listed = cm.list().await;
+        assert_eq!(listed[0].tags, vec!["prod".to_string()]);
+
+        assert!(!cm.set_tags(&Uuid::new_v4(), vec![]).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn set_read_only_updates_in_memory_info() {
+        let cm = ConnectionManager::new(None, None);
+        let (id, entry) = make_entry(None, None);
+        cm.connections.write().await.insert(id, entry);
+
+        assert!(!cm.is_read_only(&id).await);
+        assert!(cm.set_read_only(&id, true).await.unwrap());
+        assert!(cm.is_read_only(&id).await);
+        assert!(!cm.set_read_only(&Uuid::new_v4(), true).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn shutdown_empties_connection_map() {
+        let cm = ConnectionManager::new(None, None);
+        for _ in 0..2 {
+            let (id, entry) = make_entry(None, Some(Uuid::new_v4()));
+            cm.connections.write().await.insert(id, entry);
+        }
+
+        cm.shutdown().await;
+        assert!(cm.list().await.is_empty());
+    }
+
     #[tokio::test]
     async fn get_datasource_unknown_id_returns_none() {
         let cm = ConnectionManager::new(None, None);
@@ -527,4 +1330,177 @@ mod tests {
         cm.connections.write().await.insert(id, entry);
         assert!(cm.get_datasource(&id).await.is_some());
     }
+
+    #[tokio::test]
+    async fn add_uses_injected_factory() {
+        let cm = ConnectionManager::new(None, None).with_factory(Arc::new(MockFactory));
+        let owner = Uuid::new_v4();
+        let info = cm
+            .add_postgres(
+                "mocked".to_string(),
+                "localhost".to_string(),
+                5432,
+                "db".to_string(),
+                "user".to_string(),
+                "pass".to_string(),
+                None,
+                Some(owner),
+                ConnectionSettings::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(info.db_type, "postgres");
+        assert!(!info.persisted);
+        assert_eq!(cm.list_personal(&owner).await.len(), 1);
+        let ds = cm.get_datasource(&info.id).await.unwrap();
+        assert!(ds.list_tables().await.unwrap().is_empty());
+    }
+
+    async fn add_mock(cm: &ConnectionManager, name: &str, read_only: bool) -> ConnectionInfo {
+        cm.add_postgres(
+            name.to_string(),
+            "localhost".to_string(),
+            5432,
+            "db".to_string(),
+            "user".to_string(),
+            "pass".to_string(),
+            None,
+            Some(Uuid::new_v4()),
+            ConnectionSettings {
+                read_only,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn identical_connections_share_one_pool() {
+        let factory = Arc::new(CountingFactory::default());
+        let cm = ConnectionManager::new(None, None)
+            .with_factory(factory.clone())
+            .with_shared_pools(true);
+
+        let first = add_mock(&cm, "first", false).await;
+        let second = add_mock(&cm, "second", false).await;
+        let read_only = add_mock(&cm, "read-only", true).await;
+
+        assert_eq!(factory.builds.load(Ordering::SeqCst), 2);
+        {
+            let connections = cm.connections.read().await;
+            let pool = |id: &Uuid| connections[id].datasource.clone();
+            assert!(Arc::ptr_eq(&pool(&first.id), &pool(&second.id)));
+            assert!(!Arc::ptr_eq(&pool(&first.id), &pool(&read_only.id)));
+        }
+
+        // The shared pool outlives the first connection removed
+        assert!(cm.remove(&first.id).await);
+        assert_eq!(cm.pools.lock().unwrap().len(), 2);
+        assert!(cm.remove(&second.id).await);
+        assert!(cm.remove(&read_only.id).await);
+        assert!(cm.pools.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn pools_are_not_shared_unless_enabled() {
+        let factory = Arc::new(CountingFactory::default());
+        let cm = ConnectionManager::new(None, None).with_factory(factory.clone());
+
+        add_mock(&cm, "first", false).await;
+        add_mock(&cm, "second", false).await;
+
+        assert_eq!(factory.builds.load(Ordering::SeqCst), 2);
+        assert!(cm.pools.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn add_factory_error_registers_nothing() {
+        let cm = ConnectionManager::new(None, None).with_factory(Arc::new(MockFactory));
+        let result = cm
+            .add_mysql(
+                "down".to_string(),
+                "unreachable".to_string(),
+                3306,
+                "db".to_string(),
+                "user".to_string(),
+                "pass".to_string(),
+                None,
+                None,
+                ConnectionSettings::default(),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(cm.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_datasource_updates_usage_stats() {
+        let cm = ConnectionManager::new(None, None);
+        let (id, entry) = make_entry(None, None);
+        cm.connections.write().await.insert(id, entry);
+
+        let before = cm.stats(&id).await.unwrap();
+        assert!(before.last_accessed_at.is_none());
+        assert_eq!(before.query_count, 0);
+
+        cm.get_datasource(&id).await.unwrap();
+        cm.get_datasource(&id).await.unwrap();
+
+        let after = cm.stats(&id).await.unwrap();
+        assert!(after.last_accessed_at.is_some());
+        assert_eq!(after.query_count, 2);
+        assert!(cm.stats(&Uuid::new_v4()).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn add_save_error_registers_nothing() {
+        let repo = Arc::new(StubConnectionRepository { saved_id: None });
+        let cm = ConnectionManager::new(Some(repo), None).with_factory(Arc::new(MockFactory));
+        let result = cm
+            .add_postgres(
+                "unsaved".to_string(),
+                "localhost".to_string(),
+                5432,
+                "db".to_string(),
+                "user".to_string(),
+                "pass".to_string(),
+                None,
+                Some(Uuid::new_v4()),
+                ConnectionSettings::default(),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert!(cm.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn add_registers_under_saved_row_id() {
+        let saved_id = Uuid::new_v4();
+        let repo = Arc::new(StubConnectionRepository {
+            saved_id: Some(saved_id),
+        });
+        let cm = ConnectionManager::new(Some(repo), None).with_factory(Arc::new(MockFactory));
+        let info = cm
+            .add_postgres(
+                "saved".to_string(),
+                "localhost".to_string(),
+                5432,
+                "db".to_string(),
+                "user".to_string(),
+                "pass".to_string(),
+                None,
+                Some(Uuid::new_v4()),
+                ConnectionSettings::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(info.id, saved_id);
+        assert!(info.persisted);
+        assert!(cm.get_datasource(&saved_id).await.is_some());
+    }
 }
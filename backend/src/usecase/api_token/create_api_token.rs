@@ -0,0 +1,57 @@
+use uuid::Uuid;
+
+use crate::domain::api_token::CreatedApiToken;
+use crate::domain::repository::{ApiTokenRepository, ConnectionRepository};
+use crate::domain::user::AppUser;
+use crate::infrastructure::auth::api_token;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+use super::require_saved_connection;
+
+/// Issue a token scoped to one connection. The plaintext is returned here
+/// and never again; only its hash is stored.
+pub async fn create_api_token(
+    api_token_repo: &dyn ApiTokenRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    name: &str,
+    permission: &str,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<CreatedApiToken, UsecaseError> {
+    require_super_admin(caller)?;
+    if name.trim().is_empty() {
+        return Err(UsecaseError::BadRequest(
+            "name must not be empty".to_string(),
+        ));
+    }
+    if !matches!(permission, "read" | "write") {
+        return Err(UsecaseError::BadRequest(format!(
+            "Invalid permission '{}': expected read or write",
+            permission
+        )));
+    }
+    if expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+        return Err(UsecaseError::BadRequest(
+            "expires_at must be in the future".to_string(),
+        ));
+    }
+    require_saved_connection(conn_repo, conn_id).await?;
+
+    let token = api_token::generate();
+    let created = api_token_repo
+        .create(
+            conn_id,
+            name.trim(),
+            permission,
+            &api_token::hash(&token),
+            &caller.id,
+            expires_at,
+        )
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    Ok(CreatedApiToken {
+        api_token: created,
+        token,
+    })
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+
+use crate::domain::api_token::ApiToken;
+use crate::domain::repository::{ApiTokenRepository, ConnectionRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+use super::require_saved_connection;
+
+pub async fn list_api_tokens(
+    api_token_repo: &dyn ApiTokenRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<Vec<ApiToken>, UsecaseError> {
+    require_super_admin(caller)?;
+    require_saved_connection(conn_repo, conn_id).await?;
+    api_token_repo
+        .list_by_connection(conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,29 @@
+mod create_api_token;
+mod list_api_tokens;
+mod revoke_api_token;
+
+pub use create_api_token::create_api_token;
+pub use list_api_tokens::list_api_tokens;
+pub use revoke_api_token::revoke_api_token;
+
+// ============================================================
+// Shared helpers
+// ============================================================
+
+use uuid::Uuid;
+
+use crate::domain::repository::ConnectionRepository;
+use crate::usecase::error::UsecaseError;
+
+/// Tokens can only be issued for saved connections, which they reference.
+async fn require_saved_connection(
+    conn_repo: &dyn ConnectionRepository,
+    conn_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    conn_repo
+        .get(conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .map(|_| ())
+        .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))
+}
@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use crate::domain::repository::ApiTokenRepository;
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Revoke a token. Requests using it are refused from then on.
+pub async fn revoke_api_token(
+    api_token_repo: &dyn ApiTokenRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    token_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    require_super_admin(caller)?;
+    let revoked = api_token_repo
+        .revoke(conn_id, token_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if revoked {
+        Ok(())
+    } else {
+        Err(UsecaseError::NotFound("API token not found".to_string()))
+    }
+}
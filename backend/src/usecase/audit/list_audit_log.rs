@@ -0,0 +1,33 @@
+use uuid::Uuid;
+
+use crate::domain::audit::{AuditEntry, AuditFilter};
+use crate::domain::pagination::Paginated;
+use crate::domain::repository::{
+    AuditLogRepository, ConnectionRepository, OrganizationMemberRepository,
+};
+use crate::domain::user::AppUser;
+use crate::presentation::request::AuditQuery;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+pub async fn list_audit_log(
+    audit_repo: &dyn AuditLogRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    query: &AuditQuery,
+) -> Result<Paginated<AuditEntry>, UsecaseError> {
+    require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+
+    let filter = AuditFilter {
+        action: query.action.clone(),
+        actor_id: query.actor_id,
+        since: query.since,
+        page: query.page.unwrap_or(1).max(1),
+        per_page: query.per_page.unwrap_or(20).clamp(1, 100),
+    };
+    audit_repo
+        .list(conn_id, &filter)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use crate::domain::audit::QueryLogEntry;
+use crate::domain::pagination::Paginated;
+use crate::domain::repository::QueryLogRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::request::QueryLogQuery;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+pub async fn list_query_log(
+    query_log_repo: &dyn QueryLogRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    query: &QueryLogQuery,
+) -> Result<Paginated<QueryLogEntry>, UsecaseError> {
+    require_super_admin(caller)?;
+
+    query_log_repo
+        .list(
+            conn_id,
+            query.page.unwrap_or(1).max(1),
+            query.per_page.unwrap_or(50).clamp(1, 500),
+        )
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,5 @@
+mod list_audit_log;
+mod list_query_log;
+
+pub use list_audit_log::list_audit_log;
+pub use list_query_log::list_query_log;
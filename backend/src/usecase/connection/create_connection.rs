@@ -1,11 +1,19 @@
 use uuid::Uuid;
 
-use crate::domain::connection::ConnectionInfo;
+use crate::domain::connection::{
+    ConnectionInfo, ConnectionKind, ConnectionNameTaken, ConnectionSettings,
+};
 use crate::domain::repository::OrganizationMemberRepository;
 use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::error::{UsecaseError, require_org_owner};
 
+use super::{normalize_settings, resolve_port, validate_connection_target};
+
+/// Register and persist a new connection.
+/// `default_member_permission` (`none` or `read`) only applies to org
+/// connections; org members already read those through their membership,
+/// so `read` needs no extra grant.
 #[allow(clippy::too_many_arguments)]
 pub async fn create_connection(
     connection_manager: &ConnectionManager,
@@ -14,12 +22,29 @@ pub async fn create_connection(
     name: String,
     db_type: String,
     host: String,
-    port: u16,
+    port: Option<u32>,
     database: String,
     user: String,
     password: String,
     scope_org_id: Option<Uuid>,
+    settings: ConnectionSettings,
+    default_member_permission: Option<&str>,
 ) -> Result<ConnectionInfo, UsecaseError> {
+    match default_member_permission.unwrap_or("none") {
+        "none" => {}
+        "read" if scope_org_id.is_some() => {}
+        "read" => {
+            return Err(UsecaseError::BadRequest(
+                "default_member_permission requires an organization connection".to_string(),
+            ));
+        }
+        other => {
+            return Err(UsecaseError::BadRequest(format!(
+                "Invalid default_member_permission '{}': expected none or read",
+                other
+            )));
+        }
+    }
     // If creating an org connection, require org owner
     if let Some(ref org_id) = scope_org_id {
         require_org_owner(org_member_repo, &caller.id, org_id).await?;
@@ -30,9 +55,29 @@ pub async fn create_connection(
     } else {
         (None, Some(caller.id))
     };
+    let mut problems = Vec::new();
+    if name.trim().is_empty() {
+        problems.push("name: must not be empty".to_string());
+    }
+    problems.extend(validate_connection_target(
+        &db_type, &host, &database, &user, port,
+    ));
+    if !problems.is_empty() {
+        return Err(UsecaseError::Invalid(problems));
+    }
 
-    let result = match db_type.as_str() {
-        "mysql" => {
+    let kind = ConnectionKind::parse(&db_type).ok_or_else(|| {
+        UsecaseError::BadRequest(format!(
+            "Unsupported database type: '{}'. Supported types: postgres, mysql",
+            db_type
+        ))
+    })?;
+    // Range already checked by `validate_connection_target`
+    let port = resolve_port(kind, &host, port.map(|p| p as u16))?;
+    let settings = normalize_settings(kind, settings).map_err(UsecaseError::BadRequest)?;
+
+    let result = match kind {
+        ConnectionKind::MySql => {
             connection_manager
                 .add_mysql(
                     name,
@@ -43,10 +88,11 @@ pub async fn create_connection(
                     password,
                     organization_id,
                     owner_user_id,
+                    settings,
                 )
                 .await
         }
-        "postgres" => {
+        ConnectionKind::Postgres => {
             connection_manager
                 .add_postgres(
                     name,
@@ -57,16 +103,19 @@ pub async fn create_connection(
                     password,
                     organization_id,
                     owner_user_id,
+                    settings,
                 )
                 .await
         }
-        other => {
-            return Err(UsecaseError::BadRequest(format!(
-                "Unsupported database type: '{}'. Supported types: postgres, mysql",
-                other
-            )));
+        ConnectionKind::Sqlite => {
+            return Err(UsecaseError::BadRequest(
+                "SQLite connections are not supported yet".to_string(),
+            ));
         }
     };
 
-    result.map_err(|e| UsecaseError::BadRequest(e.to_string()))
+    result.map_err(|e| match e.downcast_ref::<ConnectionNameTaken>() {
+        Some(taken) => UsecaseError::Conflict(taken.to_string()),
+        None => UsecaseError::BadRequest(e.to_string()),
+    })
 }
@@ -0,0 +1,17 @@
+use crate::domain::connection::SavedConnectionResponse;
+use crate::domain::repository::ConnectionRepository;
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Export every saved connection's configuration. Passwords are never included.
+pub async fn export_connections(
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+) -> Result<Vec<SavedConnectionResponse>, UsecaseError> {
+    require_super_admin(caller)?;
+    let rows = conn_repo
+        .list()
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    Ok(rows.iter().map(SavedConnectionResponse::from).collect())
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::domain::connection::ConnectionStats;
+use crate::domain::repository::{ConnectionRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+pub async fn get_connection_stats(
+    connection_manager: &ConnectionManager,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<ConnectionStats, UsecaseError> {
+    require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+    connection_manager
+        .stats(conn_id)
+        .await
+        .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))
+}
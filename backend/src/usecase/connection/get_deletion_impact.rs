@@ -0,0 +1,28 @@
+use uuid::Uuid;
+
+use crate::domain::permission::ConnectionDeletionImpact;
+use crate::domain::repository::{ConnectionRepository, PermissionRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Report the grants that deleting a connection would cascade away, without
+/// changing anything, so the deletion can be confirmed first.
+pub async fn get_deletion_impact(
+    conn_repo: &dyn ConnectionRepository,
+    permission_repo: &dyn PermissionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<ConnectionDeletionImpact, UsecaseError> {
+    require_super_admin(caller)?;
+    let internal = |e: anyhow::Error| UsecaseError::Internal(e.to_string());
+
+    conn_repo
+        .get(conn_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))?;
+    permission_repo
+        .count_connection_grants(conn_id)
+        .await
+        .map_err(internal)
+}
@@ -0,0 +1,102 @@
+use crate::domain::connection::{ConnectionImportResult, ConnectionKind, ConnectionSettings};
+use crate::domain::user::AppUser;
+use crate::presentation::request::ImportConnectionEntry;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+use super::normalize_settings;
+
+/// Recreate exported connections. Each entry is attempted independently and
+/// its outcome reported; one failure does not stop the rest.
+pub async fn import_connections(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    entries: Vec<ImportConnectionEntry>,
+) -> Result<Vec<ConnectionImportResult>, UsecaseError> {
+    require_super_admin(caller)?;
+
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let name = entry.name.clone();
+        let outcome = import_one(connection_manager, entry).await;
+        if let Err(e) = &outcome {
+            tracing::warn!(name = %name, error = %e, "Connection import failed");
+        }
+        results.push(match outcome {
+            Ok(id) => ConnectionImportResult {
+                name,
+                connection_id: Some(id),
+                error: None,
+            },
+            Err(e) => ConnectionImportResult {
+                name,
+                connection_id: None,
+                error: Some(e),
+            },
+        });
+    }
+    Ok(results)
+}
+
+async fn import_one(
+    connection_manager: &ConnectionManager,
+    entry: ImportConnectionEntry,
+) -> Result<uuid::Uuid, String> {
+    let password = entry
+        .password
+        .ok_or_else(|| "password is required".to_string())?;
+    let kind = ConnectionKind::parse(&entry.db_type)
+        .ok_or_else(|| format!("Unsupported database type: '{}'", entry.db_type))?;
+    let settings = ConnectionSettings {
+        tags: entry.tags,
+        statement_timeout_ms: entry.statement_timeout_ms,
+        pool_idle_timeout_ms: entry.pool_idle_timeout_ms,
+        pool_max_lifetime_ms: entry.pool_max_lifetime_ms,
+        read_only: entry.read_only,
+        default_page_size: entry.default_page_size,
+        default_sort_order: entry.default_sort_order,
+        search_path: entry.search_path,
+        default_schema: entry.default_schema,
+        role: entry.role,
+        hidden_tables: entry.hidden_tables,
+        replica_hosts: entry.replica_hosts,
+    };
+    let settings = normalize_settings(kind, settings)?;
+
+    let result = match kind {
+        ConnectionKind::Postgres => {
+            connection_manager
+                .add_postgres(
+                    entry.name,
+                    entry.host,
+                    entry.port,
+                    entry.database_name,
+                    entry.username,
+                    password,
+                    entry.organization_id,
+                    entry.owner_user_id,
+                    settings,
+                )
+                .await
+        }
+        ConnectionKind::MySql => {
+            connection_manager
+                .add_mysql(
+                    entry.name,
+                    entry.host,
+                    entry.port,
+                    entry.database_name,
+                    entry.username,
+                    password,
+                    entry.organization_id,
+                    entry.owner_user_id,
+                    settings,
+                )
+                .await
+        }
+        ConnectionKind::Sqlite => {
+            return Err(format!("Unsupported database type: '{}'", entry.db_type));
+        }
+    };
+    result.map(|info| info.id).map_err(|e| e.to_string())
+}
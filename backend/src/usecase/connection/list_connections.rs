@@ -1,16 +1,24 @@
 use uuid::Uuid;
 
 use crate::domain::connection::ConnectionInfo;
+use crate::domain::repository::PermissionRepository;
 use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
+/// Connections in the requested scope that the caller can read.
+/// Super admins see everything; everyone else only sees connections they
+/// own, belong to the organization of, or hold a user or group grant on.
+/// `search` keeps connections whose name, host or database contains it.
 pub async fn list_connections(
     connection_manager: &ConnectionManager,
+    permission_repo: &dyn PermissionRepository,
     caller: &AppUser,
     scope: Option<&str>,
+    tag: Option<&str>,
+    search: Option<&str>,
 ) -> Result<Vec<ConnectionInfo>, UsecaseError> {
-    let connections = match scope {
+    let mut connections = match scope {
         Some("personal") => connection_manager.list_personal(&caller.id).await,
         Some(s) if s.starts_with("org:") => {
             let org_id_str = &s[4..];
@@ -20,5 +28,25 @@ pub async fn list_connections(
         }
         _ => connection_manager.list().await,
     };
-    Ok(connections)
+    if let Some(tag) = tag {
+        connections.retain(|c| c.has_tag(tag));
+    }
+    if let Some(search) = search.filter(|s| !s.is_empty()) {
+        connections.retain(|c| c.matches_search(search));
+    }
+    if caller.is_super_admin() {
+        return Ok(connections);
+    }
+
+    let mut visible = Vec::with_capacity(connections.len());
+    for conn in connections {
+        let (perm, _) = permission_repo
+            .resolve_connection_permission(caller, &conn.id)
+            .await
+            .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+        if perm.can_read() {
+            visible.push(conn);
+        }
+    }
+    Ok(visible)
 }
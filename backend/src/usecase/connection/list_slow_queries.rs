@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use crate::domain::connection::SlowQuery;
+use crate::domain::repository::{ConnectionRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+/// The connection's most recent datasource calls, slowest first. Kept in
+/// memory only, so the list starts empty after a restart.
+pub async fn list_slow_queries(
+    connection_manager: &ConnectionManager,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<Vec<SlowQuery>, UsecaseError> {
+    require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+    connection_manager
+        .slow_queries(conn_id)
+        .await
+        .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))
+}
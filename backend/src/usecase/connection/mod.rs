@@ -1,7 +1,216 @@
 mod create_connection;
 mod delete_connection;
+mod export_connections;
+mod get_connection_stats;
+mod get_deletion_impact;
+mod import_connections;
 mod list_connections;
+mod list_slow_queries;
+mod preview_connection_tables;
+mod reload_connections;
+mod rotate_connection_password;
+mod set_connection_read_only;
+mod set_connection_tags;
+mod transfer_connection;
 
 pub use create_connection::create_connection;
 pub use delete_connection::delete_connection;
+pub use export_connections::export_connections;
+pub use get_connection_stats::get_connection_stats;
+pub use get_deletion_impact::get_deletion_impact;
+pub use import_connections::import_connections;
 pub use list_connections::list_connections;
+pub use list_slow_queries::list_slow_queries;
+pub use preview_connection_tables::{CONNECTION_PREVIEW_TIMEOUT, preview_connection_tables};
+pub use reload_connections::reload_connections;
+pub use rotate_connection_password::rotate_connection_password;
+pub use set_connection_read_only::set_connection_read_only;
+pub use set_connection_tags::set_connection_tags;
+pub use transfer_connection::transfer_connection;
+
+use crate::domain::connection::{
+    ConnectionKind, ConnectionSettings, normalize_sort_order, validate_session_settings,
+};
+use crate::usecase::UsecaseError;
+
+/// Trim tags, drop empty ones and remove duplicates while keeping the original order.
+pub(super) fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// Check and normalize the settings of a new `kind` connection. Create and
+/// import both go through here so they accept the same settings.
+pub(super) fn normalize_settings(
+    kind: ConnectionKind,
+    mut settings: ConnectionSettings,
+) -> Result<ConnectionSettings, String> {
+    if kind != ConnectionKind::Postgres
+        && (settings.search_path.is_some()
+            || settings.default_schema.is_some()
+            || settings.role.is_some()
+            || !settings.replica_hosts.is_empty())
+    {
+        return Err(
+            "search_path, default_schema, role and replica_hosts are only supported on PostgreSQL connections"
+                .to_string(),
+        );
+    }
+    // Stored as INTEGER columns
+    for (field, value) in [
+        ("statement_timeout_ms", settings.statement_timeout_ms),
+        ("pool_idle_timeout_ms", settings.pool_idle_timeout_ms),
+        ("pool_max_lifetime_ms", settings.pool_max_lifetime_ms),
+        ("default_page_size", settings.default_page_size),
+    ] {
+        if let Some(value) = value.filter(|v| i32::try_from(*v).is_err()) {
+            return Err(format!("{}: {} is larger than {}", field, value, i32::MAX));
+        }
+    }
+    validate_session_settings(
+        settings.default_schema.as_deref(),
+        settings.search_path.as_deref(),
+        settings.role.as_deref(),
+    )?;
+    settings.default_sort_order = normalize_sort_order(settings.default_sort_order)?;
+    settings.tags = normalize_tags(settings.tags);
+    Ok(settings)
+}
+
+/// Field-level checks on where a connection points, run before any connect
+/// attempt. Returns one `field: problem` message per invalid field. The host
+/// may be empty only for file-based kinds; an unknown `db_type` is reported later.
+pub(super) fn validate_connection_target(
+    db_type: &str,
+    host: &str,
+    database: &str,
+    user: &str,
+    port: Option<u32>,
+) -> Vec<String> {
+    let networked = ConnectionKind::parse(db_type).is_none_or(|k| k.is_networked());
+    let mut problems = Vec::new();
+    let mut required = Vec::new();
+    if networked {
+        required.push(("host", host));
+    }
+    required.extend([("database", database), ("user", user)]);
+    for (field, value) in required {
+        if value.trim().is_empty() {
+            problems.push(format!("{}: must not be empty", field));
+        }
+    }
+    if let Some(port) = port
+        && !(1..=u16::MAX as u32).contains(&port)
+    {
+        problems.push(format!("port: {} is not between 1 and 65535", port));
+    }
+    problems
+}
+
+/// Fill in the kind's default port, and reject host/port on file-based kinds.
+pub(super) fn resolve_port(
+    kind: ConnectionKind,
+    host: &str,
+    port: Option<u16>,
+) -> Result<u16, UsecaseError> {
+    if !kind.is_networked() {
+        if !host.is_empty() || port.is_some() {
+            return Err(UsecaseError::BadRequest(format!(
+                "{} connections must not specify host or port",
+                kind.as_str()
+            )));
+        }
+        return Ok(0);
+    }
+    Ok(port.or(kind.default_port()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_tags_trims_and_dedupes() {
+        let tags = vec![
+            " prod ".to_string(),
+            "analytics".to_string(),
+            "prod".to_string(),
+            "  ".to_string(),
+        ];
+        assert_eq!(
+            normalize_tags(tags),
+            vec!["prod".to_string(), "analytics".to_string()]
+        );
+    }
+
+    #[test]
+    fn normalize_settings_rejects_postgres_only_settings_elsewhere() {
+        let replicas = ConnectionSettings {
+            replica_hosts: vec!["replica-1".to_string()],
+            ..Default::default()
+        };
+        assert!(normalize_settings(ConnectionKind::MySql, replicas.clone()).is_err());
+        assert!(normalize_settings(ConnectionKind::Postgres, replicas).is_ok());
+    }
+
+    #[test]
+    fn normalize_settings_rejects_values_too_large_to_store() {
+        let timeout = ConnectionSettings {
+            statement_timeout_ms: Some(u32::MAX),
+            ..Default::default()
+        };
+        assert_eq!(
+            normalize_settings(ConnectionKind::Postgres, timeout).unwrap_err(),
+            "statement_timeout_ms: 4294967295 is larger than 2147483647"
+        );
+    }
+
+    #[test]
+    fn validate_connection_target_lists_every_problem() {
+        assert!(validate_connection_target("postgres", "h", "d", "u", Some(5432)).is_empty());
+        assert_eq!(
+            validate_connection_target("postgres", "", " ", "u", Some(70000)),
+            vec![
+                "host: must not be empty".to_string(),
+                "database: must not be empty".to_string(),
+                "port: 70000 is not between 1 and 65535".to_string(),
+            ]
+        );
+        assert!(validate_connection_target("sqlite", "", "d", "u", None).is_empty());
+    }
+
+    #[test]
+    fn resolve_port_defaults_per_kind() {
+        assert_eq!(
+            resolve_port(ConnectionKind::Postgres, "db", None).unwrap(),
+            5432
+        );
+        assert_eq!(
+            resolve_port(ConnectionKind::MySql, "db", None).unwrap(),
+            3306
+        );
+        assert_eq!(
+            resolve_port(ConnectionKind::MySql, "db", Some(3307)).unwrap(),
+            3307
+        );
+    }
+
+    #[test]
+    fn resolve_port_rejects_host_or_port_for_sqlite() {
+        assert_eq!(resolve_port(ConnectionKind::Sqlite, "", None).unwrap(), 0);
+        assert!(matches!(
+            resolve_port(ConnectionKind::Sqlite, "localhost", None),
+            Err(UsecaseError::BadRequest(_))
+        ));
+        assert!(matches!(
+            resolve_port(ConnectionKind::Sqlite, "", Some(1)),
+            Err(UsecaseError::BadRequest(_))
+        ));
+    }
+}
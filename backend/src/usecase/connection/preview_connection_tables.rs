@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::domain::connection::{ConnectionInfo, ConnectionKind, validate_session_settings};
+use crate::domain::data::TableInfo;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{resolve_port, validate_connection_target};
+
+/// Upper bound on connecting and listing tables for a preview.
+pub const CONNECTION_PREVIEW_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connect to a database that is not saved, list its tables and disconnect.
+/// Nothing is registered or persisted, so this is safe to call repeatedly
+/// while filling in a new connection.
+#[allow(clippy::too_many_arguments)]
+pub async fn preview_connection_tables(
+    connection_manager: &ConnectionManager,
+    db_type: String,
+    host: String,
+    port: Option<u32>,
+    database: String,
+    user: String,
+    password: String,
+    search_path: Option<String>,
+    role: Option<String>,
+) -> Result<Vec<TableInfo>, UsecaseError> {
+    let problems = validate_connection_target(&db_type, &host, &database, &user, port);
+    if !problems.is_empty() {
+        return Err(UsecaseError::Invalid(problems));
+    }
+    let kind = ConnectionKind::parse(&db_type)
+        .filter(|k| *k != ConnectionKind::Sqlite)
+        .ok_or_else(|| {
+            UsecaseError::BadRequest(format!(
+                "Unsupported database type: '{}'. Supported types: postgres, mysql",
+                db_type
+            ))
+        })?;
+    // Range already checked by `validate_connection_target`
+    let port = resolve_port(kind, &host, port.map(|p| p as u16))?;
+    if kind != ConnectionKind::Postgres && (search_path.is_some() || role.is_some()) {
+        return Err(UsecaseError::BadRequest(
+            "search_path and role are only supported on PostgreSQL connections".to_string(),
+        ));
+    }
+    validate_session_settings(None, search_path.as_deref(), role.as_deref())
+        .map_err(UsecaseError::BadRequest)?;
+
+    let info = ConnectionInfo {
+        id: Uuid::new_v4(),
+        name: "preview".to_string(),
+        db_type,
+        host,
+        port,
+        database,
+        user,
+        password,
+        organization_id: None,
+        owner_user_id: None,
+        tags: vec![],
+        statement_timeout_ms: None,
+        pool_idle_timeout_ms: None,
+        pool_max_lifetime_ms: None,
+        read_only: true,
+        default_page_size: None,
+        default_sort_order: None,
+        search_path,
+        default_schema: None,
+        role,
+        hidden_tables: vec![],
+        replica_hosts: vec![],
+        persisted: false,
+    };
+
+    let preview = async {
+        let ds = connection_manager
+            .connect_unregistered(&info)
+            .await
+            .map_err(|e| UsecaseError::BadRequest(e.to_string()))?;
+        let tables = ds.list_tables().await;
+        ds.close().await;
+        tables.map_err(|e| UsecaseError::Internal(e.to_string()))
+    };
+    tokio::time::timeout(CONNECTION_PREVIEW_TIMEOUT, preview)
+        .await
+        .map_err(|_| {
+            UsecaseError::Timeout(format!(
+                "Connection preview did not finish within {}s",
+                CONNECTION_PREVIEW_TIMEOUT.as_secs()
+            ))
+        })?
+}
@@ -0,0 +1,17 @@
+use crate::domain::connection::ConnectionLoadResult;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Connect any saved connections that are not live yet, e.g. after an
+/// import or once a database that was down at startup is back.
+pub async fn reload_connections(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+) -> Result<Vec<ConnectionLoadResult>, UsecaseError> {
+    require_super_admin(caller)?;
+    connection_manager
+        .load_saved_connections()
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,30 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{ConnectionRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+/// Replace a connection's stored password, keeping its id and grants.
+/// The new password is verified by connecting before anything is saved.
+pub async fn rotate_connection_password(
+    connection_manager: &ConnectionManager,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    password: String,
+) -> Result<(), UsecaseError> {
+    if !caller.is_super_admin() {
+        require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+    }
+    let rotated = connection_manager
+        .rotate_password(conn_id, password)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?;
+    if rotated {
+        Ok(())
+    } else {
+        Err(UsecaseError::NotFound("Connection not found".to_string()))
+    }
+}
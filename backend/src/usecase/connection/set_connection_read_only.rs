@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{ConnectionRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+pub async fn set_connection_read_only(
+    connection_manager: &ConnectionManager,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    read_only: bool,
+) -> Result<bool, UsecaseError> {
+    require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+    let updated = connection_manager
+        .set_read_only(conn_id, read_only)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if updated {
+        Ok(read_only)
+    } else {
+        Err(UsecaseError::NotFound("Connection not found".to_string()))
+    }
+}
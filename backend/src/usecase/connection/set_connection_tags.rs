@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{ConnectionRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+use super::normalize_tags;
+
+pub async fn set_connection_tags(
+    connection_manager: &ConnectionManager,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    tags: Vec<String>,
+) -> Result<Vec<String>, UsecaseError> {
+    require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+    let tags = normalize_tags(tags);
+    let updated = connection_manager
+        .set_tags(conn_id, tags.clone())
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if updated {
+        Ok(tags)
+    } else {
+        Err(UsecaseError::NotFound("Connection not found".to_string()))
+    }
+}
@@ -0,0 +1,56 @@
+use uuid::Uuid;
+
+use crate::domain::connection::ConnectionNameTaken;
+use crate::domain::repository::{ConnectionRepository, UserRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::UsecaseError;
+
+/// Hand a personal connection over to another user.
+/// Only the current owner or a super_admin may transfer.
+pub async fn transfer_connection(
+    connection_manager: &ConnectionManager,
+    user_repo: &dyn UserRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    new_owner: &Uuid,
+) -> Result<Uuid, UsecaseError> {
+    let ownership = conn_repo
+        .get_ownership(conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    let owner_id = match ownership {
+        None => return Err(UsecaseError::NotFound("Connection not found".to_string())),
+        Some((Some(_), _)) => {
+            return Err(UsecaseError::BadRequest(
+                "Organization connections have no personal owner to transfer".to_string(),
+            ));
+        }
+        Some((None, owner_id)) => owner_id,
+    };
+    if !caller.is_super_admin() && owner_id != Some(caller.id) {
+        return Err(UsecaseError::Forbidden(
+            "Only the connection owner can transfer this connection".to_string(),
+        ));
+    }
+
+    user_repo
+        .get(new_owner)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("User not found".to_string()))?;
+
+    let updated = connection_manager
+        .set_owner(conn_id, new_owner)
+        .await
+        .map_err(|e| match e.downcast_ref::<ConnectionNameTaken>() {
+            Some(taken) => UsecaseError::Conflict(taken.to_string()),
+            None => UsecaseError::Internal(e.to_string()),
+        })?;
+    if updated {
+        Ok(*new_owner)
+    } else {
+        Err(UsecaseError::NotFound("Connection not found".to_string()))
+    }
+}
@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+use crate::domain::data::{AggregateFn, AggregateRow, AggregateSpec};
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::request::AggregateRequest;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{datasource_error, get_datasource, require_columns, require_table_read};
+
+pub async fn aggregate(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    req: &AggregateRequest,
+) -> Result<Vec<AggregateRow>, UsecaseError> {
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+
+    let agg_fn = AggregateFn::parse(&req.agg_fn).ok_or_else(|| {
+        UsecaseError::BadRequest(format!(
+            "Unsupported aggregate function '{}': use sum, avg, min, max or count",
+            req.agg_fn
+        ))
+    })?;
+    if req.metric_col.is_none() && agg_fn != AggregateFn::Count {
+        return Err(UsecaseError::BadRequest(
+            "metric_col is required unless agg_fn is count".to_string(),
+        ));
+    }
+
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let mut columns = vec![req.group_by.as_str()];
+    columns.extend(req.metric_col.as_deref());
+    require_columns(&*ds, table, &columns).await?;
+
+    let spec = AggregateSpec {
+        group_by: req.group_by.clone(),
+        metric_col: req.metric_col.clone(),
+        agg_fn,
+    };
+    ds.aggregate(table, &spec)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))
+}
@@ -0,0 +1,28 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{datasource_error, get_datasource, hold_transaction, require_connection_read};
+
+/// Open a read-only snapshot on the connection and return its token. Row
+/// listings that send the token all see the data as of the first read, so
+/// paging is stable while the table changes. The snapshot is a held
+/// transaction: it ends through commit or rollback, or after
+/// `transaction_timeout()`. Snapshots count towards `max_open_transactions()`.
+pub async fn begin_snapshot(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<Uuid, UsecaseError> {
+    require_connection_read(permission_repo, caller, conn_id).await?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let tx = ds
+        .begin_snapshot()
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))?;
+    hold_transaction(connection_manager, caller, conn_id, tx)
+}
@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{
+    datasource_error, get_writable_datasource, hold_transaction, require_connection_write,
+};
+
+/// Open a transaction on the connection and return its token. Row writes
+/// that send the token run inside it until it is committed or rolled back;
+/// one left open longer than `transaction_timeout()` is rolled back.
+/// Opening one needs write access to the connection, and table permissions
+/// are still checked on every write. A user may hold at most
+/// `max_open_transactions()` per connection.
+pub async fn begin_transaction(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<Uuid, UsecaseError> {
+    require_connection_write(permission_repo, caller, conn_id).await?;
+    let ds = get_writable_datasource(connection_manager, caller, conn_id).await?;
+    let tx = ds
+        .begin()
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))?;
+    hold_transaction(connection_manager, caller, conn_id, tx)
+}
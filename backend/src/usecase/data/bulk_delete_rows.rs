@@ -0,0 +1,35 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{datasource_error, get_writable_datasource, require_bulk_filter, require_table_write};
+
+/// Delete every row matching `filter`. Returns the number of rows affected,
+/// or with `dry_run` the number that would be.
+pub async fn bulk_delete_rows(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    filter: Option<&str>,
+    dry_run: bool,
+) -> Result<u64, UsecaseError> {
+    require_table_write(permission_repo, caller, conn_id, table).await?;
+    let ds = get_writable_datasource(connection_manager, caller, conn_id).await?;
+    let filter = require_bulk_filter(&*ds, table, filter).await?;
+
+    if dry_run {
+        return ds
+            .count_rows(table, Some(&filter))
+            .await
+            .map(|n| n as u64)
+            .map_err(|e| datasource_error(e, UsecaseError::BadRequest));
+    }
+    ds.bulk_delete(table, &filter)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))
+}
@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{
+    datasource_error, get_writable_datasource, require_bulk_filter, require_columns,
+    require_table_write,
+};
+
+/// Apply `data` to every row matching `filter`. Returns the number of rows
+/// affected, or with `dry_run` the number that would be.
+#[allow(clippy::too_many_arguments)]
+pub async fn bulk_update_rows(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    filter: Option<&str>,
+    data: &serde_json::Value,
+    dry_run: bool,
+) -> Result<u64, UsecaseError> {
+    require_table_write(permission_repo, caller, conn_id, table).await?;
+    let ds = get_writable_datasource(connection_manager, caller, conn_id).await?;
+    let filter = require_bulk_filter(&*ds, table, filter).await?;
+    let columns: Vec<&str> = match data.as_object() {
+        Some(obj) if !obj.is_empty() => obj.keys().map(String::as_str).collect(),
+        _ => {
+            return Err(UsecaseError::BadRequest(
+                "data must be a non-empty JSON object".to_string(),
+            ));
+        }
+    };
+    require_columns(&*ds, table, &columns).await?;
+
+    if dry_run {
+        return ds
+            .count_rows(table, Some(&filter))
+            .await
+            .map(|n| n as u64)
+            .map_err(|e| datasource_error(e, UsecaseError::BadRequest));
+    }
+    ds.bulk_update(table, &filter, data)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))
+}
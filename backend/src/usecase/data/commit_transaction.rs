@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::audit::PendingAuditEntry;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::transaction_not_found;
+
+/// Commit a transaction the caller began and return the audit entries of
+/// its writes, which are now due. The token is spent either way.
+pub async fn commit_transaction(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    token: &Uuid,
+) -> Result<Vec<PendingAuditEntry>, UsecaseError> {
+    let (tx, audit) = connection_manager
+        .release_transaction(token, conn_id, &caller.id)
+        .ok_or_else(transaction_not_found)?;
+    tx.commit()
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?;
+    Ok(audit)
+}
@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::request::RowsQueryLimits;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{
+    check_filter_value_length, datasource_error, get_datasource, require_table_read,
+    validate_filter,
+};
+
+pub async fn count_rows(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    filter: Option<&str>,
+) -> Result<i64, UsecaseError> {
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+    validate_filter(filter)?;
+    check_filter_value_length(filter, RowsQueryLimits::from_env().max_filter_length)?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    ds.count_rows(table, filter)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))
+}
@@ -5,8 +5,17 @@ use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_table_write};
+use super::{
+    datasource_error, get_writable_datasource, held_transaction, require_columns,
+    require_table_write,
+};
 
+/// Insert a row. With `validate`, the payload is first checked against the
+/// table schema so that missing required columns and unknown keys are
+/// reported together instead of as a raw database error. With a
+/// `transaction` token the insert runs inside that open transaction.
+/// A non-empty `returning` limits the returned row to those columns.
+#[allow(clippy::too_many_arguments)]
 pub async fn create_row(
     permission_repo: &dyn PermissionRepository,
     connection_manager: &ConnectionManager,
@@ -14,10 +23,28 @@ pub async fn create_row(
     conn_id: &Uuid,
     table: &str,
     data: &serde_json::Value,
+    validate: bool,
+    returning: &[String],
+    transaction: Option<&Uuid>,
 ) -> Result<serde_json::Value, UsecaseError> {
     require_table_write(permission_repo, caller, conn_id, table).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
-    ds.insert_row(table, data)
+    let ds = get_writable_datasource(connection_manager, caller, conn_id).await?;
+    let tx = held_transaction(connection_manager, caller, conn_id, transaction)?;
+    if !returning.is_empty() {
+        let columns: Vec<&str> = returning.iter().map(String::as_str).collect();
+        require_columns(&*ds, table, &columns).await?;
+    }
+    if validate {
+        let schema = ds
+            .get_table_schema(table)
+            .await
+            .map_err(|e| datasource_error(e, UsecaseError::Internal))?;
+        let problems = schema.validate_insert(data);
+        if !problems.is_empty() {
+            return Err(UsecaseError::Invalid(problems));
+        }
+    }
+    ds.insert_row(table, data, returning, tx.as_deref())
         .await
-        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))
 }
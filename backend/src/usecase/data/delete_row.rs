@@ -5,8 +5,15 @@ use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_table_write};
+use super::{
+    datasource_error, get_writable_datasource, held_transaction, require_table_write, validate_pk,
+};
 
+/// Delete a row by primary key. With `dry_run`, nothing is deleted and the
+/// number of rows that would have been removed is returned instead (counted
+/// outside any transaction). Otherwise the delete runs inside the open
+/// `transaction` when given.
+#[allow(clippy::too_many_arguments)]
 pub async fn delete_row(
     permission_repo: &dyn PermissionRepository,
     connection_manager: &ConnectionManager,
@@ -14,10 +21,22 @@ pub async fn delete_row(
     conn_id: &Uuid,
     table: &str,
     pk: &str,
-) -> Result<(), UsecaseError> {
+    dry_run: bool,
+    transaction: Option<&Uuid>,
+) -> Result<Option<i64>, UsecaseError> {
     require_table_write(permission_repo, caller, conn_id, table).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
-    ds.delete_row(table, pk)
+    validate_pk(pk)?;
+    let ds = get_writable_datasource(connection_manager, caller, conn_id).await?;
+    if dry_run {
+        return ds
+            .count_by_pk(table, pk)
+            .await
+            .map(Some)
+            .map_err(|e| datasource_error(e, UsecaseError::BadRequest));
+    }
+    let tx = held_transaction(connection_manager, caller, conn_id, transaction)?;
+    ds.delete_row(table, pk, tx.as_deref())
         .await
-        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+        .map(|()| None)
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))
 }
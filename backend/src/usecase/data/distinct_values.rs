@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{datasource_error, get_datasource, require_columns, require_table_read};
+
+/// Number of distinct values returned when no limit is given
+pub const DEFAULT_DISTINCT_LIMIT: u32 = 100;
+/// Upper bound on the number of distinct values returned
+pub const MAX_DISTINCT_LIMIT: u32 = 1000;
+
+pub async fn distinct_values(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    column: &str,
+    limit: Option<u32>,
+) -> Result<Vec<serde_json::Value>, UsecaseError> {
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    require_columns(&*ds, table, &[column]).await?;
+
+    let limit = limit
+        .unwrap_or(DEFAULT_DISTINCT_LIMIT)
+        .clamp(1, MAX_DISTINCT_LIMIT);
+    ds.distinct_values(table, column, limit)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))
+}
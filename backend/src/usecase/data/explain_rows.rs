@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::request::{RowsQuery, RowsQueryLimits};
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+use crate::usecase::error::require_super_admin;
+
+use super::{
+    check_query_limits, datasource_error, get_datasource, require_columns, require_table_read,
+    validate_filter,
+};
+
+/// Return the query plan for the `list_rows` query built from `query`.
+/// Super admin only, since plans reveal table sizes and index layout.
+pub async fn explain_rows(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    query: &RowsQuery,
+) -> Result<serde_json::Value, UsecaseError> {
+    require_super_admin(caller)?;
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+    validate_filter(query.filter.as_deref())?;
+    check_query_limits(query, &RowsQueryLimits::from_env())?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let sort_keys = query.sort_keys();
+    if !sort_keys.is_empty() {
+        let columns: Vec<&str> = sort_keys.iter().map(|k| k.column.as_str()).collect();
+        require_columns(&*ds, table, &columns).await?;
+    }
+    ds.explain_rows(table, query)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))
+}
@@ -5,7 +5,7 @@ use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_table_read};
+use super::{datasource_error, get_datasource, require_table_read, validate_pk};
 
 pub async fn get_row(
     permission_repo: &dyn PermissionRepository,
@@ -16,8 +16,9 @@ pub async fn get_row(
     pk: &str,
 ) -> Result<serde_json::Value, UsecaseError> {
     require_table_read(permission_repo, caller, conn_id, table).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
+    validate_pk(pk)?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
     ds.get_row(table, pk)
         .await
-        .map_err(|e| UsecaseError::NotFound(e.to_string()))
+        .map_err(|e| datasource_error(e, UsecaseError::NotFound))
 }
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{datasource_error, get_datasource, require_table_read};
+
+/// Upper bound on the number of keys accepted in a single batch get
+pub const MAX_BATCH_GET_KEYS: usize = 1000;
+
+pub async fn get_rows(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    pk_values: &[String],
+) -> Result<Vec<serde_json::Value>, UsecaseError> {
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+    if pk_values.len() > MAX_BATCH_GET_KEYS {
+        return Err(UsecaseError::BadRequest(format!(
+            "At most {} keys can be fetched at once",
+            MAX_BATCH_GET_KEYS
+        )));
+    }
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    ds.get_rows(table, pk_values)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))
+}
@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::domain::data::TableCapabilities;
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{datasource_error, get_datasource, table_read_denied, validate_identifier};
+
+/// Whether the caller can read and write a table, combining their resolved
+/// permission with the table's primary key, its kind, and the connection's
+/// read-only flag. Callers without read access are refused.
+pub async fn get_table_capabilities(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+) -> Result<TableCapabilities, UsecaseError> {
+    validate_identifier(table)?;
+    let resolved = permission_repo
+        .explain_table_permission(caller, conn_id, table)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if !resolved.level.can_read() {
+        return Err(table_read_denied(resolved));
+    }
+    let perm = resolved.level;
+
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let schema = ds
+        .get_table_schema(table)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))?;
+    let is_view = ds
+        .is_view(table)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))?;
+    let has_primary_key = !schema.primary_key_columns.is_empty();
+    let read_only = connection_manager.is_read_only(conn_id).await;
+
+    Ok(TableCapabilities {
+        can_read: true,
+        can_write: perm.can_write() && has_primary_key && !is_view && !read_only,
+        has_primary_key,
+        is_view,
+    })
+}
@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use crate::domain::data::TableMetadata;
+use crate::domain::repository::{PermissionRepository, TableMetadataRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::UsecaseError;
+
+use super::require_table_read;
+
+/// Display metadata for a table; empty when none has been set.
+pub async fn get_table_metadata(
+    permission_repo: &dyn PermissionRepository,
+    metadata_repo: &dyn TableMetadataRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+) -> Result<TableMetadata, UsecaseError> {
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+    let metadata = metadata_repo
+        .get(conn_id, table)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    Ok(metadata.unwrap_or_else(|| TableMetadata {
+        table_name: table.to_string(),
+        ..TableMetadata::default()
+    }))
+}
@@ -1,23 +1,42 @@
 use uuid::Uuid;
 
 use crate::domain::data::TableSchema;
-use crate::domain::repository::PermissionRepository;
+use crate::domain::repository::{PermissionRepository, TableMetadataRepository};
 use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_table_read};
+use super::{datasource_error, get_datasource, require_table_read};
 
+/// Table schema with column descriptions and display hints merged in from
+/// app-level column metadata.
 pub async fn get_table_schema(
     permission_repo: &dyn PermissionRepository,
+    metadata_repo: &dyn TableMetadataRepository,
     connection_manager: &ConnectionManager,
     caller: &AppUser,
     conn_id: &Uuid,
     table: &str,
 ) -> Result<TableSchema, UsecaseError> {
     require_table_read(permission_repo, caller, conn_id, table).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
-    ds.get_table_schema(table)
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let mut schema = ds
+        .get_table_schema(table)
         .await
-        .map_err(|e| UsecaseError::Internal(e.to_string()))
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))?;
+
+    let metadata = metadata_repo
+        .list_columns(conn_id, table)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    for column in &mut schema.columns {
+        if let Some(meta) = metadata
+            .iter()
+            .find(|m| m.column_name == column.column_name)
+        {
+            column.description = meta.description.clone();
+            column.display_hint = meta.display_hint.clone();
+        }
+    }
+    Ok(schema)
 }
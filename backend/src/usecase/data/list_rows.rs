@@ -3,12 +3,17 @@ use uuid::Uuid;
 use crate::domain::data::RowsResponse;
 use crate::domain::repository::PermissionRepository;
 use crate::domain::user::AppUser;
-use crate::presentation::request::RowsQuery;
+use crate::presentation::request::{RowsQuery, RowsQueryLimits};
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_table_read};
+use super::{
+    check_query_limits, datasource_error, get_datasource, held_transaction, require_columns,
+    require_table_read, validate_filter,
+};
 
+/// List a page of rows. With a `transaction` token (e.g. from
+/// `begin_snapshot`) the page is read inside that transaction.
 pub async fn list_rows(
     permission_repo: &dyn PermissionRepository,
     connection_manager: &ConnectionManager,
@@ -16,10 +21,32 @@ pub async fn list_rows(
     conn_id: &Uuid,
     table: &str,
     query: &RowsQuery,
+    transaction: Option<&Uuid>,
 ) -> Result<RowsResponse, UsecaseError> {
     require_table_read(permission_repo, caller, conn_id, table).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
-    ds.list_rows(table, query)
+    validate_filter(query.filter.as_deref())?;
+    check_query_limits(query, &RowsQueryLimits::from_env())?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let sort_keys = query.sort_keys();
+    if !sort_keys.is_empty() {
+        let columns: Vec<&str> = sort_keys.iter().map(|k| k.column.as_str()).collect();
+        require_columns(&*ds, table, &columns).await?;
+    }
+    let fields = query.field_list();
+    if !fields.is_empty() {
+        let columns: Vec<&str> = fields.iter().map(String::as_str).collect();
+        require_columns(&*ds, table, &columns).await?;
+    }
+    // The datasource still caps whatever page size ends up here
+    let mut query = query.clone();
+    if query.per_page.is_none() {
+        query.per_page = connection_manager.default_page_size(conn_id).await;
+    }
+    if query.sort_order.is_none() {
+        query.sort_order = connection_manager.default_sort_order(conn_id).await;
+    }
+    let tx = held_transaction(connection_manager, caller, conn_id, transaction)?;
+    ds.list_rows(table, &query, tx.as_deref())
         .await
-        .map_err(|e| UsecaseError::Internal(e.to_string()))
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))
 }
@@ -1,22 +1,38 @@
 use uuid::Uuid;
 
 use crate::domain::data::TableInfo;
-use crate::domain::repository::PermissionRepository;
+use crate::domain::repository::{PermissionRepository, TableMetadataRepository};
 use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_connection_read};
+use super::{datasource_error, get_datasource, require_connection_read};
 
+/// List the connection's tables, with display names and descriptions
+/// merged in from app-level table metadata.
 pub async fn list_tables(
     permission_repo: &dyn PermissionRepository,
+    metadata_repo: &dyn TableMetadataRepository,
     connection_manager: &ConnectionManager,
     caller: &AppUser,
     conn_id: &Uuid,
 ) -> Result<Vec<TableInfo>, UsecaseError> {
     require_connection_read(permission_repo, caller, conn_id).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
-    ds.list_tables()
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    let mut tables = ds
+        .list_tables()
         .await
-        .map_err(|e| UsecaseError::Internal(e.to_string()))
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))?;
+
+    let metadata = metadata_repo
+        .list(conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    for table in &mut tables {
+        if let Some(meta) = metadata.iter().find(|m| m.table_name == table.table_name) {
+            table.display_name = meta.display_name.clone();
+            table.description = meta.description.clone();
+        }
+    }
+    Ok(tables)
 }
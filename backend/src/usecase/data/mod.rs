@@ -1,17 +1,51 @@
+mod aggregate;
+mod begin_snapshot;
+mod begin_transaction;
+mod bulk_delete_rows;
+mod bulk_update_rows;
+mod commit_transaction;
+mod count_rows;
 mod create_row;
 mod delete_row;
+mod distinct_values;
+mod explain_rows;
 mod get_row;
+mod get_rows;
+mod get_table_capabilities;
+mod get_table_metadata;
 mod get_table_schema;
 mod list_rows;
 mod list_tables;
+mod refresh_schema;
+mod rollback_transaction;
+mod set_column_metadata;
+mod set_table_metadata;
+mod subscribe_table;
 mod update_row;
 
+pub use aggregate::aggregate;
+pub use begin_snapshot::begin_snapshot;
+pub use begin_transaction::begin_transaction;
+pub use bulk_delete_rows::bulk_delete_rows;
+pub use bulk_update_rows::bulk_update_rows;
+pub use commit_transaction::commit_transaction;
+pub use count_rows::count_rows;
 pub use create_row::create_row;
 pub use delete_row::delete_row;
+pub use distinct_values::distinct_values;
+pub use explain_rows::explain_rows;
 pub use get_row::get_row;
+pub use get_rows::{MAX_BATCH_GET_KEYS, get_rows};
+pub use get_table_capabilities::get_table_capabilities;
+pub use get_table_metadata::get_table_metadata;
 pub use get_table_schema::get_table_schema;
 pub use list_rows::list_rows;
 pub use list_tables::list_tables;
+pub use refresh_schema::refresh_schema;
+pub use rollback_transaction::rollback_transaction;
+pub use set_column_metadata::set_column_metadata;
+pub use set_table_metadata::set_table_metadata;
+pub use subscribe_table::subscribe_table;
 pub use update_row::update_row;
 
 // ============================================================
@@ -22,12 +56,116 @@ use std::sync::Arc;
 
 use uuid::Uuid;
 
+use crate::domain::permission::{PermissionLevel, ResolvedPermission};
 use crate::domain::repository::PermissionRepository;
 use crate::domain::user::AppUser;
-use crate::infrastructure::datasource::DataSource;
+use crate::infrastructure::datasource::{
+    DataSource, DataSourceError, DataSourceTransaction, max_open_transactions, transaction_timeout,
+};
+use crate::infrastructure::metrics;
+use crate::presentation::request::{RowsQuery, RowsQueryLimits};
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
+/// PostgreSQL truncates identifiers beyond this many bytes.
+const MAX_IDENTIFIER_BYTES: usize = 63;
+
+/// Reject table or column names that can never be valid identifiers, so they
+/// fail with a clear message instead of a confusing database error.
+pub(super) fn validate_identifier(name: &str) -> Result<(), UsecaseError> {
+    if name.len() > MAX_IDENTIFIER_BYTES {
+        return Err(UsecaseError::BadRequest(format!(
+            "Identifier '{}' is longer than {} bytes",
+            name, MAX_IDENTIFIER_BYTES
+        )));
+    }
+    if name.contains('\0') {
+        return Err(UsecaseError::BadRequest(
+            "Identifier must not contain null bytes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate the column of a `column:op:value` filter, if one is given.
+pub(super) fn validate_filter(filter: Option<&str>) -> Result<(), UsecaseError> {
+    match filter.and_then(|f| f.split(':').next()) {
+        Some(column) => validate_identifier(column),
+        None => Ok(()),
+    }
+}
+
+/// Reject a primary key value that cannot address a row. Rows are matched
+/// with `pk::text = $1`, which never matches SQL NULL, so an empty or
+/// `null` key would otherwise silently find nothing.
+pub(super) fn validate_pk(pk: &str) -> Result<(), UsecaseError> {
+    if pk.trim().is_empty() {
+        return Err(UsecaseError::BadRequest(
+            "Primary key value must not be empty".to_string(),
+        ));
+    }
+    if pk.eq_ignore_ascii_case("null") {
+        return Err(UsecaseError::BadRequest(
+            "Rows with a null primary key cannot be addressed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Refuse a `filter` or `q` value longer than `max` characters. Values are
+/// bound as parameters, so this only keeps pointless megabyte-sized ones out.
+pub(super) fn check_filter_length(
+    name: &str,
+    value: Option<&str>,
+    max: usize,
+) -> Result<(), UsecaseError> {
+    let len = value.map_or(0, |v| v.chars().count());
+    if len > max {
+        return Err(UsecaseError::BadRequest(format!(
+            "{} must be at most {} characters, got {}",
+            name, max, len
+        )));
+    }
+    Ok(())
+}
+
+/// Refuse a `column:op:value` filter whose value is longer than `max`
+/// characters. The column and operator are not counted: the column is
+/// already capped as an identifier.
+pub(super) fn check_filter_value_length(
+    filter: Option<&str>,
+    max: usize,
+) -> Result<(), UsecaseError> {
+    let value = filter.and_then(|f| f.splitn(3, ':').nth(2));
+    check_filter_length("filter value", value, max)
+}
+
+/// Refuse a rows query naming more `fields` or `sort_by` columns than
+/// `limits` allow, or with a longer filter value or `q`, before any SQL is built
+/// for it. A query carries at most one `filter`, so filters need no count cap.
+pub(super) fn check_query_limits(
+    query: &RowsQuery,
+    limits: &RowsQueryLimits,
+) -> Result<(), UsecaseError> {
+    check_filter_value_length(query.filter.as_deref(), limits.max_filter_length)?;
+    check_filter_length("q", query.q.as_deref(), limits.max_filter_length)?;
+    let fields = query.field_list().len();
+    if fields > limits.max_fields {
+        return Err(UsecaseError::BadRequest(format!(
+            "At most {} fields may be requested, got {}",
+            limits.max_fields, fields
+        )));
+    }
+    let sorts = query.sort_keys().len();
+    if sorts > limits.max_sort_columns {
+        return Err(UsecaseError::BadRequest(format!(
+            "At most {} sort columns may be requested, got {}",
+            limits.max_sort_columns, sorts
+        )));
+    }
+    Ok(())
+}
+
 /// Check that the caller has at least read access to the connection.
 pub(super) async fn require_connection_read(
     permission_repo: &dyn PermissionRepository,
@@ -46,6 +184,40 @@ pub(super) async fn require_connection_read(
     Ok(())
 }
 
+/// Check that the caller has write access to the connection as a whole.
+pub(super) async fn require_connection_write(
+    permission_repo: &dyn PermissionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    let (perm, _) = permission_repo
+        .resolve_connection_permission(caller, conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if !perm.can_write() {
+        return Err(UsecaseError::Forbidden("Write access required".to_string()));
+    }
+    Ok(())
+}
+
+/// Check that the caller has admin access to the connection.
+pub(super) async fn require_connection_admin(
+    permission_repo: &dyn PermissionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    let (perm, _) = permission_repo
+        .resolve_connection_permission(caller, conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if perm != PermissionLevel::Admin {
+        return Err(UsecaseError::Forbidden(
+            "Connection admin access required".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Check that the caller has at least read access to a specific table.
 pub(super) async fn require_table_read(
     permission_repo: &dyn PermissionRepository,
@@ -53,18 +225,27 @@ pub(super) async fn require_table_read(
     conn_id: &Uuid,
     table: &str,
 ) -> Result<(), UsecaseError> {
-    let perm = permission_repo
-        .resolve_table_permission(caller, conn_id, table)
+    validate_identifier(table)?;
+    let resolved = permission_repo
+        .explain_table_permission(caller, conn_id, table)
         .await
         .map_err(|e| UsecaseError::Internal(e.to_string()))?;
-    if !perm.can_read() {
-        return Err(UsecaseError::Forbidden(
-            "No access to this table".to_string(),
-        ));
+    if !resolved.level.can_read() {
+        return Err(table_read_denied(resolved));
     }
     Ok(())
 }
 
+/// The error for a table the caller cannot read, explaining why when the
+/// resolution recorded a reason.
+pub(super) fn table_read_denied(resolved: ResolvedPermission) -> UsecaseError {
+    let message = "No access to this table".to_string();
+    match resolved.reason {
+        Some(reason) => UsecaseError::Denied { message, reason },
+        None => UsecaseError::Forbidden(message),
+    }
+}
+
 /// Check that the caller has write access to a specific table.
 pub(super) async fn require_table_write(
     permission_repo: &dyn PermissionRepository,
@@ -72,6 +253,7 @@ pub(super) async fn require_table_write(
     conn_id: &Uuid,
     table: &str,
 ) -> Result<(), UsecaseError> {
+    validate_identifier(table)?;
     let perm = permission_repo
         .resolve_table_permission(caller, conn_id, table)
         .await
@@ -83,12 +265,263 @@ pub(super) async fn require_table_write(
 }
 
 /// Get a datasource by connection ID, returning NotFound if absent.
+/// Calls through it are recorded in the query log against the acting user.
 pub(super) async fn get_datasource(
     connection_manager: &ConnectionManager,
+    caller: &AppUser,
     conn_id: &Uuid,
 ) -> Result<Arc<dyn DataSource>, UsecaseError> {
+    let user_id = caller.actor_id();
     connection_manager
-        .get_datasource(conn_id)
+        .get_datasource_for(conn_id, &user_id)
         .await
         .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))
 }
+
+/// Like `get_datasource`, but refuses connections flagged read-only.
+/// The flag overrides permissions, so even owners and super_admins are refused.
+pub(super) async fn get_writable_datasource(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<Arc<dyn DataSource>, UsecaseError> {
+    if connection_manager.is_read_only(conn_id).await {
+        return Err(UsecaseError::Forbidden(
+            "Connection is read-only; writes are disabled".to_string(),
+        ));
+    }
+    get_datasource(connection_manager, caller, conn_id).await
+}
+
+/// Resolve a transaction token sent with a row write. Only the user who
+/// began the transaction can use it, and only on the same connection.
+pub(super) fn held_transaction(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    token: Option<&Uuid>,
+) -> Result<Option<Arc<DataSourceTransaction>>, UsecaseError> {
+    token
+        .map(|token| {
+            connection_manager
+                .transaction(token, conn_id, &caller.id)
+                .ok_or_else(transaction_not_found)
+        })
+        .transpose()
+}
+
+/// Hold a freshly begun transaction for the caller and return its token,
+/// refusing once they have `max_open_transactions()` open on the connection.
+pub(super) fn hold_transaction(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    tx: DataSourceTransaction,
+) -> Result<Uuid, UsecaseError> {
+    connection_manager
+        .hold_transaction(
+            *conn_id,
+            caller.id,
+            tx,
+            transaction_timeout(),
+            max_open_transactions(),
+        )
+        .ok_or_else(|| {
+            UsecaseError::Conflict(
+                "Too many open transactions on this connection; commit or roll one back first"
+                    .to_string(),
+            )
+        })
+}
+
+pub(super) fn transaction_not_found() -> UsecaseError {
+    UsecaseError::NotFound("Transaction not found or expired".to_string())
+}
+
+/// Check that every column exists in the table's schema, so that
+/// caller-supplied identifiers never reach SQL unvalidated.
+pub(super) async fn require_columns(
+    ds: &dyn DataSource,
+    table: &str,
+    columns: &[&str],
+) -> Result<(), UsecaseError> {
+    for column in columns {
+        validate_identifier(column)?;
+    }
+    let schema = ds
+        .get_table_schema(table)
+        .await
+        .map_err(|e| datasource_error(e, UsecaseError::Internal))?;
+    for column in columns {
+        if !schema.columns.iter().any(|c| c.column_name == *column) {
+            return Err(UsecaseError::BadRequest(format!(
+                "Unknown column '{}' in table '{}'",
+                column, table
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Check that a bulk operation carries a well-formed `column:op:value`
+/// filter naming a real column, so it can never touch the whole table.
+pub(super) async fn require_bulk_filter(
+    ds: &dyn DataSource,
+    table: &str,
+    filter: Option<&str>,
+) -> Result<String, UsecaseError> {
+    let filter = filter.map(str::trim).unwrap_or_default();
+    check_filter_value_length(Some(filter), RowsQueryLimits::from_env().max_filter_length)?;
+    let mut parts = filter.splitn(3, ':');
+    let (Some(column), Some(_), Some(_)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(UsecaseError::BadRequest(
+            "A filter of the form column:op:value is required for bulk operations".to_string(),
+        ));
+    };
+    if column.is_empty() {
+        return Err(UsecaseError::BadRequest(
+            "A filter of the form column:op:value is required for bulk operations".to_string(),
+        ));
+    }
+    require_columns(ds, table, &[column]).await?;
+    Ok(filter.to_string())
+}
+
+/// Map a datasource error to a `UsecaseError`.
+/// Statement timeouts become `Timeout`, missing tables `NotFound`,
+/// pool-acquire timeouts `Unavailable` and writes to system tables
+/// `Forbidden`; everything else goes through `fallback`.
+pub(super) fn datasource_error(
+    err: anyhow::Error,
+    fallback: fn(String) -> UsecaseError,
+) -> UsecaseError {
+    let classified = DataSourceError::classify(&err);
+    metrics::record_datasource_error(classified.as_ref().map_or("other", |e| e.kind()));
+    match classified {
+        Some(DataSourceError::Timeout) => UsecaseError::Timeout(err.to_string()),
+        Some(DataSourceError::TableNotFound) => UsecaseError::NotFound(err.to_string()),
+        Some(DataSourceError::PoolTimeout) => UsecaseError::Unavailable(err.to_string()),
+        Some(DataSourceError::SystemTable) => UsecaseError::Forbidden(err.to_string()),
+        Some(_) | None => fallback(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_identifier_accepts_ordinary_names() {
+        assert!(validate_identifier("orders").is_ok());
+        assert!(validate_identifier("Weird \"name\"; --").is_ok());
+        assert!(validate_identifier(&"a".repeat(MAX_IDENTIFIER_BYTES)).is_ok());
+    }
+
+    #[test]
+    fn validate_identifier_rejects_over_length() {
+        let err = validate_identifier(&"a".repeat(MAX_IDENTIFIER_BYTES + 1)).unwrap_err();
+        assert!(matches!(err, UsecaseError::BadRequest(_)));
+        // The limit is in bytes, not characters
+        assert!(validate_identifier(&"é".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn validate_identifier_rejects_null_bytes() {
+        let err = validate_identifier("orders\0").unwrap_err();
+        assert!(matches!(err, UsecaseError::BadRequest(_)));
+    }
+
+    #[test]
+    fn validate_pk_rejects_empty_and_null() {
+        assert!(validate_pk("42").is_ok());
+        assert!(validate_pk("nullable-slug").is_ok());
+        for pk in ["", "  ", "null", "NULL"] {
+            let err = validate_pk(pk).unwrap_err();
+            assert!(matches!(err, UsecaseError::BadRequest(_)), "{:?}", pk);
+        }
+    }
+
+    fn columns(n: usize) -> String {
+        (0..n)
+            .map(|i| format!("c{}", i))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    #[test]
+    fn check_query_limits_caps_fields_at_the_boundary() {
+        let limits = RowsQueryLimits {
+            max_fields: 3,
+            max_sort_columns: 2,
+            ..Default::default()
+        };
+        let query = |n| RowsQuery {
+            fields: Some(columns(n)),
+            ..Default::default()
+        };
+        assert!(check_query_limits(&query(3), &limits).is_ok());
+        let err = check_query_limits(&query(4), &limits).unwrap_err();
+        assert!(matches!(err, UsecaseError::BadRequest(_)));
+    }
+
+    #[test]
+    fn check_query_limits_caps_sorts_at_the_boundary() {
+        let limits = RowsQueryLimits {
+            max_fields: 3,
+            max_sort_columns: 2,
+            ..Default::default()
+        };
+        let query = |n| RowsQuery {
+            sort_by: Some(columns(n)),
+            ..Default::default()
+        };
+        assert!(check_query_limits(&query(2), &limits).is_ok());
+        let err = check_query_limits(&query(3), &limits).unwrap_err();
+        assert!(matches!(err, UsecaseError::BadRequest(_)));
+    }
+
+    #[test]
+    fn check_query_limits_accepts_a_filter_at_any_cap() {
+        let limits = RowsQueryLimits {
+            max_fields: 0,
+            max_sort_columns: 0,
+            ..Default::default()
+        };
+        let query = RowsQuery {
+            filter: Some("age:gt:30".to_string()),
+            ..Default::default()
+        };
+        assert!(check_query_limits(&query, &limits).is_ok());
+    }
+
+    #[test]
+    fn check_query_limits_caps_filter_value_and_search_length_at_the_boundary() {
+        let limits = RowsQueryLimits::default();
+        let max = limits.max_filter_length;
+        assert_eq!(max, 4096);
+        let filter = |value: String| RowsQuery {
+            filter: Some(format!("{}:eq:{}", "c".repeat(63), value)),
+            ..Default::default()
+        };
+        // Only the value counts, not the column or operator; multi-byte
+        // characters count once
+        assert!(check_query_limits(&filter("x".repeat(max)), &limits).is_ok());
+        assert!(check_query_limits(&filter("ä".repeat(max)), &limits).is_ok());
+        let err = check_query_limits(&filter("x".repeat(max + 1)), &limits).unwrap_err();
+        assert!(matches!(err, UsecaseError::BadRequest(_)));
+
+        let search = |n| RowsQuery {
+            q: Some("x".repeat(n)),
+            ..Default::default()
+        };
+        assert!(check_query_limits(&search(max), &limits).is_ok());
+        assert!(check_query_limits(&search(max + 1), &limits).is_err());
+    }
+
+    #[test]
+    fn validate_filter_checks_the_column() {
+        assert!(validate_filter(None).is_ok());
+        assert!(validate_filter(Some("age:gt:30")).is_ok());
+        assert!(validate_filter(Some("a\0ge:gt:30")).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::{get_datasource, require_connection_read};
+
+/// Clear the connection's cached table schemas, e.g. after DDL changes.
+pub async fn refresh_schema(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    require_connection_read(permission_repo, caller, conn_id).await?;
+    let ds = get_datasource(connection_manager, caller, conn_id).await?;
+    ds.invalidate_schema_cache().await;
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::transaction_not_found;
+
+/// Roll back a transaction the caller began, discarding its writes and the
+/// audit entries deferred for them.
+pub async fn rollback_transaction(
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    token: &Uuid,
+) -> Result<(), UsecaseError> {
+    let (tx, _) = connection_manager
+        .release_transaction(token, conn_id, &caller.id)
+        .ok_or_else(transaction_not_found)?;
+    tx.rollback()
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
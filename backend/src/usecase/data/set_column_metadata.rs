@@ -0,0 +1,39 @@
+use uuid::Uuid;
+
+use crate::domain::data::ColumnMetadata;
+use crate::domain::repository::{PermissionRepository, TableMetadataRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::UsecaseError;
+
+use super::require_connection_admin;
+
+/// Longest display hint accepted; hints are short tokens like "currency".
+const MAX_DISPLAY_HINT_LEN: usize = 50;
+
+/// Set a column's description and display hint (app-level metadata only).
+/// Blank values clear the field.
+#[allow(clippy::too_many_arguments)]
+pub async fn set_column_metadata(
+    permission_repo: &dyn PermissionRepository,
+    metadata_repo: &dyn TableMetadataRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    column: &str,
+    description: Option<&str>,
+    display_hint: Option<&str>,
+) -> Result<ColumnMetadata, UsecaseError> {
+    require_connection_admin(permission_repo, caller, conn_id).await?;
+    let description = description.map(str::trim).filter(|s| !s.is_empty());
+    let display_hint = display_hint.map(str::trim).filter(|s| !s.is_empty());
+    if display_hint.is_some_and(|h| h.len() > MAX_DISPLAY_HINT_LEN) {
+        return Err(UsecaseError::BadRequest(format!(
+            "display_hint must be at most {} characters",
+            MAX_DISPLAY_HINT_LEN
+        )));
+    }
+    metadata_repo
+        .upsert_column(conn_id, table, column, description, display_hint)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,28 @@
+use uuid::Uuid;
+
+use crate::domain::data::TableMetadata;
+use crate::domain::repository::{PermissionRepository, TableMetadataRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::UsecaseError;
+
+use super::require_connection_admin;
+
+/// Set a table's display name and description. This is app-level metadata
+/// only; the table itself is not renamed. Blank values clear the field.
+pub async fn set_table_metadata(
+    permission_repo: &dyn PermissionRepository,
+    metadata_repo: &dyn TableMetadataRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+    display_name: Option<&str>,
+    description: Option<&str>,
+) -> Result<TableMetadata, UsecaseError> {
+    require_connection_admin(permission_repo, caller, conn_id).await?;
+    let display_name = display_name.map(str::trim).filter(|s| !s.is_empty());
+    let description = description.map(str::trim).filter(|s| !s.is_empty());
+    metadata_repo
+        .upsert(conn_id, table, display_name, description)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
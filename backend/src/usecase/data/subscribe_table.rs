@@ -0,0 +1,42 @@
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::domain::data::RowChange;
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::infrastructure::datasource::DataSourceError;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::UsecaseError;
+
+use super::require_table_read;
+
+/// Subscribe to row changes on a table. Relies on a trigger in the
+/// connected database publishing on `notify_channel(table)`.
+pub async fn subscribe_table(
+    permission_repo: &dyn PermissionRepository,
+    connection_manager: &ConnectionManager,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    table: &str,
+) -> Result<broadcast::Receiver<RowChange>, UsecaseError> {
+    require_table_read(permission_repo, caller, conn_id, table).await?;
+    let info = connection_manager
+        .get_info(conn_id)
+        .await
+        .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))?;
+    // The change feed bypasses the datasource, so check the denylist here
+    if info.hidden_tables.iter().any(|t| t == table) {
+        return Err(UsecaseError::NotFound(
+            DataSourceError::TableNotFound.to_string(),
+        ));
+    }
+    if info.db_type != "postgres" {
+        return Err(UsecaseError::BadRequest(
+            "Change notifications are only supported for PostgreSQL connections".to_string(),
+        ));
+    }
+    connection_manager
+        .subscribe(&info, table)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
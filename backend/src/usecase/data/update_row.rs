@@ -5,8 +5,12 @@ use crate::domain::user::AppUser;
 use crate::presentation::state::ConnectionManager;
 use crate::usecase::UsecaseError;
 
-use super::{get_datasource, require_table_write};
+use super::{
+    datasource_error, get_writable_datasource, held_transaction, require_table_write, validate_pk,
+};
 
+/// Update a row by primary key, inside the open `transaction` when given.
+#[allow(clippy::too_many_arguments)]
 pub async fn update_row(
     permission_repo: &dyn PermissionRepository,
     connection_manager: &ConnectionManager,
@@ -15,10 +19,13 @@ pub async fn update_row(
     table: &str,
     pk: &str,
     data: &serde_json::Value,
+    transaction: Option<&Uuid>,
 ) -> Result<serde_json::Value, UsecaseError> {
     require_table_write(permission_repo, caller, conn_id, table).await?;
-    let ds = get_datasource(connection_manager, conn_id).await?;
-    ds.update_row(table, pk, data)
+    validate_pk(pk)?;
+    let ds = get_writable_datasource(connection_manager, caller, conn_id).await?;
+    let tx = held_transaction(connection_manager, caller, conn_id, transaction)?;
+    ds.update_row(table, pk, data, tx.as_deref())
         .await
-        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+        .map_err(|e| datasource_error(e, UsecaseError::BadRequest))
 }
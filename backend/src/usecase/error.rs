@@ -3,6 +3,7 @@ use std::fmt;
 use uuid::Uuid;
 
 use crate::domain::repository::{ConnectionRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
 
 /// Domain-level errors returned by usecases.
 /// Handlers map these to HTTP status codes.
@@ -10,8 +11,20 @@ use crate::domain::repository::{ConnectionRepository, OrganizationMemberReposito
 pub enum UsecaseError {
     Unauthorized,
     Forbidden(String),
+    /// Forbidden, with an explanation of the permission decision for the affected user.
+    Denied {
+        message: String,
+        reason: String,
+    },
     NotFound(String),
     BadRequest(String),
+    /// The request clashes with existing state (e.g. too many open transactions).
+    Conflict(String),
+    /// Request failed validation; one message per problem.
+    Invalid(Vec<String>),
+    Timeout(String),
+    /// The server is saturated (e.g. no pooled connection was free); retry later.
+    Unavailable(String),
     Internal(String),
 }
 
@@ -20,8 +33,13 @@ impl fmt::Display for UsecaseError {
         match self {
             Self::Unauthorized => write!(f, "Unauthorized"),
             Self::Forbidden(msg) => write!(f, "{}", msg),
+            Self::Denied { message, reason } => write!(f, "{} ({})", message, reason),
             Self::NotFound(msg) => write!(f, "{}", msg),
             Self::BadRequest(msg) => write!(f, "{}", msg),
+            Self::Conflict(msg) => write!(f, "{}", msg),
+            Self::Invalid(problems) => write!(f, "{}", problems.join("; ")),
+            Self::Timeout(msg) => write!(f, "{}", msg),
+            Self::Unavailable(msg) => write!(f, "{}", msg),
             Self::Internal(msg) => write!(f, "{}", msg),
         }
     }
@@ -29,6 +47,17 @@ impl fmt::Display for UsecaseError {
 
 impl std::error::Error for UsecaseError {}
 
+/// Check that the caller has the global `super_admin` role.
+pub(crate) fn require_super_admin(caller: &AppUser) -> Result<(), UsecaseError> {
+    if caller.is_super_admin() {
+        Ok(())
+    } else {
+        Err(UsecaseError::Forbidden(
+            "Super admin role required".to_string(),
+        ))
+    }
+}
+
 /// Check that the caller is an `owner` of the given organization.
 pub(crate) async fn require_org_owner(
     org_member_repo: &dyn OrganizationMemberRepository,
@@ -47,6 +76,46 @@ pub(crate) async fn require_org_owner(
     }
 }
 
+/// Check that the caller holds `org_admin` (or higher) and belongs to the
+/// organization. A `super_admin` passes without being a member.
+pub(crate) async fn require_org_admin(
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    if caller.is_super_admin() {
+        return Ok(());
+    }
+    if caller.is_org_admin() {
+        let membership = org_member_repo
+            .get_role(org_id, &caller.id)
+            .await
+            .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+        if membership.is_some() {
+            return Ok(());
+        }
+    }
+    Err(UsecaseError::Forbidden(
+        "Organization admin role required".to_string(),
+    ))
+}
+
+/// Check that the caller can manage the organization's users and groups:
+/// an org `owner`, or an admin accepted by `require_org_admin`.
+pub(crate) async fn require_org_manager(
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    if require_org_admin(org_member_repo, caller, org_id)
+        .await
+        .is_ok()
+    {
+        return Ok(());
+    }
+    require_org_owner(org_member_repo, &caller.id, org_id).await
+}
+
 /// Check that the caller can administer a connection.
 /// For org connections: caller must be `owner` of the org.
 /// For personal connections: caller must be the `owner_user_id`.
@@ -91,8 +160,21 @@ mod tests {
     fn display_variants() {
         assert_eq!(UsecaseError::Unauthorized.to_string(), "Unauthorized");
         assert_eq!(UsecaseError::Forbidden("nope".into()).to_string(), "nope");
+        assert_eq!(
+            UsecaseError::Denied {
+                message: "nope".into(),
+                reason: "why".into()
+            }
+            .to_string(),
+            "nope (why)"
+        );
         assert_eq!(UsecaseError::NotFound("gone".into()).to_string(), "gone");
         assert_eq!(UsecaseError::BadRequest("bad".into()).to_string(), "bad");
+        assert_eq!(
+            UsecaseError::Invalid(vec!["a".into(), "b".into()]).to_string(),
+            "a; b"
+        );
+        assert_eq!(UsecaseError::Timeout("slow".into()).to_string(), "slow");
         assert_eq!(UsecaseError::Internal("err".into()).to_string(), "err");
     }
 }
@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::repository::GroupRepository;
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Add several users to a group at once. Users who already belong are
+/// skipped; returns how many were newly added.
+pub async fn add_group_members_bulk(
+    group_repo: &dyn GroupRepository,
+    caller: &AppUser,
+    group_id: &Uuid,
+    user_ids: &[Uuid],
+) -> Result<u64, UsecaseError> {
+    require_super_admin(caller)?;
+    group_repo
+        .get_org_id(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))?;
+    group_repo
+        .add_members_bulk(group_id, user_ids)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+}
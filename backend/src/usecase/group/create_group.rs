@@ -3,7 +3,7 @@ use uuid::Uuid;
 use crate::domain::group::Group;
 use crate::domain::repository::{GroupRepository, OrganizationMemberRepository};
 use crate::domain::user::AppUser;
-use crate::usecase::error::{UsecaseError, require_org_owner};
+use crate::usecase::error::{UsecaseError, require_org_manager};
 
 pub async fn create_group(
     group_repo: &dyn GroupRepository,
@@ -13,7 +13,7 @@ pub async fn create_group(
     name: &str,
     description: Option<&str>,
 ) -> Result<Group, UsecaseError> {
-    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    require_org_manager(org_member_repo, caller, org_id).await?;
     group_repo
         .create(org_id, name, description)
         .await
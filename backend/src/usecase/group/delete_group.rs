@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{GroupRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_manager};
+
+pub async fn delete_group(
+    group_repo: &dyn GroupRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    group_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    let org_id = group_repo
+        .get_org_id(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))?;
+    require_org_manager(org_member_repo, caller, &org_id).await?;
+    let deleted = group_repo
+        .delete(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if !deleted {
+        return Err(UsecaseError::NotFound("Group not found".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use uuid::Uuid;
+
+use crate::domain::group::Group;
+use crate::domain::repository::{GroupRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_manager};
+
+/// Groups of `org_id` that the user belongs to. Users may list their own
+/// groups; anyone else needs to be able to manage the organization.
+pub async fn list_user_groups(
+    group_repo: &dyn GroupRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    user_id: &Uuid,
+) -> Result<Vec<Group>, UsecaseError> {
+    if caller.id != *user_id {
+        require_org_manager(org_member_repo, caller, org_id).await?;
+    }
+    org_member_repo
+        .get_role(org_id, user_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| {
+            UsecaseError::NotFound("User is not a member of this organization".to_string())
+        })?;
+    let groups = group_repo
+        .list_for_user(user_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    Ok(groups
+        .into_iter()
+        .filter(|g| g.organization_id == *org_id)
+        .collect())
+}
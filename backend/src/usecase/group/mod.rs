@@ -1,11 +1,23 @@
 mod add_group_member;
+mod add_group_members_bulk;
 mod create_group;
+mod delete_group;
 mod list_group_members;
 mod list_groups;
+mod list_user_groups;
 mod remove_group_member;
+mod remove_group_members_bulk;
+mod set_group_parent;
+mod update_group;
 
 pub use add_group_member::add_group_member;
+pub use add_group_members_bulk::add_group_members_bulk;
 pub use create_group::create_group;
+pub use delete_group::delete_group;
 pub use list_group_members::list_group_members;
 pub use list_groups::list_groups;
+pub use list_user_groups::list_user_groups;
 pub use remove_group_member::remove_group_member;
+pub use remove_group_members_bulk::remove_group_members_bulk;
+pub use set_group_parent::set_group_parent;
+pub use update_group::update_group;
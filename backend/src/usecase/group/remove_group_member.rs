@@ -2,7 +2,7 @@ use uuid::Uuid;
 
 use crate::domain::repository::{GroupRepository, OrganizationMemberRepository};
 use crate::domain::user::AppUser;
-use crate::usecase::error::{UsecaseError, require_org_owner};
+use crate::usecase::error::{UsecaseError, require_org_manager};
 
 pub async fn remove_group_member(
     group_repo: &dyn GroupRepository,
@@ -16,7 +16,7 @@ pub async fn remove_group_member(
         .await
         .map_err(|e| UsecaseError::Internal(e.to_string()))?
         .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))?;
-    require_org_owner(org_member_repo, &caller.id, &org_id).await?;
+    require_org_manager(org_member_repo, caller, &org_id).await?;
     group_repo
         .remove_member(group_id, user_id)
         .await
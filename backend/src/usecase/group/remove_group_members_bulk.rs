@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::repository::GroupRepository;
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Remove several users from a group at once. Returns how many of them
+/// were members.
+pub async fn remove_group_members_bulk(
+    group_repo: &dyn GroupRepository,
+    caller: &AppUser,
+    group_id: &Uuid,
+    user_ids: &[Uuid],
+) -> Result<u64, UsecaseError> {
+    require_super_admin(caller)?;
+    group_repo
+        .get_org_id(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))?;
+    group_repo
+        .remove_members_bulk(group_id, user_ids)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+}
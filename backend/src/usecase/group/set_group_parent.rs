@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{GroupRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_manager};
+
+/// Nest `group_id` under `parent_id`, or detach it when `parent_id` is `None`.
+/// The parent must belong to the same organization and must not be the
+/// group itself or one of its descendants.
+pub async fn set_group_parent(
+    group_repo: &dyn GroupRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    group_id: &Uuid,
+    parent_id: Option<&Uuid>,
+) -> Result<(), UsecaseError> {
+    let org_id = group_repo
+        .get_org_id(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))?;
+    require_org_manager(org_member_repo, caller, &org_id).await?;
+
+    if let Some(parent_id) = parent_id {
+        let parent_org = group_repo
+            .get_org_id(parent_id)
+            .await
+            .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+        if parent_org != Some(org_id) {
+            return Err(UsecaseError::BadRequest(
+                "Parent group must exist in the same organization".to_string(),
+            ));
+        }
+        let ancestors = group_repo
+            .list_ancestor_ids(parent_id)
+            .await
+            .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+        if ancestors.contains(group_id) {
+            return Err(UsecaseError::BadRequest(
+                "Setting this parent would create a cycle".to_string(),
+            ));
+        }
+    }
+
+    group_repo
+        .set_parent(group_id, parent_id)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+use crate::domain::group::Group;
+use crate::domain::repository::{GroupRepository, OrganizationMemberRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_manager};
+
+pub async fn update_group(
+    group_repo: &dyn GroupRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    group_id: &Uuid,
+    name: Option<&str>,
+    description: Option<&str>,
+) -> Result<Group, UsecaseError> {
+    let org_id = group_repo
+        .get_org_id(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))?;
+    require_org_manager(org_member_repo, caller, &org_id).await?;
+    if name.is_some_and(|n| n.trim().is_empty()) {
+        return Err(UsecaseError::BadRequest(
+            "Group name must not be empty".to_string(),
+        ));
+    }
+    group_repo
+        .update(group_id, name, description)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Group not found".to_string()))
+}
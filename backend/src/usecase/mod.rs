@@ -1,9 +1,12 @@
+pub mod api_token;
+pub mod audit;
 pub mod connection;
 pub mod data;
 pub mod error;
 pub mod group;
 pub mod organization;
 pub mod permission;
+pub mod permission_template;
 pub mod user;
 
 pub use error::UsecaseError;
@@ -1,5 +1,7 @@
 mod create_organization;
 mod list_organizations;
+mod register_email_domain;
 
 pub use create_organization::create_organization;
 pub use list_organizations::list_organizations;
+pub use register_email_domain::register_email_domain;
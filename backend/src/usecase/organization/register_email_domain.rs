@@ -0,0 +1,48 @@
+use uuid::Uuid;
+
+use crate::domain::organization::OrgEmailDomain;
+use crate::domain::repository::{OrgEmailDomainRepository, OrganizationRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+pub async fn register_email_domain(
+    org_repo: &dyn OrganizationRepository,
+    domain_repo: &dyn OrgEmailDomainRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    domain: &str,
+    default_role: &str,
+) -> Result<OrgEmailDomain, UsecaseError> {
+    require_super_admin(caller)?;
+
+    let domain = OrgEmailDomain::normalize(domain)
+        .ok_or_else(|| UsecaseError::BadRequest(format!("Invalid email domain: '{}'", domain)))?;
+    if !matches!(default_role, "owner" | "member") {
+        return Err(UsecaseError::BadRequest(format!(
+            "Invalid role: '{}'. Must be one of: owner, member",
+            default_role
+        )));
+    }
+
+    org_repo
+        .get(org_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Organization not found".to_string()))?;
+
+    let existing = domain_repo
+        .find_by_domain(&domain)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if existing.is_some() {
+        return Err(UsecaseError::BadRequest(format!(
+            "Domain '{}' is already mapped to an organization",
+            domain
+        )));
+    }
+
+    domain_repo
+        .create(&domain, org_id, default_role)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use crate::domain::repository::PermissionRepository;
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+pub async fn copy_user_permissions(
+    permission_repo: &dyn PermissionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    from_user: &Uuid,
+    to_user: &Uuid,
+) -> Result<u64, UsecaseError> {
+    require_super_admin(caller)?;
+    if from_user == to_user {
+        return Err(UsecaseError::BadRequest(
+            "from_user and to_user must differ".to_string(),
+        ));
+    }
+    permission_repo
+        .copy_user_permissions(conn_id, from_user, to_user)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+}
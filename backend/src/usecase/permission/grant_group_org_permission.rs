@@ -0,0 +1,33 @@
+use uuid::Uuid;
+
+use crate::domain::permission::GroupOrgPermission;
+use crate::domain::repository::{
+    GroupRepository, OrganizationMemberRepository, PermissionRepository,
+};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_owner};
+
+/// Give a group a default permission on every connection in its organization.
+/// Connection-specific grants still take precedence over it.
+pub async fn grant_group_org_permission(
+    permission_repo: &dyn PermissionRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    group_repo: &dyn GroupRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    group_id: &Uuid,
+    permission: &str,
+) -> Result<GroupOrgPermission, UsecaseError> {
+    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    let group_org = group_repo
+        .get_org_id(group_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if group_org.as_ref() != Some(org_id) {
+        return Err(UsecaseError::NotFound("Group not found".to_string()));
+    }
+    permission_repo
+        .grant_group_org_permission(org_id, group_id, permission)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+}
@@ -1,17 +1,24 @@
+use std::sync::Arc;
+
 use uuid::Uuid;
 
+use crate::domain::notification::Notifier;
 use crate::domain::permission::UserConnectionPermission;
 use crate::domain::repository::{
-    ConnectionRepository, OrganizationMemberRepository, PermissionRepository,
+    ConnectionRepository, OrganizationMemberRepository, PermissionRepository, UserRepository,
 };
 use crate::domain::user::AppUser;
 use crate::usecase::error::{UsecaseError, require_conn_owner};
 
+use super::notify_grant;
+
 #[allow(clippy::too_many_arguments)]
 pub async fn grant_user_connection_permission(
     permission_repo: &dyn PermissionRepository,
     org_member_repo: &dyn OrganizationMemberRepository,
     conn_repo: &dyn ConnectionRepository,
+    user_repo: &dyn UserRepository,
+    notifier: Option<&Arc<dyn Notifier>>,
     caller: &AppUser,
     conn_id: &Uuid,
     user_id: &Uuid,
@@ -19,8 +26,13 @@ pub async fn grant_user_connection_permission(
     all_tables: bool,
 ) -> Result<UserConnectionPermission, UsecaseError> {
     require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
-    permission_repo
+    let granted = permission_repo
         .grant_user_connection_permission(conn_id, user_id, permission, all_tables)
         .await
-        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?;
+    notify_grant(
+        notifier, user_repo, conn_repo, conn_id, user_id, permission, None,
+    )
+    .await;
+    Ok(granted)
 }
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::domain::permission::{AccessSource, ConnectionAccessEntry, PermissionLevel};
+use crate::domain::repository::{
+    ConnectionRepository, GroupRepository, OrganizationMemberRepository, PermissionRepository,
+    UserRepository,
+};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+/// List everyone with explicit access to a connection: the owner, direct
+/// user grants, and members of granted groups (including nested groups).
+/// Restricted to callers who administer the connection.
+#[allow(clippy::too_many_arguments)]
+pub async fn list_connection_access(
+    permission_repo: &dyn PermissionRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    user_repo: &dyn UserRepository,
+    group_repo: &dyn GroupRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+) -> Result<Vec<ConnectionAccessEntry>, UsecaseError> {
+    let internal = |e: anyhow::Error| UsecaseError::Internal(e.to_string());
+
+    let (_, owner_id) = conn_repo
+        .get_ownership(conn_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| UsecaseError::NotFound("Connection not found".to_string()))?;
+    let (level, _) = permission_repo
+        .resolve_connection_permission(caller, conn_id)
+        .await
+        .map_err(internal)?;
+    let is_conn_owner = require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id)
+        .await
+        .is_ok();
+    if level != PermissionLevel::Admin && !is_conn_owner {
+        return Err(UsecaseError::Forbidden(
+            "Connection admin access required".to_string(),
+        ));
+    }
+
+    // Every grant is needed here, so take them all as a single page
+    let direct = permission_repo
+        .list_user_connection_permissions(conn_id, 1, u32::MAX)
+        .await
+        .map_err(internal)?
+        .items;
+    let groups = permission_repo
+        .list_group_connection_permissions(conn_id, 1, u32::MAX)
+        .await
+        .map_err(internal)?
+        .items;
+
+    let entry =
+        |user: &AppUser, source, group_id, permission: &str, all_tables| ConnectionAccessEntry {
+            user_id: user.id,
+            name: user.name.clone(),
+            email: user.email.clone(),
+            source,
+            group_id,
+            permission: permission.to_string(),
+            all_tables,
+        };
+
+    let mut entries = Vec::new();
+    if let Some(owner_id) = owner_id
+        && let Some(owner) = user_repo.get(&owner_id).await.map_err(internal)?
+    {
+        entries.push(entry(&owner, AccessSource::Owner, None, "admin", true));
+    }
+
+    for perm in &direct {
+        if let Some(user) = user_repo.get(&perm.user_id).await.map_err(internal)? {
+            entries.push(entry(
+                &user,
+                AccessSource::Direct,
+                None,
+                &perm.permission,
+                perm.all_tables,
+            ));
+        }
+    }
+
+    for perm in &groups {
+        // Members of nested groups inherit the grant, so list each user once per granting group.
+        let mut seen = HashSet::new();
+        for group_id in group_repo
+            .list_descendant_ids(&perm.group_id)
+            .await
+            .map_err(internal)?
+        {
+            for user in group_repo.list_members(&group_id).await.map_err(internal)? {
+                if seen.insert(user.id) {
+                    entries.push(entry(
+                        &user,
+                        AccessSource::Group,
+                        Some(perm.group_id),
+                        &perm.permission,
+                        perm.all_tables,
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(entries)
+}
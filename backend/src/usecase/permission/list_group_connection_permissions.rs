@@ -1,15 +1,21 @@
 use uuid::Uuid;
 
+use crate::domain::pagination::Paginated;
 use crate::domain::permission::GroupConnectionPermission;
 use crate::domain::repository::PermissionRepository;
+use crate::presentation::request::PermissionPageQuery;
 use crate::usecase::UsecaseError;
 
+use super::page_bounds;
+
 pub async fn list_group_connection_permissions(
     permission_repo: &dyn PermissionRepository,
     conn_id: &Uuid,
-) -> Result<Vec<GroupConnectionPermission>, UsecaseError> {
+    query: &PermissionPageQuery,
+) -> Result<Paginated<GroupConnectionPermission>, UsecaseError> {
+    let (page, per_page) = page_bounds(query);
     permission_repo
-        .list_group_connection_permissions(conn_id)
+        .list_group_connection_permissions(conn_id, page, per_page)
         .await
         .map_err(|e| UsecaseError::Internal(e.to_string()))
 }
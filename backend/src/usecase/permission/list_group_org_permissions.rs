@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::pagination::Paginated;
+use crate::domain::permission::GroupOrgPermission;
+use crate::domain::repository::{OrganizationMemberRepository, PermissionRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::request::PermissionPageQuery;
+use crate::usecase::error::{UsecaseError, require_org_owner};
+
+use super::page_bounds;
+
+pub async fn list_group_org_permissions(
+    permission_repo: &dyn PermissionRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    query: &PermissionPageQuery,
+) -> Result<Paginated<GroupOrgPermission>, UsecaseError> {
+    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    let (page, per_page) = page_bounds(query);
+    permission_repo
+        .list_group_org_permissions(org_id, page, per_page)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
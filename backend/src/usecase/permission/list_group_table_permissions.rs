@@ -1,16 +1,22 @@
 use uuid::Uuid;
 
+use crate::domain::pagination::Paginated;
 use crate::domain::permission::GroupTablePermission;
 use crate::domain::repository::PermissionRepository;
+use crate::presentation::request::PermissionPageQuery;
 use crate::usecase::UsecaseError;
 
+use super::page_bounds;
+
 pub async fn list_group_table_permissions(
     permission_repo: &dyn PermissionRepository,
     conn_id: &Uuid,
     group_id: &Uuid,
-) -> Result<Vec<GroupTablePermission>, UsecaseError> {
+    query: &PermissionPageQuery,
+) -> Result<Paginated<GroupTablePermission>, UsecaseError> {
+    let (page, per_page) = page_bounds(query);
     permission_repo
-        .list_group_table_permissions(conn_id, group_id)
+        .list_group_table_permissions(conn_id, group_id, page, per_page)
         .await
         .map_err(|e| UsecaseError::Internal(e.to_string()))
 }
@@ -1,25 +1,121 @@
+mod copy_user_permissions;
 mod grant_group_connection_permission;
+mod grant_group_org_permission;
 mod grant_group_table_permission;
 mod grant_user_connection_permission;
 mod grant_user_table_permission;
+mod list_connection_access;
 mod list_group_connection_permissions;
+mod list_group_org_permissions;
 mod list_group_table_permissions;
 mod list_user_connection_permissions;
 mod list_user_table_permissions;
+mod resolve_permission;
+mod revoke_all_user_permissions;
 mod revoke_group_connection_permission;
+mod revoke_group_org_permission;
 mod revoke_group_table_permission;
 mod revoke_user_connection_permission;
 mod revoke_user_table_permission;
 
+pub use copy_user_permissions::copy_user_permissions;
 pub use grant_group_connection_permission::grant_group_connection_permission;
+pub use grant_group_org_permission::grant_group_org_permission;
 pub use grant_group_table_permission::grant_group_table_permission;
 pub use grant_user_connection_permission::grant_user_connection_permission;
 pub use grant_user_table_permission::grant_user_table_permission;
+pub use list_connection_access::list_connection_access;
 pub use list_group_connection_permissions::list_group_connection_permissions;
+pub use list_group_org_permissions::list_group_org_permissions;
 pub use list_group_table_permissions::list_group_table_permissions;
 pub use list_user_connection_permissions::list_user_connection_permissions;
 pub use list_user_table_permissions::list_user_table_permissions;
+pub use resolve_permission::resolve_permission;
+pub use revoke_all_user_permissions::revoke_all_user_permissions;
 pub use revoke_group_connection_permission::revoke_group_connection_permission;
+pub use revoke_group_org_permission::revoke_group_org_permission;
 pub use revoke_group_table_permission::revoke_group_table_permission;
 pub use revoke_user_connection_permission::revoke_user_connection_permission;
 pub use revoke_user_table_permission::revoke_user_table_permission;
+
+// ============================================================
+// Shared helpers used by individual function files
+// ============================================================
+
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::notification::Notifier;
+use crate::domain::permission::PermissionLevel;
+use crate::domain::repository::{ConnectionRepository, UserRepository};
+use crate::presentation::request::PermissionPageQuery;
+
+/// Grants per page when a permission listing omits `per_page`.
+const DEFAULT_PER_PAGE: u32 = 100;
+
+/// Largest `per_page` a permission listing accepts.
+const MAX_PER_PAGE: u32 = 500;
+
+/// Resolve `(page, per_page)` for a permission listing, defaulting to the
+/// first page and clamping the size.
+pub(super) fn page_bounds(query: &PermissionPageQuery) -> (u32, u32) {
+    (
+        query.page.unwrap_or(1).max(1),
+        query
+            .per_page
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE),
+    )
+}
+
+/// Tell a user about a grant they just received. The message is sent on a
+/// background task and failures are only logged, so mail problems never
+/// fail the grant. Does nothing when no notifier is configured or when the
+/// grant gives no access at all.
+pub(super) async fn notify_grant(
+    notifier: Option<&Arc<dyn Notifier>>,
+    user_repo: &dyn UserRepository,
+    conn_repo: &dyn ConnectionRepository,
+    conn_id: &Uuid,
+    user_id: &Uuid,
+    permission: &str,
+    table_name: Option<&str>,
+) {
+    let Some(notifier) = notifier else {
+        return;
+    };
+    if PermissionLevel::from_str(permission) == PermissionLevel::None {
+        return;
+    }
+    let user = match user_repo.get(user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(user_id = %user_id, error = %e, "Failed to look up grantee for notification");
+            return;
+        }
+    };
+    let conn_name = match conn_repo.get(conn_id).await {
+        Ok(Some(row)) => row.name,
+        _ => conn_id.to_string(),
+    };
+
+    let subject = format!("You now have {} access to {}", permission, conn_name);
+    let body = match table_name {
+        Some(table) => format!(
+            "You have been granted {} access to table '{}' on the connection '{}' in DBWorks.",
+            permission, table, conn_name
+        ),
+        None => format!(
+            "You have been granted {} access to the connection '{}' in DBWorks.",
+            permission, conn_name
+        ),
+    };
+    let notifier = notifier.clone();
+    tokio::spawn(async move {
+        if let Err(e) = notifier.send(&user.email, &subject, &body).await {
+            tracing::warn!(to = %user.email, error = %e, "Failed to send grant notification");
+        }
+    });
+}
@@ -0,0 +1,30 @@
+use uuid::Uuid;
+
+use crate::domain::permission::ResolvedPermission;
+use crate::domain::repository::{PermissionRepository, UserRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Resolve `user_id`'s effective permission on a table and report which rule
+/// decided it. A debugging aid over the normal resolution, for super_admins only.
+pub async fn resolve_permission(
+    permission_repo: &dyn PermissionRepository,
+    user_repo: &dyn UserRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    user_id: &Uuid,
+    table: &str,
+) -> Result<ResolvedPermission, UsecaseError> {
+    require_super_admin(caller)?;
+    let internal = |e: anyhow::Error| UsecaseError::Internal(e.to_string());
+
+    let user = user_repo
+        .get(user_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| UsecaseError::NotFound("User not found".to_string()))?;
+    permission_repo
+        .explain_table_permission(&user, conn_id, table)
+        .await
+        .map_err(internal)
+}
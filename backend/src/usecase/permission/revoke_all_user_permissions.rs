@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{PermissionRepository, UserRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_super_admin};
+
+/// Drop every user-level grant `user_id` holds, on all connections, while
+/// keeping the user. Returns the number of grants revoked.
+pub async fn revoke_all_user_permissions(
+    permission_repo: &dyn PermissionRepository,
+    user_repo: &dyn UserRepository,
+    caller: &AppUser,
+    user_id: &Uuid,
+) -> Result<u64, UsecaseError> {
+    require_super_admin(caller)?;
+    let internal = |e: anyhow::Error| UsecaseError::Internal(e.to_string());
+
+    user_repo
+        .get(user_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| UsecaseError::NotFound("User not found".to_string()))?;
+    permission_repo
+        .revoke_all_for_user(user_id)
+        .await
+        .map_err(internal)
+}
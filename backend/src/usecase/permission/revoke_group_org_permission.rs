@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{OrganizationMemberRepository, PermissionRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_owner};
+
+pub async fn revoke_group_org_permission(
+    permission_repo: &dyn PermissionRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    group_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    let revoked = permission_repo
+        .revoke_group_org_permission(org_id, group_id)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?;
+    if revoked {
+        Ok(())
+    } else {
+        Err(UsecaseError::NotFound("Permission not found".to_string()))
+    }
+}
@@ -0,0 +1,59 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{
+    ConnectionRepository, OrganizationMemberRepository, PermissionTemplateRepository,
+};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_conn_owner};
+
+/// Expand a template into table grants for exactly one of `user_id` or
+/// `group_id` on `conn_id`. Re-applying the same template is idempotent.
+/// Returns the number of table grants written.
+#[allow(clippy::too_many_arguments)]
+pub async fn apply_template(
+    template_repo: &dyn PermissionTemplateRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    conn_repo: &dyn ConnectionRepository,
+    caller: &AppUser,
+    conn_id: &Uuid,
+    template_id: &Uuid,
+    user_id: Option<&Uuid>,
+    group_id: Option<&Uuid>,
+) -> Result<u64, UsecaseError> {
+    require_conn_owner(org_member_repo, conn_repo, &caller.id, conn_id).await?;
+
+    let template = template_repo
+        .find_by_id(template_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Template not found".to_string()))?;
+    let conn_org = conn_repo
+        .get_ownership(conn_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .and_then(|(org_id, _)| org_id);
+    if conn_org != Some(template.organization_id) {
+        return Err(UsecaseError::BadRequest(
+            "Template belongs to a different organization than the connection".to_string(),
+        ));
+    }
+
+    let result = match (user_id, group_id) {
+        (Some(user_id), None) => {
+            template_repo
+                .apply_to_user(template_id, conn_id, user_id)
+                .await
+        }
+        (None, Some(group_id)) => {
+            template_repo
+                .apply_to_group(template_id, conn_id, group_id)
+                .await
+        }
+        _ => {
+            return Err(UsecaseError::BadRequest(
+                "Exactly one of user_id or group_id is required".to_string(),
+            ));
+        }
+    };
+    result.map_err(|e| UsecaseError::BadRequest(e.to_string()))
+}
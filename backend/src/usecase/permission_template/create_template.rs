@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::permission::{PermissionTemplate, TemplateTableGrant};
+use crate::domain::repository::{OrganizationMemberRepository, PermissionTemplateRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_owner};
+
+use super::validate_grants;
+
+pub async fn create_template(
+    template_repo: &dyn PermissionTemplateRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    name: &str,
+    description: Option<&str>,
+    tables: &[TemplateTableGrant],
+) -> Result<PermissionTemplate, UsecaseError> {
+    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    validate_grants(tables)?;
+    template_repo
+        .create(org_id, name, description, tables)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))
+}
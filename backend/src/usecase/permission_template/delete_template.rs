@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{OrganizationMemberRepository, PermissionTemplateRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::UsecaseError;
+
+use super::require_template_owner;
+
+/// Delete a template. Grants already expanded from it are left in place.
+pub async fn delete_template(
+    template_repo: &dyn PermissionTemplateRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    template_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    require_template_owner(template_repo, org_member_repo, caller, template_id).await?;
+    let deleted = template_repo
+        .delete(template_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if !deleted {
+        return Err(UsecaseError::NotFound("Template not found".to_string()));
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use uuid::Uuid;
+
+use crate::domain::permission::PermissionTemplate;
+use crate::domain::repository::{OrganizationMemberRepository, PermissionTemplateRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_owner};
+
+pub async fn list_templates(
+    template_repo: &dyn PermissionTemplateRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+) -> Result<Vec<PermissionTemplate>, UsecaseError> {
+    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    template_repo
+        .list_by_org(org_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))
+}
@@ -0,0 +1,56 @@
+mod apply_template;
+mod create_template;
+mod delete_template;
+mod list_templates;
+mod update_template;
+
+pub use apply_template::apply_template;
+pub use create_template::create_template;
+pub use delete_template::delete_template;
+pub use list_templates::list_templates;
+pub use update_template::update_template;
+
+// ============================================================
+// Shared helpers used by individual function files
+// ============================================================
+
+use uuid::Uuid;
+
+use crate::domain::permission::{PermissionLevel, PermissionTemplate, TemplateTableGrant};
+use crate::domain::repository::{OrganizationMemberRepository, PermissionTemplateRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::error::{UsecaseError, require_org_owner};
+
+/// Reject empty table names and unknown permission levels before they are stored.
+fn validate_grants(tables: &[TemplateTableGrant]) -> Result<(), UsecaseError> {
+    for grant in tables {
+        if grant.table_name.trim().is_empty() {
+            return Err(UsecaseError::BadRequest(
+                "Template table_name must not be empty".to_string(),
+            ));
+        }
+        if PermissionLevel::from_str(&grant.permission) == PermissionLevel::None {
+            return Err(UsecaseError::BadRequest(format!(
+                "Invalid permission '{}' for table '{}'",
+                grant.permission, grant.table_name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Load a template and check that the caller owns its organization.
+async fn require_template_owner(
+    template_repo: &dyn PermissionTemplateRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    template_id: &Uuid,
+) -> Result<PermissionTemplate, UsecaseError> {
+    let template = template_repo
+        .find_by_id(template_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Template not found".to_string()))?;
+    require_org_owner(org_member_repo, &caller.id, &template.organization_id).await?;
+    Ok(template)
+}
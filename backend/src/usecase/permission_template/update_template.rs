@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use crate::domain::permission::{PermissionTemplate, TemplateTableGrant};
+use crate::domain::repository::{OrganizationMemberRepository, PermissionTemplateRepository};
+use crate::domain::user::AppUser;
+use crate::usecase::UsecaseError;
+
+use super::{require_template_owner, validate_grants};
+
+pub async fn update_template(
+    template_repo: &dyn PermissionTemplateRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    template_id: &Uuid,
+    name: &str,
+    description: Option<&str>,
+    tables: &[TemplateTableGrant],
+) -> Result<PermissionTemplate, UsecaseError> {
+    require_template_owner(template_repo, org_member_repo, caller, template_id).await?;
+    validate_grants(tables)?;
+    template_repo
+        .update(template_id, name, description, tables)
+        .await
+        .map_err(|e| UsecaseError::BadRequest(e.to_string()))?
+        .ok_or_else(|| UsecaseError::NotFound("Template not found".to_string()))
+}
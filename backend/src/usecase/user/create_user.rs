@@ -1,9 +1,11 @@
 use uuid::Uuid;
 
 use crate::domain::repository::{OrganizationMemberRepository, UserRepository};
-use crate::domain::user::AppUser;
-use crate::usecase::error::{UsecaseError, require_org_owner};
+use crate::domain::user::{AppUser, Role};
+use crate::usecase::error::{UsecaseError, require_org_manager};
 
+/// Create a user in `org_id`. Org owners and org admins can create users,
+/// but never with a role above their own.
 pub async fn create_user(
     user_repo: &dyn UserRepository,
     org_member_repo: &dyn OrganizationMemberRepository,
@@ -13,7 +15,19 @@ pub async fn create_user(
     email: &str,
     role: &str,
 ) -> Result<AppUser, UsecaseError> {
-    require_org_owner(org_member_repo, &caller.id, org_id).await?;
+    require_org_manager(org_member_repo, caller, org_id).await?;
+    let level = Role::parse(role).ok_or_else(|| {
+        UsecaseError::BadRequest(format!(
+            "Invalid role '{}': expected member, org_admin or super_admin",
+            role
+        ))
+    })?;
+    if level > caller.role_level() {
+        return Err(UsecaseError::Forbidden(format!(
+            "Cannot create a user with the {} role",
+            role
+        )));
+    }
 
     // Create the user
     let user = user_repo
@@ -0,0 +1,88 @@
+use uuid::Uuid;
+
+use crate::domain::repository::{OrganizationMemberRepository, UserRepository};
+use crate::domain::user::AppUser;
+use crate::presentation::state::ConnectionManager;
+use crate::usecase::error::{UsecaseError, require_org_admin};
+
+/// Remove a member from `org_id` along with their permissions and memberships.
+/// A super_admin deletes the account outright when `org_id` is its only
+/// organization; otherwise, and always for an org admin (for members up to
+/// their own role), only the `org_id` membership is removed, so other
+/// organizations are never affected. The organization's last super_admin is
+/// never removed. Deleting the account also closes and unregisters the
+/// user's personal connections, whose saved rows go with it.
+pub async fn delete_user(
+    connection_manager: &ConnectionManager,
+    user_repo: &dyn UserRepository,
+    org_member_repo: &dyn OrganizationMemberRepository,
+    caller: &AppUser,
+    org_id: &Uuid,
+    user_id: &Uuid,
+) -> Result<(), UsecaseError> {
+    require_org_admin(org_member_repo, caller, org_id).await?;
+
+    let is_member = org_member_repo
+        .get_role(org_id, user_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .is_some();
+    if !is_member {
+        return Err(UsecaseError::NotFound("User not found".to_string()));
+    }
+
+    let members = user_repo
+        .list_by_org(org_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if members
+        .iter()
+        .any(|u| &u.id == user_id && u.role_level() > caller.role_level())
+    {
+        return Err(UsecaseError::Forbidden(
+            "Cannot delete a user with a higher role".to_string(),
+        ));
+    }
+    let target_is_super_admin = members
+        .iter()
+        .any(|u| &u.id == user_id && u.is_super_admin());
+    let super_admins = members.iter().filter(|u| u.is_super_admin()).count();
+    if target_is_super_admin && super_admins <= 1 {
+        return Err(UsecaseError::BadRequest(
+            "Cannot delete the last super_admin of the organization".to_string(),
+        ));
+    }
+
+    let in_other_orgs = org_member_repo
+        .get_user_orgs(user_id)
+        .await
+        .map_err(|e| UsecaseError::Internal(e.to_string()))?
+        .iter()
+        .any(|m| &m.organization_id != org_id);
+    let delete_account = caller.is_super_admin() && !in_other_orgs;
+    let personal_connections: Vec<Uuid> = if delete_account {
+        connection_manager
+            .list_personal(user_id)
+            .await
+            .into_iter()
+            .filter(|c| c.organization_id.is_none())
+            .map(|c| c.id)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let deleted = if delete_account {
+        user_repo.delete(user_id).await
+    } else {
+        org_member_repo.remove_member(org_id, user_id).await
+    }
+    .map_err(|e| UsecaseError::Internal(e.to_string()))?;
+    if !deleted {
+        return Err(UsecaseError::NotFound("User not found".to_string()));
+    }
+    // The saved rows are already gone; this closes their live pools
+    for conn_id in &personal_connections {
+        connection_manager.remove(conn_id).await;
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
 mod create_user;
+mod delete_user;
 mod list_users;
 
 pub use create_user::create_user;
+pub use delete_user::delete_user;
 pub use list_users::list_users;
@@ -1,13 +1,12 @@
 use dbworks_backend::domain::user::AppUser;
-use dbworks_backend::infrastructure::auth::jwt::Claims;
+use dbworks_backend::infrastructure::auth::jwt::{Claims, JwtConfig};
+use jsonwebtoken::{EncodingKey, Header, encode};
 
-/// Smoke test: verifies that the CryptoProvider required by jsonwebtoken is
-/// properly configured.  If the `rust_crypto` feature flag is removed from
-/// jsonwebtoken in Cargo.toml, this test will panic with:
-///   "Could not automatically determine the process-level CryptoProvider"
-#[test]
-fn jwt_roundtrip_smoke_test() {
-    let user = AppUser {
+const RS256_PRIVATE_KEY: &str = include_str!("../../fixtures/jwt_rs256_private.pem");
+const RS256_PUBLIC_KEY: &str = include_str!("../../fixtures/jwt_rs256_public.pem");
+
+fn test_user() -> AppUser {
+    AppUser {
         id: uuid::Uuid::new_v4(),
         email: "test@example.com".to_string(),
         name: "Test User".to_string(),
@@ -17,18 +16,86 @@ fn jwt_roundtrip_smoke_test() {
         avatar_url: None,
         created_at: None,
         updated_at: None,
-    };
+        acting_admin_id: None,
+        api_token_scope: None,
+    }
+}
 
-    let secret = "test-secret-key";
+/// Smoke test: verifies that the CryptoProvider required by jsonwebtoken is
+/// properly configured.  If the `rust_crypto` feature flag is removed from
+/// jsonwebtoken in Cargo.toml, this test will panic with:
+///   "Could not automatically determine the process-level CryptoProvider"
+#[test]
+fn jwt_roundtrip_smoke_test() {
+    let user = test_user();
+    let config = JwtConfig::hs256("test-secret-key");
 
     // This will panic if CryptoProvider is not available
-    let token = Claims::generate_token(&user, secret)
+    let token = Claims::generate_token(&user, &config)
         .expect("JWT encoding must succeed — is CryptoProvider configured?");
 
     // Verify decode also works
-    let claims = Claims::decode(&token, secret)
+    let claims = Claims::decode(&token, &config)
         .expect("JWT decoding must succeed — is CryptoProvider configured?");
 
     assert_eq!(claims.email, "test@example.com");
     assert_eq!(claims.sub, user.id.to_string());
 }
+
+#[test]
+fn rs256_roundtrip() {
+    let user = test_user();
+    let config =
+        JwtConfig::rs256(RS256_PRIVATE_KEY.as_bytes(), RS256_PUBLIC_KEY.as_bytes()).unwrap();
+
+    let token = Claims::generate_token(&user, &config).unwrap();
+    let claims = Claims::decode(&token, &config).unwrap();
+
+    assert_eq!(claims.sub, user.id.to_string());
+}
+
+#[test]
+fn hs256_token_rejected_by_rs256_config() {
+    let user = test_user();
+    let hs = JwtConfig::hs256("test-secret-key");
+    let rs = JwtConfig::rs256(RS256_PRIVATE_KEY.as_bytes(), RS256_PUBLIC_KEY.as_bytes()).unwrap();
+
+    let token = Claims::generate_token(&user, &hs).unwrap();
+    assert!(Claims::decode(&token, &rs).is_err());
+}
+
+#[test]
+fn expiry_is_configurable() {
+    let user = test_user();
+    let config = JwtConfig::hs256("test-secret-key").with_expiry_secs(120);
+
+    let token = Claims::generate_token(&user, &config).unwrap();
+    let claims = Claims::decode(&token, &config).unwrap();
+
+    let remaining = claims.exp as i64 - chrono::Utc::now().timestamp();
+    assert!((110..=120).contains(&remaining));
+}
+
+#[test]
+fn expired_token_is_rejected() {
+    let config = JwtConfig::hs256("test-secret-key");
+    let claims = Claims {
+        sub: uuid::Uuid::new_v4().to_string(),
+        role: "member".to_string(),
+        email: "test@example.com".to_string(),
+        // Well past the default validation leeway
+        exp: (chrono::Utc::now().timestamp() - 3600) as usize,
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(b"test-secret-key"),
+    )
+    .unwrap();
+
+    let err = Claims::decode(&token, &config).unwrap_err();
+    assert_eq!(
+        *err.kind(),
+        jsonwebtoken::errors::ErrorKind::ExpiredSignature
+    );
+}
@@ -1 +1,2 @@
 mod jwt_test;
+mod oauth_test;
@@ -0,0 +1,103 @@
+use crate::common;
+use dbworks_backend::domain::repository::{
+    OrgEmailDomainRepository, OrganizationMemberRepository, OrganizationRepository,
+};
+use dbworks_backend::infrastructure::auth::oauth::{OAuthProfile, find_or_create_user};
+use dbworks_backend::infrastructure::database::org_email_domain_repo::PgOrgEmailDomainRepository;
+use dbworks_backend::infrastructure::database::organization_member_repo::PgOrganizationMemberRepository;
+use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
+use serial_test::serial;
+use uuid::Uuid;
+
+fn profile<'a>(provider_id: &'a str, email: &'a str, email_verified: bool) -> OAuthProfile<'a> {
+    OAuthProfile {
+        provider: "google",
+        provider_id,
+        name: "New User",
+        email,
+        email_verified,
+        avatar_url: None,
+    }
+}
+
+async fn seed_domain(pool: &sqlx::PgPool, domain: &str, role: &str) -> Uuid {
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Acme")
+        .await
+        .unwrap();
+    PgOrgEmailDomainRepository::new(pool.clone())
+        .create(domain, &org.id, role)
+        .await
+        .unwrap();
+    org.id
+}
+
+#[tokio::test]
+#[serial]
+async fn verified_email_joins_mapped_org_with_default_role() {
+    let pool = common::setup_test_db().await;
+    let org_id = seed_domain(&pool, "acme.com", "member").await;
+    let domain_repo = PgOrgEmailDomainRepository::new(pool.clone());
+    let member_repo = PgOrganizationMemberRepository::new(pool.clone());
+
+    let user = find_or_create_user(
+        &pool,
+        &domain_repo,
+        &member_repo,
+        &profile("g-1", "alice@Acme.com", true),
+    )
+    .await
+    .unwrap();
+
+    let role = member_repo.get_role(&org_id, &user.id).await.unwrap();
+    assert_eq!(role.as_deref(), Some("member"));
+}
+
+#[tokio::test]
+#[serial]
+async fn unverified_email_does_not_join_org() {
+    let pool = common::setup_test_db().await;
+    let org_id = seed_domain(&pool, "acme.com", "member").await;
+    let domain_repo = PgOrgEmailDomainRepository::new(pool.clone());
+    let member_repo = PgOrganizationMemberRepository::new(pool.clone());
+
+    let user = find_or_create_user(
+        &pool,
+        &domain_repo,
+        &member_repo,
+        &profile("g-2", "bob@acme.com", false),
+    )
+    .await
+    .unwrap();
+
+    let role = member_repo.get_role(&org_id, &user.id).await.unwrap();
+    assert!(role.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn unmapped_domain_does_not_join_org() {
+    let pool = common::setup_test_db().await;
+    let org_id = seed_domain(&pool, "acme.com", "member").await;
+    let domain_repo = PgOrgEmailDomainRepository::new(pool.clone());
+    let member_repo = PgOrganizationMemberRepository::new(pool.clone());
+
+    let user = find_or_create_user(
+        &pool,
+        &domain_repo,
+        &member_repo,
+        &profile("g-3", "carol@other.com", true),
+    )
+    .await
+    .unwrap();
+
+    let role = member_repo.get_role(&org_id, &user.id).await.unwrap();
+    assert!(role.is_none());
+    assert!(
+        member_repo
+            .get_user_orgs(&user.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -0,0 +1,64 @@
+use crate::common;
+use dbworks_backend::infrastructure::connections_config::ConnectionsConfig;
+use dbworks_backend::presentation::state::ConnectionManager;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn configured_connections_register_once_by_name() {
+    let (host, port, database, user, password) = common::parse_db_url();
+    unsafe {
+        std::env::set_var("DBWORKS_CONFIG_TEST_PASSWORD", password);
+    }
+    let json = format!(
+        r#"{{
+            "connections": [
+                {{
+                    "name": "analytics",
+                    "host": "{host}",
+                    "port": {port},
+                    "database": "{database}",
+                    "user": "{user}",
+                    "password": "${{DBWORKS_CONFIG_TEST_PASSWORD}}",
+                    "tags": ["shared"],
+                    "read_only": true
+                }},
+                {{
+                    "name": "missing-secret",
+                    "host": "{host}",
+                    "port": {port},
+                    "database": "{database}",
+                    "user": "{user}",
+                    "password": "${{DBWORKS_CONFIG_TEST_UNSET}}"
+                }}
+            ]
+        }}"#
+    );
+    let config = ConnectionsConfig::parse(&json).unwrap();
+    assert_eq!(config.connections.len(), 2);
+
+    let cm = ConnectionManager::new(None, None);
+    let first = cm.add_configured(config.clone()).await;
+    // A second boot sees "analytics" already present and leaves it alone
+    let second = cm.add_configured(config).await;
+
+    let connections = cm.list().await;
+    cm.shutdown().await;
+
+    assert_eq!(first.len(), 2);
+    assert!(first[0].connection_id.is_some() && first[0].error.is_none());
+    assert!(
+        first[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("DBWORKS_CONFIG_TEST_UNSET")
+    );
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].name, "missing-secret");
+
+    assert_eq!(connections.len(), 1);
+    assert_eq!(connections[0].name, "analytics");
+    assert!(connections[0].read_only);
+    assert_eq!(connections[0].tags, vec!["shared".to_string()]);
+}
@@ -0,0 +1,143 @@
+use crate::common;
+use dbworks_backend::domain::audit::AuditFilter;
+use dbworks_backend::domain::repository::{
+    AuditLogRepository, OrganizationRepository, UserRepository,
+};
+use dbworks_backend::infrastructure::database::audit_log_repo::PgAuditLogRepository;
+use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
+use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use serial_test::serial;
+use uuid::Uuid;
+
+/// Seed an org connection and two users. Returns (conn_id, alice_id, bob_id).
+async fn seed(pool: &sqlx::PgPool) -> (Uuid, Uuid, Uuid) {
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Test Org")
+        .await
+        .unwrap();
+    let user_repo = PgUserRepository::new(pool.clone());
+    let alice = user_repo
+        .create("Alice", "alice@test.com", "member")
+        .await
+        .unwrap();
+    let bob = user_repo
+        .create("Bob", "bob@test.com", "member")
+        .await
+        .unwrap();
+
+    let conn_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO saved_connections (id, organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, $2, 'test-conn', 'localhost', 5432, 'testdb', 'test', 'encrypted')"#,
+    )
+    .bind(conn_id)
+    .bind(org.id)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    (conn_id, alice.id, bob.id)
+}
+
+#[tokio::test]
+#[serial]
+async fn list_returns_newest_first() {
+    let pool = common::setup_test_db().await;
+    let (conn_id, alice, _) = seed(&pool).await;
+    let repo = PgAuditLogRepository::new(pool);
+
+    for action in ["insert", "update", "delete"] {
+        repo.record(&conn_id, &alice, action, Some("users"), None)
+            .await
+            .unwrap();
+    }
+
+    let page = repo.list(&conn_id, &AuditFilter::default()).await.unwrap();
+    assert_eq!(page.total_count, 3);
+    let actions: Vec<_> = page.items.iter().map(|e| e.action.as_str()).collect();
+    assert_eq!(actions, vec!["delete", "update", "insert"]);
+}
+
+#[tokio::test]
+#[serial]
+async fn list_filters_by_action() {
+    let pool = common::setup_test_db().await;
+    let (conn_id, alice, bob) = seed(&pool).await;
+    let repo = PgAuditLogRepository::new(pool);
+
+    repo.record(&conn_id, &alice, "insert", Some("users"), None)
+        .await
+        .unwrap();
+    repo.record(&conn_id, &bob, "delete", Some("users"), None)
+        .await
+        .unwrap();
+    repo.record(&conn_id, &alice, "delete", Some("orders"), None)
+        .await
+        .unwrap();
+
+    let filter = AuditFilter {
+        action: Some("delete".to_string()),
+        ..AuditFilter::default()
+    };
+    let page = repo.list(&conn_id, &filter).await.unwrap();
+    assert_eq!(page.total_count, 2);
+    assert!(page.items.iter().all(|e| e.action == "delete"));
+}
+
+#[tokio::test]
+#[serial]
+async fn list_filters_by_actor() {
+    let pool = common::setup_test_db().await;
+    let (conn_id, alice, bob) = seed(&pool).await;
+    let repo = PgAuditLogRepository::new(pool);
+
+    repo.record(&conn_id, &alice, "insert", Some("users"), None)
+        .await
+        .unwrap();
+    repo.record(&conn_id, &bob, "update", Some("users"), None)
+        .await
+        .unwrap();
+
+    let filter = AuditFilter {
+        actor_id: Some(bob),
+        ..AuditFilter::default()
+    };
+    let page = repo.list(&conn_id, &filter).await.unwrap();
+    assert_eq!(page.total_count, 1);
+    assert_eq!(page.items[0].actor_id, Some(bob));
+    assert_eq!(page.items[0].action, "update");
+}
+
+#[tokio::test]
+#[serial]
+async fn list_filters_by_since_and_paginates() {
+    let pool = common::setup_test_db().await;
+    let (conn_id, alice, _) = seed(&pool).await;
+    let repo = PgAuditLogRepository::new(pool.clone());
+
+    let old = repo
+        .record(&conn_id, &alice, "insert", Some("users"), None)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE audit_log SET created_at = NOW() - INTERVAL '2 days' WHERE id = $1")
+        .bind(old.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    for _ in 0..3 {
+        repo.record(&conn_id, &alice, "update", Some("users"), None)
+            .await
+            .unwrap();
+    }
+
+    let filter = AuditFilter {
+        since: Some(chrono::Utc::now() - chrono::Duration::days(1)),
+        page: 2,
+        per_page: 2,
+        ..AuditFilter::default()
+    };
+    let page = repo.list(&conn_id, &filter).await.unwrap();
+    assert_eq!(page.total_count, 3);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.page, 2);
+}
@@ -33,7 +33,7 @@ fn test_encryptor() -> Encryptor {
     unsafe {
         std::env::set_var(
             "ENCRYPTION_KEY",
-            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &[42u8; 32]),
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, [42u8; 32]),
         );
     }
     Encryptor::from_env().unwrap()
@@ -51,6 +51,19 @@ fn make_connection_info(org_id: Option<Uuid>, owner_id: Option<Uuid>) -> Connect
         password: "super_secret".to_string(),
         organization_id: org_id,
         owner_user_id: owner_id,
+        tags: vec![],
+        statement_timeout_ms: None,
+        pool_idle_timeout_ms: None,
+        pool_max_lifetime_ms: None,
+        read_only: false,
+        default_page_size: None,
+        default_sort_order: None,
+        search_path: None,
+        default_schema: None,
+        role: None,
+        hidden_tables: vec![],
+        replica_hosts: vec![],
+        persisted: false,
     }
 }
 
@@ -136,3 +149,31 @@ async fn delete_nonexistent_connection_returns_false() {
     let deleted = conn_repo.delete(&Uuid::new_v4()).await.unwrap();
     assert!(!deleted);
 }
+
+#[tokio::test]
+#[serial]
+async fn save_connection_with_tags_and_update_them() {
+    let pool = common::setup_test_db().await;
+    let (org, user) = setup_org_and_user(&pool).await;
+    let enc = test_encryptor();
+    let conn_repo = PgConnectionRepository::new(pool, enc);
+
+    let info = ConnectionInfo {
+        tags: vec!["prod".to_string(), "analytics".to_string()],
+        ..make_connection_info(Some(org.id), Some(user.id))
+    };
+    let saved = conn_repo
+        .save(Some(&org.id), Some(&user.id), &info)
+        .await
+        .unwrap();
+    assert_eq!(saved.tags, vec!["prod", "analytics"]);
+
+    let updated = conn_repo
+        .set_tags(&saved.id, &["staging".to_string()])
+        .await
+        .unwrap();
+    assert!(updated);
+
+    let all = conn_repo.list().await.unwrap();
+    assert_eq!(all[0].tags, vec!["staging"]);
+}
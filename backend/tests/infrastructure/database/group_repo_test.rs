@@ -81,9 +81,9 @@ async fn add_group_member_idempotent() {
 
     let group = group_repo.create(&org.id, "Team", None).await.unwrap();
 
-    // Adding same member twice should not fail (ON CONFLICT DO NOTHING)
-    group_repo.add_member(&group.id, &alice.id).await.unwrap();
-    group_repo.add_member(&group.id, &alice.id).await.unwrap();
+    // Adding same member twice should not fail, and reports the no-op
+    assert!(group_repo.add_member(&group.id, &alice.id).await.unwrap());
+    assert!(!group_repo.add_member(&group.id, &alice.id).await.unwrap());
 
     let members = group_repo.list_members(&group.id).await.unwrap();
     assert_eq!(members.len(), 1);
@@ -116,3 +116,89 @@ async fn remove_group_member() {
         .unwrap();
     assert!(!removed_again);
 }
+
+#[tokio::test]
+#[serial]
+async fn update_group_renames_and_keeps_omitted_fields() {
+    let pool = common::setup_test_db().await;
+    let (org, _, _) = setup_org_and_users(&pool).await;
+    let group_repo = PgGroupRepository::new(pool);
+
+    let group = group_repo
+        .create(&org.id, "Engineering", Some("Builds things"))
+        .await
+        .unwrap();
+    let updated = group_repo
+        .update(&group.id, Some("Platform"), None)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(updated.name, "Platform");
+    assert_eq!(updated.description.as_deref(), Some("Builds things"));
+    assert!(
+        group_repo
+            .update(&uuid::Uuid::new_v4(), Some("x"), None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn delete_group_removes_members_and_permissions() {
+    let pool = common::setup_test_db().await;
+    let (org, alice, _) = setup_org_and_users(&pool).await;
+    let group_repo = PgGroupRepository::new(pool.clone());
+
+    let group = group_repo
+        .create(&org.id, "Engineering", None)
+        .await
+        .unwrap();
+    group_repo.add_member(&group.id, &alice.id).await.unwrap();
+    let conn_id: uuid::Uuid = sqlx::query_scalar(
+        r#"INSERT INTO saved_connections (organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, 'conn', 'localhost', 5432, 'db', 'u', 'x') RETURNING id"#,
+    )
+    .bind(org.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO group_connection_permissions (group_id, connection_id) VALUES ($1, $2)",
+    )
+    .bind(group.id)
+    .bind(conn_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO group_table_permissions (group_id, connection_id, table_name) VALUES ($1, $2, 'orders')",
+    )
+    .bind(group.id)
+    .bind(conn_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert!(group_repo.delete(&group.id).await.unwrap());
+    assert!(!group_repo.delete(&group.id).await.unwrap());
+
+    for table in [
+        "group_members",
+        "group_connection_permissions",
+        "group_table_permissions",
+    ] {
+        let remaining: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {} WHERE group_id = $1",
+            table
+        ))
+        .bind(group.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(remaining, 0, "{} still has rows", table);
+    }
+    assert!(group_repo.list_by_org(&org.id).await.unwrap().is_empty());
+}
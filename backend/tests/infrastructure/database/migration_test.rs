@@ -0,0 +1,82 @@
+use std::str::FromStr;
+
+use crate::common;
+use serial_test::serial;
+use sqlx::PgPool;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+/// Migration that renames duplicate connection names before indexing them.
+const UNIQUE_CONNECTION_NAMES: i64 = 20261016000021;
+
+const SCRATCH_SCHEMA: &str = "migration_test";
+
+/// A pool whose `search_path` is a fresh, empty schema, so migrations can be
+/// applied step by step without touching the shared test tables.
+async fn scratch_pool() -> PgPool {
+    let admin = PgPool::connect(&common::test_database_url()).await.unwrap();
+    for statement in [
+        format!("DROP SCHEMA IF EXISTS {} CASCADE", SCRATCH_SCHEMA),
+        format!("CREATE SCHEMA {}", SCRATCH_SCHEMA),
+    ] {
+        sqlx::query(&statement).execute(&admin).await.unwrap();
+    }
+    let options = PgConnectOptions::from_str(&common::test_database_url())
+        .unwrap()
+        .options([("search_path", SCRATCH_SCHEMA)]);
+    PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn duplicate_connection_names_skip_suffixes_already_taken() {
+    let pool = scratch_pool().await;
+    let mut before = sqlx::migrate!("./migrations");
+    before.migrations = before
+        .migrations
+        .iter()
+        .filter(|m| m.version < UNIQUE_CONNECTION_NAMES)
+        .cloned()
+        .collect::<Vec<_>>()
+        .into();
+    before.run(&pool).await.unwrap();
+
+    let org_id: uuid::Uuid =
+        sqlx::query_scalar("INSERT INTO organizations (name) VALUES ('Org') RETURNING id")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    for (name, age_days) in [("db", 3), ("db", 2), ("db (2)", 1)] {
+        sqlx::query(
+            r#"INSERT INTO saved_connections (organization_id, name, host, database_name, username, encrypted_password, created_at)
+               VALUES ($1, $2, 'localhost', 'testdb', 'test', 'encrypted', NOW() - make_interval(days => $3))"#,
+        )
+        .bind(org_id)
+        .bind(name)
+        .bind(age_days)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let migrator: Migrator = sqlx::migrate!("./migrations");
+    let result = migrator.run(&pool).await;
+    let names: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM saved_connections ORDER BY created_at")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    pool.close().await;
+    let admin = PgPool::connect(&common::test_database_url()).await.unwrap();
+    sqlx::query(&format!("DROP SCHEMA {} CASCADE", SCRATCH_SCHEMA))
+        .execute(&admin)
+        .await
+        .unwrap();
+
+    result.unwrap();
+    assert_eq!(names, vec!["db", "db (3)", "db (2)"]);
+}
@@ -1,5 +1,10 @@
+mod audit_log_repo_test;
 mod connection_repo_test;
 mod group_repo_test;
+mod migration_test;
 mod organization_repo_test;
 mod permission_repo_test;
+mod permission_template_repo_test;
+mod query_log_repo_test;
+mod table_metadata_repo_test;
 mod user_repo_test;
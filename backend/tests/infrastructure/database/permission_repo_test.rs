@@ -23,6 +23,7 @@ struct TestFixture {
     admin: dbworks_backend::domain::user::AppUser,
     member: dbworks_backend::domain::user::AppUser,
     conn_id: Uuid,
+    pool: sqlx::PgPool,
     org_repo: PgOrganizationRepository,
     user_repo: PgUserRepository,
     group_repo: PgGroupRepository,
@@ -53,11 +54,11 @@ async fn setup() -> TestFixture {
     unsafe {
         std::env::set_var(
             "ENCRYPTION_KEY",
-            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &[42u8; 32]),
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, [42u8; 32]),
         );
     }
     let enc = Encryptor::from_env().unwrap();
-    let conn_repo = PgConnectionRepository::new(pool, enc);
+    let conn_repo = PgConnectionRepository::new(pool.clone(), enc);
 
     let info = ConnectionInfo {
         id: Uuid::new_v4(),
@@ -70,6 +71,19 @@ async fn setup() -> TestFixture {
         password: "pass".to_string(),
         organization_id: Some(org.id),
         owner_user_id: Some(member.id),
+        tags: vec![],
+        statement_timeout_ms: None,
+        pool_idle_timeout_ms: None,
+        pool_max_lifetime_ms: None,
+        read_only: false,
+        default_page_size: None,
+        default_sort_order: None,
+        search_path: None,
+        default_schema: None,
+        role: None,
+        hidden_tables: vec![],
+        replica_hosts: vec![],
+        persisted: false,
     };
 
     let saved = conn_repo
@@ -82,6 +96,7 @@ async fn setup() -> TestFixture {
         admin,
         member,
         conn_id: saved.id,
+        pool,
         org_repo,
         user_repo,
         group_repo,
@@ -111,9 +126,10 @@ async fn grant_and_list_user_connection_permission() {
 
     let list = f
         .permission_repo
-        .list_user_connection_permissions(&f.conn_id)
+        .list_user_connection_permissions(&f.conn_id, 1, 100)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(list.len(), 1);
 }
 
@@ -140,9 +156,10 @@ async fn grant_user_connection_permission_upserts() {
     // Still only 1 record
     let list = f
         .permission_repo
-        .list_user_connection_permissions(&f.conn_id)
+        .list_user_connection_permissions(&f.conn_id, 1, 100)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(list.len(), 1);
 }
 
@@ -165,12 +182,73 @@ async fn revoke_user_connection_permission() {
 
     let list = f
         .permission_repo
-        .list_user_connection_permissions(&f.conn_id)
+        .list_user_connection_permissions(&f.conn_id, 1, 100)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert!(list.is_empty());
 }
 
+#[tokio::test]
+#[serial]
+async fn revoke_all_for_user_drops_grants_on_every_connection() {
+    let f = setup().await;
+    let other_conn: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO saved_connections (organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, 'other-conn', 'localhost', 5432, 'otherdb', 'test', 'encrypted')
+           RETURNING id"#,
+    )
+    .bind(f.org.id)
+    .fetch_one(&f.pool)
+    .await
+    .unwrap();
+
+    for conn_id in [&f.conn_id, &other_conn] {
+        f.permission_repo
+            .grant_user_connection_permission(conn_id, &f.member.id, "read", false)
+            .await
+            .unwrap();
+        f.permission_repo
+            .grant_user_table_permission(conn_id, &f.member.id, "orders", "write")
+            .await
+            .unwrap();
+    }
+    // Someone else's grant is left alone
+    f.permission_repo
+        .grant_user_connection_permission(&other_conn, &f.admin.id, "read", true)
+        .await
+        .unwrap();
+
+    let revoked = f
+        .permission_repo
+        .revoke_all_for_user(&f.member.id)
+        .await
+        .unwrap();
+    assert_eq!(revoked, 4);
+
+    for conn_id in [&f.conn_id, &other_conn] {
+        let conn_perms = f
+            .permission_repo
+            .list_user_connection_permissions(conn_id, 1, 100)
+            .await
+            .unwrap()
+            .items;
+        assert!(conn_perms.iter().all(|p| p.user_id != f.member.id));
+        let table_perms = f
+            .permission_repo
+            .list_user_table_permissions(conn_id, &f.member.id, 1, 100)
+            .await
+            .unwrap();
+        assert_eq!(table_perms.total_count, 0);
+    }
+    let remaining = f
+        .permission_repo
+        .list_user_connection_permissions(&other_conn, 1, 100)
+        .await
+        .unwrap();
+    assert_eq!(remaining.total_count, 1);
+}
+
 // ============================================================
 // User Table Permissions
 // ============================================================
@@ -191,12 +269,41 @@ async fn grant_and_list_user_table_permission() {
 
     let list = f
         .permission_repo
-        .list_user_table_permissions(&f.conn_id, &f.member.id)
+        .list_user_table_permissions(&f.conn_id, &f.member.id, 1, 100)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(list.len(), 1);
 }
 
+#[tokio::test]
+#[serial]
+async fn list_user_table_permissions_pages_by_table_name() {
+    let f = setup().await;
+
+    for i in 0..25 {
+        f.permission_repo
+            .grant_user_table_permission(&f.conn_id, &f.member.id, &format!("t{:02}", i), "read")
+            .await
+            .unwrap();
+    }
+
+    let mut seen = Vec::new();
+    for (page, expected) in [(1, 10), (2, 10), (3, 5)] {
+        let result = f
+            .permission_repo
+            .list_user_table_permissions(&f.conn_id, &f.member.id, page, 10)
+            .await
+            .unwrap();
+        assert_eq!(result.total_count, 25);
+        assert_eq!(result.page, page);
+        assert_eq!(result.items.len(), expected);
+        seen.extend(result.items.into_iter().map(|p| p.table_name));
+    }
+    let expected: Vec<String> = (0..25).map(|i| format!("t{:02}", i)).collect();
+    assert_eq!(seen, expected);
+}
+
 #[tokio::test]
 #[serial]
 async fn revoke_user_table_permission() {
@@ -215,6 +322,68 @@ async fn revoke_user_table_permission() {
     assert!(revoked);
 }
 
+#[tokio::test]
+#[serial]
+async fn copy_user_permissions_includes_table_overrides() {
+    let f = setup().await;
+    let new_hire = f
+        .user_repo
+        .create("New Hire", "newhire@test.com", "member")
+        .await
+        .unwrap();
+
+    f.permission_repo
+        .grant_user_connection_permission(&f.conn_id, &f.member.id, "read", false)
+        .await
+        .unwrap();
+    f.permission_repo
+        .grant_user_table_permission(&f.conn_id, &f.member.id, "orders", "write")
+        .await
+        .unwrap();
+    f.permission_repo
+        .grant_user_table_permission(&f.conn_id, &f.member.id, "customers", "read")
+        .await
+        .unwrap();
+
+    let copied = f
+        .permission_repo
+        .copy_user_permissions(&f.conn_id, &f.member.id, &new_hire.id)
+        .await
+        .unwrap();
+    assert_eq!(copied, 3);
+
+    let conn_perms = f
+        .permission_repo
+        .list_user_connection_permissions(&f.conn_id, 1, 100)
+        .await
+        .unwrap()
+        .items;
+    let copied_conn = conn_perms
+        .iter()
+        .find(|p| p.user_id == new_hire.id)
+        .unwrap();
+    assert_eq!(copied_conn.permission, "read");
+    assert!(!copied_conn.all_tables);
+
+    let mut tables: Vec<(String, String)> = f
+        .permission_repo
+        .list_user_table_permissions(&f.conn_id, &new_hire.id, 1, 100)
+        .await
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|p| (p.table_name, p.permission))
+        .collect();
+    tables.sort();
+    assert_eq!(
+        tables,
+        vec![
+            ("customers".to_string(), "read".to_string()),
+            ("orders".to_string(), "write".to_string()),
+        ]
+    );
+}
+
 // ============================================================
 // Group Connection Permissions
 // ============================================================
@@ -241,9 +410,10 @@ async fn grant_and_list_group_connection_permission() {
 
     let list = f
         .permission_repo
-        .list_group_connection_permissions(&f.conn_id)
+        .list_group_connection_permissions(&f.conn_id, 1, 100)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(list.len(), 1);
 }
 
@@ -288,9 +458,10 @@ async fn grant_and_list_group_table_permission() {
 
     let list = f
         .permission_repo
-        .list_group_table_permissions(&f.conn_id, &group.id)
+        .list_group_table_permissions(&f.conn_id, &group.id, 1, 100)
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(list.len(), 1);
 }
 
@@ -410,6 +581,82 @@ async fn resolve_connection_permission_group_level() {
     assert!(all_tables);
 }
 
+#[tokio::test]
+#[serial]
+async fn resolve_permission_inherited_from_ancestor_group() {
+    let f = setup().await;
+
+    let user = f
+        .user_repo
+        .create("Payments Dev", "payments@test.com", "member")
+        .await
+        .unwrap();
+
+    // Engineering > Backend > Payments; the user only belongs to Payments
+    let engineering = f
+        .group_repo
+        .create(&f.org.id, "Engineering", None)
+        .await
+        .unwrap();
+    let backend = f
+        .group_repo
+        .create(&f.org.id, "Backend", None)
+        .await
+        .unwrap();
+    let payments = f
+        .group_repo
+        .create(&f.org.id, "Payments", None)
+        .await
+        .unwrap();
+    f.group_repo
+        .set_parent(&backend.id, Some(&engineering.id))
+        .await
+        .unwrap();
+    f.group_repo
+        .set_parent(&payments.id, Some(&backend.id))
+        .await
+        .unwrap();
+    f.group_repo
+        .add_member(&payments.id, &user.id)
+        .await
+        .unwrap();
+
+    f.permission_repo
+        .grant_group_connection_permission(&f.conn_id, &engineering.id, "write", false)
+        .await
+        .unwrap();
+    f.permission_repo
+        .grant_group_table_permission(&f.conn_id, &engineering.id, "ledger", "read")
+        .await
+        .unwrap();
+
+    let (level, all_tables) = f
+        .permission_repo
+        .resolve_connection_permission(&user, &f.conn_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        level,
+        dbworks_backend::domain::permission::PermissionLevel::Write
+    );
+    assert!(!all_tables);
+
+    let level = f
+        .permission_repo
+        .resolve_table_permission(&user, &f.conn_id, "ledger")
+        .await
+        .unwrap();
+    assert_eq!(
+        level,
+        dbworks_backend::domain::permission::PermissionLevel::Read
+    );
+
+    assert_eq!(
+        f.group_repo.list_ancestor_ids(&payments.id).await.unwrap(),
+        vec![payments.id, backend.id, engineering.id]
+    );
+}
+
 #[tokio::test]
 #[serial]
 async fn resolve_table_permission_super_admin() {
@@ -472,3 +719,159 @@ async fn resolve_table_permission_with_table_override() {
         dbworks_backend::domain::permission::PermissionLevel::Read
     );
 }
+
+#[tokio::test]
+#[serial]
+async fn explain_table_permission_gives_reason_without_table_grant() {
+    let f = setup().await;
+    let user = f
+        .user_repo
+        .create("Scoped", "scoped@test.com", "member")
+        .await
+        .unwrap();
+    f.permission_repo
+        .grant_user_connection_permission(&f.conn_id, &user.id, "read", false)
+        .await
+        .unwrap();
+
+    let resolved = f
+        .permission_repo
+        .explain_table_permission(&user, &f.conn_id, "orders")
+        .await
+        .unwrap();
+    assert_eq!(
+        resolved.level,
+        dbworks_backend::domain::permission::PermissionLevel::None
+    );
+    assert_eq!(
+        resolved.reason.as_deref(),
+        Some("connection grant is all_tables=false and no table-level grant exists")
+    );
+
+    // Anything granted carries no reason
+    let resolved = f
+        .permission_repo
+        .explain_table_permission(&f.admin, &f.conn_id, "orders")
+        .await
+        .unwrap();
+    assert!(resolved.reason.is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn explain_table_permission_reports_group_source() {
+    use dbworks_backend::domain::permission::{PermissionLevel, PermissionSource};
+    let f = setup().await;
+
+    let user = f
+        .user_repo
+        .create("GroupUser", "groupuser@test.com", "member")
+        .await
+        .unwrap();
+    let group = f.group_repo.create(&f.org.id, "Team", None).await.unwrap();
+    f.group_repo.add_member(&group.id, &user.id).await.unwrap();
+    f.permission_repo
+        .grant_group_connection_permission(&f.conn_id, &group.id, "write", true)
+        .await
+        .unwrap();
+
+    let resolved = f
+        .permission_repo
+        .explain_table_permission(&user, &f.conn_id, "orders")
+        .await
+        .unwrap();
+    assert_eq!(resolved.level, PermissionLevel::Write);
+    assert_eq!(resolved.source, PermissionSource::Group);
+    assert!(!resolved.table_level);
+
+    let resolved = f
+        .permission_repo
+        .explain_table_permission(&f.admin, &f.conn_id, "orders")
+        .await
+        .unwrap();
+    assert_eq!(resolved.source, PermissionSource::SuperAdmin);
+}
+
+#[tokio::test]
+#[serial]
+async fn member_inherits_org_wide_group_grant() {
+    use dbworks_backend::domain::permission::{PermissionLevel, PermissionSource};
+    let f = setup().await;
+
+    let user = f
+        .user_repo
+        .create("OrgWide", "orgwide@test.com", "member")
+        .await
+        .unwrap();
+    let group = f
+        .group_repo
+        .create(&f.org.id, "Analysts", None)
+        .await
+        .unwrap();
+    f.group_repo.add_member(&group.id, &user.id).await.unwrap();
+    f.permission_repo
+        .grant_group_org_permission(&f.org.id, &group.id, "read")
+        .await
+        .unwrap();
+
+    let (level, all_tables) = f
+        .permission_repo
+        .resolve_connection_permission(&user, &f.conn_id)
+        .await
+        .unwrap();
+    assert_eq!(level, PermissionLevel::Read);
+    assert!(all_tables);
+
+    let resolved = f
+        .permission_repo
+        .explain_table_permission(&user, &f.conn_id, "orders")
+        .await
+        .unwrap();
+    assert_eq!(resolved.source, PermissionSource::OrgDefault);
+}
+
+#[tokio::test]
+#[serial]
+async fn specific_grant_overrides_org_wide_group_grant() {
+    use dbworks_backend::domain::permission::PermissionLevel;
+    let f = setup().await;
+
+    let user = f
+        .user_repo
+        .create("OrgWide", "orgwide@test.com", "member")
+        .await
+        .unwrap();
+    let group = f
+        .group_repo
+        .create(&f.org.id, "Analysts", None)
+        .await
+        .unwrap();
+    f.group_repo.add_member(&group.id, &user.id).await.unwrap();
+    f.permission_repo
+        .grant_group_org_permission(&f.org.id, &group.id, "write")
+        .await
+        .unwrap();
+    // A narrower connection-specific grant wins even though it is lower
+    f.permission_repo
+        .grant_group_connection_permission(&f.conn_id, &group.id, "read", true)
+        .await
+        .unwrap();
+
+    let (level, _) = f
+        .permission_repo
+        .resolve_connection_permission(&user, &f.conn_id)
+        .await
+        .unwrap();
+    assert_eq!(level, PermissionLevel::Read);
+
+    f.permission_repo
+        .grant_user_connection_permission(&f.conn_id, &user.id, "none", false)
+        .await
+        .unwrap();
+    let (level, _) = f
+        .permission_repo
+        .resolve_connection_permission(&user, &f.conn_id)
+        .await
+        .unwrap();
+    assert_eq!(level, PermissionLevel::None);
+}
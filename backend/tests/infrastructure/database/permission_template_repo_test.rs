@@ -0,0 +1,53 @@
+use crate::common;
+use dbworks_backend::domain::permission::TemplateTableGrant;
+use dbworks_backend::domain::repository::{OrganizationRepository, PermissionTemplateRepository};
+use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
+use dbworks_backend::infrastructure::database::permission_template_repo::PgPermissionTemplateRepository;
+use serial_test::serial;
+
+fn grant(table_name: &str, permission: &str) -> TemplateTableGrant {
+    TemplateTableGrant {
+        table_name: table_name.to_string(),
+        permission: permission.to_string(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn create_and_update_template_replaces_tables() {
+    let pool = common::setup_test_db().await;
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Test Org")
+        .await
+        .unwrap();
+    let repo = PgPermissionTemplateRepository::new(pool);
+
+    let created = repo
+        .create(
+            &org.id,
+            "analyst",
+            Some("Read-only reporting"),
+            &[grant("orders", "read"), grant("customers", "read")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        created.tables,
+        vec![grant("customers", "read"), grant("orders", "read")]
+    );
+
+    let updated = repo
+        .update(&created.id, "analyst", None, &[grant("orders", "write")])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.tables, vec![grant("orders", "write")]);
+    assert_eq!(updated.description, None);
+
+    let listed = repo.list_by_org(&org.id).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].tables, vec![grant("orders", "write")]);
+
+    assert!(repo.delete(&created.id).await.unwrap());
+    assert!(repo.find_by_id(&created.id).await.unwrap().is_none());
+}
@@ -0,0 +1,41 @@
+use crate::common;
+use dbworks_backend::domain::repository::{QueryLogRepository, UserRepository};
+use dbworks_backend::infrastructure::database::query_log_repo::PgQueryLogRepository;
+use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use serial_test::serial;
+use uuid::Uuid;
+
+#[tokio::test]
+#[serial]
+async fn prune_deletes_only_entries_before_the_cutoff() {
+    let pool = common::setup_test_db().await;
+    let user = PgUserRepository::new(pool.clone())
+        .create("Alice", "alice@test.com", "member")
+        .await
+        .unwrap();
+    let conn_id = Uuid::new_v4();
+    let repo = PgQueryLogRepository::new(pool.clone());
+
+    for operation in ["list_rows", "get_row"] {
+        repo.record(&conn_id, &user.id, operation, Some("users"), 3, true)
+            .await
+            .unwrap();
+    }
+    sqlx::query(
+        "UPDATE query_log SET created_at = NOW() - INTERVAL '40 days' WHERE connection_id = $1 AND operation = 'list_rows'",
+    )
+    .bind(conn_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let deleted = repo
+        .prune(chrono::Utc::now() - chrono::Duration::days(30))
+        .await
+        .unwrap();
+
+    assert_eq!(deleted, 1);
+    let page = repo.list(&conn_id, 1, 50).await.unwrap();
+    let operations: Vec<_> = page.items.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(operations, vec!["get_row"]);
+}
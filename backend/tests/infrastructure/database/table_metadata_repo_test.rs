@@ -0,0 +1,74 @@
+use crate::common;
+use dbworks_backend::domain::repository::{OrganizationRepository, TableMetadataRepository};
+use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
+use dbworks_backend::infrastructure::database::table_metadata_repo::PgTableMetadataRepository;
+use serial_test::serial;
+use uuid::Uuid;
+
+async fn seed_connection(pool: &sqlx::PgPool) -> Uuid {
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Test Org")
+        .await
+        .unwrap();
+    let conn_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO saved_connections (id, organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, $2, 'test-conn', 'localhost', 5432, 'testdb', 'test', 'encrypted')"#,
+    )
+    .bind(conn_id)
+    .bind(org.id)
+    .execute(pool)
+    .await
+    .unwrap();
+    conn_id
+}
+
+#[tokio::test]
+#[serial]
+async fn upsert_inserts_then_replaces() {
+    let pool = common::setup_test_db().await;
+    let conn_id = seed_connection(&pool).await;
+    let repo = PgTableMetadataRepository::new(pool);
+
+    repo.upsert(&conn_id, "t_usr", Some("Users"), Some("All users"))
+        .await
+        .unwrap();
+    let updated = repo
+        .upsert(&conn_id, "t_usr", Some("People"), None)
+        .await
+        .unwrap();
+    assert_eq!(updated.display_name.as_deref(), Some("People"));
+    assert!(updated.description.is_none());
+
+    let fetched = repo.get(&conn_id, "t_usr").await.unwrap().unwrap();
+    assert_eq!(fetched.display_name.as_deref(), Some("People"));
+    assert!(repo.get(&conn_id, "missing").await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn list_is_scoped_to_connection() {
+    let pool = common::setup_test_db().await;
+    let conn_a = seed_connection(&pool).await;
+    let conn_b = seed_connection(&pool).await;
+    let repo = PgTableMetadataRepository::new(pool);
+
+    repo.upsert(&conn_a, "b_tbl", Some("B"), None)
+        .await
+        .unwrap();
+    repo.upsert(&conn_a, "a_tbl", Some("A"), None)
+        .await
+        .unwrap();
+    repo.upsert(&conn_b, "c_tbl", Some("C"), None)
+        .await
+        .unwrap();
+
+    let names: Vec<_> = repo
+        .list(&conn_a)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.table_name)
+        .collect();
+    assert_eq!(names, vec!["a_tbl", "b_tbl"]);
+}
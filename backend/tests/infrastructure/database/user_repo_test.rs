@@ -93,3 +93,79 @@ async fn get_user_found_and_not_found() {
     let not_found = user_repo.get(&uuid::Uuid::new_v4()).await.unwrap();
     assert!(not_found.is_none());
 }
+
+#[tokio::test]
+#[serial]
+async fn delete_user_removes_permissions_and_memberships() {
+    let pool = common::setup_test_db().await;
+    let org_repo = PgOrganizationRepository::new(pool.clone());
+    let user_repo = PgUserRepository::new(pool.clone());
+    let org_member_repo = PgOrganizationMemberRepository::new(pool.clone());
+
+    let org = create_test_org(&org_repo).await;
+    let user = user_repo
+        .create("Alice", "alice@example.com", "member")
+        .await
+        .unwrap();
+    org_member_repo
+        .add_member(&org.id, &user.id, "member")
+        .await
+        .unwrap();
+
+    let conn_id: uuid::Uuid = sqlx::query_scalar(
+        r#"INSERT INTO saved_connections (organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, 'conn', 'localhost', 5432, 'db', 'u', 'x') RETURNING id"#,
+    )
+    .bind(org.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let group_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO groups (organization_id, name) VALUES ($1, 'g') RETURNING id",
+    )
+    .bind(org.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO group_members (group_id, user_id) VALUES ($1, $2)")
+        .bind(group_id)
+        .bind(user.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO user_connection_permissions (user_id, connection_id) VALUES ($1, $2)")
+        .bind(user.id)
+        .bind(conn_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO user_table_permissions (user_id, connection_id, table_name) VALUES ($1, $2, 'orders')",
+    )
+    .bind(user.id)
+    .bind(conn_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert!(user_repo.delete(&user.id).await.unwrap());
+    assert!(!user_repo.delete(&user.id).await.unwrap());
+
+    for table in [
+        "user_connection_permissions",
+        "user_table_permissions",
+        "group_members",
+        "organization_members",
+    ] {
+        let remaining: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {} WHERE user_id = $1",
+            table
+        ))
+        .bind(user.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(remaining, 0, "{} still has rows", table);
+    }
+    assert!(user_repo.get(&user.id).await.unwrap().is_none());
+}
@@ -0,0 +1 @@
+mod postgres_test;
@@ -0,0 +1,1044 @@
+use crate::common;
+use dbworks_backend::domain::data::{AggregateFn, AggregateRow, AggregateSpec};
+use dbworks_backend::infrastructure::datasource::postgres::PostgresDataSource;
+use dbworks_backend::infrastructure::datasource::{
+    ChangeFeed, DataSource, DataSourceError, PoolLimits, notify_channel,
+};
+use dbworks_backend::presentation::request::RowsQuery;
+use serial_test::serial;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+#[serial]
+async fn query_exceeding_statement_timeout_is_classified_as_timeout() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("CREATE OR REPLACE VIEW slow_view AS SELECT 1 AS id FROM pg_sleep(2)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_millis(100))
+        .await
+        .unwrap();
+    let result = ds.list_rows("slow_view", &RowsQuery::default(), None).await;
+
+    sqlx::query("DROP VIEW slow_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let err = result.unwrap_err();
+    assert_eq!(
+        DataSourceError::classify(&err),
+        Some(DataSourceError::Timeout)
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn warm_up_leaves_requested_connections_idle() {
+    let _pool = common::setup_test_db().await;
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    ds.warm_up(3).await;
+    let size = ds.pool_status().unwrap().size;
+    // Released connections go back to the pool on a background task
+    let mut idle = 0;
+    for _ in 0..50 {
+        idle = ds.pool_status().unwrap().idle;
+        if idle == 3 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    ds.close().await;
+
+    assert_eq!(size, 3);
+    assert_eq!(idle, 3);
+}
+
+#[tokio::test]
+#[serial]
+async fn query_within_statement_timeout_succeeds() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("CREATE OR REPLACE VIEW quick_view AS SELECT 1 AS id")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let result = ds
+        .list_rows("quick_view", &RowsQuery::default(), None)
+        .await;
+
+    sqlx::query("DROP VIEW quick_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(result.unwrap().total_count, 1);
+}
+
+async fn create_tickets_table(pool: &sqlx::PgPool) {
+    sqlx::query("DROP TABLE IF EXISTS ds_tickets")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_tickets (id SERIAL PRIMARY KEY, status TEXT NOT NULL, points INT NOT NULL)")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO ds_tickets (status, points) VALUES
+            ('open', 3), ('open', 5), ('closed', 2), ('open', 1), ('closed', 8)",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn drop_tickets_table(pool: &sqlx::PgPool) {
+    sqlx::query("DROP TABLE ds_tickets")
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn count_rows_matches_list_rows_total() {
+    let pool = common::setup_test_db().await;
+    create_tickets_table(&pool).await;
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let count = ds.count_rows("ds_tickets", Some("status:eq:open")).await;
+    let listed = ds
+        .list_rows(
+            "ds_tickets",
+            &RowsQuery {
+                filter: Some("status:eq:open".to_string()),
+                ..RowsQuery::default()
+            },
+            None,
+        )
+        .await;
+    let unfiltered = ds.count_rows("ds_tickets", None).await;
+
+    drop_tickets_table(&pool).await;
+
+    let count = count.unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(count, listed.unwrap().total_count);
+    assert_eq!(unfiltered.unwrap(), 5);
+}
+
+#[tokio::test]
+#[serial]
+async fn explain_rows_returns_plan_for_filtered_query() {
+    let pool = common::setup_test_db().await;
+    create_tickets_table(&pool).await;
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let plan = ds
+        .explain_rows(
+            "ds_tickets",
+            &RowsQuery {
+                filter: Some("status:eq:open".to_string()),
+                sort_by: Some("points".to_string()),
+                ..RowsQuery::default()
+            },
+        )
+        .await;
+
+    drop_tickets_table(&pool).await;
+
+    let plan = plan.unwrap();
+    assert!(plan[0]["Plan"]["Node Type"].is_string(), "plan: {plan}");
+}
+
+#[tokio::test]
+#[serial]
+async fn distinct_values_are_unique_sorted_and_limited() {
+    let pool = common::setup_test_db().await;
+    create_tickets_table(&pool).await;
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let all = ds.distinct_values("ds_tickets", "status", 100).await;
+    let limited = ds.distinct_values("ds_tickets", "status", 1).await;
+
+    drop_tickets_table(&pool).await;
+
+    assert_eq!(
+        all.unwrap(),
+        vec![serde_json::json!("closed"), serde_json::json!("open")]
+    );
+    assert_eq!(limited.unwrap(), vec![serde_json::json!("closed")]);
+}
+
+#[tokio::test]
+#[serial]
+async fn aggregate_sums_by_group() {
+    let pool = common::setup_test_db().await;
+    create_tickets_table(&pool).await;
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let sums = ds
+        .aggregate(
+            "ds_tickets",
+            &AggregateSpec {
+                group_by: "status".to_string(),
+                metric_col: Some("points".to_string()),
+                agg_fn: AggregateFn::Sum,
+            },
+        )
+        .await;
+    let counts = ds
+        .aggregate(
+            "ds_tickets",
+            &AggregateSpec {
+                group_by: "status".to_string(),
+                metric_col: None,
+                agg_fn: AggregateFn::Count,
+            },
+        )
+        .await;
+
+    drop_tickets_table(&pool).await;
+
+    assert_eq!(
+        sums.unwrap(),
+        vec![
+            AggregateRow {
+                group: serde_json::json!("closed"),
+                value: serde_json::json!(10),
+            },
+            AggregateRow {
+                group: serde_json::json!("open"),
+                value: serde_json::json!(9),
+            },
+        ]
+    );
+    let counts = counts.unwrap();
+    assert_eq!(counts[0].value, serde_json::json!(2));
+    assert_eq!(counts[1].value, serde_json::json!(3));
+}
+
+#[tokio::test]
+#[serial]
+async fn get_rows_skips_missing_keys_and_keeps_request_order() {
+    let pool = common::setup_test_db().await;
+    create_tickets_table(&pool).await;
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let keys: Vec<String> = ["4", "999", "1", "abc"]
+        .iter()
+        .map(|k| k.to_string())
+        .collect();
+    let rows = ds.get_rows("ds_tickets", &keys).await;
+
+    drop_tickets_table(&pool).await;
+
+    let ids: Vec<i64> = rows
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, vec![4, 1]);
+}
+
+#[tokio::test]
+#[serial]
+async fn search_matches_substring_in_any_text_column() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_contacts")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE ds_contacts (id SERIAL PRIMARY KEY, name TEXT, email VARCHAR(100), city TEXT, age INT)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO ds_contacts (name, email, city, age) VALUES
+            ('Alice', 'alice@example.com', 'Osaka', 30),
+            ('Bob', 'bob@kyoto.example', 'Tokyo', 40),
+            ('Carol', 'carol@example.com', 'Kyoto', 50),
+            ('Dave', 'dave@example.com', 'Nagoya', 60)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let searched = ds
+        .list_rows(
+            "ds_contacts",
+            &RowsQuery {
+                q: Some("KYOTO".to_string()),
+                sort_by: Some("id".to_string()),
+                ..RowsQuery::default()
+            },
+            None,
+        )
+        .await;
+    let combined = ds
+        .list_rows(
+            "ds_contacts",
+            &RowsQuery {
+                q: Some("kyoto".to_string()),
+                filter: Some("age:gt:45".to_string()),
+                ..RowsQuery::default()
+            },
+            None,
+        )
+        .await;
+
+    sqlx::query("DROP TABLE ds_contacts")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let searched = searched.unwrap();
+    assert_eq!(searched.total_count, 2);
+    let names: Vec<&str> = searched
+        .rows
+        .iter()
+        .map(|r| r["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Bob", "Carol"]);
+
+    let combined = combined.unwrap();
+    assert_eq!(combined.total_count, 1);
+    assert_eq!(combined.rows[0]["name"], "Carol");
+}
+
+#[tokio::test]
+#[serial]
+async fn search_treats_wildcards_literally() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_discounts")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_discounts (id SERIAL PRIMARY KEY, label TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO ds_discounts (label) VALUES ('10% off'), ('100 off'), ('big_sale'), ('bigsale')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let search = |term: &str| RowsQuery {
+        q: Some(term.to_string()),
+        ..RowsQuery::default()
+    };
+    let percent = ds.list_rows("ds_discounts", &search("0%"), None).await;
+    let underscore = ds.list_rows("ds_discounts", &search("g_s"), None).await;
+
+    sqlx::query("DROP TABLE ds_discounts")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let percent = percent.unwrap();
+    assert_eq!(percent.total_count, 1);
+    assert_eq!(percent.rows[0]["label"], "10% off");
+
+    let underscore = underscore.unwrap();
+    assert_eq!(underscore.total_count, 1);
+    assert_eq!(underscore.rows[0]["label"], "big_sale");
+}
+
+#[tokio::test]
+#[serial]
+async fn sort_places_nulls_first_or_last() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_tasks")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_tasks (id SERIAL PRIMARY KEY, due INT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO ds_tasks (due) VALUES (2), (NULL), (1)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let sorted = |nulls: &str| RowsQuery {
+        sort_by: Some("due".to_string()),
+        sort_order: Some("asc".to_string()),
+        nulls: Some(nulls.to_string()),
+        ..RowsQuery::default()
+    };
+    let first = ds.list_rows("ds_tasks", &sorted("first"), None).await;
+    let last = ds.list_rows("ds_tasks", &sorted("last"), None).await;
+
+    sqlx::query("DROP TABLE ds_tasks")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let dues = |rows: Vec<serde_json::Value>| -> Vec<serde_json::Value> {
+        rows.into_iter().map(|r| r["due"].clone()).collect()
+    };
+    assert_eq!(
+        dues(first.unwrap().rows),
+        vec![
+            serde_json::Value::Null,
+            serde_json::json!(1),
+            serde_json::json!(2)
+        ]
+    );
+    assert_eq!(
+        dues(last.unwrap().rows),
+        vec![
+            serde_json::json!(1),
+            serde_json::json!(2),
+            serde_json::Value::Null
+        ]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn sort_by_multiple_columns() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_people")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_people (id SERIAL PRIMARY KEY, last_name TEXT, age INT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO ds_people (last_name, age) VALUES
+            ('Sato', 30), ('Abe', 20), ('Sato', 50), ('Abe', 40)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let result = ds
+        .list_rows(
+            "ds_people",
+            &RowsQuery {
+                sort_by: Some("last_name:asc,age:desc".to_string()),
+                ..RowsQuery::default()
+            },
+            None,
+        )
+        .await;
+
+    sqlx::query("DROP TABLE ds_people")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ages: Vec<i64> = result
+        .unwrap()
+        .rows
+        .iter()
+        .map(|r| r["age"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ages, vec![40, 20, 50, 30]);
+}
+
+#[tokio::test]
+#[serial]
+async fn wrong_password_is_classified_as_authentication_failed() {
+    let (host, port, database, _, _) = common::parse_db_url();
+    // An unknown role is rejected even where the test server trusts local connections
+    let url = format!("postgres://dbworks_no_such_user:not-the-password@{host}:{port}/{database}");
+
+    let err = PostgresDataSource::new(&url, Duration::from_secs(5))
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        DataSourceError::classify(&err),
+        Some(DataSourceError::AuthenticationFailed)
+    );
+    assert!(err.to_string().starts_with("Authentication failed"));
+}
+
+#[tokio::test]
+#[serial]
+async fn closed_port_is_classified_as_host_unreachable() {
+    let (host, _, database, user, password) = common::parse_db_url();
+    // Port 1 (tcpmux) has nothing listening on the test host
+    let url = format!("postgres://{user}:{password}@{host}:1/{database}");
+
+    let err = PostgresDataSource::new(&url, Duration::from_secs(5))
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        DataSourceError::classify(&err),
+        Some(DataSourceError::HostUnreachable)
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn missing_database_is_classified_as_database_not_found() {
+    let (host, port, _, user, password) = common::parse_db_url();
+    let url = format!("postgres://{user}:{password}@{host}:{port}/dbworks_no_such_db");
+
+    let err = PostgresDataSource::new(&url, Duration::from_secs(5))
+        .await
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        DataSourceError::classify(&err),
+        Some(DataSourceError::DatabaseNotFound)
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn schema_cache_holds_until_invalidated() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS cached_widgets")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE cached_widgets (id SERIAL PRIMARY KEY, name TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let before = ds.get_table_schema("cached_widgets").await.unwrap();
+    sqlx::query("ALTER TABLE cached_widgets ADD COLUMN color TEXT")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let cached = ds.get_table_schema("cached_widgets").await.unwrap();
+    ds.invalidate_schema_cache().await;
+    let refreshed = ds.get_table_schema("cached_widgets").await.unwrap();
+
+    sqlx::query("DROP TABLE cached_widgets")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(before.columns.len(), 2);
+    assert_eq!(cached.columns.len(), 2);
+    assert_eq!(refreshed.columns.len(), 3);
+    assert_eq!(refreshed.columns[2].column_name, "color");
+}
+
+#[tokio::test]
+#[serial]
+async fn schema_reports_enum_values_in_sort_order() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS enum_moods")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DROP TYPE IF EXISTS test_mood")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TYPE test_mood AS ENUM ('sad', 'ok', 'happy')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE enum_moods (id SERIAL PRIMARY KEY, mood test_mood NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let schema = ds.get_table_schema("enum_moods").await.unwrap();
+
+    sqlx::query("DROP TABLE enum_moods")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DROP TYPE test_mood")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(schema.columns[0].enum_values, None);
+    assert_eq!(
+        schema.columns[1].enum_values,
+        Some(vec![
+            "sad".to_string(),
+            "ok".to_string(),
+            "happy".to_string()
+        ])
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn change_feed_fans_out_notifications_to_subscribers() {
+    let pool = common::setup_test_db().await;
+    let feed = ChangeFeed::default();
+    let conn_id = uuid::Uuid::new_v4();
+    let url = common::test_database_url();
+    let mut first = feed.subscribe(conn_id, &url, "feed_items").await.unwrap();
+    let mut second = feed.subscribe(conn_id, &url, "feed_items").await.unwrap();
+
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(notify_channel("feed_items"))
+        .bind(r#"{"id":7}"#)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    for rx in [&mut first, &mut second] {
+        let change = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.table, "feed_items");
+        assert_eq!(change.row, serde_json::json!({ "id": 7 }));
+    }
+    feed.remove_connection(&conn_id).await;
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_with_fields_returns_only_those_keys() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_fields")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_fields (id SERIAL PRIMARY KEY, name TEXT, email TEXT, bio TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO ds_fields (name, email, bio) VALUES ('Ann', 'ann@x.test', 'long')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let sparse = ds
+        .list_rows(
+            "ds_fields",
+            &RowsQuery {
+                fields: Some("name, id".to_string()),
+                ..RowsQuery::default()
+            },
+            None,
+        )
+        .await;
+    let full = ds.list_rows("ds_fields", &RowsQuery::default(), None).await;
+
+    sqlx::query("DROP TABLE ds_fields")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let sparse = sparse.unwrap();
+    assert_eq!(sparse.total_count, 1);
+    assert_eq!(
+        sparse.rows[0],
+        serde_json::json!({ "name": "Ann", "id": 1 })
+    );
+    let row = full.unwrap().rows[0].as_object().unwrap().clone();
+    assert_eq!(row.len(), 4);
+}
+
+#[tokio::test]
+#[serial]
+async fn bytea_round_trips_as_base64_marker() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_blobs")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_blobs (id SERIAL PRIMARY KEY, payload BYTEA)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let marker = serde_json::json!({ "$type": "bytea", "base64": "3q2+7w==" });
+    let inserted = ds
+        .insert_row(
+            "ds_blobs",
+            &serde_json::json!({ "payload": marker }),
+            &[],
+            None,
+        )
+        .await;
+    let fetched = ds.get_row("ds_blobs", "1").await;
+    let listed = ds.list_rows("ds_blobs", &RowsQuery::default(), None).await;
+    let stored: Option<String> =
+        sqlx::query_scalar("SELECT encode(payload, 'hex') FROM ds_blobs WHERE id = 1")
+            .fetch_optional(&pool)
+            .await
+            .unwrap();
+
+    sqlx::query("DROP TABLE ds_blobs")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(stored.as_deref(), Some("deadbeef"));
+    assert_eq!(inserted.unwrap()["payload"], marker);
+    assert_eq!(fetched.unwrap()["payload"], marker);
+    assert_eq!(listed.unwrap().rows[0]["payload"], marker);
+}
+
+#[tokio::test]
+#[serial]
+async fn array_columns_round_trip_as_json_arrays() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_labels")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_labels (id SERIAL PRIMARY KEY, tags TEXT[], scores INT[])")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let tags = serde_json::json!(["red", "a, b", "say \"hi\"", null]);
+    let inserted = ds
+        .insert_row(
+            "ds_labels",
+            &serde_json::json!({ "tags": tags, "scores": [3, 1, 2] }),
+            &[],
+            None,
+        )
+        .await;
+    let updated = ds
+        .update_row(
+            "ds_labels",
+            "1",
+            &serde_json::json!({ "tags": [], "scores": [7] }),
+            None,
+        )
+        .await;
+    let stored: Option<(Vec<String>, Vec<i32>)> =
+        sqlx::query_as("SELECT tags, scores FROM ds_labels WHERE id = 1")
+            .fetch_optional(&pool)
+            .await
+            .unwrap();
+
+    sqlx::query("DROP TABLE ds_labels")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let inserted = inserted.unwrap();
+    assert_eq!(inserted["tags"], tags);
+    assert_eq!(inserted["scores"], serde_json::json!([3, 1, 2]));
+    let updated = updated.unwrap();
+    assert_eq!(updated["tags"], serde_json::json!([]));
+    assert_eq!(stored, Some((vec![], vec![7])));
+}
+
+#[tokio::test]
+#[serial]
+async fn timestamps_are_normalized_to_fixed_formats() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS ds_events")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE ds_events (id SERIAL PRIMARY KEY, at TIMESTAMPTZ, local_at TIMESTAMP)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO ds_events (at, local_at) VALUES ('2024-03-01 12:30:00.25+09', '2024-03-01 12:30:00'), ('infinity', NULL)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let listed = ds
+        .list_rows(
+            "ds_events",
+            &RowsQuery {
+                sort_by: Some("id".to_string()),
+                ..RowsQuery::default()
+            },
+            None,
+        )
+        .await;
+    let fetched = ds.get_row("ds_events", "1").await;
+
+    sqlx::query("DROP TABLE ds_events")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let rows = listed.unwrap().rows;
+    assert_eq!(rows[0]["at"], "2024-03-01T03:30:00.250000Z");
+    assert_eq!(rows[0]["local_at"], "2024-03-01T12:30:00.000000");
+    assert_eq!(rows[1]["at"], "infinity");
+    assert!(rows[1]["local_at"].is_null());
+    assert_eq!(
+        fetched.unwrap(),
+        serde_json::json!({
+            "id": 1,
+            "at": "2024-03-01T03:30:00.250000Z",
+            "local_at": "2024-03-01T12:30:00.000000",
+        })
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn search_path_resolves_tables_in_that_schema() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP SCHEMA IF EXISTS ds_tenant CASCADE")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE SCHEMA ds_tenant")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ds_tenant.tenant_widgets (id SERIAL PRIMARY KEY, name TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO ds_tenant.tenant_widgets (name) VALUES ('gear')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ds = PostgresDataSource::with_session(
+        &common::test_database_url(),
+        Duration::from_secs(5),
+        Some("ds_tenant, public"),
+        None,
+        PoolLimits::from_env(),
+        &[],
+    )
+    .await
+    .unwrap();
+    let tables = ds.list_tables().await;
+    let rows = ds
+        .list_rows("tenant_widgets", &RowsQuery::default(), None)
+        .await;
+    ds.close().await;
+
+    sqlx::query("DROP SCHEMA ds_tenant CASCADE")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let tables = tables.unwrap();
+    assert!(
+        tables
+            .iter()
+            .any(|t| t.table_name == "tenant_widgets" && t.table_schema == "ds_tenant")
+    );
+    assert_eq!(rows.unwrap().rows[0]["name"], "gear");
+}
+
+#[tokio::test]
+#[serial]
+async fn reads_use_replica_pool_and_writes_use_primary() {
+    let pool = common::setup_test_db().await;
+    // Same database on both pools, told apart by the session's application_name
+    sqlx::query(
+        "CREATE TABLE routed_items (id SERIAL PRIMARY KEY, label TEXT, served_by TEXT DEFAULT current_setting('application_name'))",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "CREATE OR REPLACE VIEW routed_view AS SELECT 1 AS id, current_setting('application_name') AS served_by",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let url = common::test_database_url();
+    let ds = PostgresDataSource::with_session(
+        &format!("{}?application_name=dbw_primary", url),
+        Duration::from_secs(5),
+        None,
+        None,
+        PoolLimits::from_env(),
+        &[format!("{}?application_name=dbw_replica", url)],
+    )
+    .await
+    .unwrap();
+    let read = ds
+        .list_rows("routed_view", &RowsQuery::default(), None)
+        .await;
+    let written = ds
+        .insert_row(
+            "routed_items",
+            &serde_json::json!({ "label": "a" }),
+            &[],
+            None,
+        )
+        .await;
+    ds.close().await;
+
+    sqlx::query("DROP VIEW routed_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE routed_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(read.unwrap().rows[0]["served_by"], "dbw_replica");
+    assert_eq!(written.unwrap()["served_by"], "dbw_primary");
+}
+
+/// Collects everything a `fmt` subscriber writes.
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn debug_logs_show_placeholders_but_never_bound_values() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS log_secrets")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE log_secrets (id TEXT PRIMARY KEY, note TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let guard = tracing::subscriber::set_default(subscriber);
+
+    let ds = PostgresDataSource::new(&common::test_database_url(), Duration::from_secs(5))
+        .await
+        .unwrap();
+    let inserted = ds
+        .insert_row(
+            "log_secrets",
+            &serde_json::json!({ "id": "pk-secret-4711", "note": "insert-secret-4711" }),
+            &[],
+            None,
+        )
+        .await;
+    let listed = ds
+        .list_rows(
+            "log_secrets",
+            &RowsQuery {
+                filter: Some("note:eq:filter-secret-4711".to_string()),
+                q: Some("search-secret-4711".to_string()),
+                ..Default::default()
+            },
+            None,
+        )
+        .await;
+    let updated = ds
+        .update_row(
+            "log_secrets",
+            "pk-secret-4711",
+            &serde_json::json!({ "note": "update-secret-4711" }),
+            None,
+        )
+        .await;
+    let fetched = ds.get_row("log_secrets", "pk-secret-4711").await;
+    let deleted = ds.delete_row("log_secrets", "pk-secret-4711", None).await;
+    ds.close().await;
+    drop(guard);
+
+    sqlx::query("DROP TABLE log_secrets")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    inserted.unwrap();
+    listed.unwrap();
+    updated.unwrap();
+    fetched.unwrap();
+    deleted.unwrap();
+
+    let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("Executing count query"));
+    assert!(output.contains("$1"));
+    for secret in [
+        "pk-secret",
+        "insert-secret",
+        "filter-secret",
+        "search-secret",
+        "update-secret",
+    ] {
+        assert!(!output.contains(secret), "{} leaked into logs", secret);
+    }
+}
@@ -1,2 +1,4 @@
 mod auth;
+mod connections_config_test;
 mod database;
+mod datasource;
@@ -0,0 +1,259 @@
+use crate::common;
+use crate::presentation::helpers::build_test_state;
+
+use dbworks_backend::domain::connection::ConnectionSettings;
+use dbworks_backend::domain::repository::{OrganizationRepository, UserRepository};
+use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
+use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::presentation::routes::create_router;
+use http::Request;
+use http_body_util::BodyExt;
+use serial_test::serial;
+use tower::ServiceExt;
+
+async fn body_json(resp: axum::response::Response) -> serde_json::Value {
+    let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+/// Register a connection to the test database (also saved, since tokens
+/// reference a saved connection) and return the app, org, admin and
+/// connection ids.
+async fn setup_token_connection(
+    pool: &sqlx::PgPool,
+) -> (axum::Router, uuid::Uuid, uuid::Uuid, uuid::Uuid) {
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Org")
+        .await
+        .unwrap();
+    let admin = PgUserRepository::new(pool.clone())
+        .create("Admin", "admin@test.com", "super_admin")
+        .await
+        .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "token-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            Some(org.id),
+            None,
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    // The test manager does not persist, but tokens reference a saved connection
+    sqlx::query(
+        r#"INSERT INTO saved_connections (id, organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, $2, 'token-conn', 'localhost', 5432, 'testdb', 'test', 'encrypted')"#,
+    )
+    .bind(info.id)
+    .bind(org.id)
+    .execute(pool)
+    .await
+    .unwrap();
+    (create_router().with_state(state), org.id, admin.id, info.id)
+}
+
+/// Create a token on `conn_id` as `admin_id` and return the response body.
+async fn create_token(
+    app: &axum::Router,
+    conn_id: uuid::Uuid,
+    admin_id: uuid::Uuid,
+    permission: &str,
+) -> serde_json::Value {
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/api/connections/{}/api-tokens", conn_id))
+        .header("Content-Type", "application/json")
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::from(format!(
+            r#"{{"name":"reporting","permission":"{}"}}"#,
+            permission
+        )))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 201);
+    body_json(resp).await
+}
+
+/// A read-scoped token lists rows but is refused on writes; once revoked it
+/// no longer authenticates.
+#[tokio::test]
+#[serial]
+async fn read_token_can_list_rows_but_not_write() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS token_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE token_notes (id SERIAL PRIMARY KEY, body TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (app, _, admin_id, conn_id) = setup_token_connection(&pool).await;
+
+    let created = create_token(&app, conn_id, admin_id, "read").await;
+    let token = created["token"].as_str().unwrap().to_string();
+    assert!(created.get("token_hash").is_none());
+
+    let rows_uri = format!("/api/connections/{}/tables/token_notes/rows", conn_id);
+    let list = || {
+        Request::builder()
+            .uri(&rows_uri)
+            .header("Authorization", format!("Bearer {}", token))
+            .body(axum::body::Body::empty())
+            .unwrap()
+    };
+    let resp = app.clone().oneshot(list()).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri(&rows_uri)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Content-Type", "application/json")
+        .body(axum::body::Body::from(r#"{"body":"hello"}"#))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 403);
+
+    let req = Request::builder()
+        .method("DELETE")
+        .uri(format!(
+            "/api/connections/{}/api-tokens/{}",
+            conn_id,
+            created["id"].as_str().unwrap()
+        ))
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 204);
+
+    let resp = app.oneshot(list()).await.unwrap();
+    sqlx::query("DROP TABLE token_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 401);
+}
+
+/// Outside the data routes of its own connection a token is refused with
+/// 403, whatever its permission.
+#[tokio::test]
+#[serial]
+async fn token_is_refused_outside_its_connection_data_routes() {
+    let pool = common::setup_test_db().await;
+    let (app, org_id, admin_id, conn_id) = setup_token_connection(&pool).await;
+    let created = create_token(&app, conn_id, admin_id, "write").await;
+    let token = created["token"].as_str().unwrap().to_string();
+
+    let call = |method: &str, uri: String, body: &'static str| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .body(axum::body::Body::from(body))
+            .unwrap()
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(call(
+            "GET",
+            format!("/api/connections/{}/tables", conn_id),
+            "",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let refused = [
+        call("GET", format!("/api/organizations/{}/users", org_id), ""),
+        call("GET", format!("/api/organizations/{}/groups", org_id), ""),
+        call(
+            "POST",
+            "/api/organizations".to_string(),
+            r#"{"name":"Evil"}"#,
+        ),
+        call(
+            "POST",
+            "/api/connections".to_string(),
+            r#"{"name":"x","host":"localhost","port":5432,"database":"d","user":"u","password":"p"}"#,
+        ),
+        call(
+            "GET",
+            format!("/api/connections/{}/api-tokens", conn_id),
+            "",
+        ),
+        call(
+            "GET",
+            format!("/api/connections/{}/tables", uuid::Uuid::new_v4()),
+            "",
+        ),
+    ];
+    for req in refused {
+        let uri = req.uri().to_string();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 403, "{}", uri);
+        assert_eq!(body_json(resp).await["code"], "api_token_out_of_scope");
+    }
+}
+
+/// Token writes are attributed to the token's issuer, with the token named in
+/// the details, and are never logged as impersonation.
+#[tokio::test]
+#[serial]
+async fn token_writes_are_audited_as_the_issuer() {
+    let pool = common::setup_test_db().await;
+    sqlx::query("DROP TABLE IF EXISTS token_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE token_notes (id SERIAL PRIMARY KEY, body TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (app, _, admin_id, conn_id) = setup_token_connection(&pool).await;
+    let created = create_token(&app, conn_id, admin_id, "write").await;
+    let token = created["token"].as_str().unwrap().to_string();
+
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/api/connections/{}/tables/token_notes/rows",
+            conn_id
+        ))
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Content-Type", "application/json")
+        .body(axum::body::Body::from(r#"{"body":"hello"}"#))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 201);
+
+    let entries: Vec<(String, Option<uuid::Uuid>, Option<serde_json::Value>)> =
+        sqlx::query_as("SELECT action, actor_id, details FROM audit_log WHERE actor_id = $1")
+            .bind(admin_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    sqlx::query("DROP TABLE token_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    let (action, actor_id, details) = &entries[0];
+    assert_eq!(action, "insert");
+    assert_eq!(*actor_id, Some(admin_id));
+    let details = details.as_ref().unwrap();
+    assert_eq!(details["api_token_id"], created["id"]);
+    assert!(details.get("acting_as").is_none());
+}
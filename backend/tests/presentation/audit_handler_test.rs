@@ -0,0 +1,74 @@
+use crate::common;
+use crate::presentation::helpers::{build_test_app, seed_connection, seed_org_and_owner};
+
+use dbworks_backend::domain::repository::{AuditLogRepository, UserRepository};
+use dbworks_backend::infrastructure::database::audit_log_repo::PgAuditLogRepository;
+use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use http::Request;
+use http_body_util::BodyExt;
+use serial_test::serial;
+use tower::ServiceExt;
+
+#[tokio::test]
+#[serial]
+async fn list_audit_log_filters_by_action_and_actor() {
+    let pool = common::setup_test_db().await;
+    let (org_id, owner_id) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+    let other = PgUserRepository::new(pool.clone())
+        .create("Other", "other@test.com", "member")
+        .await
+        .unwrap();
+
+    let repo = PgAuditLogRepository::new(pool.clone());
+    repo.record(&conn_id, &owner_id, "insert", Some("users"), None)
+        .await
+        .unwrap();
+    repo.record(&conn_id, &other.id, "delete", Some("users"), None)
+        .await
+        .unwrap();
+    repo.record(&conn_id, &other.id, "insert", Some("users"), None)
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+    let req = Request::builder()
+        .uri(format!(
+            "/api/connections/{}/audit?action=insert&actor_id={}",
+            conn_id, other.id
+        ))
+        .header("X-User-Id", owner_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total_count"], 1);
+    assert_eq!(json["items"][0]["action"], "insert");
+    assert_eq!(json["items"][0]["actor_id"], other.id.to_string());
+}
+
+#[tokio::test]
+#[serial]
+async fn list_audit_log_requires_connection_owner() {
+    let pool = common::setup_test_db().await;
+    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+    let outsider = PgUserRepository::new(pool.clone())
+        .create("Outsider", "outsider@test.com", "member")
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+    let req = Request::builder()
+        .uri(format!("/api/connections/{}/audit", conn_id))
+        .header("X-User-Id", outsider.id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 403);
+}
@@ -163,6 +163,41 @@ async fn create_connection_without_db_type_defaults_to_postgres() {
     }
 }
 
+/// The test app has no connection repository, so created connections only
+/// live in memory and must say so.
+#[tokio::test]
+#[serial]
+async fn create_connection_without_repository_reports_not_persisted() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed_org_and_owner(&pool).await;
+    let app = build_test_app(pool);
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let body = serde_json::json!({
+        "name": "ephemeral",
+        "host": host,
+        "port": port,
+        "database": database,
+        "user": user,
+        "password": password
+    });
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/connections")
+        .header("Content-Type", "application/json")
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 201);
+
+    let body_bytes = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+    assert_eq!(json["persisted"], false);
+}
+
 /// When db_type is explicitly "mysql"
 #[tokio::test]
 #[serial]
@@ -213,7 +248,7 @@ async fn create_connection_with_unsupported_db_type_returns_400() {
 
     let body = serde_json::json!({
         "name": "mydb",
-        "db_type": "sqlite",
+        "db_type": "oracle",
         "host": "localhost",
         "port": 5432,
         "database": "testdb",
@@ -241,3 +276,44 @@ async fn create_connection_with_unsupported_db_type_returns_400() {
         err_msg
     );
 }
+
+#[tokio::test]
+#[serial]
+async fn preview_connection_tables_lists_tables_without_saving() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed_org_and_owner(&pool).await;
+    let app = build_test_app(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+
+    let body = serde_json::json!({
+        "host": host,
+        "port": port,
+        "database": database,
+        "user": user,
+        "password": password
+    });
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/connections/test/tables")
+        .header("Content-Type", "application/json")
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let tables = json.as_array().unwrap();
+    assert!(
+        tables
+            .iter()
+            .any(|t| t["table_name"] == "saved_connections")
+    );
+
+    let saved: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM saved_connections")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(saved, 0);
+}
@@ -1,13 +1,16 @@
 use crate::common;
-use crate::presentation::helpers::{build_test_app, seed_connection};
+use crate::presentation::helpers::{build_test_app, build_test_state, seed_connection};
 
+use dbworks_backend::domain::connection::ConnectionSettings;
 use dbworks_backend::domain::repository::{
     OrganizationRepository, PermissionRepository, UserRepository,
 };
 use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
 use dbworks_backend::infrastructure::database::permission_repo::PgPermissionRepository;
 use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::presentation::routes::create_router;
 use http::Request;
+use http_body_util::BodyExt;
 use serial_test::serial;
 use tower::ServiceExt;
 
@@ -23,7 +26,8 @@ async fn seed(pool: &sqlx::PgPool) -> (uuid::Uuid, uuid::Uuid) {
 }
 
 /// data endpoints require a valid connection. With no connection registered,
-/// list_tables should return 401 for unknown user.
+/// list_tables should return 401 for unknown user, with a Bearer challenge
+/// and a code that tells it apart from a bad token.
 #[tokio::test]
 #[serial]
 async fn list_tables_returns_401_for_unknown_user() {
@@ -39,6 +43,41 @@ async fn list_tables_returns_401_for_unknown_user() {
 
     let resp = app.oneshot(req).await.unwrap();
     assert_eq!(resp.status(), 401);
+    assert_eq!(
+        resp.headers()["www-authenticate"],
+        "Bearer error=\"invalid_token\""
+    );
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"], "Unauthorized");
+    assert_eq!(json["code"], "unknown_user");
+}
+
+/// A bearer token that does not decode is rejected as an invalid token.
+#[tokio::test]
+#[serial]
+async fn list_tables_returns_401_for_invalid_token() {
+    let pool = common::setup_test_db().await;
+    let app = build_test_app(pool);
+
+    let conn_id = uuid::Uuid::new_v4();
+    let req = Request::builder()
+        .uri(format!("/api/connections/{}/tables", conn_id))
+        .header("Authorization", "Bearer not-a-jwt")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 401);
+    assert!(
+        resp.headers()["www-authenticate"]
+            .to_str()
+            .unwrap()
+            .starts_with("Bearer")
+    );
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["code"], "invalid_token");
 }
 
 /// Authenticated user but no permission → 403
@@ -96,3 +135,621 @@ async fn list_tables_returns_404_for_missing_connection() {
     // Connection not registered in ConnectionManager → 404
     assert_eq!(resp.status(), 404);
 }
+
+#[tokio::test]
+#[serial]
+async fn create_row_with_repeated_idempotency_key_inserts_once() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed(&pool).await;
+    sqlx::query("DROP TABLE IF EXISTS idem_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE idem_notes (id SERIAL PRIMARY KEY, body TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "idem-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(admin_id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    let post = || {
+        Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/api/connections/{}/tables/idem_notes/rows",
+                info.id
+            ))
+            .header("Content-Type", "application/json")
+            .header("X-User-Id", admin_id.to_string())
+            .header("Idempotency-Key", "retry-1")
+            .body(axum::body::Body::from(r#"{"body":"hello"}"#))
+            .unwrap()
+    };
+    let first = app.clone().oneshot(post()).await.unwrap();
+    let second = app.oneshot(post()).await.unwrap();
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM idem_notes")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE idem_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(first.status(), 201);
+    assert_eq!(second.status(), 201);
+    let first = first.into_body().collect().await.unwrap().to_bytes();
+    let second = second.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(first, second);
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn get_row_returns_etag_and_304_when_unchanged() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed(&pool).await;
+    sqlx::query("DROP TABLE IF EXISTS etag_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE etag_notes (id INT PRIMARY KEY, body TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO etag_notes VALUES (1, 'hello')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "etag-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(admin_id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    let get = |if_none_match: Option<&str>| {
+        let mut req = Request::builder()
+            .uri(format!(
+                "/api/connections/{}/tables/etag_notes/rows/1",
+                info.id
+            ))
+            .header("X-User-Id", admin_id.to_string());
+        if let Some(tag) = if_none_match {
+            req = req.header("If-None-Match", tag);
+        }
+        req.body(axum::body::Body::empty()).unwrap()
+    };
+
+    let first = app.clone().oneshot(get(None)).await.unwrap();
+    assert_eq!(first.status(), 200);
+    let etag = first.headers()["etag"].to_str().unwrap().to_string();
+    let body = first.into_body().collect().await.unwrap().to_bytes();
+    let row: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(row["body"], "hello");
+
+    let cached = app.clone().oneshot(get(Some(&etag))).await.unwrap();
+    assert_eq!(cached.status(), 304);
+    assert_eq!(cached.headers()["etag"], etag.as_str());
+    let body = cached.into_body().collect().await.unwrap().to_bytes();
+    assert!(body.is_empty());
+
+    // A changed row no longer matches the old tag
+    sqlx::query("UPDATE etag_notes SET body = 'changed' WHERE id = 1")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let changed = app.oneshot(get(Some(&etag))).await.unwrap();
+    sqlx::query("DROP TABLE etag_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(changed.status(), 200);
+    assert_ne!(changed.headers()["etag"], etag.as_str());
+}
+
+/// Row writes larger than the body limit are rejected before reaching the handler.
+#[tokio::test]
+#[serial]
+async fn create_row_with_oversized_body_returns_413() {
+    let pool = common::setup_test_db().await;
+    let (_org_id, admin_id) = seed(&pool).await;
+    let app = build_test_app(pool);
+
+    let padding = "x".repeat(dbworks_backend::presentation::routes::DEFAULT_ROW_BODY_LIMIT_BYTES);
+    let body = serde_json::json!({ "name": padding }).to_string();
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!(
+            "/api/connections/{}/tables/widgets/rows",
+            uuid::Uuid::new_v4()
+        ))
+        .header("X-User-Id", admin_id.to_string())
+        .header("Content-Type", "application/json")
+        .body(axum::body::Body::from(body))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 413);
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_of_missing_table_returns_404() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed(&pool).await;
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "missing-table-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(admin_id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    for path in ["rows", "schema"] {
+        let req = Request::builder()
+            .uri(format!(
+                "/api/connections/{}/tables/no_such_table/{}",
+                info.id, path
+            ))
+            .header("X-User-Id", admin_id.to_string())
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 404, "{}", path);
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn insert_defaults_report_now_and_literal_defaults() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed(&pool).await;
+    sqlx::query(
+        "CREATE TABLE default_notes (id SERIAL PRIMARY KEY, status TEXT NOT NULL DEFAULT 'pending', created_at TIMESTAMPTZ NOT NULL DEFAULT now(), body TEXT)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "defaults-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(admin_id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    let req = Request::builder()
+        .uri(format!(
+            "/api/connections/{}/tables/default_notes/insert-defaults",
+            info.id
+        ))
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    sqlx::query("DROP TABLE default_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), 200);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let defaults: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    let by_name = |name: &str| {
+        defaults
+            .iter()
+            .find(|d| d["column_name"] == name)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(defaults.len(), 3);
+    assert_eq!(by_name("id")["kind"], "sequence");
+    assert_eq!(by_name("status")["kind"], "literal");
+    assert_eq!(by_name("status")["expression"], "'pending'::text");
+    assert_eq!(by_name("status")["value"], "pending");
+    assert_eq!(by_name("created_at")["kind"], "timestamp");
+    assert_eq!(by_name("created_at")["expression"], "now()");
+    assert!(by_name("created_at")["value"].is_string());
+}
+
+#[tokio::test]
+#[serial]
+async fn create_row_returning_only_the_generated_pk() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed(&pool).await;
+    sqlx::query("DROP TABLE IF EXISTS returning_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE returning_notes (id SERIAL PRIMARY KEY, body TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'new')",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "returning-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(admin_id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    let post = |query: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/api/connections/{}/tables/returning_notes/rows{}",
+                info.id, query
+            ))
+            .header("Content-Type", "application/json")
+            .header("X-User-Id", admin_id.to_string())
+            .body(axum::body::Body::from(r#"{"body":"hello"}"#))
+            .unwrap()
+    };
+    let full = app.clone().oneshot(post("")).await.unwrap();
+    let narrowed = app.clone().oneshot(post("?returning=id")).await.unwrap();
+    let unknown = app.oneshot(post("?returning=nope")).await.unwrap();
+    sqlx::query("DROP TABLE returning_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // The full row still carries the serial pk and the column default
+    assert_eq!(full.status(), 201);
+    let body = full.into_body().collect().await.unwrap().to_bytes();
+    let row: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        row,
+        serde_json::json!({ "id": 1, "body": "hello", "status": "new" })
+    );
+
+    assert_eq!(narrowed.status(), 201);
+    let body = narrowed.into_body().collect().await.unwrap().to_bytes();
+    let row: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(row, serde_json::json!({ "id": 2 }));
+
+    assert_eq!(unknown.status(), 400);
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_is_recorded_in_query_log() {
+    let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed(&pool).await;
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "query-log-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(admin_id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    // Sorting makes the usecase look up the table schema, which is not logged
+    let req = Request::builder()
+        .uri(format!(
+            "/api/connections/{}/tables/organizations/rows?sort_by=name",
+            info.id
+        ))
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // Entries are written in the background
+    let mut items = Vec::new();
+    for _ in 0..50 {
+        let req = Request::builder()
+            .uri(format!("/api/connections/{}/query-log", info.id))
+            .header("X-User-Id", admin_id.to_string())
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        items = page["items"].as_array().unwrap().clone();
+        if !items.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(items.len(), 1, "{:?}", items);
+    let entry = &items[0];
+    assert_eq!(entry["operation"], "list_rows");
+    assert_eq!(entry["table_name"], "organizations");
+    assert_eq!(entry["user_id"], admin_id.to_string());
+    assert_eq!(entry["success"], true);
+}
+
+#[tokio::test]
+#[serial]
+async fn transactional_writes_are_audited_only_on_commit() {
+    let pool = common::setup_test_db().await;
+    let (org_id, admin_id) = seed(&pool).await;
+    sqlx::query("DROP TABLE IF EXISTS tx_audit_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE tx_audit_notes (id SERIAL PRIMARY KEY, body TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "tx-audit-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            Some(org_id),
+            None,
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    // The test manager does not persist, but audit entries reference a saved connection
+    sqlx::query(
+        r#"INSERT INTO saved_connections (id, organization_id, name, host, port, database_name, username, encrypted_password)
+           VALUES ($1, $2, 'tx-audit-conn', 'localhost', 5432, 'testdb', 'test', 'encrypted')"#,
+    )
+    .bind(info.id)
+    .bind(org_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let app = create_router().with_state(state);
+
+    let post = |uri: String| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("X-User-Id", admin_id.to_string())
+            .body(axum::body::Body::empty())
+            .unwrap()
+    };
+    let begin = || async {
+        let resp = app
+            .clone()
+            .oneshot(post(format!("/api/connections/{}/transactions", info.id)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 201);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["transaction_id"].as_str().unwrap().to_string()
+    };
+    let insert = |tx_id: String| {
+        Request::builder()
+            .method("POST")
+            .uri(format!(
+                "/api/connections/{}/tables/tx_audit_notes/rows",
+                info.id
+            ))
+            .header("Content-Type", "application/json")
+            .header("X-User-Id", admin_id.to_string())
+            .header("X-Transaction-Id", tx_id)
+            .body(axum::body::Body::from(r#"{"body":"hello"}"#))
+            .unwrap()
+    };
+    let audited = || async {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM audit_log WHERE connection_id = $1 AND action = 'insert'",
+        )
+        .bind(info.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+    };
+
+    let rolled_back = begin().await;
+    let insert_rolled_back = app.clone().oneshot(insert(rolled_back.clone())).await;
+    let before_rollback = audited().await;
+    let rollback = app
+        .clone()
+        .oneshot(post(format!(
+            "/api/connections/{}/transactions/{}/rollback",
+            info.id, rolled_back
+        )))
+        .await;
+    let after_rollback = audited().await;
+
+    let committed = begin().await;
+    let insert_committed = app.clone().oneshot(insert(committed.clone())).await;
+    let commit = app
+        .clone()
+        .oneshot(post(format!(
+            "/api/connections/{}/transactions/{}/commit",
+            info.id, committed
+        )))
+        .await;
+    let after_commit = audited().await;
+
+    sqlx::query("DROP TABLE tx_audit_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(insert_rolled_back.unwrap().status(), 201);
+    assert_eq!(before_rollback, 0);
+    assert_eq!(rollback.unwrap().status(), 204);
+    assert_eq!(after_rollback, 0);
+    assert_eq!(insert_committed.unwrap().status(), 201);
+    assert_eq!(commit.unwrap().status(), 204);
+    assert_eq!(after_commit, 1);
+}
+
+/// Routes that cannot run inside a held transaction refuse its id instead of
+/// autocommitting outside it, so a later rollback never leaves their writes behind.
+#[tokio::test]
+#[serial]
+async fn bulk_writes_refuse_a_transaction_id() {
+    let pool = common::setup_test_db().await;
+    let (org_id, admin_id) = seed(&pool).await;
+    sqlx::query("DROP TABLE IF EXISTS tx_bulk_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE tx_bulk_notes (id SERIAL PRIMARY KEY, body TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO tx_bulk_notes (body) VALUES ('kept')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let state = build_test_state(pool.clone());
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "tx-bulk-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            Some(org_id),
+            None,
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/api/connections/{}/transactions", info.id))
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 201);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let tx_id = json["transaction_id"].as_str().unwrap().to_string();
+
+    let rows_uri = format!("/api/connections/{}/tables/tx_bulk_notes/rows", info.id);
+    let call = |method: &str, uri: String, body: &'static str| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("X-User-Id", admin_id.to_string())
+            .header("X-Transaction-Id", tx_id.clone())
+            .body(axum::body::Body::from(body))
+            .unwrap()
+    };
+    let refused = [
+        call(
+            "PATCH",
+            rows_uri.clone(),
+            r#"{"filter":"id:eq:1","data":{"body":"changed"}}"#,
+        ),
+        call("DELETE", format!("{}?filter=id:eq:1", rows_uri), ""),
+        call("GET", format!("{}/1", rows_uri), ""),
+    ];
+    let mut statuses = Vec::new();
+    for req in refused {
+        statuses.push(app.clone().oneshot(req).await.unwrap().status());
+    }
+
+    let bodies: Vec<String> = sqlx::query_scalar("SELECT body FROM tx_bulk_notes")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE tx_bulk_notes")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(statuses, vec![400, 400, 400]);
+    assert_eq!(bodies, vec!["kept".to_string()]);
+}
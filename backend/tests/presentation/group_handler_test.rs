@@ -38,7 +38,7 @@ async fn create_group_returns_201() {
 #[serial]
 async fn list_groups_returns_200() {
     let pool = common::setup_test_db().await;
-    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let (org_id, owner_id) = seed_org_and_owner(&pool).await;
 
     let group_repo = PgGroupRepository::new(pool.clone());
     group_repo.create(&org_id, "G1", None).await.unwrap();
@@ -48,6 +48,7 @@ async fn list_groups_returns_200() {
 
     let req = Request::builder()
         .uri(format!("/api/organizations/{}/groups", org_id))
+        .header("X-User-Id", owner_id.to_string())
         .body(axum::body::Body::empty())
         .unwrap();
 
@@ -61,7 +62,7 @@ async fn list_groups_returns_200() {
 
 #[tokio::test]
 #[serial]
-async fn add_group_member_returns_204() {
+async fn add_group_member_returns_201() {
     let pool = common::setup_test_db().await;
     let (org_id, admin_id) = seed_org_and_owner(&pool).await;
 
@@ -86,7 +87,123 @@ async fn add_group_member_returns_204() {
         .unwrap();
 
     let resp = app.oneshot(req).await.unwrap();
-    assert_eq!(resp.status(), 204);
+    assert_eq!(resp.status(), 201);
+}
+
+#[tokio::test]
+#[serial]
+async fn bulk_group_members_report_newly_added_and_removed_counts() {
+    let pool = common::setup_test_db().await;
+    let (org_id, owner_id) = seed_org_and_owner(&pool).await;
+
+    let group_repo = PgGroupRepository::new(pool.clone());
+    let user_repo = PgUserRepository::new(pool.clone());
+
+    let group = group_repo.create(&org_id, "Team", None).await.unwrap();
+    let root = user_repo
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+    let mut user_ids = Vec::new();
+    for i in 0..3 {
+        let user = user_repo
+            .create(
+                &format!("User {}", i),
+                &format!("user{}@test.com", i),
+                "member",
+            )
+            .await
+            .unwrap();
+        user_ids.push(user.id);
+    }
+    // Already a member, so not counted as newly added
+    group_repo
+        .add_member(&group.id, &user_ids[0])
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+    let bulk = |method: &str, caller: uuid::Uuid, ids: &[uuid::Uuid]| {
+        Request::builder()
+            .method(method)
+            .uri(format!("/api/groups/{}/members/bulk", group.id))
+            .header("Content-Type", "application/json")
+            .header("X-User-Id", caller.to_string())
+            .body(axum::body::Body::from(
+                serde_json::to_vec(&serde_json::json!({ "user_ids": ids })).unwrap(),
+            ))
+            .unwrap()
+    };
+
+    let forbidden = app
+        .clone()
+        .oneshot(bulk("POST", owner_id, &user_ids))
+        .await
+        .unwrap();
+    assert_eq!(forbidden.status(), 403);
+
+    let with_duplicate = [user_ids.clone(), vec![user_ids[1]]].concat();
+    let resp = app
+        .clone()
+        .oneshot(bulk("POST", root.id, &with_duplicate))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["added"], 2);
+    assert_eq!(group_repo.list_members(&group.id).await.unwrap().len(), 3);
+
+    let resp = app
+        .oneshot(bulk(
+            "DELETE",
+            root.id,
+            &[user_ids[0], user_ids[1], uuid::Uuid::new_v4()],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["removed"], 2);
+    let remaining = group_repo.list_members(&group.id).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, user_ids[2]);
+}
+
+#[tokio::test]
+#[serial]
+async fn re_adding_group_member_returns_200() {
+    let pool = common::setup_test_db().await;
+    let (org_id, admin_id) = seed_org_and_owner(&pool).await;
+
+    let group_repo = PgGroupRepository::new(pool.clone());
+    let user_repo = PgUserRepository::new(pool.clone());
+
+    let group = group_repo.create(&org_id, "Team", None).await.unwrap();
+    let member = user_repo
+        .create("Member", "member@test.com", "member")
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+
+    let body = serde_json::json!({ "user_id": member.id });
+    let add = || {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/api/groups/{}/members", group.id))
+            .header("Content-Type", "application/json")
+            .header("X-User-Id", admin_id.to_string())
+            .body(axum::body::Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    let first = app.clone().oneshot(add()).await.unwrap();
+    assert_eq!(first.status(), 201);
+
+    let second = app.oneshot(add()).await.unwrap();
+    assert_eq!(second.status(), 200);
 }
 
 #[tokio::test]
@@ -148,7 +265,7 @@ async fn remove_nonexistent_member_returns_404() {
 #[serial]
 async fn list_group_members_returns_200() {
     let pool = common::setup_test_db().await;
-    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let (org_id, owner_id) = seed_org_and_owner(&pool).await;
 
     let group_repo = PgGroupRepository::new(pool.clone());
     let group = group_repo.create(&org_id, "Team", None).await.unwrap();
@@ -157,6 +274,7 @@ async fn list_group_members_returns_200() {
 
     let req = Request::builder()
         .uri(format!("/api/groups/{}/members", group.id))
+        .header("X-User-Id", owner_id.to_string())
         .body(axum::body::Body::empty())
         .unwrap();
 
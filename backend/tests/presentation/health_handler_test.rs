@@ -0,0 +1,62 @@
+use crate::common;
+use crate::presentation::helpers::build_test_app;
+
+use http::Request;
+use http_body_util::BodyExt;
+use serial_test::serial;
+use tower::ServiceExt;
+
+#[tokio::test]
+#[serial]
+async fn healthz_returns_200_without_auth() {
+    let pool = common::setup_test_db().await;
+    let app = build_test_app(pool);
+
+    let req = Request::builder()
+        .uri("/healthz")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test]
+#[serial]
+async fn readyz_returns_200_with_live_pool() {
+    let pool = common::setup_test_db().await;
+    let app = build_test_app(pool);
+
+    let req = Request::builder()
+        .uri("/readyz")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "ready");
+}
+
+#[tokio::test]
+#[serial]
+async fn readyz_returns_503_when_db_unreachable() {
+    let pool = common::setup_test_db().await;
+    let app = build_test_app(pool.clone());
+    // Simulate the app DB going away
+    pool.close().await;
+
+    let req = Request::builder()
+        .uri("/readyz")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 503);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], "unavailable");
+}
@@ -1,57 +1,85 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::Router;
 use sqlx::PgPool;
 
+use dbworks_backend::infrastructure::auth::jwt::JwtConfig;
 use dbworks_backend::infrastructure::auth::oauth::OAuthClients;
 use dbworks_backend::infrastructure::crypto::Encryptor;
+use dbworks_backend::infrastructure::database::api_token_repo::PgApiTokenRepository;
+use dbworks_backend::infrastructure::database::audit_log_repo::PgAuditLogRepository;
 use dbworks_backend::infrastructure::database::connection_repo::PgConnectionRepository;
 use dbworks_backend::infrastructure::database::group_repo::PgGroupRepository;
+use dbworks_backend::infrastructure::database::org_email_domain_repo::PgOrgEmailDomainRepository;
 use dbworks_backend::infrastructure::database::organization_member_repo::PgOrganizationMemberRepository;
 use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
 use dbworks_backend::infrastructure::database::permission_repo::PgPermissionRepository;
+use dbworks_backend::infrastructure::database::permission_template_repo::PgPermissionTemplateRepository;
+use dbworks_backend::infrastructure::database::query_log_repo::PgQueryLogRepository;
+use dbworks_backend::infrastructure::database::table_metadata_repo::PgTableMetadataRepository;
 use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::presentation::idempotency::IdempotencyStore;
 use dbworks_backend::presentation::routes::create_router;
-use dbworks_backend::presentation::state::{AppStateInner, ConnectionManager};
+use dbworks_backend::presentation::state::{AppState, AppStateInner, ConnectionManager};
 
 /// Build a fully wired axum Router backed by the test database pool.
 pub fn build_test_app(pool: PgPool) -> Router {
+    create_router().with_state(build_test_state(pool))
+}
+
+/// Build the application state used by `build_test_app`, for tests that
+/// need to register datasources before sending requests.
+pub fn build_test_state(pool: PgPool) -> AppState {
     let organization_repo = Arc::new(PgOrganizationRepository::new(pool.clone()));
     let user_repo = Arc::new(PgUserRepository::new(pool.clone()));
     let group_repo = Arc::new(PgGroupRepository::new(pool.clone()));
     let permission_repo = Arc::new(PgPermissionRepository::new(pool.clone()));
     let org_member_repo = Arc::new(PgOrganizationMemberRepository::new(pool.clone()));
+    let org_email_domain_repo = Arc::new(PgOrgEmailDomainRepository::new(pool.clone()));
+    let audit_repo = Arc::new(PgAuditLogRepository::new(pool.clone()));
+    let query_log_repo = Arc::new(PgQueryLogRepository::new(pool.clone()));
+    let template_repo = Arc::new(PgPermissionTemplateRepository::new(pool.clone()));
+    let table_metadata_repo = Arc::new(PgTableMetadataRepository::new(pool.clone()));
+    let api_token_repo = Arc::new(PgApiTokenRepository::new(pool.clone()));
     let encryptor = {
         unsafe {
             std::env::set_var(
                 "ENCRYPTION_KEY",
-                base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &[42u8; 32]),
+                base64::Engine::encode(&base64::engine::general_purpose::STANDARD, [42u8; 32]),
             );
         }
         Encryptor::from_env().unwrap()
     };
     let conn_repo = Arc::new(PgConnectionRepository::new(pool.clone(), encryptor));
-    let connection_manager = ConnectionManager::new(None, None);
+    let connection_manager =
+        ConnectionManager::new(None, None).with_query_log(query_log_repo.clone());
 
     let oauth_clients = OAuthClients {
         google: None,
         github: None,
     };
 
-    let state = Arc::new(AppStateInner {
+    Arc::new(AppStateInner {
         connection_manager,
         pool,
         oauth_clients,
-        jwt_secret: "test-secret".to_string(),
+        jwt: JwtConfig::hs256("test-secret"),
         organization_repo,
         user_repo,
         group_repo,
         permission_repo,
         org_member_repo,
         conn_repo,
-    });
-
-    create_router().with_state(state)
+        org_email_domain_repo,
+        audit_repo,
+        query_log_repo,
+        template_repo,
+        table_metadata_repo,
+        api_token_repo,
+        idempotency: IdempotencyStore::new(Duration::from_secs(600)),
+        notifier: None,
+    })
 }
 
 /// Insert a minimal `saved_connections` row so that permission FK constraints
@@ -0,0 +1,98 @@
+use crate::common;
+use crate::presentation::helpers::{build_test_app, build_test_state};
+
+use dbworks_backend::domain::connection::ConnectionSettings;
+use dbworks_backend::domain::repository::UserRepository;
+use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::infrastructure::metrics;
+use dbworks_backend::presentation::routes::create_router;
+use http::Request;
+use http_body_util::BodyExt;
+use serial_test::serial;
+use tower::ServiceExt;
+
+#[tokio::test]
+#[serial]
+async fn metrics_returns_prometheus_text_format() {
+    metrics::install();
+    let pool = common::setup_test_db().await;
+    let user_repo = PgUserRepository::new(pool.clone());
+    let user = user_repo
+        .create("Admin", "admin@test.com", "member")
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+
+    // Make a request so the route counter has a sample
+    let req = Request::builder()
+        .uri("/api/organizations")
+        .header("X-User-Id", user.id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let req = Request::builder()
+        .uri("/metrics")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(
+        resp.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain")
+    );
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let text = String::from_utf8(body.to_vec()).unwrap();
+    assert!(text.contains("# TYPE dbworks_http_requests_total counter"));
+    assert!(text.contains(r#"route="/api/organizations""#));
+    assert!(text.contains("dbworks_live_connections 0"));
+}
+
+#[tokio::test]
+#[serial]
+async fn removed_connection_pool_gauges_are_cleared() {
+    metrics::install();
+    let pool = common::setup_test_db().await;
+    let state = build_test_state(pool);
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = state
+        .connection_manager
+        .add_postgres(
+            "metrics-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            None,
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    let app = create_router().with_state(state.clone());
+
+    let scrape = || async {
+        let req = Request::builder()
+            .uri("/metrics")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    };
+    let gauge = format!(r#"dbworks_pool_connections{{connection_id="{}"}}"#, info.id);
+
+    let before = scrape().await;
+    assert!(before.contains(&gauge));
+    assert!(!before.contains(&format!("{} 0\n", gauge)));
+
+    assert!(state.connection_manager.remove(&info.id).await);
+    let after = scrape().await;
+    assert!(after.contains(&format!("{} 0\n", gauge)));
+}
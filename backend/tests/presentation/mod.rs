@@ -1,7 +1,13 @@
+pub mod api_token_handler_test;
+pub mod audit_handler_test;
 pub mod connection_handler_test;
 pub mod data_handler_test;
 pub mod group_handler_test;
+pub mod health_handler_test;
 pub mod helpers;
+#[cfg(feature = "metrics")]
+pub mod metrics_handler_test;
+pub mod openapi_handler_test;
 pub mod organization_handler_test;
 pub mod permission_handler_test;
 pub mod user_handler_test;
@@ -0,0 +1,32 @@
+use crate::common;
+use crate::presentation::helpers::build_test_app;
+
+use http::Request;
+use http_body_util::BodyExt;
+use serial_test::serial;
+use tower::ServiceExt;
+
+#[tokio::test]
+#[serial]
+async fn openapi_json_describes_connection_routes_without_auth() {
+    let pool = common::setup_test_db().await;
+    let app = build_test_app(pool);
+
+    let req = Request::builder()
+        .uri("/api/openapi.json")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(spec["openapi"], "3.0.3");
+    let create = &spec["paths"]["/api/connections"]["post"];
+    assert_eq!(
+        create["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ConnectionRequest"
+    );
+    assert!(spec["components"]["schemas"]["ConnectionRequest"]["properties"]["host"].is_object());
+    assert!(spec["paths"]["/api/connections"]["get"].is_object());
+}
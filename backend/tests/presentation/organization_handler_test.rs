@@ -1,5 +1,5 @@
 use crate::common;
-use crate::presentation::helpers::{build_test_app, seed_org_and_owner};
+use crate::presentation::helpers::build_test_app;
 
 use dbworks_backend::domain::repository::{
     OrganizationMemberRepository, OrganizationRepository, UserRepository,
@@ -83,3 +83,77 @@ async fn list_organizations_returns_200() {
     let arr = json.as_array().unwrap();
     assert_eq!(arr.len(), 2);
 }
+
+async fn post_email_domain(
+    pool: sqlx::PgPool,
+    org_id: &uuid::Uuid,
+    user_id: &uuid::Uuid,
+    domain: &str,
+) -> http::Response<axum::body::Body> {
+    let app = build_test_app(pool);
+    let body = serde_json::json!({ "domain": domain });
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/api/organizations/{}/email-domains", org_id))
+        .header("Content-Type", "application/json")
+        .header("X-User-Id", user_id.to_string())
+        .body(axum::body::Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn register_email_domain_as_super_admin_returns_201() {
+    let pool = common::setup_test_db().await;
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Acme")
+        .await
+        .unwrap();
+    let admin = PgUserRepository::new(pool.clone())
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+
+    let resp = post_email_domain(pool, &org.id, &admin.id, "Acme.com").await;
+    assert_eq!(resp.status(), 201);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["domain"], "acme.com");
+    assert_eq!(json["default_role"], "member");
+}
+
+#[tokio::test]
+#[serial]
+async fn register_email_domain_as_member_returns_403() {
+    let pool = common::setup_test_db().await;
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Acme")
+        .await
+        .unwrap();
+    let user = PgUserRepository::new(pool.clone())
+        .create("User", "user@test.com", "member")
+        .await
+        .unwrap();
+
+    let resp = post_email_domain(pool, &org.id, &user.id, "acme.com").await;
+    assert_eq!(resp.status(), 403);
+}
+
+#[tokio::test]
+#[serial]
+async fn register_invalid_email_domain_returns_400() {
+    let pool = common::setup_test_db().await;
+    let org = PgOrganizationRepository::new(pool.clone())
+        .create("Acme")
+        .await
+        .unwrap();
+    let admin = PgUserRepository::new(pool.clone())
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+
+    let resp = post_email_domain(pool, &org.id, &admin.id, "not a domain").await;
+    assert_eq!(resp.status(), 400);
+}
@@ -96,11 +96,13 @@ async fn grant_user_conn_permission_as_member_returns_403() {
 #[serial]
 async fn list_user_conn_permissions_returns_200() {
     let pool = common::setup_test_db().await;
+    let (_, admin_id) = seed_org_and_owner(&pool).await;
     let app = build_test_app(pool);
 
     let conn_id = uuid::Uuid::new_v4();
     let req = Request::builder()
         .uri(format!("/api/connections/{}/user-permissions", conn_id))
+        .header("X-User-Id", admin_id.to_string())
         .body(axum::body::Body::empty())
         .unwrap();
 
@@ -109,7 +111,8 @@ async fn list_user_conn_permissions_returns_200() {
 
     let body = resp.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert!(json.as_array().unwrap().is_empty());
+    assert!(json["items"].as_array().unwrap().is_empty());
+    assert_eq!(json["total_count"], 0);
 }
 
 #[tokio::test]
@@ -213,3 +216,248 @@ async fn grant_group_conn_permission_returns_201() {
     assert_eq!(json["permission"], "read");
     assert_eq!(json["all_tables"], false);
 }
+
+#[tokio::test]
+#[serial]
+async fn list_connection_access_labels_direct_and_group_sources() {
+    use dbworks_backend::domain::repository::GroupRepository;
+    use dbworks_backend::infrastructure::database::group_repo::PgGroupRepository;
+
+    let pool = common::setup_test_db().await;
+    let (org_id, admin_id) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let group_repo = PgGroupRepository::new(pool.clone());
+    let permission_repo = PgPermissionRepository::new(pool.clone());
+
+    let direct = user_repo
+        .create("Direct", "direct@test.com", "member")
+        .await
+        .unwrap();
+    let via_group = user_repo
+        .create("ViaGroup", "viagroup@test.com", "member")
+        .await
+        .unwrap();
+    let group = group_repo.create(&org_id, "Analysts", None).await.unwrap();
+    group_repo
+        .add_member(&group.id, &via_group.id)
+        .await
+        .unwrap();
+    permission_repo
+        .grant_user_connection_permission(&conn_id, &direct.id, "write", true)
+        .await
+        .unwrap();
+    permission_repo
+        .grant_group_connection_permission(&conn_id, &group.id, "read", true)
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+    let req = Request::builder()
+        .uri(format!("/api/connections/{}/access", conn_id))
+        .header("X-User-Id", admin_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    let find = |user_id: uuid::Uuid| {
+        entries
+            .iter()
+            .find(|e| e["user_id"] == user_id.to_string())
+            .unwrap()
+    };
+    assert_eq!(find(direct.id)["source"], "direct");
+    assert_eq!(find(direct.id)["permission"], "write");
+    assert_eq!(find(via_group.id)["source"], "group");
+    assert_eq!(find(via_group.id)["group_id"], group.id.to_string());
+    assert_eq!(find(via_group.id)["permission"], "read");
+}
+
+/// GET the table listing for `conn_id` as `user_id`, optionally acting as `act_as`.
+async fn list_tables_status(
+    pool: sqlx::PgPool,
+    conn_id: &uuid::Uuid,
+    user_id: &uuid::Uuid,
+    act_as: Option<&uuid::Uuid>,
+) -> http::StatusCode {
+    let mut req = Request::builder()
+        .method("GET")
+        .uri(format!("/api/connections/{}/tables", conn_id))
+        .header("X-User-Id", user_id.to_string());
+    if let Some(target) = act_as {
+        req = req.header("X-Act-As", target.to_string());
+    }
+    let req = req.body(axum::body::Body::empty()).unwrap();
+    build_test_app(pool).oneshot(req).await.unwrap().status()
+}
+
+#[tokio::test]
+#[serial]
+async fn super_admin_acting_as_member_gets_member_permissions() {
+    let pool = common::setup_test_db().await;
+    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let admin = user_repo
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+    let member = user_repo
+        .create("Member", "member@test.com", "member")
+        .await
+        .unwrap();
+
+    // The connection isn't live, so passing the permission check surfaces as 404
+    let as_admin = list_tables_status(pool.clone(), &conn_id, &admin.id, None).await;
+    let acting = list_tables_status(pool.clone(), &conn_id, &admin.id, Some(&member.id)).await;
+    assert_eq!(as_admin, 404);
+    assert_eq!(acting, 403);
+
+    // Granting the member read access flips what the admin sees while acting as them
+    PgPermissionRepository::new(pool.clone())
+        .grant_user_connection_permission(&conn_id, &member.id, "read", true)
+        .await
+        .unwrap();
+    let acting = list_tables_status(pool, &conn_id, &admin.id, Some(&member.id)).await;
+    assert_eq!(acting, 404);
+}
+
+#[tokio::test]
+#[serial]
+async fn act_as_is_recorded_in_the_audit_log() {
+    let pool = common::setup_test_db().await;
+    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let admin = user_repo
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+    let member = user_repo
+        .create("Member", "member@test.com", "member")
+        .await
+        .unwrap();
+
+    list_tables_status(pool.clone(), &conn_id, &admin.id, None).await;
+    list_tables_status(pool.clone(), &conn_id, &admin.id, Some(&member.id)).await;
+
+    let entries: Vec<(Option<uuid::Uuid>, Option<uuid::Uuid>, serde_json::Value)> = sqlx::query_as(
+        "SELECT connection_id, actor_id, details FROM audit_log WHERE action = 'act_as'",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(entries.len(), 1);
+    let (connection_id, actor_id, details) = &entries[0];
+    assert_eq!(*connection_id, None);
+    assert_eq!(*actor_id, Some(admin.id));
+    assert_eq!(details["acting_as"], member.id.to_string());
+    assert_eq!(details["method"], "GET");
+    assert_eq!(
+        details["path"],
+        format!("/api/connections/{}/tables", conn_id)
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn act_as_header_from_member_is_ignored() {
+    let pool = common::setup_test_db().await;
+    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let admin = user_repo
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+    let member = user_repo
+        .create("Member", "member@test.com", "member")
+        .await
+        .unwrap();
+
+    let status = list_tables_status(pool, &conn_id, &member.id, Some(&admin.id)).await;
+    assert_eq!(status, 403);
+}
+
+#[tokio::test]
+#[serial]
+async fn resolve_matches_repository_resolution() {
+    let pool = common::setup_test_db().await;
+    let (org_id, _) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+
+    let user_repo = PgUserRepository::new(pool.clone());
+    let admin = user_repo
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+    let member = user_repo
+        .create("Member", "member@test.com", "member")
+        .await
+        .unwrap();
+    let perm_repo = PgPermissionRepository::new(pool.clone());
+    perm_repo
+        .grant_user_connection_permission(&conn_id, &member.id, "read", true)
+        .await
+        .unwrap();
+    perm_repo
+        .grant_user_table_permission(&conn_id, &member.id, "orders", "write")
+        .await
+        .unwrap();
+    let expected = perm_repo
+        .explain_table_permission(&member, &conn_id, "orders")
+        .await
+        .unwrap();
+
+    let app = build_test_app(pool);
+    let req = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/api/connections/{}/resolve?user_id={}&table=orders",
+            conn_id, member.id
+        ))
+        .header("X-User-Id", admin.id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 200);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["level"], serde_json::json!(expected.level));
+    assert_eq!(json["source"], serde_json::json!(expected.source));
+    assert_eq!(json["level"], "write");
+    assert_eq!(json["source"], "user");
+    assert_eq!(json["table_level"], true);
+}
+
+#[tokio::test]
+#[serial]
+async fn resolve_as_member_returns_403() {
+    let pool = common::setup_test_db().await;
+    let (org_id, owner_id) = seed_org_and_owner(&pool).await;
+    let conn_id = seed_connection(&pool, &org_id).await;
+
+    let app = build_test_app(pool);
+    let req = Request::builder()
+        .method("GET")
+        .uri(format!(
+            "/api/connections/{}/resolve?user_id={}&table=orders",
+            conn_id, owner_id
+        ))
+        .header("X-User-Id", owner_id.to_string())
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), 403);
+}
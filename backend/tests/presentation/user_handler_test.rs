@@ -109,12 +109,13 @@ async fn create_user_as_member_returns_403() {
 #[serial]
 async fn list_users_returns_200() {
     let pool = common::setup_test_db().await;
-    let (org_id, _admin_id) = seed_org_and_owner(&pool).await;
+    let (org_id, admin_id) = seed_org_and_owner(&pool).await;
 
     let app = build_test_app(pool);
 
     let req = Request::builder()
         .uri(format!("/api/organizations/{}/users", org_id))
+        .header("X-User-Id", admin_id.to_string())
         .body(axum::body::Body::empty())
         .unwrap();
 
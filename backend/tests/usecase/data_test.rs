@@ -1,5 +1,5 @@
 use crate::common;
-use dbworks_backend::domain::connection::ConnectionInfo;
+use dbworks_backend::domain::connection::{ConnectionInfo, ConnectionSettings};
 use dbworks_backend::domain::repository::{
     ConnectionRepository, OrganizationRepository, PermissionRepository, UserRepository,
 };
@@ -8,11 +8,18 @@ use dbworks_backend::infrastructure::crypto::Encryptor;
 use dbworks_backend::infrastructure::database::connection_repo::PgConnectionRepository;
 use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
 use dbworks_backend::infrastructure::database::permission_repo::PgPermissionRepository;
+use dbworks_backend::infrastructure::database::table_metadata_repo::PgTableMetadataRepository;
 use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::infrastructure::datasource::postgres::PostgresDataSource;
+use dbworks_backend::infrastructure::datasource::{
+    DEFAULT_MAX_OPEN_TRANSACTIONS, DataSource, DataSourceFactory, PoolLimits, connection_string,
+};
+use dbworks_backend::presentation::request::{AggregateRequest, RowsQuery};
 use dbworks_backend::presentation::state::ConnectionManager;
 use dbworks_backend::usecase::{self, UsecaseError};
 use serial_test::serial;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[allow(dead_code)]
@@ -26,6 +33,7 @@ struct TestFixture {
     no_perm_user: AppUser,
     conn_id: Uuid,
     permission_repo: PgPermissionRepository,
+    metadata_repo: PgTableMetadataRepository,
     cm: ConnectionManager,
 }
 
@@ -34,11 +42,12 @@ async fn setup() -> TestFixture {
     let org_repo = PgOrganizationRepository::new(pool.clone());
     let user_repo = PgUserRepository::new(pool.clone());
     let permission_repo = PgPermissionRepository::new(pool.clone());
+    let metadata_repo = PgTableMetadataRepository::new(pool.clone());
 
     unsafe {
         std::env::set_var(
             "ENCRYPTION_KEY",
-            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &[42u8; 32]),
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, [42u8; 32]),
         );
     }
     let enc = Encryptor::from_env().unwrap();
@@ -75,6 +84,19 @@ async fn setup() -> TestFixture {
         password: "pass".to_string(),
         organization_id: Some(org.id),
         owner_user_id: Some(owner.id),
+        tags: vec![],
+        statement_timeout_ms: None,
+        pool_idle_timeout_ms: None,
+        pool_max_lifetime_ms: None,
+        read_only: false,
+        default_page_size: None,
+        default_sort_order: None,
+        search_path: None,
+        default_schema: None,
+        role: None,
+        hidden_tables: vec![],
+        replica_hosts: vec![],
+        persisted: false,
     };
     let saved = conn_repo
         .save(Some(&org.id), Some(&owner.id), &info)
@@ -96,6 +118,7 @@ async fn setup() -> TestFixture {
         no_perm_user,
         conn_id: saved.id,
         permission_repo,
+        metadata_repo,
         cm,
     }
 }
@@ -109,8 +132,14 @@ async fn setup() -> TestFixture {
 async fn list_tables_no_permission_forbidden() {
     let f = setup().await;
 
-    let result =
-        usecase::data::list_tables(&f.permission_repo, &f.cm, &f.no_perm_user, &f.conn_id).await;
+    let result = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.no_perm_user,
+        &f.conn_id,
+    )
+    .await;
 
     assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
 }
@@ -121,7 +150,14 @@ async fn list_tables_super_admin_allowed_but_no_datasource() {
     let f = setup().await;
 
     // super_admin has Admin permission but no live datasource was loaded
-    let result = usecase::data::list_tables(&f.permission_repo, &f.cm, &f.admin, &f.conn_id).await;
+    let result = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.admin,
+        &f.conn_id,
+    )
+    .await;
 
     // Passes permission check, fails because no live datasource
     assert!(matches!(result.unwrap_err(), UsecaseError::NotFound(_)));
@@ -133,7 +169,14 @@ async fn list_tables_reader_with_read_no_datasource() {
     let f = setup().await;
 
     // reader has read permission, but no live datasource
-    let result = usecase::data::list_tables(&f.permission_repo, &f.cm, &f.reader, &f.conn_id).await;
+    let result = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.reader,
+        &f.conn_id,
+    )
+    .await;
 
     // Passes permission check → fails on get_datasource (NotFound)
     assert!(matches!(result.unwrap_err(), UsecaseError::NotFound(_)));
@@ -146,6 +189,7 @@ async fn get_table_schema_no_permission() {
 
     let result = usecase::data::get_table_schema(
         &f.permission_repo,
+        &f.metadata_repo,
         &f.cm,
         &f.no_perm_user,
         &f.conn_id,
@@ -153,7 +197,7 @@ async fn get_table_schema_no_permission() {
     )
     .await;
 
-    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+    assert!(matches!(result.unwrap_err(), UsecaseError::Denied { .. }));
 }
 
 #[tokio::test]
@@ -170,6 +214,9 @@ async fn create_row_read_only_forbidden() {
         &f.conn_id,
         "users",
         &data,
+        true,
+        &[],
+        None,
     )
     .await;
 
@@ -190,6 +237,7 @@ async fn update_row_read_only_forbidden() {
         "users",
         "1",
         &data,
+        None,
     )
     .await;
 
@@ -208,12 +256,50 @@ async fn delete_row_read_only_forbidden() {
         &f.conn_id,
         "users",
         "1",
+        true,
+        None,
     )
     .await;
 
+    // A dry run is a would-be write, so read-only callers are refused too
     assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
 }
 
+#[tokio::test]
+#[serial]
+async fn empty_or_null_primary_key_bad_request() {
+    let f = setup().await;
+
+    let data = serde_json::json!({"name": "updated"});
+    for pk in ["", "null"] {
+        let updated = usecase::data::update_row(
+            &f.permission_repo,
+            &f.cm,
+            &f.admin,
+            &f.conn_id,
+            "users",
+            pk,
+            &data,
+            None,
+        )
+        .await;
+        assert!(matches!(updated.unwrap_err(), UsecaseError::BadRequest(_)));
+
+        let deleted = usecase::data::delete_row(
+            &f.permission_repo,
+            &f.cm,
+            &f.admin,
+            &f.conn_id,
+            "users",
+            pk,
+            false,
+            None,
+        )
+        .await;
+        assert!(matches!(deleted.unwrap_err(), UsecaseError::BadRequest(_)));
+    }
+}
+
 // ============================================================
 // Nonexistent Connection Tests
 // ============================================================
@@ -224,8 +310,1498 @@ async fn list_tables_nonexistent_connection_forbidden() {
     let f = setup().await;
 
     // super_admin + nonexistent conn_id → admin permission, then NotFound for datasource
-    let result =
-        usecase::data::list_tables(&f.permission_repo, &f.cm, &f.admin, &Uuid::new_v4()).await;
+    let result = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.admin,
+        &Uuid::new_v4(),
+    )
+    .await;
 
     assert!(matches!(result.unwrap_err(), UsecaseError::NotFound(_)));
 }
+
+// ============================================================
+// Live Datasource Tests
+// ============================================================
+
+/// Builds PostgreSQL datasources with a single pooled connection and a short
+/// acquire timeout, so a second concurrent call cannot get a connection.
+struct SingleConnectionFactory;
+
+#[async_trait::async_trait]
+impl DataSourceFactory for SingleConnectionFactory {
+    async fn build(&self, info: &ConnectionInfo) -> anyhow::Result<Arc<dyn DataSource>> {
+        let limits = PoolLimits {
+            max_connections: 1,
+            acquire_timeout: Duration::from_millis(200),
+            ..PoolLimits::from_env()
+        };
+        let ds = PostgresDataSource::with_session(
+            &connection_string(info),
+            Duration::from_secs(30),
+            None,
+            None,
+            limits,
+            &[],
+        )
+        .await?;
+        Ok(Arc::new(ds))
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn saturated_pool_returns_unavailable() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE OR REPLACE VIEW busy_view AS SELECT 1 AS id FROM pg_sleep(1)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let cm = ConnectionManager::new(None, None).with_factory(Arc::new(SingleConnectionFactory));
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info = cm
+        .add_postgres(
+            "busy-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let query = RowsQuery::default();
+    let list = || {
+        usecase::data::list_rows(
+            &f.permission_repo,
+            &cm,
+            &f.admin,
+            &info.id,
+            "busy_view",
+            &query,
+            None,
+        )
+    };
+    let (first, second) = tokio::join!(list(), list());
+
+    sqlx::query("DROP VIEW busy_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // One call holds the only connection for the whole sleep; the other gives up
+    let errors: Vec<_> = [first, second]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+    assert!(!errors.is_empty());
+    assert!(
+        errors
+            .iter()
+            .all(|e| matches!(e, UsecaseError::Unavailable(_))),
+        "{:?}",
+        errors
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_exceeding_statement_timeout_returns_timeout() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE OR REPLACE VIEW slow_view AS SELECT 1 AS id FROM pg_sleep(2)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "slow-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings {
+                statement_timeout_ms: Some(100),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let result = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "slow_view",
+        &RowsQuery::default(),
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP VIEW slow_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::Timeout(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn distinct_values_unknown_column_bad_request() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE OR REPLACE VIEW facet_view AS SELECT 1 AS id, 'a' AS status")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "facet-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let result = usecase::data::distinct_values(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "facet_view",
+        "status\"; DROP TABLE x; --",
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP VIEW facet_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn aggregate_invalid_function_bad_request() {
+    let f = setup().await;
+
+    let req = AggregateRequest {
+        group_by: "status".to_string(),
+        metric_col: Some("points".to_string()),
+        agg_fn: "pg_sleep".to_string(),
+    };
+    let result =
+        usecase::data::aggregate(&f.permission_repo, &f.cm, &f.admin, &f.conn_id, "t", &req).await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_sort_by_unknown_column_bad_request() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE OR REPLACE VIEW sort_view AS SELECT 1 AS id, 'a' AS name")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "sort-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let result = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "sort_view",
+        &RowsQuery {
+            sort_by: Some("name:asc,missing:desc".to_string()),
+            ..RowsQuery::default()
+        },
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP VIEW sort_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    match result.unwrap_err() {
+        UsecaseError::BadRequest(msg) => assert!(msg.contains("missing"), "{}", msg),
+        other => panic!("expected BadRequest, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn create_row_validation_reports_missing_and_unknown_columns() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE IF EXISTS validated_accounts")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE validated_accounts (
+            id INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+            email TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'active',
+            nickname TEXT
+        )",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "validate-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let create = |data: serde_json::Value, validate: bool| {
+        let (permission_repo, cm, admin, conn_id) = (&f.permission_repo, &f.cm, &f.admin, info.id);
+        async move {
+            usecase::data::create_row(
+                permission_repo,
+                cm,
+                admin,
+                &conn_id,
+                "validated_accounts",
+                &data,
+                validate,
+                &[],
+                None,
+            )
+            .await
+        }
+    };
+    let invalid = create(
+        serde_json::json!({ "nickname": "x", "emial": "a@b.c" }),
+        true,
+    )
+    .await;
+    let unvalidated = create(serde_json::json!({ "nickname": "x" }), false).await;
+    let valid = create(serde_json::json!({ "email": "a@b.c" }), true).await;
+
+    sqlx::query("DROP TABLE validated_accounts")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    match invalid.unwrap_err() {
+        UsecaseError::Invalid(problems) => assert_eq!(
+            problems,
+            vec![
+                "Missing required column 'email'".to_string(),
+                "Unknown column 'emial'".to_string(),
+            ]
+        ),
+        other => panic!("expected Invalid, got {:?}", other),
+    }
+    // Without validation the raw database error surfaces instead.
+    assert!(matches!(
+        unvalidated.unwrap_err(),
+        UsecaseError::BadRequest(_)
+    ));
+    assert_eq!(valid.unwrap()["status"], "active");
+}
+
+#[tokio::test]
+#[serial]
+async fn table_alias_appears_in_table_listing() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE IF EXISTS tbl_cust_x1")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE tbl_cust_x1 (id SERIAL PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "alias-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let forbidden = usecase::data::set_table_metadata(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.reader,
+        &info.id,
+        "tbl_cust_x1",
+        Some("Customers"),
+        None,
+    )
+    .await;
+    let set = usecase::data::set_table_metadata(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.admin,
+        &info.id,
+        "tbl_cust_x1",
+        Some(" Customers "),
+        Some("Active customer accounts"),
+    )
+    .await;
+    let tables = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+    )
+    .await;
+
+    sqlx::query("DROP TABLE tbl_cust_x1")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert!(matches!(forbidden.unwrap_err(), UsecaseError::Forbidden(_)));
+    assert_eq!(set.unwrap().display_name.as_deref(), Some("Customers"));
+    let tables = tables.unwrap();
+    let table = tables
+        .iter()
+        .find(|t| t.table_name == "tbl_cust_x1")
+        .unwrap();
+    assert_eq!(table.display_name.as_deref(), Some("Customers"));
+    assert_eq!(
+        table.description.as_deref(),
+        Some("Active customer accounts")
+    );
+    assert!(
+        tables
+            .iter()
+            .filter(|t| t.table_name != "tbl_cust_x1")
+            .all(|t| t.display_name.is_none())
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn column_description_round_trips_into_schema() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE IF EXISTS annotated_invoices")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE annotated_invoices (id SERIAL PRIMARY KEY, amt NUMERIC NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "column-meta-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let forbidden = usecase::data::set_column_metadata(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.reader,
+        &info.id,
+        "annotated_invoices",
+        "amt",
+        Some("Invoice total"),
+        None,
+    )
+    .await;
+    usecase::data::set_column_metadata(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.admin,
+        &info.id,
+        "annotated_invoices",
+        "amt",
+        Some("Invoice total"),
+        Some("currency"),
+    )
+    .await
+    .unwrap();
+    let schema = usecase::data::get_table_schema(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "annotated_invoices",
+    )
+    .await;
+
+    sqlx::query("DROP TABLE annotated_invoices")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert!(matches!(forbidden.unwrap_err(), UsecaseError::Forbidden(_)));
+    let schema = schema.unwrap();
+    let amt = schema
+        .columns
+        .iter()
+        .find(|c| c.column_name == "amt")
+        .unwrap();
+    assert_eq!(amt.description.as_deref(), Some("Invoice total"));
+    assert_eq!(amt.display_hint.as_deref(), Some("currency"));
+    let id = schema
+        .columns
+        .iter()
+        .find(|c| c.column_name == "id")
+        .unwrap();
+    assert!(id.description.is_none());
+    let json = serde_json::to_value(id).unwrap();
+    assert!(json.get("display_hint").is_none());
+}
+
+#[tokio::test]
+#[serial]
+async fn delete_row_dry_run_counts_without_deleting() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE IF EXISTS dry_run_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE dry_run_items (id INT PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO dry_run_items (id) VALUES (1), (2)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "dry-run-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let delete = |pk: &'static str, dry_run: bool| {
+        let (permission_repo, cm, admin, conn_id) = (&f.permission_repo, &f.cm, &f.admin, info.id);
+        async move {
+            usecase::data::delete_row(
+                permission_repo,
+                cm,
+                admin,
+                &conn_id,
+                "dry_run_items",
+                pk,
+                dry_run,
+                None,
+            )
+            .await
+        }
+    };
+    let existing = delete("1", true).await;
+    let missing = delete("99", true).await;
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM dry_run_items")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("DROP TABLE dry_run_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(existing.unwrap(), Some(1));
+    assert_eq!(missing.unwrap(), Some(0));
+    assert_eq!(remaining, 2);
+}
+
+/// Create `bulk_tasks` with three open and two done rows, register a live
+/// connection owned by the admin, and return its id.
+async fn setup_bulk_tasks(f: &TestFixture, pool: &sqlx::PgPool) -> Uuid {
+    sqlx::query("DROP TABLE IF EXISTS bulk_tasks")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE bulk_tasks (id SERIAL PRIMARY KEY, status TEXT NOT NULL, owner TEXT)",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO bulk_tasks (status, owner) VALUES
+            ('open', 'a'), ('open', 'b'), ('done', 'a'), ('open', 'c'), ('done', 'b')",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    f.cm.add_postgres(
+        "bulk-conn".into(),
+        host,
+        port,
+        database,
+        user,
+        password,
+        None,
+        Some(f.admin.id),
+        ConnectionSettings::default(),
+    )
+    .await
+    .unwrap()
+    .id
+}
+
+async fn count_status(pool: &sqlx::PgPool, status: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM bulk_tasks WHERE status = $1")
+        .bind(status)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn bulk_update_and_delete_only_touch_filtered_rows() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    let conn_id = setup_bulk_tasks(&f, &pool).await;
+
+    let data = serde_json::json!({ "status": "archived", "owner": null });
+    let preview = usecase::data::bulk_update_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "bulk_tasks",
+        Some("status:eq:open"),
+        &data,
+        true,
+    )
+    .await;
+    let open_after_preview = count_status(&pool, "open").await;
+    let updated = usecase::data::bulk_update_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "bulk_tasks",
+        Some("status:eq:open"),
+        &data,
+        false,
+    )
+    .await;
+    let archived = count_status(&pool, "archived").await;
+    let done = count_status(&pool, "done").await;
+    let deleted = usecase::data::bulk_delete_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "bulk_tasks",
+        Some("status:eq:done"),
+        false,
+    )
+    .await;
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bulk_tasks")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("DROP TABLE bulk_tasks")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(preview.unwrap(), 3);
+    assert_eq!(open_after_preview, 3);
+    assert_eq!(updated.unwrap(), 3);
+    assert_eq!(archived, 3);
+    assert_eq!(done, 2);
+    assert_eq!(deleted.unwrap(), 2);
+    assert_eq!(remaining, 3);
+}
+
+#[tokio::test]
+#[serial]
+async fn bulk_operations_refuse_missing_or_empty_filter() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    let conn_id = setup_bulk_tasks(&f, &pool).await;
+
+    let data = serde_json::json!({ "status": "archived" });
+    let mut results = Vec::new();
+    for filter in [None, Some(""), Some("status")] {
+        results.push(
+            usecase::data::bulk_update_rows(
+                &f.permission_repo,
+                &f.cm,
+                &f.admin,
+                &conn_id,
+                "bulk_tasks",
+                filter,
+                &data,
+                false,
+            )
+            .await,
+        );
+        results.push(
+            usecase::data::bulk_delete_rows(
+                &f.permission_repo,
+                &f.cm,
+                &f.admin,
+                &conn_id,
+                "bulk_tasks",
+                filter,
+                false,
+            )
+            .await,
+        );
+    }
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bulk_tasks")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let archived = count_status(&pool, "archived").await;
+
+    sqlx::query("DROP TABLE bulk_tasks")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    for result in results {
+        assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+    }
+    assert_eq!(remaining, 5);
+    assert_eq!(archived, 0);
+}
+
+#[tokio::test]
+#[serial]
+async fn read_only_connection_refuses_writes_but_allows_reads() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE IF EXISTS ro_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE ro_items (id SERIAL PRIMARY KEY, name TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO ro_items (name) VALUES ('kept')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let conn_id =
+        f.cm.add_postgres(
+            "ro-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings {
+                read_only: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+        .id;
+
+    let data = serde_json::json!({ "name": "new" });
+    let created = usecase::data::create_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "ro_items",
+        &data,
+        true,
+        &[],
+        None,
+    )
+    .await;
+    let updated = usecase::data::update_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "ro_items",
+        "1",
+        &data,
+        None,
+    )
+    .await;
+    let deleted = usecase::data::delete_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "ro_items",
+        "1",
+        false,
+        None,
+    )
+    .await;
+    let bulk_deleted = usecase::data::bulk_delete_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "ro_items",
+        Some("id:eq:1"),
+        false,
+    )
+    .await;
+    let read = usecase::data::get_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "ro_items",
+        "1",
+    )
+    .await;
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM ro_items")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("DROP TABLE ro_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert!(matches!(created, Err(UsecaseError::Forbidden(_))));
+    assert!(matches!(updated, Err(UsecaseError::Forbidden(_))));
+    assert!(matches!(deleted, Err(UsecaseError::Forbidden(_))));
+    assert!(matches!(bulk_deleted, Err(UsecaseError::Forbidden(_))));
+    assert_eq!(read.unwrap()["name"], "kept");
+    assert_eq!(names, vec!["kept".to_string()]);
+}
+
+#[tokio::test]
+#[serial]
+async fn writes_to_system_tables_are_forbidden() {
+    let f = setup().await;
+    let (host, port, database, user, password) = common::parse_db_url();
+    let conn_id =
+        f.cm.add_postgres(
+            "system-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap()
+        .id;
+
+    // Unqualified names resolve through pg_catalog first, as the INSERT would
+    let inserted = usecase::data::create_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "pg_description",
+        &serde_json::json!({ "objoid": 1, "classoid": 1, "objsubid": 0, "description": "x" }),
+        false,
+        &[],
+        None,
+    )
+    .await;
+    let deleted = usecase::data::delete_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "pg_namespace",
+        "1",
+        false,
+        None,
+    )
+    .await;
+    let read = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &conn_id,
+        "pg_namespace",
+        &RowsQuery::default(),
+        None,
+    )
+    .await;
+
+    assert!(matches!(inserted, Err(UsecaseError::Forbidden(_))));
+    assert!(matches!(deleted, Err(UsecaseError::Forbidden(_))));
+    // Reads are not refused as system-table writes
+    assert!(!matches!(read, Err(UsecaseError::Forbidden(_))));
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_applies_connection_default_page_size() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE OR REPLACE VIEW paged_view AS SELECT generate_series(1, 30) AS id")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "paged-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings {
+                default_page_size: Some(7),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let defaulted = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "paged_view",
+        &RowsQuery::default(),
+        None,
+    )
+    .await;
+    let explicit = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "paged_view",
+        &RowsQuery {
+            per_page: Some(3),
+            ..Default::default()
+        },
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP VIEW paged_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let defaulted = defaulted.unwrap();
+    assert_eq!(defaulted.per_page, 7);
+    assert_eq!(defaulted.rows.len(), 7);
+    let explicit = explicit.unwrap();
+    assert_eq!(explicit.per_page, 3);
+    assert_eq!(explicit.rows.len(), 3);
+}
+
+#[tokio::test]
+#[serial]
+async fn list_rows_applies_connection_default_sort_order() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE OR REPLACE VIEW sorted_view AS SELECT generate_series(1, 5) AS id")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "newest-first-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings {
+                default_sort_order: Some("desc".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let by_id = |sort_order: Option<&str>| RowsQuery {
+        sort_by: Some("id".to_string()),
+        sort_order: sort_order.map(str::to_string),
+        ..Default::default()
+    };
+    let defaulted = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "sorted_view",
+        &by_id(None),
+        None,
+    )
+    .await;
+    let explicit = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "sorted_view",
+        &by_id(Some("asc")),
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP VIEW sorted_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ids = |rows: Vec<serde_json::Value>| -> Vec<i64> {
+        rows.iter().map(|r| r["id"].as_i64().unwrap()).collect()
+    };
+    assert_eq!(ids(defaulted.unwrap().rows), vec![5, 4, 3, 2, 1]);
+    assert_eq!(ids(explicit.unwrap().rows), vec![1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+#[serial]
+async fn table_capabilities_report_primary_key_and_view() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    for sql in [
+        "DROP VIEW IF EXISTS cap_view",
+        "DROP TABLE IF EXISTS cap_keyed, cap_keyless",
+        "CREATE TABLE cap_keyed (id SERIAL PRIMARY KEY, note TEXT)",
+        "CREATE TABLE cap_keyless (note TEXT)",
+        "CREATE VIEW cap_view AS SELECT id, note FROM cap_keyed",
+    ] {
+        sqlx::query(sql).execute(&pool).await.unwrap();
+    }
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "cap-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let mut results = Vec::new();
+    for table in ["cap_keyed", "cap_keyless", "cap_view"] {
+        results.push(
+            usecase::data::get_table_capabilities(
+                &f.permission_repo,
+                &f.cm,
+                &f.admin,
+                &info.id,
+                table,
+            )
+            .await,
+        );
+    }
+
+    sqlx::query("DROP VIEW cap_view")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DROP TABLE cap_keyed, cap_keyless")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let keyed = results.remove(0).unwrap();
+    assert!(keyed.can_read && keyed.can_write);
+    assert!(keyed.has_primary_key && !keyed.is_view);
+
+    let keyless = results.remove(0).unwrap();
+    assert!(keyless.can_read);
+    assert!(!keyless.has_primary_key);
+    assert!(!keyless.can_write);
+
+    let view = results.remove(0).unwrap();
+    assert!(view.is_view);
+    assert!(!view.can_write);
+}
+
+#[tokio::test]
+#[serial]
+async fn hidden_tables_are_unlisted_and_not_found() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE hidden_secrets (id SERIAL PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE visible_items (id SERIAL PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "hiding-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings {
+                hidden_tables: vec!["hidden_secrets".to_string()],
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let tables = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+    )
+    .await;
+    let rows = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "hidden_secrets",
+        &RowsQuery::default(),
+        None,
+    )
+    .await;
+    let visible = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "visible_items",
+        &RowsQuery::default(),
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP TABLE hidden_secrets, visible_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let names: Vec<String> = tables.unwrap().into_iter().map(|t| t.table_name).collect();
+    assert!(names.contains(&"visible_items".to_string()));
+    assert!(!names.contains(&"hidden_secrets".to_string()));
+    assert!(matches!(rows, Err(UsecaseError::NotFound(_))));
+    assert!(visible.is_ok());
+}
+
+#[tokio::test]
+#[serial]
+async fn default_schema_lists_and_reads_that_schema() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("DROP SCHEMA IF EXISTS analytics CASCADE")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE SCHEMA analytics")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE analytics.daily_events (id SERIAL PRIMARY KEY, name TEXT)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO analytics.daily_events (name) VALUES ('signup')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "analytics-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings {
+                default_schema: Some("analytics".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let tables = usecase::data::list_tables(
+        &f.permission_repo,
+        &f.metadata_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+    )
+    .await;
+    let rows = usecase::data::list_rows(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "daily_events",
+        &RowsQuery::default(),
+        None,
+    )
+    .await;
+
+    sqlx::query("DROP SCHEMA analytics CASCADE")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let tables = tables.unwrap();
+    assert!(!tables.is_empty());
+    assert!(tables.iter().all(|t| t.table_schema == "analytics"));
+    assert!(tables.iter().any(|t| t.table_name == "daily_events"));
+    assert_eq!(rows.unwrap().rows[0]["name"], "signup");
+}
+
+#[tokio::test]
+#[serial]
+async fn rolled_back_transaction_persists_nothing() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE tx_items (id SERIAL PRIMARY KEY, label TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "tx-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let token = usecase::data::begin_transaction(&f.permission_repo, &f.cm, &f.admin, &info.id)
+        .await
+        .unwrap();
+    let data = serde_json::json!({ "label": "uncommitted" });
+    let inserted = usecase::data::create_row(
+        &f.permission_repo,
+        &f.cm,
+        &f.admin,
+        &info.id,
+        "tx_items",
+        &data,
+        true,
+        &[],
+        Some(&token),
+    )
+    .await;
+    // Outside the transaction the insert is not visible yet
+    let (outside,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tx_items")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let rolled_back = usecase::data::rollback_transaction(&f.cm, &f.admin, &info.id, &token).await;
+    let (after,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tx_items")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let reused = usecase::data::commit_transaction(&f.cm, &f.admin, &info.id, &token).await;
+
+    sqlx::query("DROP TABLE tx_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(inserted.unwrap()["label"], "uncommitted");
+    assert_eq!(outside, 0);
+    assert!(rolled_back.is_ok());
+    assert_eq!(after, 0);
+    assert!(matches!(reused, Err(UsecaseError::NotFound(_))));
+}
+
+#[tokio::test]
+#[serial]
+async fn transactions_need_write_access_and_are_capped_per_user() {
+    let f = setup().await;
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "tx-cap-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+    f.permission_repo
+        .grant_user_connection_permission(&info.id, &f.reader.id, "read", true)
+        .await
+        .unwrap();
+
+    let reader_result =
+        usecase::data::begin_transaction(&f.permission_repo, &f.cm, &f.reader, &info.id).await;
+    let begin = || usecase::data::begin_transaction(&f.permission_repo, &f.cm, &f.admin, &info.id);
+    let mut tokens = Vec::new();
+    for _ in 0..DEFAULT_MAX_OPEN_TRANSACTIONS {
+        tokens.push(begin().await.unwrap());
+    }
+    let over_cap = begin().await;
+    usecase::data::rollback_transaction(&f.cm, &f.admin, &info.id, &tokens[0])
+        .await
+        .unwrap();
+    let after_rollback = begin().await;
+
+    f.cm.shutdown().await;
+
+    assert!(matches!(reader_result, Err(UsecaseError::Forbidden(_))));
+    assert!(matches!(over_cap, Err(UsecaseError::Conflict(_))));
+    assert!(after_rollback.is_ok());
+}
+
+#[tokio::test]
+#[serial]
+async fn snapshot_paging_ignores_rows_inserted_mid_paging() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE snap_items (id INT PRIMARY KEY)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO snap_items SELECT generate_series(1, 4)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (host, port, database, user, password) = common::parse_db_url();
+    let info =
+        f.cm.add_postgres(
+            "snapshot-conn".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.admin.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    let token = usecase::data::begin_snapshot(&f.permission_repo, &f.cm, &f.admin, &info.id)
+        .await
+        .unwrap();
+    let page = |page: u32, transaction: Option<Uuid>| {
+        let query = RowsQuery {
+            page: Some(page),
+            per_page: Some(2),
+            sort_by: Some("id".to_string()),
+            ..Default::default()
+        };
+        let cm = &f.cm;
+        let permission_repo = &f.permission_repo;
+        let admin = &f.admin;
+        let conn_id = info.id;
+        async move {
+            usecase::data::list_rows(
+                permission_repo,
+                cm,
+                admin,
+                &conn_id,
+                "snap_items",
+                &query,
+                transaction.as_ref(),
+            )
+            .await
+        }
+    };
+    let first = page(1, Some(token)).await.unwrap();
+    // A row sorting before the second page would shift it under OFFSET paging
+    sqlx::query("INSERT INTO snap_items VALUES (0)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let second = page(2, Some(token)).await.unwrap();
+    let live = page(2, None).await.unwrap();
+    usecase::data::rollback_transaction(&f.cm, &f.admin, &info.id, &token)
+        .await
+        .unwrap();
+
+    sqlx::query("DROP TABLE snap_items")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let ids = |rows: &[serde_json::Value]| -> Vec<i64> {
+        rows.iter().map(|r| r["id"].as_i64().unwrap()).collect()
+    };
+    assert_eq!(ids(&first.rows), vec![1, 2]);
+    assert_eq!(ids(&second.rows), vec![3, 4]);
+    assert_eq!(second.total_count, 4);
+    assert_eq!(ids(&live.rows), vec![2, 3]);
+    assert_eq!(live.total_count, 5);
+}
@@ -7,6 +7,7 @@ use dbworks_backend::infrastructure::database::group_repo::PgGroupRepository;
 use dbworks_backend::infrastructure::database::organization_member_repo::PgOrganizationMemberRepository;
 use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
 use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::presentation::state::ConnectionManager;
 use dbworks_backend::usecase::{self, UsecaseError};
 use serial_test::serial;
 use std::sync::Arc;
@@ -146,6 +147,57 @@ async fn add_and_list_group_members_via_usecase() {
     assert_eq!(members[0].id, f.member.id);
 }
 
+#[tokio::test]
+#[serial]
+async fn list_user_groups_returns_every_membership() {
+    let f = setup().await;
+
+    let alpha = f.group_repo.create(&f.org_id, "Alpha", None).await.unwrap();
+    let beta = f.group_repo.create(&f.org_id, "Beta", None).await.unwrap();
+    f.group_repo.create(&f.org_id, "Gamma", None).await.unwrap();
+    f.group_repo
+        .add_member(&alpha.id, &f.member.id)
+        .await
+        .unwrap();
+    f.group_repo
+        .add_member(&beta.id, &f.member.id)
+        .await
+        .unwrap();
+
+    let groups = usecase::group::list_user_groups(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        &f.member.id,
+    )
+    .await
+    .unwrap();
+    let names: Vec<&str> = groups.iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(names, vec!["Alpha", "Beta"]);
+
+    // A plain member may list their own groups but not someone else's
+    let own = usecase::group::list_user_groups(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.member,
+        &f.org_id,
+        &f.member.id,
+    )
+    .await
+    .unwrap();
+    assert_eq!(own.len(), 2);
+    let other = usecase::group::list_user_groups(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.member,
+        &f.org_id,
+        &f.admin.id,
+    )
+    .await;
+    assert!(matches!(other.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
 #[tokio::test]
 #[serial]
 async fn add_group_member_as_member_forbidden() {
@@ -245,3 +297,152 @@ async fn remove_group_member_as_member_forbidden() {
 
     assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
 }
+
+#[tokio::test]
+#[serial]
+async fn update_and_delete_group_as_org_owner() {
+    let f = setup().await;
+    let group = usecase::group::create_group(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        "Engineering",
+        None,
+    )
+    .await
+    .unwrap();
+
+    let result = usecase::group::update_group(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.member,
+        &group.id,
+        Some("Platform"),
+        None,
+    )
+    .await;
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+    let result =
+        usecase::group::delete_group(&f.group_repo, &*f.org_member_repo, &f.member, &group.id)
+            .await;
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+
+    let renamed = usecase::group::update_group(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &group.id,
+        Some("Platform"),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(renamed.name, "Platform");
+    usecase::group::delete_group(&f.group_repo, &*f.org_member_repo, &f.admin, &group.id)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn set_group_parent_rejects_cycle() {
+    let f = setup().await;
+    let create = |name: &'static str| {
+        usecase::group::create_group(
+            &f.group_repo,
+            &*f.org_member_repo,
+            &f.admin,
+            &f.org_id,
+            name,
+            None,
+        )
+    };
+    let parent = create("Engineering").await.unwrap();
+    let child = create("Backend").await.unwrap();
+
+    usecase::group::set_group_parent(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &child.id,
+        Some(&parent.id),
+    )
+    .await
+    .unwrap();
+
+    let result = usecase::group::set_group_parent(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &parent.id,
+        Some(&child.id),
+    )
+    .await;
+    assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+
+    let result = usecase::group::set_group_parent(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &parent.id,
+        Some(&parent.id),
+    )
+    .await;
+    assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn org_admin_manages_groups_but_not_platform() {
+    let f = setup().await;
+    let pool = sqlx::PgPool::connect(&common::test_database_url())
+        .await
+        .unwrap();
+    let org_admin = PgUserRepository::new(pool)
+        .create("Org Admin", "org-admin@test.com", "org_admin")
+        .await
+        .unwrap();
+    f.org_member_repo
+        .add_member(&f.org_id, &org_admin.id, "member")
+        .await
+        .unwrap();
+
+    let group = usecase::group::create_group(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &org_admin,
+        &f.org_id,
+        "Engineering",
+        None,
+    )
+    .await
+    .unwrap();
+    usecase::group::add_group_member(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &org_admin,
+        &group.id,
+        &f.member.id,
+    )
+    .await
+    .unwrap();
+    let renamed = usecase::group::update_group(
+        &f.group_repo,
+        &*f.org_member_repo,
+        &org_admin,
+        &group.id,
+        Some("Platform"),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(renamed.name, "Platform");
+    usecase::group::delete_group(&f.group_repo, &*f.org_member_repo, &org_admin, &group.id)
+        .await
+        .unwrap();
+
+    let cm = ConnectionManager::new(None, None);
+    let result = usecase::connection::reload_connections(&cm, &org_admin).await;
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+}
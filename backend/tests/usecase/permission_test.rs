@@ -1,8 +1,11 @@
 use crate::common;
+use async_trait::async_trait;
 use dbworks_backend::domain::connection::ConnectionInfo;
+use dbworks_backend::domain::notification::Notifier;
+use dbworks_backend::domain::permission::TemplateTableGrant;
 use dbworks_backend::domain::repository::{
     ConnectionRepository, GroupRepository, OrganizationMemberRepository, OrganizationRepository,
-    UserRepository,
+    PermissionRepository, UserRepository,
 };
 use dbworks_backend::domain::user::AppUser;
 use dbworks_backend::infrastructure::crypto::Encryptor;
@@ -11,10 +14,12 @@ use dbworks_backend::infrastructure::database::group_repo::PgGroupRepository;
 use dbworks_backend::infrastructure::database::organization_member_repo::PgOrganizationMemberRepository;
 use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
 use dbworks_backend::infrastructure::database::permission_repo::PgPermissionRepository;
+use dbworks_backend::infrastructure::database::permission_template_repo::PgPermissionTemplateRepository;
 use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::presentation::request::PermissionPageQuery;
 use dbworks_backend::usecase::{self, UsecaseError};
 use serial_test::serial;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 #[allow(dead_code)]
@@ -25,9 +30,11 @@ struct TestFixture {
     conn_id: Uuid,
     org_id: Uuid,
     permission_repo: PgPermissionRepository,
+    template_repo: PgPermissionTemplateRepository,
     group_repo: PgGroupRepository,
     org_member_repo: Arc<PgOrganizationMemberRepository>,
     conn_repo: Arc<PgConnectionRepository>,
+    user_repo: PgUserRepository,
 }
 
 async fn setup() -> TestFixture {
@@ -36,12 +43,13 @@ async fn setup() -> TestFixture {
     let user_repo = PgUserRepository::new(pool.clone());
     let group_repo = PgGroupRepository::new(pool.clone());
     let permission_repo = PgPermissionRepository::new(pool.clone());
+    let template_repo = PgPermissionTemplateRepository::new(pool.clone());
     let org_member_repo = Arc::new(PgOrganizationMemberRepository::new(pool.clone()));
 
     unsafe {
         std::env::set_var(
             "ENCRYPTION_KEY",
-            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &[42u8; 32]),
+            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, [42u8; 32]),
         );
     }
     let enc = Encryptor::from_env().unwrap();
@@ -85,6 +93,19 @@ async fn setup() -> TestFixture {
         password: "pass".to_string(),
         organization_id: Some(org.id),
         owner_user_id: Some(member.id),
+        tags: vec![],
+        statement_timeout_ms: None,
+        pool_idle_timeout_ms: None,
+        pool_max_lifetime_ms: None,
+        read_only: false,
+        default_page_size: None,
+        default_sort_order: None,
+        search_path: None,
+        default_schema: None,
+        role: None,
+        hidden_tables: vec![],
+        replica_hosts: vec![],
+        persisted: false,
     };
     let saved = conn_repo
         .save(Some(&org.id), Some(&member.id), &info)
@@ -98,9 +119,11 @@ async fn setup() -> TestFixture {
         conn_id: saved.id,
         org_id: org.id,
         permission_repo,
+        template_repo,
         group_repo,
         org_member_repo,
         conn_repo,
+        user_repo,
     }
 }
 
@@ -117,6 +140,8 @@ async fn grant_user_connection_permission_as_org_owner() {
         &f.permission_repo,
         &*f.org_member_repo,
         &*f.conn_repo,
+        &f.user_repo,
+        None,
         &f.admin,
         &f.conn_id,
         &f.other.id,
@@ -131,6 +156,86 @@ async fn grant_user_connection_permission_as_org_owner() {
     assert_eq!(perm.user_id, f.other.id);
 }
 
+/// Records every message instead of sending it.
+#[derive(Default)]
+struct RecordingNotifier {
+    sent: Mutex<Vec<(String, String, String)>>,
+}
+
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> anyhow::Result<()> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((to.to_string(), subject.to_string(), body.to_string()));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn grant_user_connection_permission_notifies_grantee() {
+    let f = setup().await;
+    let recorder = Arc::new(RecordingNotifier::default());
+    let notifier: Arc<dyn Notifier> = recorder.clone();
+
+    usecase::permission::grant_user_connection_permission(
+        &f.permission_repo,
+        &*f.org_member_repo,
+        &*f.conn_repo,
+        &f.user_repo,
+        Some(&notifier),
+        &f.admin,
+        &f.conn_id,
+        &f.other.id,
+        "read",
+        true,
+    )
+    .await
+    .unwrap();
+
+    // Sending happens on a background task
+    for _ in 0..50 {
+        if !recorder.sent.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let sent = recorder.sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    let (to, subject, _) = &sent[0];
+    assert_eq!(to, "other@test.com");
+    assert!(subject.contains("read"));
+    assert!(subject.contains("test-conn"));
+}
+
+#[tokio::test]
+#[serial]
+async fn granting_no_access_sends_no_notification() {
+    let f = setup().await;
+    let recorder = Arc::new(RecordingNotifier::default());
+    let notifier: Arc<dyn Notifier> = recorder.clone();
+
+    usecase::permission::grant_user_connection_permission(
+        &f.permission_repo,
+        &*f.org_member_repo,
+        &*f.conn_repo,
+        &f.user_repo,
+        Some(&notifier),
+        &f.admin,
+        &f.conn_id,
+        &f.other.id,
+        "none",
+        true,
+    )
+    .await
+    .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(recorder.sent.lock().unwrap().is_empty());
+}
+
 #[tokio::test]
 #[serial]
 async fn grant_user_connection_permission_as_member_forbidden() {
@@ -140,6 +245,8 @@ async fn grant_user_connection_permission_as_member_forbidden() {
         &f.permission_repo,
         &*f.org_member_repo,
         &*f.conn_repo,
+        &f.user_repo,
+        None,
         &f.member,
         &f.conn_id,
         &f.other.id,
@@ -160,6 +267,8 @@ async fn revoke_user_connection_permission_found() {
         &f.permission_repo,
         &*f.org_member_repo,
         &*f.conn_repo,
+        &f.user_repo,
+        None,
         &f.admin,
         &f.conn_id,
         &f.other.id,
@@ -180,10 +289,14 @@ async fn revoke_user_connection_permission_found() {
     .await
     .unwrap();
 
-    let list =
-        usecase::permission::list_user_connection_permissions(&f.permission_repo, &f.conn_id)
-            .await
-            .unwrap();
+    let list = usecase::permission::list_user_connection_permissions(
+        &f.permission_repo,
+        &f.conn_id,
+        &PermissionPageQuery::default(),
+    )
+    .await
+    .unwrap()
+    .items;
     assert!(list.is_empty());
 }
 
@@ -214,6 +327,8 @@ async fn list_user_connection_permissions_via_usecase() {
         &f.permission_repo,
         &*f.org_member_repo,
         &*f.conn_repo,
+        &f.user_repo,
+        None,
         &f.admin,
         &f.conn_id,
         &f.other.id,
@@ -223,10 +338,14 @@ async fn list_user_connection_permissions_via_usecase() {
     .await
     .unwrap();
 
-    let list =
-        usecase::permission::list_user_connection_permissions(&f.permission_repo, &f.conn_id)
-            .await
-            .unwrap();
+    let list = usecase::permission::list_user_connection_permissions(
+        &f.permission_repo,
+        &f.conn_id,
+        &PermissionPageQuery::default(),
+    )
+    .await
+    .unwrap()
+    .items;
 
     assert_eq!(list.len(), 1);
     assert_eq!(list[0].permission, "write");
@@ -245,6 +364,8 @@ async fn grant_user_table_permission_via_usecase() {
         &f.permission_repo,
         &*f.org_member_repo,
         &*f.conn_repo,
+        &f.user_repo,
+        None,
         &f.admin,
         &f.conn_id,
         &f.other.id,
@@ -373,3 +494,200 @@ async fn revoke_group_table_permission_not_found() {
 
     assert!(matches!(result.unwrap_err(), UsecaseError::NotFound(_)));
 }
+
+#[tokio::test]
+#[serial]
+async fn copy_user_permissions_requires_super_admin() {
+    let f = setup().await;
+
+    let result = usecase::permission::copy_user_permissions(
+        &f.permission_repo,
+        &f.admin,
+        &f.conn_id,
+        &f.member.id,
+        &f.other.id,
+    )
+    .await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn revoke_all_user_permissions_requires_super_admin() {
+    let f = setup().await;
+
+    let result = usecase::permission::revoke_all_user_permissions(
+        &f.permission_repo,
+        &f.user_repo,
+        &f.admin,
+        &f.member.id,
+    )
+    .await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn deletion_impact_counts_seeded_grants() {
+    let f = setup().await;
+    let root = f
+        .user_repo
+        .create("Root", "root@test.com", "super_admin")
+        .await
+        .unwrap();
+    let group = f.group_repo.create(&f.org_id, "Team", None).await.unwrap();
+
+    let repo = &f.permission_repo;
+    for user in [&f.member, &f.other] {
+        repo.grant_user_connection_permission(&f.conn_id, &user.id, "read", false)
+            .await
+            .unwrap();
+        repo.grant_user_table_permission(&f.conn_id, &user.id, "orders", "write")
+            .await
+            .unwrap();
+    }
+    repo.grant_user_table_permission(&f.conn_id, &f.other.id, "customers", "read")
+        .await
+        .unwrap();
+    repo.grant_group_connection_permission(&f.conn_id, &group.id, "read", true)
+        .await
+        .unwrap();
+    repo.grant_group_table_permission(&f.conn_id, &group.id, "orders", "read")
+        .await
+        .unwrap();
+
+    let impact = usecase::connection::get_deletion_impact(&*f.conn_repo, repo, &root, &f.conn_id)
+        .await
+        .unwrap();
+    assert_eq!(impact.user_connection_permissions, 2);
+    assert_eq!(impact.user_table_permissions, 3);
+    assert_eq!(impact.group_connection_permissions, 1);
+    assert_eq!(impact.group_table_permissions, 1);
+
+    let forbidden =
+        usecase::connection::get_deletion_impact(&*f.conn_repo, repo, &f.admin, &f.conn_id).await;
+    assert!(matches!(forbidden.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
+// ============================================================
+// Permission Templates
+// ============================================================
+
+fn analyst_tables() -> Vec<TemplateTableGrant> {
+    ["orders", "customers"]
+        .iter()
+        .map(|t| TemplateTableGrant {
+            table_name: t.to_string(),
+            permission: "read".to_string(),
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_template_grants_table_permissions_idempotently() {
+    let f = setup().await;
+
+    let template = usecase::permission_template::create_template(
+        &f.template_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        "analyst",
+        None,
+        &analyst_tables(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(template.tables.len(), 2);
+
+    for _ in 0..2 {
+        let applied = usecase::permission_template::apply_template(
+            &f.template_repo,
+            &*f.org_member_repo,
+            &*f.conn_repo,
+            &f.admin,
+            &f.conn_id,
+            &template.id,
+            Some(&f.other.id),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(applied, 2);
+    }
+
+    let perms = usecase::permission::list_user_table_permissions(
+        &f.permission_repo,
+        &f.conn_id,
+        &f.other.id,
+        &PermissionPageQuery::default(),
+    )
+    .await
+    .unwrap();
+    let mut grants: Vec<(String, String)> = perms
+        .items
+        .into_iter()
+        .map(|p| (p.table_name, p.permission))
+        .collect();
+    grants.sort();
+    assert_eq!(
+        grants,
+        vec![
+            ("customers".to_string(), "read".to_string()),
+            ("orders".to_string(), "read".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn create_template_as_member_forbidden() {
+    let f = setup().await;
+
+    let result = usecase::permission_template::create_template(
+        &f.template_repo,
+        &*f.org_member_repo,
+        &f.member,
+        &f.org_id,
+        "analyst",
+        None,
+        &analyst_tables(),
+    )
+    .await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn apply_template_requires_single_target() {
+    let f = setup().await;
+    let template = usecase::permission_template::create_template(
+        &f.template_repo,
+        &*f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        "analyst",
+        None,
+        &analyst_tables(),
+    )
+    .await
+    .unwrap();
+
+    let result = usecase::permission_template::apply_template(
+        &f.template_repo,
+        &*f.org_member_repo,
+        &*f.conn_repo,
+        &f.admin,
+        &f.conn_id,
+        &template.id,
+        None,
+        None,
+    )
+    .await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+}
@@ -1,4 +1,5 @@
 use crate::common;
+use dbworks_backend::domain::connection::ConnectionSettings;
 use dbworks_backend::domain::repository::{
     OrganizationMemberRepository, OrganizationRepository, UserRepository,
 };
@@ -6,6 +7,7 @@ use dbworks_backend::domain::user::AppUser;
 use dbworks_backend::infrastructure::database::organization_member_repo::PgOrganizationMemberRepository;
 use dbworks_backend::infrastructure::database::organization_repo::PgOrganizationRepository;
 use dbworks_backend::infrastructure::database::user_repo::PgUserRepository;
+use dbworks_backend::presentation::state::ConnectionManager;
 use dbworks_backend::usecase::{self, UsecaseError};
 use serial_test::serial;
 use uuid::Uuid;
@@ -16,13 +18,15 @@ struct TestFixture {
     member: AppUser,
     user_repo: PgUserRepository,
     org_member_repo: PgOrganizationMemberRepository,
+    org_repo: PgOrganizationRepository,
+    cm: ConnectionManager,
 }
 
 async fn setup() -> TestFixture {
     let pool = common::setup_test_db().await;
     let org_repo = PgOrganizationRepository::new(pool.clone());
     let user_repo = PgUserRepository::new(pool.clone());
-    let org_member_repo = PgOrganizationMemberRepository::new(pool);
+    let org_member_repo = PgOrganizationMemberRepository::new(pool.clone());
 
     let org = org_repo.create("Test Org").await.unwrap();
     let admin = user_repo
@@ -50,6 +54,8 @@ async fn setup() -> TestFixture {
         member,
         user_repo,
         org_member_repo,
+        org_repo,
+        cm: ConnectionManager::new(None, None),
     }
 }
 
@@ -106,3 +112,207 @@ async fn list_users_via_usecase() {
 
     assert_eq!(users.len(), 2);
 }
+
+#[tokio::test]
+#[serial]
+async fn delete_user_as_super_admin() {
+    let f = setup().await;
+
+    usecase::user::delete_user(
+        &f.cm,
+        &f.user_repo,
+        &f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        &f.member.id,
+    )
+    .await
+    .unwrap();
+
+    let users = usecase::user::list_users(&f.user_repo, &f.org_id)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 1);
+}
+
+#[tokio::test]
+#[serial]
+async fn delete_last_super_admin_refused() {
+    let f = setup().await;
+
+    let result = usecase::user::delete_user(
+        &f.cm,
+        &f.user_repo,
+        &f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        &f.admin.id,
+    )
+    .await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::BadRequest(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn delete_user_as_member_forbidden() {
+    let f = setup().await;
+
+    let result = usecase::user::delete_user(
+        &f.cm,
+        &f.user_repo,
+        &f.org_member_repo,
+        &f.member,
+        &f.org_id,
+        &f.admin.id,
+    )
+    .await;
+
+    assert!(matches!(result.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn org_admin_cannot_create_higher_role() {
+    let f = setup().await;
+    let org_admin = f
+        .user_repo
+        .create("Org Admin", "org-admin@test.com", "org_admin")
+        .await
+        .unwrap();
+    f.org_member_repo
+        .add_member(&f.org_id, &org_admin.id, "member")
+        .await
+        .unwrap();
+
+    let create = |email: &'static str, role: &'static str| {
+        usecase::user::create_user(
+            &f.user_repo,
+            &f.org_member_repo,
+            &org_admin,
+            &f.org_id,
+            "Alice",
+            email,
+            role,
+        )
+    };
+    let member = create("alice@test.com", "member").await;
+    let escalated = create("mallory@test.com", "super_admin").await;
+
+    assert_eq!(member.unwrap().role, "member");
+    assert!(matches!(escalated.unwrap_err(), UsecaseError::Forbidden(_)));
+}
+
+#[tokio::test]
+#[serial]
+async fn org_admin_delete_only_removes_from_their_org() {
+    let f = setup().await;
+    let other_org = f.org_repo.create("Other Org").await.unwrap();
+    f.org_member_repo
+        .add_member(&other_org.id, &f.member.id, "member")
+        .await
+        .unwrap();
+    let org_admin = f
+        .user_repo
+        .create("Org Admin", "org-admin@test.com", "org_admin")
+        .await
+        .unwrap();
+    f.org_member_repo
+        .add_member(&f.org_id, &org_admin.id, "member")
+        .await
+        .unwrap();
+
+    usecase::user::delete_user(
+        &f.cm,
+        &f.user_repo,
+        &f.org_member_repo,
+        &org_admin,
+        &f.org_id,
+        &f.member.id,
+    )
+    .await
+    .unwrap();
+
+    let remaining = f.user_repo.list_by_org(&f.org_id).await.unwrap();
+    assert!(remaining.iter().all(|u| u.id != f.member.id));
+    assert!(f.user_repo.get(&f.member.id).await.unwrap().is_some());
+    let orgs = f.org_member_repo.get_user_orgs(&f.member.id).await.unwrap();
+    assert_eq!(orgs.len(), 1);
+    assert_eq!(orgs[0].organization_id, other_org.id);
+}
+
+#[tokio::test]
+#[serial]
+async fn super_admin_delete_keeps_account_in_other_orgs() {
+    let f = setup().await;
+    // The member is the only super_admin of another organization
+    let other_org = f.org_repo.create("Other Org").await.unwrap();
+    let other_admin = f
+        .user_repo
+        .create("Other Admin", "other-admin@test.com", "super_admin")
+        .await
+        .unwrap();
+    f.org_member_repo
+        .add_member(&f.org_id, &other_admin.id, "member")
+        .await
+        .unwrap();
+    f.org_member_repo
+        .add_member(&other_org.id, &other_admin.id, "owner")
+        .await
+        .unwrap();
+
+    usecase::user::delete_user(
+        &f.cm,
+        &f.user_repo,
+        &f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        &other_admin.id,
+    )
+    .await
+    .unwrap();
+
+    assert!(f.user_repo.get(&other_admin.id).await.unwrap().is_some());
+    let orgs = f
+        .org_member_repo
+        .get_user_orgs(&other_admin.id)
+        .await
+        .unwrap();
+    assert_eq!(orgs.len(), 1);
+    assert_eq!(orgs[0].organization_id, other_org.id);
+}
+
+#[tokio::test]
+#[serial]
+async fn deleting_account_unregisters_personal_connections() {
+    let f = setup().await;
+    let (host, port, database, user, password) = common::parse_db_url();
+    let conn =
+        f.cm.add_postgres(
+            "member-personal".into(),
+            host,
+            port,
+            database,
+            user,
+            password,
+            None,
+            Some(f.member.id),
+            ConnectionSettings::default(),
+        )
+        .await
+        .unwrap();
+
+    usecase::user::delete_user(
+        &f.cm,
+        &f.user_repo,
+        &f.org_member_repo,
+        &f.admin,
+        &f.org_id,
+        &f.member.id,
+    )
+    .await
+    .unwrap();
+
+    assert!(f.cm.get_info(&conn.id).await.is_none());
+    assert!(f.cm.list_personal(&f.member.id).await.is_empty());
+}
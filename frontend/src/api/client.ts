@@ -1,6 +1,8 @@
 import type {
   Connection,
+  ConnectionDeletionImpact,
   ConnectionRequest,
+  PreviewConnectionRequest,
   Organization,
   CreateOrganizationRequest,
   AppUser,
@@ -15,11 +17,19 @@ import type {
   GrantGroupConnectionPermissionRequest,
   GroupTablePermission,
   GrantGroupTablePermissionRequest,
+  Paginated,
+  PageParams,
+  ApiToken,
+  CreatedApiToken,
+  CreateApiTokenRequest,
   TableInfo,
   TableSchema,
+  TableCapabilities,
+  InsertDefault,
   RowsResponse,
   RowData,
   ListRowsParams,
+  Transaction,
 } from '../types';
 
 const API_BASE = 'http://localhost:3001/api';
@@ -48,12 +58,21 @@ async function request<T>(path: string, options: RequestInit = {}): Promise<T> {
   });
   if (!res.ok) {
     const err = await res.json().catch(() => ({ error: res.statusText }));
-    throw new Error(err.error || 'Request failed');
+    const message = err.error || 'Request failed';
+    throw new Error(err.reason ? `${message}: ${err.reason}` : message);
   }
   if (res.status === 204) return null as T;
   return res.json() as Promise<T>;
 }
 
+function pageQuery(params: PageParams): string {
+  const qs = new URLSearchParams();
+  if (params.page) qs.set('page', String(params.page));
+  if (params.per_page) qs.set('per_page', String(params.per_page));
+  const query = qs.toString();
+  return query ? `?${query}` : '';
+}
+
 export const api = {
   // Auth
   getMe: (): Promise<AppUser> =>
@@ -76,60 +95,84 @@ export const api = {
     request<Group>(`/organizations/${orgId}/groups`, { method: 'POST', body: JSON.stringify(data) }),
   listGroups: (orgId: string): Promise<Group[]> =>
     request<Group[]>(`/organizations/${orgId}/groups`),
+  listUserGroups: (orgId: string, userId: string): Promise<Group[]> =>
+    request<Group[]>(`/organizations/${orgId}/users/${userId}/groups`),
   addGroupMember: (groupId: string, userId: string): Promise<null> =>
     request<null>(`/groups/${groupId}/members`, { method: 'POST', body: JSON.stringify({ user_id: userId }) }),
   removeGroupMember: (groupId: string, userId: string): Promise<null> =>
     request<null>(`/groups/${groupId}/members/${userId}`, { method: 'DELETE' }),
+  addGroupMembersBulk: (groupId: string, userIds: string[]): Promise<{ added: number }> =>
+    request<{ added: number }>(`/groups/${groupId}/members/bulk`, { method: 'POST', body: JSON.stringify({ user_ids: userIds }) }),
+  removeGroupMembersBulk: (groupId: string, userIds: string[]): Promise<{ removed: number }> =>
+    request<{ removed: number }>(`/groups/${groupId}/members/bulk`, { method: 'DELETE', body: JSON.stringify({ user_ids: userIds }) }),
   listGroupMembers: (groupId: string): Promise<AppUser[]> =>
     request<AppUser[]>(`/groups/${groupId}/members`),
 
   // Connections
   createConnection: (data: ConnectionRequest): Promise<Connection> =>
     request<Connection>('/connections', { method: 'POST', body: JSON.stringify(data) }),
+  previewConnectionTables: (data: PreviewConnectionRequest): Promise<TableInfo[]> =>
+    request<TableInfo[]>('/connections/test/tables', { method: 'POST', body: JSON.stringify(data) }),
   listConnections: (scope?: string): Promise<Connection[]> => {
     const qs = scope ? `?scope=${encodeURIComponent(scope)}` : '';
     return request<Connection[]>(`/connections${qs}`);
   },
   deleteConnection: (id: string): Promise<null> =>
     request<null>(`/connections/${id}`, { method: 'DELETE' }),
+  getDeletionImpact: (id: string): Promise<ConnectionDeletionImpact> =>
+    request<ConnectionDeletionImpact>(`/connections/${id}/deletion-impact`),
 
   // User Connection Permissions
   grantUserConnPermission: (connId: string, data: GrantUserConnectionPermissionRequest): Promise<UserConnectionPermission> =>
     request<UserConnectionPermission>(`/connections/${connId}/user-permissions`, { method: 'POST', body: JSON.stringify(data) }),
   revokeUserConnPermission: (connId: string, userId: string): Promise<null> =>
     request<null>(`/connections/${connId}/user-permissions/${userId}`, { method: 'DELETE' }),
-  listUserConnPermissions: (connId: string): Promise<UserConnectionPermission[]> =>
-    request<UserConnectionPermission[]>(`/connections/${connId}/user-permissions`),
+  listUserConnPermissions: (connId: string, params: PageParams = {}): Promise<Paginated<UserConnectionPermission>> =>
+    request<Paginated<UserConnectionPermission>>(`/connections/${connId}/user-permissions${pageQuery(params)}`),
+  revokeAllUserPermissions: (userId: string): Promise<{ revoked: number }> =>
+    request<{ revoked: number }>(`/users/${userId}/permissions`, { method: 'DELETE' }),
 
   // User Table Permissions
   grantUserTablePermission: (connId: string, userId: string, data: GrantUserTablePermissionRequest): Promise<UserTablePermission> =>
     request<UserTablePermission>(`/connections/${connId}/user-permissions/${userId}/tables`, { method: 'POST', body: JSON.stringify(data) }),
   revokeUserTablePermission: (connId: string, userId: string, table: string): Promise<null> =>
     request<null>(`/connections/${connId}/user-permissions/${userId}/tables/${table}`, { method: 'DELETE' }),
-  listUserTablePermissions: (connId: string, userId: string): Promise<UserTablePermission[]> =>
-    request<UserTablePermission[]>(`/connections/${connId}/user-permissions/${userId}/tables`),
+  listUserTablePermissions: (connId: string, userId: string, params: PageParams = {}): Promise<Paginated<UserTablePermission>> =>
+    request<Paginated<UserTablePermission>>(`/connections/${connId}/user-permissions/${userId}/tables${pageQuery(params)}`),
 
   // Group Connection Permissions
   grantGroupConnPermission: (connId: string, data: GrantGroupConnectionPermissionRequest): Promise<GroupConnectionPermission> =>
     request<GroupConnectionPermission>(`/connections/${connId}/group-permissions`, { method: 'POST', body: JSON.stringify(data) }),
   revokeGroupConnPermission: (connId: string, groupId: string): Promise<null> =>
     request<null>(`/connections/${connId}/group-permissions/${groupId}`, { method: 'DELETE' }),
-  listGroupConnPermissions: (connId: string): Promise<GroupConnectionPermission[]> =>
-    request<GroupConnectionPermission[]>(`/connections/${connId}/group-permissions`),
+  listGroupConnPermissions: (connId: string, params: PageParams = {}): Promise<Paginated<GroupConnectionPermission>> =>
+    request<Paginated<GroupConnectionPermission>>(`/connections/${connId}/group-permissions${pageQuery(params)}`),
 
   // Group Table Permissions
   grantGroupTablePermission: (connId: string, groupId: string, data: GrantGroupTablePermissionRequest): Promise<GroupTablePermission> =>
     request<GroupTablePermission>(`/connections/${connId}/group-permissions/${groupId}/tables`, { method: 'POST', body: JSON.stringify(data) }),
   revokeGroupTablePermission: (connId: string, groupId: string, table: string): Promise<null> =>
     request<null>(`/connections/${connId}/group-permissions/${groupId}/tables/${table}`, { method: 'DELETE' }),
-  listGroupTablePermissions: (connId: string, groupId: string): Promise<GroupTablePermission[]> =>
-    request<GroupTablePermission[]>(`/connections/${connId}/group-permissions/${groupId}/tables`),
+  listGroupTablePermissions: (connId: string, groupId: string, params: PageParams = {}): Promise<Paginated<GroupTablePermission>> =>
+    request<Paginated<GroupTablePermission>>(`/connections/${connId}/group-permissions/${groupId}/tables${pageQuery(params)}`),
+
+  // API Tokens
+  createApiToken: (connId: string, data: CreateApiTokenRequest): Promise<CreatedApiToken> =>
+    request<CreatedApiToken>(`/connections/${connId}/api-tokens`, { method: 'POST', body: JSON.stringify(data) }),
+  listApiTokens: (connId: string): Promise<ApiToken[]> =>
+    request<ApiToken[]>(`/connections/${connId}/api-tokens`),
+  revokeApiToken: (connId: string, tokenId: string): Promise<null> =>
+    request<null>(`/connections/${connId}/api-tokens/${tokenId}`, { method: 'DELETE' }),
 
   // Tables
   listTables: (connId: string): Promise<TableInfo[]> =>
     request<TableInfo[]>(`/connections/${connId}/tables`),
   getTableSchema: (connId: string, table: string): Promise<TableSchema> =>
     request<TableSchema>(`/connections/${connId}/tables/${table}/schema`),
+  getTableCapabilities: (connId: string, table: string): Promise<TableCapabilities> =>
+    request<TableCapabilities>(`/connections/${connId}/tables/${table}/capabilities`),
+  getInsertDefaults: (connId: string, table: string): Promise<InsertDefault[]> =>
+    request<InsertDefault[]>(`/connections/${connId}/tables/${table}/insert-defaults`),
 
   // Rows
   listRows: (connId: string, table: string, params: ListRowsParams = {}): Promise<RowsResponse> => {
@@ -143,8 +186,8 @@ export const api = {
   },
   getRow: (connId: string, table: string, pk: string): Promise<RowData> =>
     request<RowData>(`/connections/${connId}/tables/${table}/rows/${pk}`),
-  createRow: (connId: string, table: string, data: RowData): Promise<RowData> =>
-    request<RowData>(`/connections/${connId}/tables/${table}/rows`, {
+  createRow: (connId: string, table: string, data: RowData, returning?: string[]): Promise<RowData> =>
+    request<RowData>(`/connections/${connId}/tables/${table}/rows${returning?.length ? `?returning=${encodeURIComponent(returning.join(','))}` : ''}`, {
       method: 'POST',
       body: JSON.stringify(data),
     }),
@@ -157,4 +200,14 @@ export const api = {
     request<null>(`/connections/${connId}/tables/${table}/rows/${pk}`, {
       method: 'DELETE',
     }),
+
+  // Transactions
+  beginTransaction: (connId: string): Promise<Transaction> =>
+    request<Transaction>(`/connections/${connId}/transactions`, { method: 'POST' }),
+  beginSnapshot: (connId: string): Promise<Transaction> =>
+    request<Transaction>(`/connections/${connId}/snapshots`, { method: 'POST' }),
+  commitTransaction: (connId: string, txId: string): Promise<null> =>
+    request<null>(`/connections/${connId}/transactions/${txId}/commit`, { method: 'POST' }),
+  rollbackTransaction: (connId: string, txId: string): Promise<null> =>
+    request<null>(`/connections/${connId}/transactions/${txId}/rollback`, { method: 'POST' }),
 };
@@ -55,8 +55,22 @@ export interface ConnectionRequest {
   password: string;
   scope?: string;
   organization_id?: string;
+  read_only?: boolean;
+  default_page_size?: number;
+  default_sort_order?: "asc" | "desc";
+  search_path?: string;
+  default_schema?: string;
+  role?: string;
+  hidden_tables?: string[];
+  replica_hosts?: string[];
+  default_member_permission?: "none" | "read";
 }
 
+export type PreviewConnectionRequest = Pick<
+  ConnectionRequest,
+  'db_type' | 'host' | 'port' | 'database' | 'user' | 'password' | 'search_path' | 'role'
+>;
+
 export interface Connection {
   id: string;
   name: string;
@@ -66,9 +80,37 @@ export interface Connection {
   database: string;
   user: string;
   organization_id?: string;
+  read_only?: boolean;
+  default_page_size?: number | null;
+  default_sort_order?: "asc" | "desc" | null;
+  search_path?: string | null;
+  default_schema?: string | null;
+  role?: string | null;
+  hidden_tables?: string[];
+  replica_hosts?: string[];
+  persisted?: boolean;
+}
+
+export interface ConnectionDeletionImpact {
+  user_connection_permissions: number;
+  user_table_permissions: number;
+  group_connection_permissions: number;
+  group_table_permissions: number;
 }
 
 // ---- Permissions ----
+export interface Paginated<T> {
+  items: T[];
+  total_count: number;
+  page: number;
+  per_page: number;
+}
+
+export interface PageParams {
+  page?: number;
+  per_page?: number;
+}
+
 export interface UserConnectionPermission {
   id: string;
   user_id: string;
@@ -131,6 +173,8 @@ export interface GrantGroupTablePermissionRequest {
 export interface TableInfo {
   table_name: string;
   table_type: string;
+  display_name?: string;
+  description?: string;
 }
 
 export interface ColumnInfo {
@@ -140,6 +184,11 @@ export interface ColumnInfo {
   column_default: string | null;
   is_primary_key: boolean;
   max_length: number | null;
+  is_generated: boolean;
+  description?: string;
+  display_hint?: string;
+  enum_values?: string[];
+  element_type?: string;
 }
 
 export interface TableSchema {
@@ -148,6 +197,43 @@ export interface TableSchema {
   primary_key_columns: string[];
 }
 
+export interface InsertDefault {
+  column_name: string;
+  expression: string;
+  kind: 'literal' | 'timestamp' | 'sequence' | 'expression';
+  value: unknown;
+}
+
+export interface TableCapabilities {
+  can_read: boolean;
+  can_write: boolean;
+  has_primary_key: boolean;
+  is_view: boolean;
+}
+
+// ---- API tokens ----
+export interface ApiToken {
+  id: string;
+  connection_id: string;
+  name: string;
+  permission: 'read' | 'write';
+  created_by: string;
+  expires_at: string | null;
+  revoked_at: string | null;
+  created_at: string;
+}
+
+/** Returned only on creation; `token` is never shown again. */
+export interface CreatedApiToken extends ApiToken {
+  token: string;
+}
+
+export interface CreateApiTokenRequest {
+  name: string;
+  permission: 'read' | 'write';
+  expires_at?: string;
+}
+
 // ---- Rows ----
 export type RowData = Record<string, unknown>;
 
@@ -158,6 +244,11 @@ export interface RowsResponse {
   per_page: number;
 }
 
+export interface Transaction {
+  transaction_id: string;
+  expires_in_ms: number;
+}
+
 export interface ListRowsParams {
   page?: number;
   per_page?: number;